@@ -37,6 +37,19 @@ export function getFeeTierPda(feeRate: number): [PublicKey, number] {
   );
 }
 
+/**
+ * Get the fee tier index PDA, which points at the tier created `index`-th.
+ * Walk indices 0..config.feeTierCount to enumerate every tier.
+ */
+export function getFeeTierIndexPda(index: number): [PublicKey, number] {
+  const buf = Buffer.alloc(2);
+  buf.writeUInt16LE(index, 0);
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("fee_tier_index"), buf],
+    PROGRAM_ID
+  );
+}
+
 /**
  * Get the pool PDA
  */
@@ -67,7 +67,7 @@ export function useCreatePool() {
         // Build initialize pool instruction
         // eslint-disable-next-line @typescript-eslint/no-explicit-any
         const tx = await (program.methods as any)
-          .initializePool(sqrtPriceX64)
+          .initializePool(sqrtPriceX64, null, 0)
           .accounts({
             config: configPda,
             feeTier: feeTierPda,
@@ -128,7 +128,9 @@ export function useLiquidity() {
           .increaseLiquidity(
             new BN(liquidityAmount.toString()),
             new BN(amountAMax.toString()),
-            new BN(amountBMax.toString())
+            new BN(amountBMax.toString()),
+            null,
+            null
           )
           .accounts({
             pool: pool.address,
@@ -190,7 +192,9 @@ export function useLiquidity() {
           .decreaseLiquidity(
             new BN(liquidityAmount.toString()),
             new BN(amountAMin.toString()),
-            new BN(amountBMin.toString())
+            new BN(amountBMin.toString()),
+            null,
+            null
           )
           .accounts({
             pool: pool.address,
@@ -87,6 +87,10 @@ export function useSwap() {
           otherAmountThreshold: new BN(minimumAmountOut.toString()),
           sqrtPriceLimitX64: priceLimit,
           aToB,
+          referralFeeRate: 0,
+          deadlineUnixTs: null,
+          maxSlot: null,
+          maxTwapDeviationTicks: null,
         };
 
         console.log("Swap params:", {
@@ -0,0 +1,74 @@
+//! HTTP API
+//!
+//! Serves the same `GET /api/stats` response as the Next.js route so the
+//! front end can point at a local indexer.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tiny_http::{Header, Method, Response, Server};
+
+use crate::error::{IndexerError, Result};
+use crate::store::Store;
+
+/// Serve requests until the process exits
+pub fn serve(store: &Store, addr: &str) -> Result<()> {
+    let server = Server::http(addr).map_err(|err| IndexerError::Rpc(err.to_string()))?;
+    println!("Serving /api/stats on http://{addr}");
+
+    for request in server.incoming_requests() {
+        let (status, body) = handle(store, request.method(), request.url());
+        let content_type = Header::from_bytes("Content-Type", "application/json")
+            .expect("static header is valid");
+        let response = Response::from_string(body)
+            .with_status_code(status)
+            .with_header(content_type);
+        if let Err(err) = request.respond(response) {
+            eprintln!("[api] failed to respond: {err}");
+        }
+    }
+    Ok(())
+}
+
+/// Route a request to a (status, JSON body) pair
+pub fn handle(store: &Store, method: &Method, url: &str) -> (u16, String) {
+    let path = url.split('?').next().unwrap_or_default();
+    match (method, path) {
+        (Method::Get, "/api/stats") => match store.global_stats(unix_now()) {
+            Ok(stats) => (200, serde_json::to_string(&stats).unwrap_or_default()),
+            Err(err) => {
+                eprintln!("[api] global stats error: {err}");
+                (500, r#"{"error":"Internal server error"}"#.to_string())
+            }
+        },
+        _ => (404, r#"{"error":"Not found"}"#.to_string()),
+    }
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_route_shape() {
+        let store = Store::open_in_memory().unwrap();
+        let (status, body) = handle(&store, &Method::Get, "/api/stats");
+        assert_eq!(status, 200);
+
+        let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+        for key in [
+            "totalPools", "volume24h", "txCount24h", "activePositions",
+            "uniqueUsers", "tvlTotal", "timestamp",
+        ] {
+            assert!(json.get(key).is_some(), "missing {key}");
+        }
+
+        assert_eq!(handle(&store, &Method::Get, "/api/nope").0, 404);
+    }
+}
@@ -0,0 +1,281 @@
+//! Transaction Decoder
+//!
+//! Turns a `getTransaction` (encoding = "json") response into the list of
+//! SuniSwap events it emitted. Events are read from `Program data:` log lines,
+//! attributed to the program currently on top of the invoke stack so that
+//! events logged by other programs in the same transaction are ignored.
+
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::Engine;
+use serde::Deserialize;
+use suniswap::events::*;
+
+use crate::error::{IndexerError, Result};
+
+/// Minimal view of a `getTransaction` response
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RawTransaction {
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub meta: Option<RawMeta>,
+    pub transaction: RawTransactionBody,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RawMeta {
+    pub err: Option<serde_json::Value>,
+    pub log_messages: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RawTransactionBody {
+    pub signatures: Vec<String>,
+}
+
+/// Every event the program emits
+#[derive(Debug, Clone)]
+pub enum SuniswapEvent {
+    PoolInitialized(PoolInitializedEvent),
+    PositionOpened(PositionOpenedEvent),
+    PositionClosed(PositionClosedEvent),
+    LiquidityIncreased(LiquidityIncreasedEvent),
+    LiquidityDecreased(LiquidityDecreasedEvent),
+    PositionCompounded(PositionCompoundedEvent),
+    PositionSplit(PositionSplitEvent),
+    PositionsMerged(PositionsMergedEvent),
+    FeesCollected(FeesCollectedEvent),
+    Swap(SwapEvent),
+    ProtocolFeesCollected(ProtocolFeesCollectedEvent),
+}
+
+/// Events the store has no table for; recognised so they are not reported
+/// as unknown
+const IGNORED_EVENTS: &[&[u8]] = &[
+    // The new position arrives as PositionOpened + LiquidityIncreased
+    PositionRepositionedEvent::DISCRIMINATOR,
+    PositionDelegateUpdatedEvent::DISCRIMINATOR,
+    ReferralFeeEvent::DISCRIMINATOR,
+    FeeTierUpdatedEvent::DISCRIMINATOR,
+    MaxReferralFeeRateUpdatedEvent::DISCRIMINATOR,
+    MaxCreatorFeeRateUpdatedEvent::DISCRIMINATOR,
+    PoolFeeUpdatedEvent::DISCRIMINATOR,
+    CreatorFeesCollectedEvent::DISCRIMINATOR,
+    ProtocolFeeRecipientsUpdatedEvent::DISCRIMINATOR,
+    ProtocolFeesDistributedEvent::DISCRIMINATOR,
+    VaultInitializedEvent::DISCRIMINATOR,
+    VaultParamsUpdatedEvent::DISCRIMINATOR,
+    VaultDepositEvent::DISCRIMINATOR,
+    VaultWithdrawEvent::DISCRIMINATOR,
+    VaultFeesHarvestedEvent::DISCRIMINATOR,
+    VaultRebalancedEvent::DISCRIMINATOR,
+    VaultFeesCollectedEvent::DISCRIMINATOR,
+    PoolCreationModeUpdatedEvent::DISCRIMINATOR,
+    PoolCreationAllowlistUpdatedEvent::DISCRIMINATOR,
+    PoolCreationFeeUpdatedEvent::DISCRIMINATOR,
+    PoolCreationFeePaidEvent::DISCRIMINATOR,
+    TraderAllowlistUpdatedEvent::DISCRIMINATOR,
+    AllowedTraderUpdatedEvent::DISCRIMINATOR,
+    PoolCircuitBreakerUpdatedEvent::DISCRIMINATOR,
+    PoolTwapGuardUpdatedEvent::DISCRIMINATOR,
+];
+
+/// A successful transaction and the events it emitted, in log order
+#[derive(Debug, Clone)]
+pub struct DecodedTransaction {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: i64,
+    pub events: Vec<SuniswapEvent>,
+}
+
+/// Decode one transaction
+/// Returns None for failed transactions, which emit no durable state
+pub fn decode_transaction(raw: &RawTransaction) -> Result<Option<DecodedTransaction>> {
+    let meta = match &raw.meta {
+        Some(meta) => meta,
+        None => return Ok(None),
+    };
+    if meta.err.as_ref().is_some_and(|err| !err.is_null()) {
+        return Ok(None);
+    }
+
+    let signature = raw
+        .transaction
+        .signatures
+        .first()
+        .ok_or_else(|| IndexerError::Decode("transaction has no signature".into()))?
+        .clone();
+
+    let logs = meta.log_messages.as_deref().unwrap_or_default();
+    let events = decode_logs(logs, &suniswap::ID.to_string())?;
+
+    Ok(Some(DecodedTransaction {
+        signature,
+        slot: raw.slot,
+        block_time: raw.block_time.unwrap_or_default(),
+        events,
+    }))
+}
+
+/// Extract program events from a transaction's log messages
+pub fn decode_logs(logs: &[String], program_id: &str) -> Result<Vec<SuniswapEvent>> {
+    let mut stack: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+
+    for line in logs {
+        if let Some(rest) = line.strip_prefix("Program data: ") {
+            if stack.last() != Some(&program_id) {
+                continue;
+            }
+            let bytes = base64::engine::general_purpose::STANDARD
+                .decode(rest.trim())
+                .map_err(|err| IndexerError::Decode(err.to_string()))?;
+            if let Some(event) = decode_event(&bytes)? {
+                events.push(event);
+            }
+        } else if let Some(rest) = line.strip_prefix("Program ") {
+            let mut parts = rest.split_whitespace();
+            let id = parts.next().unwrap_or_default();
+            match parts.next() {
+                Some("invoke") => stack.push(id),
+                Some("success") | Some("failed:") => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(events)
+}
+
+/// Decode a single `discriminator || borsh` event payload
+/// Unknown discriminators are logged and skipped so older indexers tolerate
+/// new events
+pub fn decode_event(data: &[u8]) -> Result<Option<SuniswapEvent>> {
+    fn parse<T: AnchorDeserialize + Discriminator>(data: &[u8]) -> Result<Option<T>> {
+        if !data.starts_with(T::DISCRIMINATOR) {
+            return Ok(None);
+        }
+        T::deserialize(&mut &data[T::DISCRIMINATOR.len()..])
+            .map(Some)
+            .map_err(|err| IndexerError::Decode(err.to_string()))
+    }
+
+    if let Some(e) = parse::<PoolInitializedEvent>(data)? {
+        return Ok(Some(SuniswapEvent::PoolInitialized(e)));
+    }
+    if let Some(e) = parse::<PositionOpenedEvent>(data)? {
+        return Ok(Some(SuniswapEvent::PositionOpened(e)));
+    }
+    if let Some(e) = parse::<PositionClosedEvent>(data)? {
+        return Ok(Some(SuniswapEvent::PositionClosed(e)));
+    }
+    if let Some(e) = parse::<LiquidityIncreasedEvent>(data)? {
+        return Ok(Some(SuniswapEvent::LiquidityIncreased(e)));
+    }
+    if let Some(e) = parse::<LiquidityDecreasedEvent>(data)? {
+        return Ok(Some(SuniswapEvent::LiquidityDecreased(e)));
+    }
+    if let Some(e) = parse::<PositionCompoundedEvent>(data)? {
+        return Ok(Some(SuniswapEvent::PositionCompounded(e)));
+    }
+    if let Some(e) = parse::<PositionSplitEvent>(data)? {
+        return Ok(Some(SuniswapEvent::PositionSplit(e)));
+    }
+    if let Some(e) = parse::<PositionsMergedEvent>(data)? {
+        return Ok(Some(SuniswapEvent::PositionsMerged(e)));
+    }
+    if let Some(e) = parse::<FeesCollectedEvent>(data)? {
+        return Ok(Some(SuniswapEvent::FeesCollected(e)));
+    }
+    if let Some(e) = parse::<SwapEvent>(data)? {
+        return Ok(Some(SuniswapEvent::Swap(e)));
+    }
+    if let Some(e) = parse::<ProtocolFeesCollectedEvent>(data)? {
+        return Ok(Some(SuniswapEvent::ProtocolFeesCollected(e)));
+    }
+    if !IGNORED_EVENTS.iter().any(|discriminator| data.starts_with(discriminator)) {
+        let discriminator = &data[..data.len().min(8)];
+        eprintln!("[decoder] skipping unknown event {discriminator:02x?}");
+    }
+    Ok(None)
+}
+
+/// Parse a dump file: a single transaction object or an array of them
+pub fn parse_dump(json: &str) -> Result<Vec<RawTransaction>> {
+    let value: serde_json::Value = serde_json::from_str(json)?;
+    if value.is_array() {
+        Ok(serde_json::from_value(value)?)
+    } else {
+        Ok(vec![serde_json::from_value(value)?])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::prelude::Pubkey;
+    use anchor_lang::Event;
+
+    fn data_line(event: &impl Event) -> String {
+        format!(
+            "Program data: {}",
+            base64::engine::general_purpose::STANDARD.encode(event.data())
+        )
+    }
+
+    #[test]
+    fn test_decode_logs_only_attributes_program_events() {
+        let program = suniswap::ID.to_string();
+        let other = Pubkey::new_unique().to_string();
+        let event = FeesCollectedEvent {
+            pool: Pubkey::new_unique(),
+            position: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            amount_a: 7,
+            amount_b: 9,
+        };
+
+        let logs = vec![
+            format!("Program {program} invoke [1]"),
+            format!("Program {other} invoke [2]"),
+            data_line(&event),
+            format!("Program {other} success"),
+            data_line(&event),
+            format!("Program {program} success"),
+        ];
+
+        let events = decode_logs(&logs, &program).unwrap();
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            SuniswapEvent::FeesCollected(e) => {
+                assert_eq!(e.amount_a, 7);
+                assert_eq!(e.amount_b, 9);
+            }
+            other => panic!("unexpected event {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_decode_event_skips_events_without_a_table() {
+        let event = FeeTierUpdatedEvent {
+            fee_tier: Pubkey::new_unique(),
+            fee_rate: 3000,
+            disabled: true,
+        };
+        assert!(decode_event(&event.data()).unwrap().is_none());
+        assert!(decode_event(&[0xff; 8]).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_failed_transaction_is_skipped() {
+        let raw: RawTransaction = serde_json::from_str(
+            r#"{"slot":1,"blockTime":0,"meta":{"err":{"InstructionError":[0,"Custom"]},"logMessages":[]},"transaction":{"signatures":["sig"]}}"#,
+        )
+        .unwrap();
+        assert!(decode_transaction(&raw).unwrap().is_none());
+    }
+}
@@ -0,0 +1,51 @@
+//! Indexer error type
+
+use std::fmt;
+
+pub type Result<T> = std::result::Result<T, IndexerError>;
+
+#[derive(Debug)]
+pub enum IndexerError {
+    /// SQLite failure
+    Database(rusqlite::Error),
+    /// Malformed transaction JSON or RPC response
+    Json(serde_json::Error),
+    /// File or socket failure
+    Io(std::io::Error),
+    /// JSON-RPC transport failure
+    Rpc(String),
+    /// Event payload could not be decoded
+    Decode(String),
+}
+
+impl fmt::Display for IndexerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IndexerError::Database(err) => write!(f, "database error: {err}"),
+            IndexerError::Json(err) => write!(f, "json error: {err}"),
+            IndexerError::Io(err) => write!(f, "io error: {err}"),
+            IndexerError::Rpc(msg) => write!(f, "rpc error: {msg}"),
+            IndexerError::Decode(msg) => write!(f, "decode error: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for IndexerError {}
+
+impl From<rusqlite::Error> for IndexerError {
+    fn from(err: rusqlite::Error) -> Self {
+        IndexerError::Database(err)
+    }
+}
+
+impl From<serde_json::Error> for IndexerError {
+    fn from(err: serde_json::Error) -> Self {
+        IndexerError::Json(err)
+    }
+}
+
+impl From<std::io::Error> for IndexerError {
+    fn from(err: std::io::Error) -> Self {
+        IndexerError::Io(err)
+    }
+}
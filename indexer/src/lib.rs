@@ -0,0 +1,23 @@
+//! SuniSwap Indexer
+//!
+//! Offline replacement for the Helius + Supabase pipeline in `app/src/lib/indexer`.
+//!
+//! ## Architecture
+//!
+//! ```text
+//! Local validator (JSON-RPC)  or  replayed getTransaction dumps
+//!       ↓
+//! decoder: "Program data:" logs → SuniSwap events
+//!       ↓
+//! store: SQLite tables matching supabase/migrations/20240105000000_init.sql
+//!       ↓
+//! api: GET /api/stats
+//! ```
+
+pub mod api;
+pub mod decoder;
+pub mod error;
+pub mod rpc;
+pub mod store;
+
+pub use error::{IndexerError, Result};
@@ -0,0 +1,138 @@
+//! SuniSwap indexer CLI
+//!
+//! ```text
+//! suniswap-indexer replay --db indexer.db <dump.json | dir>...
+//! suniswap-indexer watch  --db indexer.db [--rpc http://127.0.0.1:8899] [--interval-ms 1000]
+//! suniswap-indexer serve  --db indexer.db [--addr 127.0.0.1:8787]
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use suniswap_indexer::decoder::{decode_transaction, parse_dump};
+use suniswap_indexer::rpc::RpcClient;
+use suniswap_indexer::store::Store;
+use suniswap_indexer::{api, Result};
+
+const USAGE: &str = "usage:
+  suniswap-indexer replay --db <path> <dump.json | dir>...
+  suniswap-indexer watch  --db <path> [--rpc <url>] [--interval-ms <ms>]
+  suniswap-indexer serve  --db <path> [--addr <host:port>]";
+
+struct Args {
+    command: String,
+    db: PathBuf,
+    rpc: String,
+    addr: String,
+    interval_ms: u64,
+    inputs: Vec<PathBuf>,
+}
+
+fn parse_args() -> Option<Args> {
+    let mut argv = std::env::args().skip(1);
+    let mut args = Args {
+        command: argv.next()?,
+        db: PathBuf::from("suniswap-indexer.db"),
+        rpc: "http://127.0.0.1:8899".to_string(),
+        addr: "127.0.0.1:8787".to_string(),
+        interval_ms: 1000,
+        inputs: Vec::new(),
+    };
+
+    while let Some(arg) = argv.next() {
+        match arg.as_str() {
+            "--db" => args.db = PathBuf::from(argv.next()?),
+            "--rpc" => args.rpc = argv.next()?,
+            "--addr" => args.addr = argv.next()?,
+            "--interval-ms" => args.interval_ms = argv.next()?.parse().ok()?,
+            _ => args.inputs.push(PathBuf::from(arg)),
+        }
+    }
+    Some(args)
+}
+
+fn main() {
+    let Some(args) = parse_args() else {
+        eprintln!("{USAGE}");
+        std::process::exit(2);
+    };
+
+    let result = match args.command.as_str() {
+        "replay" => replay(&args),
+        "watch" => watch(&args),
+        "serve" => Store::open(&args.db).and_then(|store| api::serve(&store, &args.addr)),
+        _ => {
+            eprintln!("{USAGE}");
+            std::process::exit(2);
+        }
+    };
+
+    if let Err(err) = result {
+        eprintln!("error: {err}");
+        std::process::exit(1);
+    }
+}
+
+/// Index JSON transaction dumps from files or directories (sorted by name)
+fn replay(args: &Args) -> Result<()> {
+    let mut store = Store::open(&args.db)?;
+    let mut files = Vec::new();
+    for input in &args.inputs {
+        collect_files(input, &mut files)?;
+    }
+
+    let mut raws = Vec::new();
+    for file in &files {
+        raws.extend(parse_dump(&std::fs::read_to_string(file)?)?);
+    }
+    raws.sort_by_key(|raw| raw.slot);
+
+    let mut applied = 0;
+    for raw in &raws {
+        if let Some(tx) = decode_transaction(raw)? {
+            if store.apply(&tx)? {
+                applied += 1;
+            }
+        }
+    }
+
+    println!("Replayed {} transactions ({} new) from {} files", raws.len(), applied, files.len());
+    Ok(())
+}
+
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    if path.is_dir() {
+        let mut entries: Vec<PathBuf> = std::fs::read_dir(path)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .collect();
+        entries.sort();
+        for entry in entries {
+            collect_files(&entry, files)?;
+        }
+    } else if path.extension().is_some_and(|ext| ext == "json") {
+        files.push(path.to_path_buf());
+    }
+    Ok(())
+}
+
+/// Follow a validator, indexing new program transactions as they confirm
+fn watch(args: &Args) -> Result<()> {
+    let mut store = Store::open(&args.db)?;
+    let rpc = RpcClient::new(&args.rpc);
+    println!("Watching {} for program {}", args.rpc, suniswap::ID);
+
+    loop {
+        let cursor = store.last_signature()?;
+        for signature in rpc.signatures_since(cursor.as_deref())? {
+            let Some(raw) = rpc.transaction(&signature)? else {
+                continue;
+            };
+            if let Some(tx) = decode_transaction(&raw)? {
+                if store.apply(&tx)? {
+                    println!("Indexed {} ({} events)", tx.signature, tx.events.len());
+                }
+            }
+        }
+        std::thread::sleep(Duration::from_millis(args.interval_ms));
+    }
+}
@@ -0,0 +1,91 @@
+//! JSON-RPC Source
+//!
+//! Polls a validator (typically `solana-test-validator`) for new program
+//! transactions. Only plain HTTP is supported; this is meant for local use.
+
+use serde_json::{json, Value};
+
+use crate::decoder::RawTransaction;
+use crate::error::{IndexerError, Result};
+
+/// Largest page `getSignaturesForAddress` returns
+const SIGNATURES_PAGE_LIMIT: usize = 1000;
+
+pub struct RpcClient {
+    url: String,
+    agent: ureq::Agent,
+}
+
+impl RpcClient {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            agent: ureq::Agent::new(),
+        }
+    }
+
+    fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let mut response: Value = self
+            .agent
+            .post(&self.url)
+            .send_json(body)
+            .map_err(|err| IndexerError::Rpc(err.to_string()))?
+            .into_json()?;
+
+        if let Some(err) = response.get("error") {
+            return Err(IndexerError::Rpc(err.to_string()));
+        }
+        Ok(response["result"].take())
+    }
+
+    /// Signatures for the program newer than `until`, oldest first
+    /// Pages backwards with `before` so gaps longer than one page are not lost
+    pub fn signatures_since(&self, until: Option<&str>) -> Result<Vec<String>> {
+        let mut signatures: Vec<String> = Vec::new();
+        loop {
+            let mut config = json!({ "commitment": "confirmed", "limit": SIGNATURES_PAGE_LIMIT });
+            if let Some(until) = until {
+                config["until"] = json!(until);
+            }
+            if let Some(before) = signatures.last() {
+                config["before"] = json!(before);
+            }
+            let result = self.call(
+                "getSignaturesForAddress",
+                json!([suniswap::ID.to_string(), config]),
+            )?;
+
+            let page: Vec<String> = result
+                .as_array()
+                .map(|entries| {
+                    entries
+                        .iter()
+                        .filter_map(|entry| entry["signature"].as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default();
+            if page.is_empty() {
+                break;
+            }
+            signatures.extend(page);
+        }
+        signatures.reverse();
+        Ok(signatures)
+    }
+
+    /// Fetch a confirmed transaction in the dump format the decoder expects
+    pub fn transaction(&self, signature: &str) -> Result<Option<RawTransaction>> {
+        let result = self.call(
+            "getTransaction",
+            json!([
+                signature,
+                { "encoding": "json", "commitment": "confirmed", "maxSupportedTransactionVersion": 0 }
+            ]),
+        )?;
+        if result.is_null() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_value(result)?))
+    }
+}
@@ -0,0 +1,188 @@
+-- SuniSwap Indexer Schema (SQLite)
+-- Mirrors app/supabase/migrations/20240105000000_init.sql
+--
+-- Differences forced by SQLite:
+--   * UUID ids are random 16-byte hex strings
+--   * TIMESTAMP WITH TIME ZONE columns hold ISO-8601 UTC text
+--   * Row level security policies are omitted
+
+-- ============================================================================
+-- CORE TABLES
+-- ============================================================================
+
+-- Pools table
+CREATE TABLE IF NOT EXISTS pools (
+  id TEXT PRIMARY KEY DEFAULT (lower(hex(randomblob(16)))),
+  address VARCHAR(44) UNIQUE NOT NULL,
+  token_mint_a VARCHAR(44) NOT NULL,
+  token_mint_b VARCHAR(44) NOT NULL,
+  token_vault_a VARCHAR(44) NOT NULL,
+  token_vault_b VARCHAR(44) NOT NULL,
+  fee_rate INTEGER NOT NULL,
+  tick_spacing INTEGER NOT NULL,
+  sqrt_price_x64 VARCHAR(40) NOT NULL,
+  tick_current INTEGER NOT NULL,
+  liquidity VARCHAR(40) NOT NULL DEFAULT '0',
+  fee_growth_global_a VARCHAR(40) NOT NULL DEFAULT '0',
+  fee_growth_global_b VARCHAR(40) NOT NULL DEFAULT '0',
+  protocol_fees_a VARCHAR(40) NOT NULL DEFAULT '0',
+  protocol_fees_b VARCHAR(40) NOT NULL DEFAULT '0',
+  is_paused BOOLEAN NOT NULL DEFAULT false,
+  created_at TIMESTAMP WITH TIME ZONE DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+  updated_at TIMESTAMP WITH TIME ZONE DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+  created_slot BIGINT,
+  created_tx VARCHAR(88)
+);
+
+-- Positions table
+CREATE TABLE IF NOT EXISTS positions (
+  id TEXT PRIMARY KEY DEFAULT (lower(hex(randomblob(16)))),
+  address VARCHAR(44) UNIQUE NOT NULL,
+  pool_address VARCHAR(44) NOT NULL REFERENCES pools(address),
+  owner VARCHAR(44) NOT NULL,
+  tick_lower INTEGER NOT NULL,
+  tick_upper INTEGER NOT NULL,
+  liquidity VARCHAR(40) NOT NULL DEFAULT '0',
+  fee_growth_inside_a VARCHAR(40) NOT NULL DEFAULT '0',
+  fee_growth_inside_b VARCHAR(40) NOT NULL DEFAULT '0',
+  tokens_owed_a VARCHAR(40) NOT NULL DEFAULT '0',
+  tokens_owed_b VARCHAR(40) NOT NULL DEFAULT '0',
+  is_open BOOLEAN NOT NULL DEFAULT true,
+  created_at TIMESTAMP WITH TIME ZONE DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+  updated_at TIMESTAMP WITH TIME ZONE DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+  created_slot BIGINT,
+  created_tx VARCHAR(88)
+);
+
+-- Swaps table
+CREATE TABLE IF NOT EXISTS swaps (
+  id TEXT PRIMARY KEY DEFAULT (lower(hex(randomblob(16)))),
+  signature VARCHAR(88) UNIQUE NOT NULL,
+  pool_address VARCHAR(44) NOT NULL REFERENCES pools(address),
+  user_address VARCHAR(44) NOT NULL,
+  token_in VARCHAR(44) NOT NULL,
+  token_out VARCHAR(44) NOT NULL,
+  amount_in VARCHAR(40) NOT NULL,
+  amount_out VARCHAR(40) NOT NULL,
+  sqrt_price_after VARCHAR(40) NOT NULL,
+  tick_after INTEGER NOT NULL,
+  fee_amount VARCHAR(40) NOT NULL,
+  slot BIGINT NOT NULL,
+  block_time TIMESTAMP WITH TIME ZONE NOT NULL,
+  created_at TIMESTAMP WITH TIME ZONE DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+-- Liquidity events table
+CREATE TABLE IF NOT EXISTS liquidity_events (
+  id TEXT PRIMARY KEY DEFAULT (lower(hex(randomblob(16)))),
+  signature VARCHAR(88) UNIQUE NOT NULL,
+  pool_address VARCHAR(44) NOT NULL REFERENCES pools(address),
+  position_address VARCHAR(44) NOT NULL,
+  user_address VARCHAR(44) NOT NULL,
+  event_type VARCHAR(20) NOT NULL, -- 'add', 'remove', 'collect_fees'
+  tick_lower INTEGER,
+  tick_upper INTEGER,
+  liquidity_delta VARCHAR(40),
+  amount_a VARCHAR(40),
+  amount_b VARCHAR(40),
+  slot BIGINT NOT NULL,
+  block_time TIMESTAMP WITH TIME ZONE NOT NULL,
+  created_at TIMESTAMP WITH TIME ZONE DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+-- Pool snapshots for TVL/Volume tracking (hourly)
+CREATE TABLE IF NOT EXISTS pool_snapshots (
+  id TEXT PRIMARY KEY DEFAULT (lower(hex(randomblob(16)))),
+  pool_address VARCHAR(44) NOT NULL REFERENCES pools(address),
+  snapshot_time TIMESTAMP WITH TIME ZONE NOT NULL,
+  sqrt_price_x64 VARCHAR(40) NOT NULL,
+  tick_current INTEGER NOT NULL,
+  liquidity VARCHAR(40) NOT NULL,
+  tvl_usd DECIMAL(24, 6),
+  volume_usd DECIMAL(24, 6) DEFAULT 0,
+  fees_usd DECIMAL(24, 6) DEFAULT 0,
+  tx_count INTEGER DEFAULT 0,
+  created_at TIMESTAMP WITH TIME ZONE DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+  UNIQUE(pool_address, snapshot_time)
+);
+
+-- Token prices cache
+CREATE TABLE IF NOT EXISTS token_prices (
+  id TEXT PRIMARY KEY DEFAULT (lower(hex(randomblob(16)))),
+  mint_address VARCHAR(44) UNIQUE NOT NULL,
+  symbol VARCHAR(20),
+  price_usd DECIMAL(24, 12) NOT NULL,
+  updated_at TIMESTAMP WITH TIME ZONE DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+-- Transactions already applied (makes replays idempotent, doubles as RPC cursor)
+CREATE TABLE IF NOT EXISTS indexed_transactions (
+  signature VARCHAR(88) PRIMARY KEY,
+  slot BIGINT NOT NULL
+);
+
+-- ============================================================================
+-- INDEXES
+-- ============================================================================
+
+CREATE INDEX IF NOT EXISTS idx_pools_tokens ON pools(token_mint_a, token_mint_b);
+CREATE INDEX IF NOT EXISTS idx_positions_pool ON positions(pool_address);
+CREATE INDEX IF NOT EXISTS idx_positions_owner ON positions(owner);
+CREATE INDEX IF NOT EXISTS idx_swaps_pool ON swaps(pool_address);
+CREATE INDEX IF NOT EXISTS idx_swaps_user ON swaps(user_address);
+CREATE INDEX IF NOT EXISTS idx_swaps_time ON swaps(block_time DESC);
+CREATE INDEX IF NOT EXISTS idx_liquidity_events_pool ON liquidity_events(pool_address);
+CREATE INDEX IF NOT EXISTS idx_liquidity_events_user ON liquidity_events(user_address);
+CREATE INDEX IF NOT EXISTS idx_liquidity_events_time ON liquidity_events(block_time DESC);
+CREATE INDEX IF NOT EXISTS idx_pool_snapshots_pool_time ON pool_snapshots(pool_address, snapshot_time DESC);
+CREATE INDEX IF NOT EXISTS idx_indexed_transactions_slot ON indexed_transactions(slot DESC);
+
+-- ============================================================================
+-- TRIGGERS
+-- ============================================================================
+
+-- Auto-update updated_at timestamp
+CREATE TRIGGER IF NOT EXISTS pools_updated_at
+  AFTER UPDATE ON pools
+  FOR EACH ROW WHEN NEW.updated_at = OLD.updated_at
+BEGIN
+  UPDATE pools SET updated_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now') WHERE id = NEW.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS positions_updated_at
+  AFTER UPDATE ON positions
+  FOR EACH ROW WHEN NEW.updated_at = OLD.updated_at
+BEGIN
+  UPDATE positions SET updated_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now') WHERE id = NEW.id;
+END;
+
+-- ============================================================================
+-- VIEWS
+-- ============================================================================
+
+-- Pool stats view (24h metrics)
+CREATE VIEW IF NOT EXISTS pool_stats_24h AS
+SELECT
+  p.address,
+  p.token_mint_a,
+  p.token_mint_b,
+  p.fee_rate,
+  p.tick_current,
+  p.liquidity,
+  COALESCE(SUM(CAST(s.amount_in AS REAL)), 0) as volume_24h,
+  COALESCE(SUM(CAST(s.fee_amount AS REAL)), 0) as fees_24h,
+  COUNT(s.id) as tx_count_24h
+FROM pools p
+LEFT JOIN swaps s ON s.pool_address = p.address
+  AND s.block_time > strftime('%Y-%m-%dT%H:%M:%SZ', 'now', '-24 hours')
+GROUP BY p.address, p.token_mint_a, p.token_mint_b, p.fee_rate, p.tick_current, p.liquidity;
+
+-- User position summary
+CREATE VIEW IF NOT EXISTS user_position_summary AS
+SELECT
+  owner,
+  COUNT(*) as total_positions,
+  SUM(CASE WHEN is_open THEN 1 ELSE 0 END) as open_positions,
+  SUM(CASE WHEN is_open THEN CAST(liquidity AS REAL) ELSE 0 END) as total_liquidity
+FROM positions
+GROUP BY owner;
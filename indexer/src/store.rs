@@ -0,0 +1,646 @@
+//! SQLite Store
+//!
+//! Applies decoded events to the indexer tables. Each transaction is applied
+//! atomically and at most once, keyed by signature in `indexed_transactions`.
+//!
+//! u64/u128 amounts are stored as decimal strings, matching the Postgres
+//! schema. Timestamps are ISO-8601 UTC strings so they sort lexically.
+
+use std::path::Path;
+
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use serde::Serialize;
+
+use crate::decoder::{DecodedTransaction, SuniswapEvent};
+use crate::error::Result;
+
+const SCHEMA: &str = include_str!("schema.sql");
+
+/// ISO-8601 expression for a unix timestamp bound as the first parameter
+const ISO_FROM_UNIX: &str = "strftime('%Y-%m-%dT%H:%M:%SZ', ?1, 'unixepoch')";
+
+/// Response body of `GET /api/stats`
+/// Field names match app/src/app/api/stats/route.ts
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalStats {
+    pub total_pools: u64,
+    pub volume_24h: f64,
+    pub tx_count_24h: u64,
+    pub active_positions: u64,
+    pub unique_users: u64,
+    /// Raw token amounts backing open positions, summed like `volume_24h`
+    pub tvl_total: f64,
+    pub timestamp: String,
+}
+
+pub struct Store {
+    conn: Connection,
+}
+
+impl Store {
+    /// Open (and migrate) a database file
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        Self::with_connection(conn)
+    }
+
+    /// Open a throwaway in-memory database
+    pub fn open_in_memory() -> Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn })
+    }
+
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// Most recently indexed signature, used as the RPC polling cursor
+    pub fn last_signature(&self) -> Result<Option<String>> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT signature FROM indexed_transactions ORDER BY slot DESC, rowid DESC LIMIT 1",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
+
+    /// Apply a decoded transaction
+    /// Returns false if the signature was already indexed
+    pub fn apply(&mut self, tx: &DecodedTransaction) -> Result<bool> {
+        let db = self.conn.transaction()?;
+
+        let inserted = db.execute(
+            "INSERT OR IGNORE INTO indexed_transactions (signature, slot) VALUES (?1, ?2)",
+            params![tx.signature, tx.slot as i64],
+        )?;
+        if inserted == 0 {
+            return Ok(false);
+        }
+
+        let block_time: String =
+            db.query_row(&format!("SELECT {ISO_FROM_UNIX}"), [tx.block_time], |row| row.get(0))?;
+
+        for event in &tx.events {
+            apply_event(&db, tx, &block_time, event)?;
+        }
+
+        db.commit()?;
+        Ok(true)
+    }
+
+    /// Global protocol stats for the 24h window ending at `now` (unix seconds)
+    pub fn global_stats(&self, now: i64) -> Result<GlobalStats> {
+        let count = |sql: &str| -> Result<u64> {
+            Ok(self.conn.query_row(sql, [], |row| row.get::<_, i64>(0))? as u64)
+        };
+
+        let timestamp: String =
+            self.conn.query_row(&format!("SELECT {ISO_FROM_UNIX}"), [now], |row| row.get(0))?;
+        let since: String = self.conn.query_row(
+            &format!("SELECT {ISO_FROM_UNIX}"),
+            [now - 24 * 60 * 60],
+            |row| row.get(0),
+        )?;
+
+        let (volume_24h, tx_count_24h): (f64, i64) = self.conn.query_row(
+            "SELECT COALESCE(SUM(CAST(amount_in AS REAL)), 0), COUNT(*) \
+             FROM swaps WHERE block_time >= ?1",
+            [&since],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+
+        Ok(GlobalStats {
+            total_pools: count("SELECT COUNT(*) FROM pools")?,
+            volume_24h,
+            tx_count_24h: tx_count_24h as u64,
+            active_positions: count("SELECT COUNT(*) FROM positions WHERE is_open")?,
+            unique_users: count("SELECT COUNT(DISTINCT user_address) FROM swaps")?,
+            tvl_total: self.tvl_total()?,
+            timestamp,
+        })
+    }
+
+    /// Token amounts held by open positions at each pool's current price
+    fn tvl_total(&self) -> Result<f64> {
+        let mut stmt = self.conn.prepare(
+            "SELECT p.liquidity, p.tick_lower, p.tick_upper, pl.sqrt_price_x64 \
+             FROM positions p JOIN pools pl ON pl.address = p.pool_address \
+             WHERE p.is_open AND p.liquidity != '0'",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i32>(1)?,
+                row.get::<_, i32>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })?;
+
+        let mut total = 0.0;
+        for row in rows {
+            let (liquidity, tick_lower, tick_upper, sqrt_price_x64) = row?;
+            let (amount_a, amount_b) = position_amounts(
+                parse_amount(Some(&liquidity)) as f64,
+                tick_lower,
+                tick_upper,
+                parse_amount(Some(&sqrt_price_x64)) as f64 / 2f64.powi(64),
+            );
+            total += amount_a + amount_b;
+        }
+        Ok(total)
+    }
+}
+
+/// Token amounts for `liquidity` over a tick range at `sqrt_price`
+/// Float math is precise enough for stats
+fn position_amounts(liquidity: f64, tick_lower: i32, tick_upper: i32, sqrt_price: f64) -> (f64, f64) {
+    let sqrt_lower = 1.0001f64.powf(tick_lower as f64 / 2.0);
+    let sqrt_upper = 1.0001f64.powf(tick_upper as f64 / 2.0);
+    let sqrt_price = sqrt_price.clamp(sqrt_lower, sqrt_upper);
+    (
+        liquidity * (1.0 / sqrt_price - 1.0 / sqrt_upper),
+        liquidity * (sqrt_price - sqrt_lower),
+    )
+}
+
+fn apply_event(
+    db: &Transaction,
+    tx: &DecodedTransaction,
+    block_time: &str,
+    event: &SuniswapEvent,
+) -> Result<()> {
+    match event {
+        SuniswapEvent::PoolInitialized(e) => {
+            db.execute(
+                "INSERT INTO pools (address, token_mint_a, token_mint_b, token_vault_a, token_vault_b, \
+                 fee_rate, tick_spacing, sqrt_price_x64, tick_current, created_at, created_slot, created_tx) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12) \
+                 ON CONFLICT(address) DO NOTHING",
+                params![
+                    e.pool.to_string(),
+                    e.token_mint_a.to_string(),
+                    e.token_mint_b.to_string(),
+                    e.token_vault_a.to_string(),
+                    e.token_vault_b.to_string(),
+                    e.fee_rate,
+                    e.tick_spacing,
+                    e.sqrt_price_x64.to_string(),
+                    e.tick_current,
+                    block_time,
+                    tx.slot as i64,
+                    tx.signature,
+                ],
+            )?;
+        }
+        SuniswapEvent::PositionOpened(e) => {
+            db.execute(
+                "INSERT INTO positions (address, pool_address, owner, tick_lower, tick_upper, \
+                 created_at, created_slot, created_tx) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8) \
+                 ON CONFLICT(address) DO UPDATE SET \
+                 liquidity = '0', tokens_owed_a = '0', tokens_owed_b = '0', is_open = true, \
+                 created_at = excluded.created_at, created_slot = excluded.created_slot, \
+                 created_tx = excluded.created_tx",
+                params![
+                    e.position.to_string(),
+                    e.pool.to_string(),
+                    e.owner.to_string(),
+                    e.tick_lower,
+                    e.tick_upper,
+                    block_time,
+                    tx.slot as i64,
+                    tx.signature,
+                ],
+            )?;
+        }
+        SuniswapEvent::PositionClosed(e) => {
+            db.execute(
+                "UPDATE positions SET is_open = false WHERE address = ?1",
+                [e.position.to_string()],
+            )?;
+        }
+        SuniswapEvent::LiquidityIncreased(e) => {
+            update_position(db, &e.position.to_string(), |p| {
+                p.liquidity = p.liquidity.saturating_add(e.liquidity_delta);
+            })?;
+            set_pool_liquidity(db, &e.pool.to_string(), e.pool_liquidity)?;
+            insert_liquidity_event(
+                db, tx, block_time, "add",
+                &e.pool, &e.position, &e.owner,
+                Some((e.tick_lower, e.tick_upper, e.liquidity_delta)),
+                e.amount_a, e.amount_b,
+            )?;
+        }
+        SuniswapEvent::LiquidityDecreased(e) => {
+            update_position(db, &e.position.to_string(), |p| {
+                p.liquidity = p.liquidity.saturating_sub(e.liquidity_delta);
+                p.tokens_owed_a = p.tokens_owed_a.saturating_add(e.amount_a as u128);
+                p.tokens_owed_b = p.tokens_owed_b.saturating_add(e.amount_b as u128);
+            })?;
+            set_pool_liquidity(db, &e.pool.to_string(), e.pool_liquidity)?;
+            insert_liquidity_event(
+                db, tx, block_time, "remove",
+                &e.pool, &e.position, &e.owner,
+                Some((e.tick_lower, e.tick_upper, e.liquidity_delta)),
+                e.amount_a, e.amount_b,
+            )?;
+        }
+        SuniswapEvent::PositionCompounded(e) => {
+            // The liquidity itself arrives in the preceding LiquidityIncreased
+            update_position(db, &e.position.to_string(), |p| {
+                p.tokens_owed_a = p.tokens_owed_a.saturating_sub(e.amount_a as u128);
+                p.tokens_owed_b = p.tokens_owed_b.saturating_sub(e.amount_b as u128);
+            })?;
+        }
+        SuniswapEvent::PositionSplit(e) => {
+            // The new position's row comes from the preceding PositionOpened
+            update_position(db, &e.position.to_string(), |p| {
+                p.liquidity = p.liquidity.saturating_sub(e.liquidity);
+                p.tokens_owed_a = p.tokens_owed_a.saturating_sub(e.tokens_owed_a as u128);
+                p.tokens_owed_b = p.tokens_owed_b.saturating_sub(e.tokens_owed_b as u128);
+            })?;
+            update_position(db, &e.new_position.to_string(), |p| {
+                p.liquidity = p.liquidity.saturating_add(e.liquidity);
+                p.tokens_owed_a = p.tokens_owed_a.saturating_add(e.tokens_owed_a as u128);
+                p.tokens_owed_b = p.tokens_owed_b.saturating_add(e.tokens_owed_b as u128);
+            })?;
+        }
+        SuniswapEvent::PositionsMerged(e) => {
+            // The merged position was already marked closed by PositionClosed
+            update_position(db, &e.merged_position.to_string(), |p| {
+                *p = PositionAmounts { liquidity: 0, tokens_owed_a: 0, tokens_owed_b: 0 };
+            })?;
+            update_position(db, &e.position.to_string(), |p| {
+                p.liquidity = p.liquidity.saturating_add(e.liquidity);
+                p.tokens_owed_a = p.tokens_owed_a.saturating_add(e.tokens_owed_a as u128);
+                p.tokens_owed_b = p.tokens_owed_b.saturating_add(e.tokens_owed_b as u128);
+            })?;
+        }
+        SuniswapEvent::FeesCollected(e) => {
+            // Collected amounts include fees that accrued without an event,
+            // so owed balances are only a lower-bound estimate
+            update_position(db, &e.position.to_string(), |p| {
+                p.tokens_owed_a = p.tokens_owed_a.saturating_sub(e.amount_a as u128);
+                p.tokens_owed_b = p.tokens_owed_b.saturating_sub(e.amount_b as u128);
+            })?;
+            insert_liquidity_event(
+                db, tx, block_time, "collect_fees",
+                &e.pool, &e.position, &e.owner,
+                None,
+                e.amount_a, e.amount_b,
+            )?;
+        }
+        SuniswapEvent::Swap(e) => {
+            let pool = e.pool.to_string();
+            let mints: Option<(String, String)> = db
+                .query_row(
+                    "SELECT token_mint_a, token_mint_b FROM pools WHERE address = ?1",
+                    [&pool],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()?;
+            let (mint_a, mint_b) = mints.unwrap_or_default();
+            let (token_in, token_out) = if e.a_to_b { (mint_a, mint_b) } else { (mint_b, mint_a) };
+
+            db.execute(
+                "INSERT INTO swaps (signature, pool_address, user_address, token_in, token_out, \
+                 amount_in, amount_out, sqrt_price_after, tick_after, fee_amount, slot, block_time) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12) \
+                 ON CONFLICT(signature) DO NOTHING",
+                params![
+                    tx.signature,
+                    pool,
+                    e.user.to_string(),
+                    token_in,
+                    token_out,
+                    e.amount_in.to_string(),
+                    e.amount_out.to_string(),
+                    e.sqrt_price_x64.to_string(),
+                    e.tick_current,
+                    e.fee_amount.to_string(),
+                    tx.slot as i64,
+                    block_time,
+                ],
+            )?;
+
+            let protocol_fees_column = if e.a_to_b { "protocol_fees_a" } else { "protocol_fees_b" };
+            let protocol_fees: Option<String> = db
+                .query_row(
+                    &format!("SELECT {protocol_fees_column} FROM pools WHERE address = ?1"),
+                    [&pool],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            let protocol_fees = parse_amount(protocol_fees.as_deref())
+                .saturating_add(e.protocol_fee as u128);
+
+            db.execute(
+                &format!(
+                    "UPDATE pools SET sqrt_price_x64 = ?2, tick_current = ?3, liquidity = ?4, \
+                     {protocol_fees_column} = ?5 WHERE address = ?1"
+                ),
+                params![
+                    pool,
+                    e.sqrt_price_x64.to_string(),
+                    e.tick_current,
+                    e.liquidity.to_string(),
+                    protocol_fees.to_string(),
+                ],
+            )?;
+
+            // Hourly snapshot: latest price in the hour plus swap count.
+            // USD columns are left for the price service to fill.
+            db.execute(
+                "INSERT INTO pool_snapshots (pool_address, snapshot_time, sqrt_price_x64, \
+                 tick_current, liquidity, tx_count) \
+                 VALUES (?1, strftime('%Y-%m-%dT%H:00:00Z', ?2, 'unixepoch'), ?3, ?4, ?5, 1) \
+                 ON CONFLICT(pool_address, snapshot_time) DO UPDATE SET \
+                 sqrt_price_x64 = excluded.sqrt_price_x64, tick_current = excluded.tick_current, \
+                 liquidity = excluded.liquidity, tx_count = tx_count + 1",
+                params![
+                    pool,
+                    tx.block_time,
+                    e.sqrt_price_x64.to_string(),
+                    e.tick_current,
+                    e.liquidity.to_string(),
+                ],
+            )?;
+        }
+        SuniswapEvent::ProtocolFeesCollected(e) => {
+            let pool = e.pool.to_string();
+            let fees: Option<(String, String)> = db
+                .query_row(
+                    "SELECT protocol_fees_a, protocol_fees_b FROM pools WHERE address = ?1",
+                    [&pool],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()?;
+            if let Some((fees_a, fees_b)) = fees {
+                let fees_a = parse_amount(Some(&fees_a)).saturating_sub(e.amount_a as u128);
+                let fees_b = parse_amount(Some(&fees_b)).saturating_sub(e.amount_b as u128);
+                db.execute(
+                    "UPDATE pools SET protocol_fees_a = ?2, protocol_fees_b = ?3 WHERE address = ?1",
+                    params![pool, fees_a.to_string(), fees_b.to_string()],
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Numeric columns of a position row
+struct PositionAmounts {
+    liquidity: u128,
+    tokens_owed_a: u128,
+    tokens_owed_b: u128,
+}
+
+fn update_position(
+    db: &Transaction,
+    address: &str,
+    update: impl FnOnce(&mut PositionAmounts),
+) -> Result<()> {
+    let row: Option<(String, String, String)> = db
+        .query_row(
+            "SELECT liquidity, tokens_owed_a, tokens_owed_b FROM positions WHERE address = ?1",
+            [address],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .optional()?;
+
+    // Positions opened before indexing started are unknown; skip them
+    let Some((liquidity, owed_a, owed_b)) = row else {
+        return Ok(());
+    };
+
+    let mut amounts = PositionAmounts {
+        liquidity: parse_amount(Some(&liquidity)),
+        tokens_owed_a: parse_amount(Some(&owed_a)),
+        tokens_owed_b: parse_amount(Some(&owed_b)),
+    };
+    update(&mut amounts);
+
+    db.execute(
+        "UPDATE positions SET liquidity = ?2, tokens_owed_a = ?3, tokens_owed_b = ?4 WHERE address = ?1",
+        params![
+            address,
+            amounts.liquidity.to_string(),
+            amounts.tokens_owed_a.to_string(),
+            amounts.tokens_owed_b.to_string(),
+        ],
+    )?;
+    Ok(())
+}
+
+fn set_pool_liquidity(db: &Transaction, pool: &str, liquidity: u128) -> Result<()> {
+    db.execute(
+        "UPDATE pools SET liquidity = ?2 WHERE address = ?1",
+        params![pool, liquidity.to_string()],
+    )?;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn insert_liquidity_event(
+    db: &Transaction,
+    tx: &DecodedTransaction,
+    block_time: &str,
+    event_type: &str,
+    pool: &anchor_lang::prelude::Pubkey,
+    position: &anchor_lang::prelude::Pubkey,
+    user: &anchor_lang::prelude::Pubkey,
+    range: Option<(i32, i32, u128)>,
+    amount_a: u64,
+    amount_b: u64,
+) -> Result<()> {
+    db.execute(
+        "INSERT INTO liquidity_events (signature, pool_address, position_address, user_address, \
+         event_type, tick_lower, tick_upper, liquidity_delta, amount_a, amount_b, slot, block_time) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12) \
+         ON CONFLICT(signature) DO NOTHING",
+        params![
+            tx.signature,
+            pool.to_string(),
+            position.to_string(),
+            user.to_string(),
+            event_type,
+            range.map(|r| r.0),
+            range.map(|r| r.1),
+            range.map(|r| r.2.to_string()),
+            amount_a.to_string(),
+            amount_b.to_string(),
+            tx.slot as i64,
+            block_time,
+        ],
+    )?;
+    Ok(())
+}
+
+fn parse_amount(value: Option<&str>) -> u128 {
+    value.and_then(|v| v.parse().ok()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::prelude::Pubkey;
+    use suniswap::events::*;
+
+    fn tx(signature: &str, block_time: i64, events: Vec<SuniswapEvent>) -> DecodedTransaction {
+        DecodedTransaction {
+            signature: signature.to_string(),
+            slot: block_time as u64,
+            block_time,
+            events,
+        }
+    }
+
+    #[test]
+    fn test_apply_lifecycle_and_stats() {
+        let mut store = Store::open_in_memory().unwrap();
+        let pool = Pubkey::new_unique();
+        let position = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let now = 1_700_000_000;
+
+        store.apply(&tx("init", now - 100, vec![
+            SuniswapEvent::PoolInitialized(PoolInitializedEvent {
+                pool,
+                token_mint_a: Pubkey::new_unique(),
+                token_mint_b: Pubkey::new_unique(),
+                token_vault_a: Pubkey::new_unique(),
+                token_vault_b: Pubkey::new_unique(),
+                fee_tier: Pubkey::new_unique(),
+                fee_rate: 3000,
+                tick_spacing: 60,
+                sqrt_price_x64: 1 << 64,
+                tick_current: 0,
+            }),
+            SuniswapEvent::PositionOpened(PositionOpenedEvent {
+                pool, position, owner, tick_lower: -60, tick_upper: 60,
+            }),
+            SuniswapEvent::LiquidityIncreased(LiquidityIncreasedEvent {
+                pool, position, owner, tick_lower: -60, tick_upper: 60,
+                liquidity_delta: 1_000, amount_a: 3, amount_b: 3, pool_liquidity: 1_000,
+            }),
+        ])).unwrap();
+
+        let swap = SuniswapEvent::Swap(SwapEvent {
+            pool,
+            user: owner,
+            a_to_b: true,
+            amount_in: 250,
+            amount_out: 240,
+            fee_amount: 1,
+            protocol_fee: 0,
+            creator_fee: 0,
+            sqrt_price_x64: (1 << 64) - 5,
+            tick_current: -1,
+            liquidity: 1_000,
+        });
+        assert!(store.apply(&tx("swap", now - 10, vec![swap.clone()])).unwrap());
+        // Replaying the same signature is a no-op
+        assert!(!store.apply(&tx("swap", now - 10, vec![swap])).unwrap());
+
+        let (liquidity, tick): (String, i32) = store
+            .connection()
+            .query_row(
+                "SELECT liquidity, tick_current FROM pools WHERE address = ?1",
+                [pool.to_string()],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(liquidity, "1000");
+        assert_eq!(tick, -1);
+
+        let stats = store.global_stats(now).unwrap();
+        assert_eq!(stats.total_pools, 1);
+        assert_eq!(stats.tx_count_24h, 1);
+        assert_eq!(stats.volume_24h, 250.0);
+        assert_eq!(stats.active_positions, 1);
+        // ~3 of each token back the position, as deposited
+        assert!((stats.tvl_total - 6.0).abs() < 0.1, "{}", stats.tvl_total);
+        assert_eq!(stats.unique_users, 1);
+
+        // Outside the 24h window
+        let later = store.global_stats(now + 2 * 24 * 60 * 60).unwrap();
+        assert_eq!(later.tx_count_24h, 0);
+        assert_eq!(store.last_signature().unwrap().as_deref(), Some("swap"));
+    }
+
+    #[test]
+    fn test_split_and_merge_move_liquidity() {
+        let mut store = Store::open_in_memory().unwrap();
+        let pool = Pubkey::new_unique();
+        let position = Pubkey::new_unique();
+        let split = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let partner = Pubkey::new_unique();
+        let row = |store: &Store, address: &Pubkey| -> (String, String, bool) {
+            store
+                .connection()
+                .query_row(
+                    "SELECT liquidity, tokens_owed_a, is_open FROM positions WHERE address = ?1",
+                    [address.to_string()],
+                    |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+                )
+                .unwrap()
+        };
+
+        store.apply(&tx("open", 1, vec![
+            SuniswapEvent::PoolInitialized(PoolInitializedEvent {
+                pool,
+                token_mint_a: Pubkey::new_unique(),
+                token_mint_b: Pubkey::new_unique(),
+                token_vault_a: Pubkey::new_unique(),
+                token_vault_b: Pubkey::new_unique(),
+                fee_tier: Pubkey::new_unique(),
+                fee_rate: 3000,
+                tick_spacing: 60,
+                sqrt_price_x64: 1 << 64,
+                tick_current: 0,
+            }),
+            SuniswapEvent::PositionOpened(PositionOpenedEvent {
+                pool, position, owner, tick_lower: -60, tick_upper: 60,
+            }),
+            SuniswapEvent::LiquidityIncreased(LiquidityIncreasedEvent {
+                pool, position, owner, tick_lower: -60, tick_upper: 60,
+                liquidity_delta: 1_000, amount_a: 3, amount_b: 3, pool_liquidity: 1_000,
+            }),
+            SuniswapEvent::LiquidityDecreased(LiquidityDecreasedEvent {
+                pool, position, owner, tick_lower: -60, tick_upper: 60,
+                liquidity_delta: 200, amount_a: 8, amount_b: 0, pool_liquidity: 800,
+            }),
+        ])).unwrap();
+
+        store.apply(&tx("split", 2, vec![
+            SuniswapEvent::PositionOpened(PositionOpenedEvent {
+                pool, position: split, owner: partner, tick_lower: -60, tick_upper: 60,
+            }),
+            SuniswapEvent::PositionSplit(PositionSplitEvent {
+                pool, position, new_position: split, owner, new_owner: partner,
+                liquidity: 200, tokens_owed_a: 2, tokens_owed_b: 0,
+            }),
+        ])).unwrap();
+        assert_eq!(row(&store, &position), ("600".to_string(), "6".to_string(), true));
+        assert_eq!(row(&store, &split), ("200".to_string(), "2".to_string(), true));
+
+        store.apply(&tx("merge", 3, vec![
+            SuniswapEvent::PositionClosed(PositionClosedEvent { pool, position: split, owner: partner }),
+            SuniswapEvent::PositionsMerged(PositionsMergedEvent {
+                pool, position, merged_position: split, owner, merged_owner: partner,
+                liquidity: 200, tokens_owed_a: 2, tokens_owed_b: 0,
+            }),
+        ])).unwrap();
+        assert_eq!(row(&store, &position), ("800".to_string(), "8".to_string(), true));
+        assert_eq!(row(&store, &split), ("0".to_string(), "0".to_string(), false));
+    }
+}
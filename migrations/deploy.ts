@@ -78,11 +78,22 @@ module.exports = async function (provider: anchor.AnchorProvider) {
       console.log(`  [SKIP] Fee tier ${tier.feeRate / 100}% already exists`);
     } catch {
       try {
+        // New tiers are indexed in creation order
+        const { feeTierCount } = await program.account.suniswapConfig.fetch(configPda);
+        const [feeTierIndexPda] = PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("fee_tier_index"),
+            Buffer.from(new Uint8Array(new Uint16Array([feeTierCount]).buffer)),
+          ],
+          program.programId
+        );
+
         const tx = await program.methods
           .initializeFeeTier(tier.feeRate, tier.tickSpacing)
           .accounts({
             config: configPda,
             feeTier: feeTierPda,
+            feeTierIndex: feeTierIndexPda,
             authority: payer.publicKey,
             payer: payer.publicKey,
             systemProgram: SystemProgram.programId,
@@ -0,0 +1,240 @@
+//! Swap/liquidity state machine fuzzer
+//!
+//! Builds a pool from fuzzed positions (which populate its tick arrays), then
+//! applies a fuzzed sequence of `increase_liquidity`, `decrease_liquidity`,
+//! `swap` and `collect_fees` instructions through the program using the same
+//! in-process bank as the integration tests. Instructions are allowed to fail;
+//! the invariants are checked after every step either way.
+//!
+//! Run with: cargo +nightly fuzz run swap_liquidity
+
+#![no_main]
+
+#[path = "../../tests/common/mod.rs"]
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use arbitrary::Arbitrary;
+use common::*;
+use libfuzzer_sys::fuzz_target;
+use suniswap::constants::{MAX_TICK, MIN_TICK, TICK_ARRAY_SIZE};
+use suniswap::instructions::SwapParams;
+use suniswap::math::{get_amounts_for_liquidity, get_sqrt_price_at_tick, get_tick_at_sqrt_price};
+use suniswap::state::Position;
+
+/// (fee rate, tick spacing) pairs the pool is created with
+const FEE_TIERS: [(u32, u16); 3] = [(500, 10), (3000, 60), (10_000, 200)];
+
+const LP_COUNT: usize = 3;
+const MAX_POSITIONS: usize = 8;
+const MAX_OPERATIONS: usize = 64;
+const BALANCE: u64 = u64::MAX / 8;
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    fee_tier: u8,
+    /// Initial tick, in tick spacings
+    initial_tick: i16,
+    positions: Vec<PositionSeed>,
+    operations: Vec<Operation>,
+}
+
+/// A position placed relative to the initial tick, within three tick arrays of it
+/// so that swaps can reach its bounds
+#[derive(Arbitrary, Debug)]
+struct PositionSeed {
+    owner: u8,
+    /// Lower bound offset from the initial tick, in tick spacings
+    lower_offset: i8,
+    /// Width in tick spacings
+    width: u8,
+    liquidity: u64,
+}
+
+#[derive(Arbitrary, Debug)]
+enum Operation {
+    IncreaseLiquidity { position: u8, liquidity: u64 },
+    /// Removes `share / 255` of the position's liquidity
+    DecreaseLiquidity { position: u8, share: u8 },
+    Swap { a_to_b: bool, exact_input: bool, amount: u64, price_limit: Option<i16> },
+    CollectFees { position: u8 },
+}
+
+struct Harness {
+    ctx: TestContext,
+    pool: PoolFixture,
+    lps: Vec<User>,
+    trader: User,
+    /// (owner index, tick lower, tick upper)
+    positions: Vec<(usize, i32, i32)>,
+}
+
+impl Harness {
+    fn new(input: &Input) -> Option<Self> {
+        let (fee_rate, tick_spacing) = FEE_TIERS[input.fee_tier as usize % FEE_TIERS.len()];
+        let spacing = tick_spacing as i32;
+        // Positions span at most five tick arrays from the initial tick; keep a sixth
+        // clear of the range ends, where tick arrays cannot be initialized
+        let usable = (MAX_TICK / spacing - 6 * TICK_ARRAY_SIZE as i32) * spacing;
+        let initial_tick = (input.initial_tick as i32 * spacing).clamp(-usable, usable);
+
+        let (mut ctx, pool) = TestContext::with_pool(fee_rate, tick_spacing, initial_tick);
+        let lps: Vec<User> = (0..LP_COUNT).map(|_| ctx.create_user(&pool, BALANCE)).collect();
+        let trader = ctx.create_user(&pool, BALANCE);
+        let mut harness = Self { ctx, pool, lps, trader, positions: Vec::new() };
+
+        let base = initial_tick.div_euclid(spacing) * spacing;
+        for seed in input.positions.iter().take(MAX_POSITIONS) {
+            let owner = seed.owner as usize % LP_COUNT;
+            let tick_lower = base + seed.lower_offset as i32 % 24 * spacing;
+            let tick_upper = tick_lower + (seed.width as i32 % 16 + 1) * spacing;
+            if harness.positions.contains(&(owner, tick_lower, tick_upper)) {
+                continue;
+            }
+            let user = harness.lps[owner].clone();
+            harness.ctx.ensure_tick_array(&harness.pool, tick_lower);
+            harness.ctx.ensure_tick_array(&harness.pool, tick_upper);
+            harness.ctx.open_position(&harness.pool, &user, tick_lower, tick_upper).ok()?;
+            harness.positions.push((owner, tick_lower, tick_upper));
+            let _ = harness.ctx.increase_liquidity(
+                &harness.pool,
+                &user,
+                tick_lower,
+                tick_upper,
+                seed.liquidity as u128,
+                u64::MAX,
+                u64::MAX,
+            );
+        }
+        harness.check_invariants();
+        Some(harness)
+    }
+
+    fn position(&self, index: u8) -> Option<(User, i32, i32)> {
+        if self.positions.is_empty() {
+            return None;
+        }
+        let (owner, tick_lower, tick_upper) = self.positions[index as usize % self.positions.len()];
+        Some((self.lps[owner].clone(), tick_lower, tick_upper))
+    }
+
+    fn apply(&mut self, operation: &Operation) {
+        let pool = self.pool.clone();
+        match *operation {
+            Operation::IncreaseLiquidity { position, liquidity } => {
+                if let Some((owner, lower, upper)) = self.position(position) {
+                    let _ = self.ctx.increase_liquidity(
+                        &pool,
+                        &owner,
+                        lower,
+                        upper,
+                        liquidity as u128,
+                        u64::MAX,
+                        u64::MAX,
+                    );
+                }
+            }
+            Operation::DecreaseLiquidity { position, share } => {
+                if let Some((owner, lower, upper)) = self.position(position) {
+                    let key = position_pda(&pool.pool, &owner.key, lower, upper);
+                    let liquidity = self.ctx.zero_copy::<Position>(&key).liquidity;
+                    let delta = liquidity * share as u128 / u8::MAX as u128;
+                    let _ = self.ctx.decrease_liquidity(&pool, &owner, lower, upper, delta, 0, 0);
+                }
+            }
+            Operation::Swap { a_to_b, exact_input, amount, price_limit } => {
+                let amount = amount.min(i64::MAX as u64);
+                let mut params = if exact_input { exact_in(amount, a_to_b) } else { exact_out(amount, a_to_b) };
+                if let Some(limit) = price_limit {
+                    let tick = (limit as i32 * pool.tick_spacing as i32).clamp(MIN_TICK, MAX_TICK);
+                    params = SwapParams { sqrt_price_limit_x64: sqrt_price_at(tick), ..params };
+                }
+                // Near the ends of the range the arrays ahead of the price may not be
+                // initializable, in which case there is nothing to swap through
+                if let Some(tick_arrays) = self.swap_tick_arrays(a_to_b) {
+                    let ix = self.ctx.ix_swap(&pool, &self.trader, params, tick_arrays);
+                    let _ = self.ctx.bank.process(&ix);
+                }
+            }
+            Operation::CollectFees { position } => {
+                if let Some((owner, lower, upper)) = self.position(position) {
+                    let _ = self.ctx.collect_fees(&pool, &owner, lower, upper, u64::MAX, u64::MAX);
+                }
+            }
+        }
+        self.check_invariants();
+    }
+
+    /// Like `TestContext::swap_tick_arrays`, but returns None instead of panicking
+    /// when an array cannot be initialized
+    fn swap_tick_arrays(&mut self, a_to_b: bool) -> Option<[Pubkey; 3]> {
+        let tick = self.ctx.pool(&self.pool).tick_current;
+        let step = if a_to_b { -self.pool.ticks_per_array() } else { self.pool.ticks_per_array() };
+        let start = self.pool.tick_array_start(tick);
+        let mut tick_arrays = [Pubkey::default(); 3];
+        for (i, key) in tick_arrays.iter_mut().enumerate() {
+            let start_tick_index = start + step * i as i32;
+            *key = tick_array_pda(&self.pool.pool, start_tick_index);
+            if !self.ctx.exists(key) {
+                self.ctx.initialize_tick_array(&self.pool, start_tick_index).ok()?;
+            }
+        }
+        Some(tick_arrays)
+    }
+
+    fn check_invariants(&self) {
+        let state = self.ctx.pool(&self.pool);
+
+        // The current tick is the one containing the price. After crossing a tick
+        // downwards the price sits exactly on the crossed tick, which is then
+        // excluded and the current tick is the one below it
+        let tick = state.tick_current;
+        let tick_at_price = get_tick_at_sqrt_price(state.sqrt_price_x64).unwrap();
+        assert!(
+            tick_at_price == tick
+                || (tick_at_price == tick + 1
+                    && state.sqrt_price_x64 == get_sqrt_price_at_tick(tick + 1).unwrap()),
+            "tick {tick} inconsistent with sqrt price {} (tick {tick_at_price})",
+            state.sqrt_price_x64,
+        );
+
+        let mut active_liquidity = 0u128;
+        let (mut owed_a, mut owed_b) = (state.protocol_fees_a as u128, state.protocol_fees_b as u128);
+        for &(owner, lower, upper) in &self.positions {
+            let key = position_pda(&self.pool.pool, &self.lps[owner].key, lower, upper);
+            let position = self.ctx.zero_copy::<Position>(&key);
+            if lower <= tick && tick < upper {
+                active_liquidity += position.liquidity;
+            }
+            // Withdrawing every position at the current price must be possible
+            let (principal_a, principal_b) = get_amounts_for_liquidity(
+                state.sqrt_price_x64,
+                get_sqrt_price_at_tick(lower).unwrap(),
+                get_sqrt_price_at_tick(upper).unwrap(),
+                position.liquidity,
+                false,
+            )
+            .unwrap();
+            owed_a += position.tokens_owed_a as u128 + principal_a as u128;
+            owed_b += position.tokens_owed_b as u128 + principal_b as u128;
+        }
+        assert_eq!(
+            state.liquidity, active_liquidity,
+            "pool liquidity != in-range position liquidity at tick {tick}"
+        );
+
+        let vault_a = self.ctx.token_balance(&self.pool.vault_a) as u128;
+        let vault_b = self.ctx.token_balance(&self.pool.vault_b) as u128;
+        assert!(vault_a >= owed_a, "vault A {vault_a} below owed {owed_a}");
+        assert!(vault_b >= owed_b, "vault B {vault_b} below owed {owed_b}");
+    }
+}
+
+fuzz_target!(|input: Input| {
+    let Some(mut harness) = Harness::new(&input) else {
+        return;
+    };
+    for operation in input.operations.iter().take(MAX_OPERATIONS) {
+        harness.apply(operation);
+    }
+});
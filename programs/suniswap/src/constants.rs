@@ -11,11 +11,11 @@ pub const MIN_TICK: i32 = -443636;
 /// Maximum tick index (this gives price ~= infinity for practical purposes)
 pub const MAX_TICK: i32 = 443636;
 
-/// Minimum sqrt price (Q64.64 format)
-pub const MIN_SQRT_PRICE_X64: u128 = 4295048016;
+/// Minimum sqrt price (Q64.64 format), equal to get_sqrt_price_at_tick(MIN_TICK)
+pub const MIN_SQRT_PRICE_X64: u128 = 4295048017;
 
-/// Maximum sqrt price (Q64.64 format)
-pub const MAX_SQRT_PRICE_X64: u128 = 79226673515401279992447579055;
+/// Maximum sqrt price (Q64.64 format), equal to get_sqrt_price_at_tick(MAX_TICK)
+pub const MAX_SQRT_PRICE_X64: u128 = 79226673515401279992447579062;
 
 /// Q64 multiplier (2^64)
 pub const Q64: u128 = 1 << 64;
@@ -26,6 +26,9 @@ pub const Q128: u128 = 1 << 64 << 64; // Can't do 1 << 128 directly
 /// Protocol fee denominator (1/4 = 25% max protocol fee share)
 pub const PROTOCOL_FEE_DENOMINATOR: u8 = 4;
 
+/// Highest creator fee cap the protocol authority can set (25% of swap fees)
+pub const MAX_CREATOR_FEE_RATE: u8 = 25;
+
 /// Basis point denominator (10000 = 100%)
 pub const FEE_RATE_DENOMINATOR: u32 = 1_000_000;
 
@@ -63,15 +66,88 @@ pub mod hook_flags {
     pub const AFTER_REMOVE_LIQUIDITY: u8 = 0b10000000;
 }
 
+/// Position delegate permissions - each bit lets the delegate call a group of
+/// position instructions on the owner's behalf
+pub mod position_permissions {
+    /// collect_fees, and withdraw without removing liquidity
+    pub const COLLECT: u8 = 0b00000001;
+    /// decrease_liquidity; withdrawing liquidity needs COLLECT as well
+    pub const DECREASE: u8 = 0b00000010;
+    /// increase_liquidity, increase_liquidity_by_amounts and compound_position
+    pub const INCREASE: u8 = 0b00000100;
+    /// reposition
+    pub const REPOSITION: u8 = 0b00001000;
+    pub const ALL: u8 = COLLECT | DECREASE | INCREASE | REPOSITION;
+}
+
 /// Account seeds for PDA derivation
 pub mod seeds {
     pub const CONFIG_SEED: &[u8] = b"config";
     pub const FEE_TIER_SEED: &[u8] = b"fee_tier";
+    pub const FEE_TIER_INDEX_SEED: &[u8] = b"fee_tier_index";
     pub const POOL_SEED: &[u8] = b"pool";
     pub const TICK_ARRAY_SEED: &[u8] = b"tick_array";
     pub const POSITION_SEED: &[u8] = b"position";
     pub const ORACLE_SEED: &[u8] = b"oracle";
     pub const POOL_VAULT_SEED: &[u8] = b"pool_vault";
+    pub const MANAGED_VAULT_SEED: &[u8] = b"managed_vault";
+    pub const VAULT_SHARE_MINT_SEED: &[u8] = b"vault_share_mint";
+    pub const VAULT_RESERVE_SEED: &[u8] = b"vault_reserve";
+    pub const NATIVE_SOL_SEED: &[u8] = b"native_sol";
+    pub const PROTOCOL_FEE_CONFIG_SEED: &[u8] = b"protocol_fee_config";
+    pub const POOL_CREATION_CONFIG_SEED: &[u8] = b"pool_creation_config";
+    pub const TRADER_ALLOWLIST_SEED: &[u8] = b"trader_allowlist";
+    pub const ALLOWED_TRADER_SEED: &[u8] = b"allowed_trader";
+}
+
+/// Protocol fee split constants
+pub mod protocol_fee_split {
+    /// Most recipients protocol fees can be split between
+    pub const MAX_RECIPIENTS: usize = 4;
+    /// Recipient weights are in basis points and sum to this
+    pub const WEIGHT_DENOMINATOR: u16 = 10_000;
+}
+
+/// Pool creation access constants
+pub mod pool_creation {
+    /// Anyone may create pools
+    pub const MODE_OPEN: u8 = 0;
+    /// Only allowlisted keys may create pools
+    pub const MODE_ALLOWLIST: u8 = 1;
+    /// Anyone may create pools by paying the creation fee to the treasury
+    pub const MODE_FEE: u8 = 2;
+    /// Most creator keys the allowlist can hold
+    pub const MAX_ALLOWED_CREATORS: usize = 16;
+}
+
+/// Swap referral fee constants
+pub mod referral {
+    /// Referral fee rates are in basis points
+    pub const FEE_RATE_DENOMINATOR: u16 = 10_000;
+    /// Highest cap the protocol authority can set (5%)
+    pub const MAX_FEE_RATE: u16 = 500;
+}
+
+/// Managed vault constants
+pub mod managed_vault {
+    /// Most positions a vault can hold at once
+    pub const MAX_POSITIONS: usize = 3;
+    /// Vault fee rates are in basis points
+    pub const FEE_RATE_DENOMINATOR: u16 = 10_000;
+    /// Highest share of harvested fees the treasury can take (50%)
+    pub const MAX_PERFORMANCE_FEE_RATE: u16 = 5_000;
+    /// Highest yearly management fee (5%)
+    pub const MAX_MANAGEMENT_FEE_RATE: u16 = 500;
+    /// Management fees accrue per second over a 365 day year
+    pub const SECONDS_PER_YEAR: u64 = 31_536_000;
+    /// Decimals of vault share mints
+    pub const SHARE_DECIMALS: u8 = 9;
+    /// Shares locked in a vault by its first deposit, so the share price
+    /// can't be inflated off a near-empty supply
+    pub const MINIMUM_SHARES: u64 = 1_000;
+    /// Most ticks the price may sit from the pool's TWAP for vault deposits
+    /// and withdrawals (about 1%)
+    pub const MAX_TWAP_DEVIATION_TICKS: u32 = 100;
 }
 
 /// Oracle constants
@@ -80,4 +156,6 @@ pub mod oracle {
     pub const OBSERVATION_CARDINALITY_MAX: u16 = 65535;
     /// Initial observation cardinality
     pub const OBSERVATION_CARDINALITY_INIT: u16 = 1;
+    /// Observation slots in an oracle account
+    pub const OBSERVATION_CAPACITY: usize = 32;
 }
@@ -335,4 +335,148 @@ pub enum SuniswapError {
     /// Input exceeds maximum
     #[msg("Input exceeds maximum")]
     InputExceedsMaximum, // 6070
+
+    /// Tick has no liquidity referencing it
+    #[msg("Tick not initialized")]
+    TickNotInitialized, // 6071
+
+    /// Fee tier is disabled for new pools
+    #[msg("Fee tier disabled")]
+    FeeTierDisabled, // 6072
+
+    /// Adaptive fee bounds or decay period are invalid
+    #[msg("Invalid adaptive fee parameters")]
+    InvalidAdaptiveFeeParams, // 6073
+
+    /// Pool is not at the legacy size migrate_pool expects
+    #[msg("Pool already migrated")]
+    PoolAlreadyMigrated, // 6074
+
+    /// Pool price moved outside the caller's accepted band
+    #[msg("Price outside accepted band")]
+    PriceOutsideBand, // 6075
+
+    /// A delegate tried to pay out to accounts the position owner does not own
+    #[msg("Token account not owned by position owner")]
+    InvalidTokenAccountOwner, // 6076
+
+    /// Delegate permissions contain unknown bits
+    #[msg("Invalid delegate permissions")]
+    InvalidDelegatePermissions, // 6077
+
+    /// Vault fee rates above their caps, or position limits that aren't a valid range
+    #[msg("Invalid vault parameters")]
+    InvalidVaultParams, // 6078
+
+    /// Signer is not the vault's strategist
+    #[msg("Not the vault strategist")]
+    NotVaultStrategist, // 6079
+
+    /// Remaining accounts don't match the vault's positions
+    #[msg("Vault position accounts do not match the vault")]
+    InvalidVaultPositions, // 6080
+
+    /// The vault already holds the most positions it can
+    #[msg("Vault position limit reached")]
+    VaultPositionLimit, // 6081
+
+    /// Position range falls outside the vault's tick limits
+    #[msg("Range outside vault limits")]
+    VaultRangeOutsideLimits, // 6082
+
+    /// A deposit or withdrawal would move zero shares
+    #[msg("Zero vault shares")]
+    ZeroShares, // 6083
+
+    /// Fee recipient account is not owned by the vault treasury
+    #[msg("Token account not owned by vault treasury")]
+    InvalidVaultTreasury, // 6084
+
+    /// Vault does not belong to the pool, or a reserve doesn't match the vault
+    #[msg("Invalid managed vault")]
+    InvalidManagedVault, // 6085
+
+    /// A vault's first deposit must mint more than the shares it locks
+    #[msg("Vault deposit too small")]
+    VaultDepositTooSmall, // 6086
+
+    /// A user token account was omitted for a mint that isn't native SOL
+    #[msg("Token account can only be omitted for native SOL")]
+    NotNativeMint, // 6087
+
+    /// Native SOL handling needs the signer's temporary wSOL account and the system program
+    #[msg("Missing or invalid native SOL account")]
+    InvalidNativeSolAccount, // 6088
+
+    /// Referral fee rate is above the config cap, or the cap above its maximum
+    #[msg("Referral fee too high")]
+    ReferralFeeTooHigh, // 6089
+
+    /// Referral fee requested without the config and a referrer account for a swap token
+    #[msg("Invalid referral account")]
+    InvalidReferralAccount, // 6090
+
+    /// Transaction landed after its `deadline_unix_ts`
+    #[msg("Transaction deadline exceeded")]
+    DeadlineExceeded, // 6091
+
+    /// Transaction landed after its `max_slot`
+    #[msg("Transaction max slot exceeded")]
+    MaxSlotExceeded, // 6092
+
+    /// Protocol fee recipients must number 1 to MAX_RECIPIENTS with nonzero weights summing to 100%
+    #[msg("Invalid protocol fee recipients")]
+    InvalidProtocolFeeRecipients, // 6093
+
+    /// Account passed for a protocol fee recipient isn't its associated token account
+    #[msg("Invalid protocol fee recipient account")]
+    InvalidProtocolFeeRecipientAccount, // 6094
+
+    /// Creator fee rate is above the config cap, or the cap above its maximum
+    #[msg("Creator fee too high")]
+    CreatorFeeTooHigh, // 6095
+
+    /// Signer is not the pool's creator, or a creator fee was set without a creator
+    #[msg("Invalid pool creator")]
+    InvalidPoolCreator, // 6096
+
+    /// Pool creation is allowlisted and the payer isn't on the list
+    #[msg("Pool creator not allowed")]
+    PoolCreatorNotAllowed, // 6097
+
+    /// Unknown pool creation mode, or one whose allowlist or fee isn't set up
+    #[msg("Invalid pool creation mode")]
+    InvalidPoolCreationMode, // 6098
+
+    /// Pool creation allowlist is longer than MAX_ALLOWED_CREATORS
+    #[msg("Invalid pool creation allowlist")]
+    InvalidPoolCreationAllowlist, // 6099
+
+    /// Pool creation config, fee mint or treasury account missing or not the configured one
+    #[msg("Invalid pool creation fee account")]
+    InvalidPoolCreationFeeAccount, // 6100
+
+    /// Pool is allowlisted and the signer's allowed trader entry is missing or for another wallet
+    #[msg("Trader not allowed")]
+    TraderNotAllowed, // 6101
+
+    /// Signer is not the trader allowlist's admin
+    #[msg("Not trader allowlist admin")]
+    NotTraderAllowlistAdmin, // 6102
+
+    /// Swap would move the price more ticks than the pool's per-swap or per-slot cap
+    #[msg("Price move limit exceeded")]
+    PriceMoveLimitExceeded, // 6103
+
+    /// Swap would leave the price further from the oracle TWAP than allowed
+    #[msg("TWAP deviation exceeded")]
+    TwapDeviationExceeded, // 6104
+
+    /// Oracle account missing or not the pool's
+    #[msg("Invalid oracle account")]
+    InvalidOracleAccount, // 6105
+
+    /// TWAP window must be nonzero
+    #[msg("Invalid TWAP window")]
+    InvalidTwapWindow, // 6106
 }
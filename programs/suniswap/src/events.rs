@@ -0,0 +1,434 @@
+//! Program Events
+//!
+//! Anchor events emitted by each state-changing instruction.
+//! Off-chain indexers decode these from `Program data:` log lines, so every
+//! event carries the account keys and post-state needed to rebuild the
+//! pool and position tables without reading accounts.
+
+use anchor_lang::prelude::*;
+use crate::state::ProtocolFeeRecipient;
+
+/// Emitted when a new pool is created
+#[event]
+#[derive(Debug, Clone)]
+pub struct PoolInitializedEvent {
+    pub pool: Pubkey,
+    pub token_mint_a: Pubkey,
+    pub token_mint_b: Pubkey,
+    pub token_vault_a: Pubkey,
+    pub token_vault_b: Pubkey,
+    pub fee_tier: Pubkey,
+    pub fee_rate: u32,
+    pub tick_spacing: u16,
+    pub sqrt_price_x64: u128,
+    pub tick_current: i32,
+}
+
+/// Emitted when a position account is opened
+#[event]
+#[derive(Debug, Clone)]
+pub struct PositionOpenedEvent {
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+}
+
+/// Emitted when an empty position account is closed
+#[event]
+#[derive(Debug, Clone)]
+pub struct PositionClosedEvent {
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+}
+
+/// Emitted when liquidity is added to a position
+#[event]
+#[derive(Debug, Clone)]
+pub struct LiquidityIncreasedEvent {
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub liquidity_delta: u128,
+    pub amount_a: u64,
+    pub amount_b: u64,
+    /// Pool liquidity after the update
+    pub pool_liquidity: u128,
+}
+
+/// Emitted when a position reinvests its owed tokens
+/// Amounts are taken from `tokens_owed_*`, not transferred
+#[event]
+#[derive(Debug, Clone)]
+pub struct PositionCompoundedEvent {
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub liquidity_delta: u128,
+    pub amount_a: u64,
+    pub amount_b: u64,
+    /// Owed tokens left on the position
+    pub tokens_owed_a: u64,
+    pub tokens_owed_b: u64,
+}
+
+/// Emitted when a position's liquidity and fees move into a new tick range
+/// The old position is closed and any tokens the new range cannot use are refunded
+#[event]
+#[derive(Debug, Clone)]
+pub struct PositionRepositionedEvent {
+    pub pool: Pubkey,
+    pub old_position: Pubkey,
+    pub new_position: Pubkey,
+    pub owner: Pubkey,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    /// Liquidity of the new position
+    pub liquidity: u128,
+    /// Amounts deposited into the new position
+    pub amount_a: u64,
+    pub amount_b: u64,
+    /// Amounts returned to the owner
+    pub refund_a: u64,
+    pub refund_b: u64,
+}
+
+/// Emitted when part of a position moves into a new position over the same range
+/// Amounts are the owed tokens that moved with the liquidity
+#[event]
+#[derive(Debug, Clone)]
+pub struct PositionSplitEvent {
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub new_position: Pubkey,
+    pub owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub liquidity: u128,
+    pub tokens_owed_a: u64,
+    pub tokens_owed_b: u64,
+}
+
+/// Emitted when one position is folded into another over the same range
+/// `merged_position` is closed; amounts are what moved into `position`
+#[event]
+#[derive(Debug, Clone)]
+pub struct PositionsMergedEvent {
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub merged_position: Pubkey,
+    pub owner: Pubkey,
+    pub merged_owner: Pubkey,
+    pub liquidity: u128,
+    pub tokens_owed_a: u64,
+    pub tokens_owed_b: u64,
+}
+
+/// Emitted when a position's delegate or its permissions change
+/// A zero `permissions` means the delegate can no longer act
+#[event]
+#[derive(Debug, Clone)]
+pub struct PositionDelegateUpdatedEvent {
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+    pub permissions: u8,
+}
+
+/// Emitted when liquidity is removed from a position
+/// Amounts are credited to `tokens_owed_*`, not transferred
+#[event]
+#[derive(Debug, Clone)]
+pub struct LiquidityDecreasedEvent {
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub liquidity_delta: u128,
+    pub amount_a: u64,
+    pub amount_b: u64,
+    /// Pool liquidity after the update
+    pub pool_liquidity: u128,
+}
+
+/// Emitted when owed tokens are transferred out of a position
+#[event]
+#[derive(Debug, Clone)]
+pub struct FeesCollectedEvent {
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+}
+
+/// Emitted after every swap with the resulting pool price
+#[event]
+#[derive(Debug, Clone)]
+pub struct SwapEvent {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub a_to_b: bool,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    /// Total LP, protocol and creator fee paid in the input token
+    pub fee_amount: u64,
+    /// Protocol share of `fee_amount`
+    pub protocol_fee: u64,
+    /// Pool creator share of `fee_amount`
+    pub creator_fee: u64,
+    pub sqrt_price_x64: u128,
+    pub tick_current: i32,
+    pub liquidity: u128,
+}
+
+/// Emitted when a swap pays a referral fee
+#[event]
+#[derive(Debug, Clone)]
+pub struct ReferralFeeEvent {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub referrer_token_account: Pubkey,
+    /// Token the fee was paid in, the swap's input or output
+    pub mint: Pubkey,
+    pub amount: u64,
+    /// Basis points
+    pub fee_rate: u16,
+}
+
+/// Emitted when the protocol authority changes a fee tier's rate or status
+#[event]
+#[derive(Debug, Clone)]
+pub struct FeeTierUpdatedEvent {
+    pub fee_tier: Pubkey,
+    pub fee_rate: u32,
+    pub disabled: bool,
+}
+
+/// Emitted when the protocol authority changes the referral fee cap
+#[event]
+#[derive(Debug, Clone)]
+pub struct MaxReferralFeeRateUpdatedEvent {
+    pub max_referral_fee_rate: u16,
+}
+
+/// Emitted when the protocol authority changes the creator fee cap
+#[event]
+#[derive(Debug, Clone)]
+pub struct MaxCreatorFeeRateUpdatedEvent {
+    pub max_creator_fee_rate: u8,
+}
+
+/// Emitted when the protocol authority changes a pool's fee settings
+#[event]
+#[derive(Debug, Clone)]
+pub struct PoolFeeUpdatedEvent {
+    pub pool: Pubkey,
+    pub fee_rate: u32,
+    /// Whether `fee_rate` is charged instead of the fee tier's rate
+    pub fee_rate_override: bool,
+    pub adaptive_fee_enabled: bool,
+    pub min_fee_rate: u32,
+    pub max_fee_rate: u32,
+}
+
+/// Emitted when the fee authority withdraws protocol fees
+#[event]
+#[derive(Debug, Clone)]
+pub struct ProtocolFeesCollectedEvent {
+    pub pool: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+}
+
+/// Emitted when a pool's creator withdraws creator fees
+#[event]
+#[derive(Debug, Clone)]
+pub struct CreatorFeesCollectedEvent {
+    pub pool: Pubkey,
+    pub creator: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+}
+
+/// Emitted when the protocol fee recipients are set
+#[event]
+#[derive(Debug, Clone)]
+pub struct ProtocolFeeRecipientsUpdatedEvent {
+    pub recipients: Vec<ProtocolFeeRecipient>,
+}
+
+/// Emitted for each recipient's share when protocol fees are distributed
+#[event]
+#[derive(Debug, Clone)]
+pub struct ProtocolFeesDistributedEvent {
+    pub pool: Pubkey,
+    pub recipient: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+}
+
+/// Emitted when a managed vault is created
+#[event]
+#[derive(Debug, Clone)]
+pub struct VaultInitializedEvent {
+    pub vault: Pubkey,
+    pub pool: Pubkey,
+    pub share_mint: Pubkey,
+    pub reserve_a: Pubkey,
+    pub reserve_b: Pubkey,
+}
+
+/// Emitted when a managed vault is created or its parameters change
+#[event]
+#[derive(Debug, Clone)]
+pub struct VaultParamsUpdatedEvent {
+    pub vault: Pubkey,
+    pub strategist: Pubkey,
+    pub treasury: Pubkey,
+    pub performance_fee_rate: u16,
+    pub management_fee_rate: u16,
+    pub tick_lower_limit: i32,
+    pub tick_upper_limit: i32,
+}
+
+/// Emitted when tokens are deposited into a managed vault for shares
+#[event]
+#[derive(Debug, Clone)]
+pub struct VaultDepositEvent {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub shares: u64,
+    pub amount_a: u64,
+    pub amount_b: u64,
+}
+
+/// Emitted when shares are burned for their part of a managed vault
+#[event]
+#[derive(Debug, Clone)]
+pub struct VaultWithdrawEvent {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub shares: u64,
+    pub amount_a: u64,
+    pub amount_b: u64,
+}
+
+/// Emitted when a vault position's fees are moved into the vault reserves
+/// `treasury_fee_*` is the performance fee set aside out of `amount_*`
+#[event]
+#[derive(Debug, Clone)]
+pub struct VaultFeesHarvestedEvent {
+    pub vault: Pubkey,
+    pub position: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+    pub treasury_fee_a: u64,
+    pub treasury_fee_b: u64,
+}
+
+/// Emitted when the strategist moves liquidity between a vault position and
+/// the vault reserves
+#[event]
+#[derive(Debug, Clone)]
+pub struct VaultRebalancedEvent {
+    pub vault: Pubkey,
+    pub position: Pubkey,
+    pub liquidity_removed: u128,
+    pub liquidity_added: u128,
+    pub amount_a_added: u64,
+    pub amount_b_added: u64,
+}
+
+/// Emitted when accrued vault fees are paid to the treasury
+#[event]
+#[derive(Debug, Clone)]
+pub struct VaultFeesCollectedEvent {
+    pub vault: Pubkey,
+    pub treasury: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+    pub shares: u64,
+}
+
+/// Emitted when the protocol authority switches who may create pools
+#[event]
+#[derive(Debug, Clone)]
+pub struct PoolCreationModeUpdatedEvent {
+    pub pool_creation_mode: u8,
+}
+
+/// Emitted when the pool creation allowlist is set
+#[event]
+#[derive(Debug, Clone)]
+pub struct PoolCreationAllowlistUpdatedEvent {
+    pub creators: Vec<Pubkey>,
+}
+
+/// Emitted when the pool creation fee is set
+#[event]
+#[derive(Debug, Clone)]
+pub struct PoolCreationFeeUpdatedEvent {
+    /// None for native SOL
+    pub fee_mint: Option<Pubkey>,
+    pub fee_amount: u64,
+    pub treasury: Pubkey,
+}
+
+/// Emitted when a pool creator pays the creation fee
+#[event]
+#[derive(Debug, Clone)]
+pub struct PoolCreationFeePaidEvent {
+    pub pool: Pubkey,
+    pub payer: Pubkey,
+    /// None for native SOL
+    pub fee_mint: Option<Pubkey>,
+    pub amount: u64,
+    pub treasury: Pubkey,
+}
+
+/// Emitted when a pool's trader allowlist is created, handed to a new admin,
+/// or switched on or off
+#[event]
+#[derive(Debug, Clone)]
+pub struct TraderAllowlistUpdatedEvent {
+    pub pool: Pubkey,
+    pub admin: Pubkey,
+    pub enabled: bool,
+}
+
+/// Emitted when a wallet is added to or removed from a pool's trader allowlist
+#[event]
+#[derive(Debug, Clone)]
+pub struct AllowedTraderUpdatedEvent {
+    pub pool: Pubkey,
+    pub wallet: Pubkey,
+    pub allowed: bool,
+}
+
+/// Emitted when a pool's circuit breaker caps are changed
+#[event]
+#[derive(Debug, Clone)]
+pub struct PoolCircuitBreakerUpdatedEvent {
+    pub pool: Pubkey,
+    /// 0 = unlimited
+    pub max_tick_move_per_swap: u32,
+    /// 0 = unlimited
+    pub max_tick_move_per_slot: u32,
+}
+
+/// Emitted when a pool's oracle is initialized or its TWAP guard is changed
+#[event]
+#[derive(Debug, Clone)]
+pub struct PoolTwapGuardUpdatedEvent {
+    pub pool: Pubkey,
+    pub oracle: Pubkey,
+    pub twap_window: u32,
+    /// 0 = only swaps that request the guard are checked
+    pub max_twap_deviation_ticks: u32,
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::state::{Pool, TraderAllowlist, AllowedTrader};
+use crate::constants::seeds;
+use crate::errors::SuniswapError;
+use crate::events::AllowedTraderUpdatedEvent;
+
+/// Allow a wallet to swap and add liquidity in an allowlisted pool
+/// Only callable by the allowlist's admin
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct AddAllowedTrader<'info> {
+    /// The gated pool (zero-copy)
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// The pool's trader allowlist
+    #[account(
+        seeds = [seeds::TRADER_ALLOWLIST_SEED, pool.key().as_ref()],
+        bump = trader_allowlist.bump,
+        has_one = admin @ SuniswapError::NotTraderAllowlistAdmin,
+    )]
+    pub trader_allowlist: Account<'info, TraderAllowlist>,
+
+    /// The wallet's allowlist entry to create
+    #[account(
+        init,
+        payer = payer,
+        space = AllowedTrader::LEN,
+        seeds = [seeds::ALLOWED_TRADER_SEED, pool.key().as_ref(), wallet.as_ref()],
+        bump
+    )]
+    pub allowed_trader: Account<'info, AllowedTrader>,
+
+    /// Allowlist admin
+    pub admin: Signer<'info>,
+
+    /// The payer for account creation
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+/// Add allowed trader handler
+pub fn handler(ctx: Context<AddAllowedTrader>, wallet: Pubkey) -> Result<()> {
+    let allowed_trader = &mut ctx.accounts.allowed_trader;
+    allowed_trader.pool = ctx.accounts.pool.key();
+    allowed_trader.wallet = wallet;
+    allowed_trader.bump = ctx.bumps.allowed_trader;
+
+    emit!(AllowedTraderUpdatedEvent {
+        pool: allowed_trader.pool,
+        wallet,
+        allowed: true,
+    });
+
+    msg!("Allowed trader added: {}", wallet);
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use crate::state::{Pool, Position};
 use crate::errors::SuniswapError;
+use crate::events::PositionClosedEvent;
 
 /// Close an empty position and reclaim rent
 /// Note: For zero-copy accounts, we need to handle closing differently
@@ -52,9 +53,30 @@ pub fn handler(ctx: Context<ClosePosition>) -> Result<()> {
     drop(position);
 
     // Close the account and transfer rent to receiver
-    let position_account_info = ctx.accounts.position.to_account_info();
-    let receiver_account_info = ctx.accounts.receiver.to_account_info();
+    close_position_account(
+        &ctx.accounts.position.to_account_info(),
+        &ctx.accounts.receiver.to_account_info(),
+    )?;
 
+    emit!(PositionClosedEvent {
+        pool: pool_key,
+        position: ctx.accounts.position.key(),
+        owner: ctx.accounts.owner.key(),
+    });
+
+    msg!("Position closed");
+    msg!("Pool: {}", pool_key);
+    msg!("Position owner: {}", ctx.accounts.owner.key());
+    msg!("Rent returned to: {}", ctx.accounts.receiver.key());
+
+    Ok(())
+}
+
+/// Move an empty position's rent to `receiver` and release the account
+pub(crate) fn close_position_account(
+    position_account_info: &AccountInfo,
+    receiver_account_info: &AccountInfo,
+) -> Result<()> {
     // Transfer lamports
     let dest_starting_lamports = receiver_account_info.lamports();
     **receiver_account_info.lamports.borrow_mut() = dest_starting_lamports
@@ -66,10 +88,5 @@ pub fn handler(ctx: Context<ClosePosition>) -> Result<()> {
     position_account_info.assign(&anchor_lang::solana_program::system_program::ID);
     position_account_info.resize(0)?;
 
-    msg!("Position closed");
-    msg!("Pool: {}", pool_key);
-    msg!("Position owner: {}", ctx.accounts.owner.key());
-    msg!("Rent returned to: {}", ctx.accounts.receiver.key());
-
     Ok(())
 }
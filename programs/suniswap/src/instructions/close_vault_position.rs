@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use crate::state::{Pool, Position, ManagedVault};
+use crate::errors::SuniswapError;
+use crate::events::PositionClosedEvent;
+use crate::instructions::close_position::close_position_account;
+
+/// Close an emptied vault position and drop it from the vault
+/// Only callable by the vault strategist, who receives the rent
+#[derive(Accounts)]
+pub struct CloseVaultPosition<'info> {
+    /// The vault's pool (zero-copy)
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// The vault holding the position
+    #[account(
+        mut,
+        constraint = vault.pool == pool.key() @ SuniswapError::InvalidManagedVault,
+        constraint = vault.is_strategist(&strategist.key()) @ SuniswapError::NotVaultStrategist,
+    )]
+    pub vault: Account<'info, ManagedVault>,
+
+    /// The position to close (zero-copy)
+    #[account(mut)]
+    pub position: AccountLoader<'info, Position>,
+
+    /// Vault strategist
+    #[account(mut)]
+    pub strategist: Signer<'info>,
+}
+
+/// Close vault position handler
+pub fn handler(ctx: Context<CloseVaultPosition>) -> Result<()> {
+    let pool_key = ctx.accounts.pool.key();
+    let position_key = ctx.accounts.position.key();
+    let vault = &mut ctx.accounts.vault;
+
+    let index = vault
+        .active_positions()
+        .iter()
+        .position(|key| *key == position_key)
+        .ok_or(SuniswapError::InvalidVaultPositions)?;
+
+    // Rebalancing pays everything out, so an empty vault position owes nothing
+    let position = ctx.accounts.position.load()?;
+    require!(
+        position.liquidity == 0,
+        SuniswapError::PositionHasLiquidity
+    );
+    require!(
+        position.tokens_owed_a == 0 && position.tokens_owed_b == 0,
+        SuniswapError::PositionHasOwedTokens
+    );
+    drop(position);
+
+    close_position_account(
+        &ctx.accounts.position.to_account_info(),
+        &ctx.accounts.strategist.to_account_info(),
+    )?;
+
+    // Keep the remaining positions in order
+    let count = vault.position_count as usize;
+    vault.positions.copy_within(index + 1..count, index);
+    vault.positions[count - 1] = Pubkey::default();
+    vault.position_count -= 1;
+
+    emit!(PositionClosedEvent {
+        pool: pool_key,
+        position: position_key,
+        owner: vault.key(),
+    });
+
+    msg!("Vault position closed, {} left", vault.position_count);
+
+    Ok(())
+}
@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{TokenAccount, TokenInterface, Mint, TransferChecked, transfer_checked};
 use crate::state::{Pool, Position, TickArray, Tick, FeeTier};
-use crate::constants::seeds;
+use crate::constants::{seeds, position_permissions};
 use crate::errors::SuniswapError;
+use crate::instructions::native_sol::NativeSol;
+use crate::events::FeesCollectedEvent;
 
 /// Collect accumulated fees from a position
 #[derive(Accounts)]
@@ -37,19 +39,28 @@ pub struct CollectFees<'info> {
     #[account(mut)]
     pub token_vault_b: InterfaceAccount<'info, TokenAccount>,
 
-    /// User's token A account
+    /// User's token A account, omitted to receive native SOL
     #[account(mut)]
-    pub user_token_a: InterfaceAccount<'info, TokenAccount>,
+    pub user_token_a: Option<InterfaceAccount<'info, TokenAccount>>,
 
-    /// User's token B account
+    /// User's token B account, omitted to receive native SOL
     #[account(mut)]
-    pub user_token_b: InterfaceAccount<'info, TokenAccount>,
+    pub user_token_b: Option<InterfaceAccount<'info, TokenAccount>>,
 
-    /// Position owner
+    /// Position owner, or a delegate allowed to collect
+    #[account(mut)]
     pub owner: Signer<'info>,
 
     /// Token program
     pub token_program: Interface<'info, TokenInterface>,
+
+    /// CHECK: Temporary wSOL account, only needed when a user token account
+    /// is omitted; validated and created by the handler
+    #[account(mut)]
+    pub native_sol_account: Option<UncheckedAccount<'info>>,
+
+    /// System program, only needed with `native_sol_account`
+    pub system_program: Option<Program<'info, System>>,
 }
 
 /// Collect fees handler
@@ -85,61 +96,29 @@ pub fn handler(
         SuniswapError::InvalidVault
     );
 
-    let tick_current = pool.tick_current;
-    let tick_spacing = pool.tick_spacing;
-    let fee_growth_global_a = pool.fee_growth_global_a_x128;
-    let fee_growth_global_b = pool.fee_growth_global_b_x128;
     let pool_bump = pool.bump;
     let token_mint_a_bytes = pool.token_mint_a;
     let token_mint_b_bytes = pool.token_mint_b;
     drop(pool);
 
-    // Load and validate position
-    let position = ctx.accounts.position.load()?;
-    require!(
-        position.pool == pool_key.to_bytes(),
-        SuniswapError::InvalidPosition
-    );
-    require!(
-        position.owner == ctx.accounts.owner.key().to_bytes(),
-        SuniswapError::InvalidPositionOwner
-    );
-
-    let tick_lower = position.tick_lower;
-    let tick_upper = position.tick_upper;
-    drop(position);
-
-    // Validate tick arrays
-    let tick_array_lower = ctx.accounts.tick_array_lower.load()?;
-    require!(
-        tick_array_lower.pool == pool_key.to_bytes(),
-        SuniswapError::InvalidTickArray
-    );
-
-    let tick_array_upper = ctx.accounts.tick_array_upper.load()?;
-    require!(
-        tick_array_upper.pool == pool_key.to_bytes(),
-        SuniswapError::InvalidTickArray
-    );
-
-    // Calculate fee growth inside
-    let (fee_growth_inside_a, fee_growth_inside_b) = calculate_fee_growth_inside(
-        &tick_array_lower,
-        &tick_array_upper,
-        tick_lower,
-        tick_upper,
-        tick_current,
-        fee_growth_global_a,
-        fee_growth_global_b,
-        tick_spacing,
+    // Settle fees earned since the position was last updated
+    settle_position_fees(
+        &ctx.accounts.pool,
+        &ctx.accounts.position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        ctx.accounts.owner.key(),
+        position_permissions::COLLECT,
+    )?;
+    validate_payout_accounts(
+        &ctx.accounts.position,
+        ctx.accounts.owner.key(),
+        ctx.accounts.user_token_a.as_ref(),
+        ctx.accounts.user_token_b.as_ref(),
     )?;
-    drop(tick_array_lower);
-    drop(tick_array_upper);
 
-    // Update position and calculate amounts
+    // Calculate amounts
     let mut position = ctx.accounts.position.load_mut()?;
-    position.update_owed_tokens(fee_growth_inside_a, fee_growth_inside_b)?;
-
     let amount_a = position.tokens_owed_a.min(amount_a_requested);
     let amount_b = position.tokens_owed_b.min(amount_b_requested);
 
@@ -152,6 +131,7 @@ pub fn handler(
 
     let remaining_a = position.tokens_owed_a;
     let remaining_b = position.tokens_owed_b;
+    let owner = position.owner_pubkey();
     drop(position);
 
     // Transfer tokens from vaults to user
@@ -159,50 +139,162 @@ pub fn handler(
         seeds::POOL_SEED,
         &token_mint_a_bytes,
         &token_mint_b_bytes,
-        &fee_tier.fee_rate.to_le_bytes(),
+        &fee_tier.seed_bytes(),
         &[pool_bump],
     ];
-
-    if amount_a > 0 {
-        transfer_checked(
+    let native_sol = NativeSol {
+        user: &ctx.accounts.owner,
+        native_account: ctx.accounts.native_sol_account.as_ref(),
+        system_program: ctx.accounts.system_program.as_ref(),
+        token_program: &ctx.accounts.token_program,
+    };
+
+    match &ctx.accounts.user_token_a {
+        Some(user_token_a) if amount_a > 0 => transfer_checked(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
                 TransferChecked {
                     from: ctx.accounts.token_vault_a.to_account_info(),
                     mint: ctx.accounts.token_mint_a.to_account_info(),
-                    to: ctx.accounts.user_token_a.to_account_info(),
+                    to: user_token_a.to_account_info(),
                     authority: ctx.accounts.pool.to_account_info(),
                 },
                 &[pool_seeds],
             ),
             amount_a,
             ctx.accounts.token_mint_a.decimals,
-        )?;
+        )?,
+        Some(_) => {}
+        None => native_sol.unwrap(
+            &ctx.accounts.token_mint_a,
+            &ctx.accounts.token_vault_a,
+            ctx.accounts.pool.to_account_info(),
+            pool_seeds,
+            amount_a,
+        )?,
     }
 
-    if amount_b > 0 {
-        transfer_checked(
+    match &ctx.accounts.user_token_b {
+        Some(user_token_b) if amount_b > 0 => transfer_checked(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
                 TransferChecked {
                     from: ctx.accounts.token_vault_b.to_account_info(),
                     mint: ctx.accounts.token_mint_b.to_account_info(),
-                    to: ctx.accounts.user_token_b.to_account_info(),
+                    to: user_token_b.to_account_info(),
                     authority: ctx.accounts.pool.to_account_info(),
                 },
                 &[pool_seeds],
             ),
             amount_b,
             ctx.accounts.token_mint_b.decimals,
-        )?;
+        )?,
+        Some(_) => {}
+        None => native_sol.unwrap(
+            &ctx.accounts.token_mint_b,
+            &ctx.accounts.token_vault_b,
+            ctx.accounts.pool.to_account_info(),
+            pool_seeds,
+            amount_b,
+        )?,
     }
 
+    emit!(FeesCollectedEvent {
+        pool: pool_key,
+        position: ctx.accounts.position.key(),
+        owner,
+        amount_a,
+        amount_b,
+    });
+
     msg!("Fees collected: A={}, B={}", amount_a, amount_b);
     msg!("Remaining owed: A={}, B={}", remaining_a, remaining_b);
 
     Ok(())
 }
 
+/// Credit the fees a position has earned since its last update to its owed tokens
+/// `authority` must be the owner or a delegate holding `permissions`
+pub(crate) fn settle_position_fees(
+    pool_loader: &AccountLoader<Pool>,
+    position_loader: &AccountLoader<Position>,
+    tick_array_lower_loader: &AccountLoader<TickArray>,
+    tick_array_upper_loader: &AccountLoader<TickArray>,
+    authority: Pubkey,
+    permissions: u8,
+) -> Result<()> {
+    let pool_key = pool_loader.key();
+    let pool = pool_loader.load()?;
+    let tick_current = pool.tick_current;
+    let tick_spacing = pool.tick_spacing;
+    let fee_growth_global_a = pool.fee_growth_global_a_x128;
+    let fee_growth_global_b = pool.fee_growth_global_b_x128;
+    drop(pool);
+
+    let position = position_loader.load()?;
+    require!(
+        position.pool == pool_key.to_bytes(),
+        SuniswapError::InvalidPosition
+    );
+    require!(
+        position.is_authorized(&authority, permissions),
+        SuniswapError::InvalidPositionOwner
+    );
+    let tick_lower = position.tick_lower;
+    let tick_upper = position.tick_upper;
+    drop(position);
+
+    let tick_array_lower = tick_array_lower_loader.load()?;
+    require!(
+        tick_array_lower.pool == pool_key.to_bytes(),
+        SuniswapError::InvalidTickArray
+    );
+    let tick_array_upper = tick_array_upper_loader.load()?;
+    require!(
+        tick_array_upper.pool == pool_key.to_bytes(),
+        SuniswapError::InvalidTickArray
+    );
+
+    let (fee_growth_inside_a, fee_growth_inside_b) = calculate_fee_growth_inside(
+        &tick_array_lower,
+        &tick_array_upper,
+        tick_lower,
+        tick_upper,
+        tick_current,
+        fee_growth_global_a,
+        fee_growth_global_b,
+        tick_spacing,
+    )?;
+    drop(tick_array_lower);
+    drop(tick_array_upper);
+
+    position_loader
+        .load_mut()?
+        .update_owed_tokens(fee_growth_inside_a, fee_growth_inside_b)
+}
+
+/// A delegate may only pay out to the position owner's token accounts; the
+/// owner may pay out anywhere. An omitted account pays native SOL to the
+/// signer, so only the owner may omit one
+pub(crate) fn validate_payout_accounts(
+    position_loader: &AccountLoader<Position>,
+    authority: Pubkey,
+    token_account_a: Option<&InterfaceAccount<TokenAccount>>,
+    token_account_b: Option<&InterfaceAccount<TokenAccount>>,
+) -> Result<()> {
+    let owner = position_loader.load()?.owner_pubkey();
+    if authority != owner {
+        let pays_owner = |account: Option<&InterfaceAccount<TokenAccount>>| {
+            account.is_some_and(|account| account.owner == owner)
+        };
+        require!(
+            pays_owner(token_account_a) && pays_owner(token_account_b),
+            SuniswapError::InvalidTokenAccountOwner
+        );
+    }
+    Ok(())
+}
+
 fn calculate_fee_growth_inside(
     tick_array_lower: &TickArray,
     tick_array_upper: &TickArray,
@@ -3,6 +3,7 @@ use anchor_spl::token_interface::{TokenAccount, TokenInterface, Mint, TransferCh
 use crate::state::{Pool, SuniswapConfig, FeeTier};
 use crate::constants::seeds;
 use crate::errors::SuniswapError;
+use crate::events::ProtocolFeesCollectedEvent;
 
 /// Collect accumulated protocol fees from a pool
 /// Only callable by fee authority
@@ -114,7 +115,7 @@ pub fn handler(
         seeds::POOL_SEED,
         &token_mint_a_bytes,
         &token_mint_b_bytes,
-        &fee_tier.fee_rate.to_le_bytes(),
+        &fee_tier.seed_bytes(),
         &[pool_bump],
     ];
 
@@ -152,6 +153,12 @@ pub fn handler(
         )?;
     }
 
+    emit!(ProtocolFeesCollectedEvent {
+        pool: ctx.accounts.pool.key(),
+        amount_a,
+        amount_b,
+    });
+
     // Get remaining fees for logging
     let pool = ctx.accounts.pool.load()?;
     msg!("Protocol fees collected: A={}, B={}", amount_a, amount_b);
@@ -0,0 +1,134 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenAccount, TokenInterface, Mint, MintTo, mint_to};
+use crate::state::ManagedVault;
+use crate::constants::seeds;
+use crate::errors::SuniswapError;
+use crate::events::VaultFeesCollectedEvent;
+use crate::instructions::deposit_vault::transfer_signed;
+
+/// Pay a managed vault's accrued performance and management fees to its treasury
+/// Anyone can call this; the fees only go to accounts the treasury owns
+#[derive(Accounts)]
+pub struct CollectVaultFees<'info> {
+    /// The vault to collect from
+    #[account(mut)]
+    pub vault: Account<'info, ManagedVault>,
+
+    /// The vault's share mint
+    #[account(
+        mut,
+        constraint = share_mint.key() == vault.share_mint @ SuniswapError::InvalidTokenMint,
+    )]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+
+    /// Token A mint
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+
+    /// Token B mint
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    /// Vault reserve for token A
+    #[account(
+        mut,
+        constraint = reserve_a.key() == vault.reserve_a @ SuniswapError::InvalidManagedVault,
+    )]
+    pub reserve_a: InterfaceAccount<'info, TokenAccount>,
+
+    /// Vault reserve for token B
+    #[account(
+        mut,
+        constraint = reserve_b.key() == vault.reserve_b @ SuniswapError::InvalidManagedVault,
+    )]
+    pub reserve_b: InterfaceAccount<'info, TokenAccount>,
+
+    /// Treasury token A account
+    #[account(
+        mut,
+        constraint = treasury_token_a.owner == vault.treasury @ SuniswapError::InvalidVaultTreasury,
+    )]
+    pub treasury_token_a: InterfaceAccount<'info, TokenAccount>,
+
+    /// Treasury token B account
+    #[account(
+        mut,
+        constraint = treasury_token_b.owner == vault.treasury @ SuniswapError::InvalidVaultTreasury,
+    )]
+    pub treasury_token_b: InterfaceAccount<'info, TokenAccount>,
+
+    /// Treasury share token account
+    #[account(
+        mut,
+        constraint = treasury_shares.owner == vault.treasury @ SuniswapError::InvalidVaultTreasury,
+    )]
+    pub treasury_shares: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Collect vault fees handler
+pub fn handler(ctx: Context<CollectVaultFees>) -> Result<()> {
+    let minted = ctx.accounts.share_mint.supply;
+    let vault = &mut ctx.accounts.vault;
+    vault.accrue_management_fee(minted, Clock::get()?.unix_timestamp)?;
+
+    let amount_a = vault.treasury_fees_a;
+    let amount_b = vault.treasury_fees_b;
+    let shares = vault.treasury_shares;
+    vault.treasury_fees_a = 0;
+    vault.treasury_fees_b = 0;
+    vault.treasury_shares = 0;
+
+    let vault = &ctx.accounts.vault;
+    let vault_seeds: &[&[u8]] = &[
+        seeds::MANAGED_VAULT_SEED,
+        vault.pool.as_ref(),
+        &vault.id.to_le_bytes(),
+        &[vault.bump],
+    ];
+    transfer_signed(
+        &ctx.accounts.token_program,
+        &ctx.accounts.reserve_a,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.treasury_token_a,
+        vault.to_account_info(),
+        vault_seeds,
+        amount_a,
+    )?;
+    transfer_signed(
+        &ctx.accounts.token_program,
+        &ctx.accounts.reserve_b,
+        &ctx.accounts.token_mint_b,
+        &ctx.accounts.treasury_token_b,
+        vault.to_account_info(),
+        vault_seeds,
+        amount_b,
+    )?;
+
+    if shares > 0 {
+        mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.share_mint.to_account_info(),
+                    to: ctx.accounts.treasury_shares.to_account_info(),
+                    authority: vault.to_account_info(),
+                },
+                &[vault_seeds],
+            ),
+            shares,
+        )?;
+    }
+
+    emit!(VaultFeesCollectedEvent {
+        vault: vault.key(),
+        treasury: vault.treasury,
+        amount_a,
+        amount_b,
+        shares,
+    });
+
+    msg!("Vault fees collected: A={}, B={}, shares={}", amount_a, amount_b, shares);
+
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use crate::state::{Pool, Position, TickArray, AllowedTrader};
+use crate::constants::position_permissions;
+use crate::errors::SuniswapError;
+use crate::events::PositionCompoundedEvent;
+use crate::instructions::collect_fees::settle_position_fees;
+use crate::instructions::increase_liquidity::add_liquidity;
+use crate::instructions::trader_access::check_trader_access;
+use crate::math::liquidity_math::get_liquidity_for_amounts;
+use crate::math::tick_math::get_sqrt_price_at_tick;
+
+/// Reinvest a position's owed tokens as liquidity
+/// The tokens are already in the vaults, so nothing is transferred
+#[derive(Accounts)]
+pub struct CompoundPosition<'info> {
+    /// The pool (zero-copy)
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// The position to compound (zero-copy)
+    #[account(mut)]
+    pub position: AccountLoader<'info, Position>,
+
+    /// Tick array containing lower tick (zero-copy)
+    #[account(mut)]
+    pub tick_array_lower: AccountLoader<'info, TickArray>,
+
+    /// Tick array containing upper tick (zero-copy)
+    #[account(mut)]
+    pub tick_array_upper: AccountLoader<'info, TickArray>,
+
+    /// Position owner, or a delegate allowed to increase
+    pub owner: Signer<'info>,
+
+    /// The position owner's entry on the pool's trader allowlist, only needed while it is enabled
+    pub allowed_trader: Option<Account<'info, AllowedTrader>>,
+}
+
+/// Compound position handler
+pub fn handler(ctx: Context<CompoundPosition>) -> Result<()> {
+    let pool_key = ctx.accounts.pool.key();
+    let authority = ctx.accounts.owner.key();
+
+    // Compounding adds liquidity like any deposit, so it is gated the same way
+    let position_owner = ctx.accounts.position.load()?.owner_pubkey();
+    let pool = ctx.accounts.pool.load()?;
+    require!(pool.is_paused == 0, SuniswapError::PoolPaused);
+    check_trader_access(&pool, &pool_key, ctx.accounts.allowed_trader.as_ref(), &position_owner)?;
+    let sqrt_price_x64 = pool.sqrt_price_x64;
+    drop(pool);
+
+    // Bring the owed tokens up to date with the fees earned so far
+    settle_position_fees(
+        &ctx.accounts.pool,
+        &ctx.accounts.position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        authority,
+        position_permissions::INCREASE,
+    )?;
+
+    let position = ctx.accounts.position.load()?;
+    let owed_a = position.tokens_owed_a;
+    let owed_b = position.tokens_owed_b;
+    let liquidity_delta = get_liquidity_for_amounts(
+        sqrt_price_x64,
+        get_sqrt_price_at_tick(position.tick_lower)?,
+        get_sqrt_price_at_tick(position.tick_upper)?,
+        owed_a,
+        owed_b,
+    )?;
+    drop(position);
+
+    // Funded from the owed tokens, which cap the amounts
+    let (amount_a, amount_b) = add_liquidity(
+        &ctx.accounts.pool,
+        &ctx.accounts.position,
+        ctx.accounts.tick_array_lower.as_ref(),
+        ctx.accounts.tick_array_upper.as_ref(),
+        authority,
+        position_permissions::INCREASE,
+        liquidity_delta,
+        owed_a,
+        owed_b,
+    )?;
+
+    let mut position = ctx.accounts.position.load_mut()?;
+    position.tokens_owed_a = position
+        .tokens_owed_a
+        .checked_sub(amount_a)
+        .ok_or(SuniswapError::MathOverflow)?;
+    position.tokens_owed_b = position
+        .tokens_owed_b
+        .checked_sub(amount_b)
+        .ok_or(SuniswapError::MathOverflow)?;
+    let remaining_a = position.tokens_owed_a;
+    let remaining_b = position.tokens_owed_b;
+    let owner = position.owner_pubkey();
+    drop(position);
+
+    emit!(PositionCompoundedEvent {
+        pool: pool_key,
+        position: ctx.accounts.position.key(),
+        owner,
+        liquidity_delta,
+        amount_a,
+        amount_b,
+        tokens_owed_a: remaining_a,
+        tokens_owed_b: remaining_b,
+    });
+
+    msg!("Position compounded: {}", liquidity_delta);
+    msg!("Remaining owed: A={}, B={}", remaining_a, remaining_b);
+
+    Ok(())
+}
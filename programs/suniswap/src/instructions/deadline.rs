@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+use crate::errors::SuniswapError;
+
+/// Reject a transaction that lands after the client's deadline or max slot
+///
+/// Slippage bounds are computed from a quote, so a transaction that lands
+/// late can meet them at a price the user no longer expects
+pub(crate) fn check_deadline(deadline_unix_ts: Option<i64>, max_slot: Option<u64>) -> Result<()> {
+    if deadline_unix_ts.is_none() && max_slot.is_none() {
+        return Ok(());
+    }
+
+    let clock = Clock::get()?;
+    if let Some(deadline_unix_ts) = deadline_unix_ts {
+        require!(clock.unix_timestamp <= deadline_unix_ts, SuniswapError::DeadlineExceeded);
+    }
+    if let Some(max_slot) = max_slot {
+        require!(clock.slot <= max_slot, SuniswapError::MaxSlotExceeded);
+    }
+    Ok(())
+}
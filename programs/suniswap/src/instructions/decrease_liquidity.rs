@@ -1,6 +1,9 @@
 use anchor_lang::prelude::*;
 use crate::state::{Pool, Position, TickArray, Tick};
+use crate::constants::position_permissions;
 use crate::errors::SuniswapError;
+use crate::events::LiquidityDecreasedEvent;
+use crate::instructions::deadline::check_deadline;
 use crate::math::liquidity_math::{get_amounts_for_liquidity, add_liquidity_delta};
 
 /// Decrease liquidity from an existing position
@@ -22,7 +25,7 @@ pub struct DecreaseLiquidity<'info> {
     #[account(mut)]
     pub tick_array_upper: AccountLoader<'info, TickArray>,
 
-    /// Position owner
+    /// Position owner, or a delegate allowed to decrease
     pub owner: Signer<'info>,
 }
 
@@ -32,12 +35,43 @@ pub fn handler(
     liquidity_delta: u128,
     amount_a_min: u64,
     amount_b_min: u64,
+    deadline_unix_ts: Option<i64>,
+    max_slot: Option<u64>,
+) -> Result<()> {
+    check_deadline(deadline_unix_ts, max_slot)?;
+
+    remove_liquidity(
+        &ctx.accounts.pool,
+        &ctx.accounts.position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        ctx.accounts.owner.key(),
+        position_permissions::DECREASE,
+        liquidity_delta,
+        amount_a_min,
+        amount_b_min,
+    )
+}
+
+/// Remove liquidity from a position, crediting the principal to its owed tokens
+/// `authority` must be the owner or a delegate holding `permissions`
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn remove_liquidity(
+    pool_loader: &AccountLoader<Pool>,
+    position_loader: &AccountLoader<Position>,
+    tick_array_lower_loader: &AccountLoader<TickArray>,
+    tick_array_upper_loader: &AccountLoader<TickArray>,
+    authority: Pubkey,
+    permissions: u8,
+    liquidity_delta: u128,
+    amount_a_min: u64,
+    amount_b_min: u64,
 ) -> Result<()> {
     require!(liquidity_delta > 0, SuniswapError::ZeroLiquidity);
 
     // Load pool
-    let pool = ctx.accounts.pool.load()?;
-    let pool_key = ctx.accounts.pool.key();
+    let pool = pool_loader.load()?;
+    let pool_key = pool_loader.key();
 
     require!(pool.is_paused == 0, SuniswapError::PoolPaused);
 
@@ -46,19 +80,22 @@ pub fn handler(
     let tick_spacing = pool.tick_spacing;
     let fee_growth_global_a = pool.fee_growth_global_a_x128;
     let fee_growth_global_b = pool.fee_growth_global_b_x128;
+    let timestamp = Clock::get()?.unix_timestamp as u32;
+    let cumulatives = pool.observe(timestamp);
 
     drop(pool);
 
     // Load and validate position
-    let position = ctx.accounts.position.load()?;
+    let position = position_loader.load()?;
     require!(
         position.pool == pool_key.to_bytes(),
         SuniswapError::InvalidPosition
     );
     require!(
-        position.owner == ctx.accounts.owner.key().to_bytes(),
+        position.is_authorized(&authority, permissions),
         SuniswapError::InvalidPositionOwner
     );
+    let owner = position.owner_pubkey();
     require!(
         position.liquidity >= liquidity_delta,
         SuniswapError::InsufficientLiquidity
@@ -69,14 +106,14 @@ pub fn handler(
     drop(position);
 
     // Validate tick arrays
-    let tick_array_lower = ctx.accounts.tick_array_lower.load()?;
+    let tick_array_lower = tick_array_lower_loader.load()?;
     require!(
         tick_array_lower.pool == pool_key.to_bytes(),
         SuniswapError::InvalidTickArray
     );
     drop(tick_array_lower);
 
-    let tick_array_upper = ctx.accounts.tick_array_upper.load()?;
+    let tick_array_upper = tick_array_upper_loader.load()?;
     require!(
         tick_array_upper.pool == pool_key.to_bytes(),
         SuniswapError::InvalidTickArray
@@ -97,8 +134,10 @@ pub fn handler(
 
     // Update tick arrays and position
     {
-        let mut tick_array_lower = ctx.accounts.tick_array_lower.load_mut()?;
-        let mut tick_array_upper = ctx.accounts.tick_array_upper.load_mut()?;
+        // Both bounds may live in the same tick array, so only ever hold one
+        // mutable borrow at a time
+        let tick_array_lower = tick_array_lower_loader.load()?;
+        let tick_array_upper = tick_array_upper_loader.load()?;
 
         let (fee_growth_inside_a, fee_growth_inside_b) = calculate_fee_growth_inside(
             &tick_array_lower,
@@ -110,9 +149,11 @@ pub fn handler(
             fee_growth_global_b,
             tick_spacing,
         )?;
+        drop(tick_array_lower);
+        drop(tick_array_upper);
 
         // Update position
-        let mut position = ctx.accounts.position.load_mut()?;
+        let mut position = position_loader.load_mut()?;
         position.update_owed_tokens(fee_growth_inside_a, fee_growth_inside_b)?;
         position.tokens_owed_a = position.tokens_owed_a
             .checked_add(amount_a)
@@ -130,23 +171,25 @@ pub fn handler(
             .map_err(|_| SuniswapError::LiquidityOverflow)?;
 
         // Update ticks (negative delta for decrease)
-        let _flipped_lower = tick_array_lower.update_tick(
+        let _flipped_lower = tick_array_lower_loader.load_mut()?.update_tick(
             tick_lower,
             tick_spacing,
             tick_current,
             -liquidity_delta_signed,
             fee_growth_global_a,
             fee_growth_global_b,
+            &cumulatives,
             false,
         )?;
 
-        let _flipped_upper = tick_array_upper.update_tick(
+        let _flipped_upper = tick_array_upper_loader.load_mut()?.update_tick(
             tick_upper,
             tick_spacing,
             tick_current,
             -liquidity_delta_signed,
             fee_growth_global_a,
             fee_growth_global_b,
+            &cumulatives,
             true,
         )?;
     }
@@ -155,10 +198,23 @@ pub fn handler(
     if tick_current >= tick_lower && tick_current < tick_upper {
         let liquidity_delta_signed = i128::try_from(liquidity_delta)
             .map_err(|_| SuniswapError::LiquidityOverflow)?;
-        let mut pool = ctx.accounts.pool.load_mut()?;
+        let mut pool = pool_loader.load_mut()?;
+        pool.update_cumulatives(timestamp);
         pool.liquidity = add_liquidity_delta(pool.liquidity, -liquidity_delta_signed)?;
     }
 
+    emit!(LiquidityDecreasedEvent {
+        pool: pool_key,
+        position: position_loader.key(),
+        owner,
+        tick_lower,
+        tick_upper,
+        liquidity_delta,
+        amount_a,
+        amount_b,
+        pool_liquidity: pool_loader.load()?.liquidity,
+    });
+
     msg!("Liquidity decreased: {}", liquidity_delta);
     msg!("Amount A owed: {}, Amount B owed: {}", amount_a, amount_b);
 
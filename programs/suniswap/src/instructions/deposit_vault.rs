@@ -0,0 +1,480 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    TokenAccount, TokenInterface, Mint, TransferChecked, transfer_checked, MintTo, mint_to,
+};
+use crate::state::{Pool, Position, TickArray, FeeTier, ManagedVault, AllowedTrader, Oracle};
+use crate::constants::seeds;
+use crate::constants::managed_vault::{MAX_TWAP_DEVIATION_TICKS, MINIMUM_SHARES};
+use crate::errors::SuniswapError;
+use crate::events::{FeesCollectedEvent, VaultDepositEvent, VaultFeesHarvestedEvent};
+use crate::instructions::collect_fees::settle_position_fees;
+use crate::instructions::trader_access::check_trader_access;
+use crate::math::liquidity_math::get_amounts_for_liquidity;
+use crate::math::share_math::{get_amount_for_shares, get_shares_for_amounts};
+use crate::math::tick_math::get_sqrt_price_at_tick;
+
+/// Deposit tokens into a managed vault for shares
+/// Deposits are priced against everything the vault holds and stay in its
+/// reserves until the strategist deploys them. The pool needs an oracle, and
+/// its price must be near the TWAP
+///
+/// Remaining accounts: (position, tick_array_lower, tick_array_upper) for each
+/// vault position, in vault order, all writable
+#[derive(Accounts)]
+pub struct DepositVault<'info> {
+    /// The vault's pool (zero-copy)
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// The fee tier for this pool
+    pub fee_tier: Account<'info, FeeTier>,
+
+    /// The vault to deposit into
+    #[account(
+        mut,
+        constraint = vault.pool == pool.key() @ SuniswapError::InvalidManagedVault,
+    )]
+    pub vault: Account<'info, ManagedVault>,
+
+    /// The vault's share mint
+    #[account(
+        mut,
+        constraint = share_mint.key() == vault.share_mint @ SuniswapError::InvalidTokenMint,
+    )]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+
+    /// Token A mint
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+
+    /// Token B mint
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    /// Pool vault for token A
+    #[account(mut)]
+    pub token_vault_a: InterfaceAccount<'info, TokenAccount>,
+
+    /// Pool vault for token B
+    #[account(mut)]
+    pub token_vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    /// Vault reserve for token A
+    #[account(
+        mut,
+        constraint = reserve_a.key() == vault.reserve_a @ SuniswapError::InvalidManagedVault,
+    )]
+    pub reserve_a: InterfaceAccount<'info, TokenAccount>,
+
+    /// Vault reserve for token B
+    #[account(
+        mut,
+        constraint = reserve_b.key() == vault.reserve_b @ SuniswapError::InvalidManagedVault,
+    )]
+    pub reserve_b: InterfaceAccount<'info, TokenAccount>,
+
+    /// User's token A account
+    #[account(mut)]
+    pub user_token_a: InterfaceAccount<'info, TokenAccount>,
+
+    /// User's token B account
+    #[account(mut)]
+    pub user_token_b: InterfaceAccount<'info, TokenAccount>,
+
+    /// User's share token account
+    #[account(mut)]
+    pub user_shares: InterfaceAccount<'info, TokenAccount>,
+
+    /// Depositor
+    pub user: Signer<'info>,
+
+    /// Token program
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The signer's entry on the pool's trader allowlist, only needed while it is enabled
+    pub allowed_trader: Option<Account<'info, AllowedTrader>>,
+
+    /// The pool's oracle
+    pub oracle: Option<Account<'info, Oracle>>,
+}
+
+/// A vault position with the tick arrays holding its bounds
+pub(crate) struct VaultPositionAccounts<'info> {
+    pub position: AccountLoader<'info, Position>,
+    pub tick_array_lower: AccountLoader<'info, TickArray>,
+    pub tick_array_upper: AccountLoader<'info, TickArray>,
+}
+
+/// Load the vault's positions from remaining accounts, which must list every
+/// vault position in order, each followed by its two tick arrays
+pub(crate) fn load_vault_positions<'info>(
+    vault: &ManagedVault,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<Vec<VaultPositionAccounts<'info>>> {
+    let expected = vault.active_positions();
+    require!(
+        remaining_accounts.len() == expected.len() * 3,
+        SuniswapError::InvalidVaultPositions
+    );
+
+    remaining_accounts
+        .chunks(3)
+        .zip(expected)
+        .map(|(accounts, key)| {
+            require_keys_eq!(accounts[0].key(), *key, SuniswapError::InvalidVaultPositions);
+            Ok(VaultPositionAccounts {
+                position: AccountLoader::try_from(&accounts[0])?,
+                tick_array_lower: AccountLoader::try_from(&accounts[1])?,
+                tick_array_upper: AccountLoader::try_from(&accounts[2])?,
+            })
+        })
+        .collect()
+}
+
+/// Zero a position's owed tokens and return them; the caller moves them out
+/// of the pool vaults
+pub(crate) fn take_owed_tokens(pool_key: Pubkey, position_loader: &AccountLoader<Position>) -> Result<(u64, u64)> {
+    let mut position = position_loader.load_mut()?;
+    let amount_a = position.tokens_owed_a;
+    let amount_b = position.tokens_owed_b;
+    position.tokens_owed_a = 0;
+    position.tokens_owed_b = 0;
+    let owner = position.owner_pubkey();
+    drop(position);
+
+    if amount_a > 0 || amount_b > 0 {
+        emit!(FeesCollectedEvent {
+            pool: pool_key,
+            position: position_loader.key(),
+            owner,
+            amount_a,
+            amount_b,
+        });
+    }
+
+    Ok((amount_a, amount_b))
+}
+
+/// Settle a vault position's fees and take them, setting the performance fee
+/// aside for the treasury; returns the full amount to move into the reserves
+pub(crate) fn harvest_vault_position(
+    pool_loader: &AccountLoader<Pool>,
+    vault: &mut Account<ManagedVault>,
+    position_loader: &AccountLoader<Position>,
+    tick_array_lower_loader: &AccountLoader<TickArray>,
+    tick_array_upper_loader: &AccountLoader<TickArray>,
+) -> Result<(u64, u64)> {
+    settle_position_fees(
+        pool_loader,
+        position_loader,
+        tick_array_lower_loader,
+        tick_array_upper_loader,
+        vault.key(),
+        0,
+    )?;
+
+    // Vault positions never keep principal owed, so this is all fees
+    let (fees_a, fees_b) = take_owed_tokens(pool_loader.key(), position_loader)?;
+    if fees_a > 0 || fees_b > 0 {
+        let (kept_a, kept_b) = vault.take_performance_fee(fees_a, fees_b)?;
+        emit!(VaultFeesHarvestedEvent {
+            vault: vault.key(),
+            position: position_loader.key(),
+            amount_a: fees_a,
+            amount_b: fees_b,
+            treasury_fee_a: fees_a - kept_a,
+            treasury_fee_b: fees_b - kept_b,
+        });
+    }
+
+    Ok((fees_a, fees_b))
+}
+
+/// Harvest every vault position, returning the total to move into the reserves
+pub(crate) fn harvest_vault_positions(
+    pool_loader: &AccountLoader<Pool>,
+    vault: &mut Account<ManagedVault>,
+    positions: &[VaultPositionAccounts],
+) -> Result<(u64, u64)> {
+    let mut total_a: u64 = 0;
+    let mut total_b: u64 = 0;
+    for accounts in positions {
+        let (fees_a, fees_b) = harvest_vault_position(
+            pool_loader,
+            vault,
+            &accounts.position,
+            &accounts.tick_array_lower,
+            &accounts.tick_array_upper,
+        )?;
+        total_a = total_a.checked_add(fees_a).ok_or(SuniswapError::MathOverflow)?;
+        total_b = total_b.checked_add(fees_b).ok_or(SuniswapError::MathOverflow)?;
+    }
+    Ok((total_a, total_b))
+}
+
+/// Check the pool's price is within `MAX_TWAP_DEVIATION_TICKS` of its TWAP,
+/// or the pool's own cap if tighter, so shares aren't priced off a price
+/// pushed for the transaction
+pub(crate) fn check_vault_price(pool: &Pool, oracle: Option<&Account<Oracle>>, timestamp: u32) -> Result<()> {
+    let oracle = match oracle {
+        Some(oracle) => {
+            require_keys_eq!(oracle.key(), pool.oracle_pubkey(), SuniswapError::InvalidOracleAccount);
+            oracle
+        }
+        None => {
+            require!(pool.oracle == [0u8; 32], SuniswapError::InvalidOracleAccount);
+            return err!(SuniswapError::OracleNotInitialized);
+        }
+    };
+
+    let limit = pool.twap_deviation_limit(Some(MAX_TWAP_DEVIATION_TICKS)).unwrap_or(MAX_TWAP_DEVIATION_TICKS);
+    let twap_tick = oracle.twap_tick(pool.twap_window, pool, timestamp)?;
+    require!(
+        pool.tick_current.abs_diff(twap_tick) <= limit,
+        SuniswapError::TwapDeviationExceeded
+    );
+    Ok(())
+}
+
+/// Reserve balances that belong to shareholders, excluding treasury fees
+pub(crate) fn idle_amounts(
+    vault: &ManagedVault,
+    reserve_a: &InterfaceAccount<TokenAccount>,
+    reserve_b: &InterfaceAccount<TokenAccount>,
+) -> Result<(u64, u64)> {
+    let idle_a = reserve_a.amount
+        .checked_sub(vault.treasury_fees_a)
+        .ok_or(SuniswapError::MathUnderflow)?;
+    let idle_b = reserve_b.amount
+        .checked_sub(vault.treasury_fees_b)
+        .ok_or(SuniswapError::MathUnderflow)?;
+    Ok((idle_a, idle_b))
+}
+
+/// Tokens backing the vault's shares: idle reserves plus the principal of
+/// every position at the current price, rounded down
+pub(crate) fn vault_totals(
+    sqrt_price_x64: u128,
+    idle: (u64, u64),
+    positions: &[VaultPositionAccounts],
+) -> Result<(u64, u64)> {
+    let (mut total_a, mut total_b) = idle;
+    for accounts in positions {
+        let position = accounts.position.load()?;
+        let (amount_a, amount_b) = get_amounts_for_liquidity(
+            sqrt_price_x64,
+            get_sqrt_price_at_tick(position.tick_lower)?,
+            get_sqrt_price_at_tick(position.tick_upper)?,
+            position.liquidity,
+            false,
+        )?;
+        total_a = total_a.checked_add(amount_a).ok_or(SuniswapError::MathOverflow)?;
+        total_b = total_b.checked_add(amount_b).ok_or(SuniswapError::MathOverflow)?;
+    }
+    Ok((total_a, total_b))
+}
+
+/// Transfer out of a program-owned token account; zero amounts are skipped
+pub(crate) fn transfer_signed<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    from: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    authority: AccountInfo<'info>,
+    signer_seeds: &[&[u8]],
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: from.to_account_info(),
+                mint: mint.to_account_info(),
+                to: to.to_account_info(),
+                authority,
+            },
+            &[signer_seeds],
+        ),
+        amount,
+        mint.decimals,
+    )
+}
+
+/// Deposit into vault handler
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DepositVault<'info>>,
+    amount_a_max: u64,
+    amount_b_max: u64,
+    shares_min: u64,
+) -> Result<()> {
+    let fee_tier = &ctx.accounts.fee_tier;
+
+    // Load and validate pool
+    let pool = ctx.accounts.pool.load()?;
+    require!(pool.is_paused == 0, SuniswapError::PoolPaused);
+    check_trader_access(
+        &pool,
+        &ctx.accounts.pool.key(),
+        ctx.accounts.allowed_trader.as_ref(),
+        &ctx.accounts.user.key(),
+    )?;
+    require!(
+        pool.fee_tier == fee_tier.key().to_bytes(),
+        SuniswapError::InvalidFeeTier
+    );
+    require!(
+        pool.token_mint_a == ctx.accounts.token_mint_a.key().to_bytes(),
+        SuniswapError::InvalidTokenMint
+    );
+    require!(
+        pool.token_mint_b == ctx.accounts.token_mint_b.key().to_bytes(),
+        SuniswapError::InvalidTokenMint
+    );
+    require!(
+        pool.token_vault_a == ctx.accounts.token_vault_a.key().to_bytes(),
+        SuniswapError::InvalidVault
+    );
+    require!(
+        pool.token_vault_b == ctx.accounts.token_vault_b.key().to_bytes(),
+        SuniswapError::InvalidVault
+    );
+    let now = Clock::get()?.unix_timestamp;
+    check_vault_price(&pool, ctx.accounts.oracle.as_ref(), now as u32)?;
+
+    let sqrt_price_x64 = pool.sqrt_price_x64;
+    let pool_bump = pool.bump;
+    let token_mint_a_bytes = pool.token_mint_a;
+    let token_mint_b_bytes = pool.token_mint_b;
+    drop(pool);
+
+    // Bring fees into the reserves so the share price includes them
+    let positions = load_vault_positions(&ctx.accounts.vault, ctx.remaining_accounts)?;
+    let (harvested_a, harvested_b) = harvest_vault_positions(&ctx.accounts.pool, &mut ctx.accounts.vault, &positions)?;
+
+    let pool_seeds: &[&[u8]] = &[
+        seeds::POOL_SEED,
+        &token_mint_a_bytes,
+        &token_mint_b_bytes,
+        &fee_tier.seed_bytes(),
+        &[pool_bump],
+    ];
+    let pool_info = ctx.accounts.pool.to_account_info();
+    transfer_signed(
+        &ctx.accounts.token_program,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.reserve_a,
+        pool_info.clone(),
+        pool_seeds,
+        harvested_a,
+    )?;
+    transfer_signed(
+        &ctx.accounts.token_program,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.token_mint_b,
+        &ctx.accounts.reserve_b,
+        pool_info,
+        pool_seeds,
+        harvested_b,
+    )?;
+    ctx.accounts.reserve_a.reload()?;
+    ctx.accounts.reserve_b.reload()?;
+
+    let minted = ctx.accounts.share_mint.supply;
+    let vault = &mut ctx.accounts.vault;
+    vault.accrue_management_fee(minted, now)?;
+
+    // Price the deposit against everything the vault holds
+    let idle = idle_amounts(vault, &ctx.accounts.reserve_a, &ctx.accounts.reserve_b)?;
+    let (total_a, total_b) = vault_totals(sqrt_price_x64, idle, &positions)?;
+    let share_supply = vault.share_supply(minted)?;
+    let deposit_shares = get_shares_for_amounts(amount_a_max, amount_b_max, total_a, total_b, share_supply)?;
+    require!(deposit_shares > 0, SuniswapError::ZeroShares);
+
+    // The first deposit locks a few shares for good, so nobody can later
+    // hold a tiny supply and inflate what each share is worth
+    let shares = if share_supply == 0 {
+        require!(deposit_shares > MINIMUM_SHARES, SuniswapError::VaultDepositTooSmall);
+        vault.locked_shares = MINIMUM_SHARES;
+        deposit_shares - MINIMUM_SHARES
+    } else {
+        deposit_shares
+    };
+    require!(shares >= shares_min, SuniswapError::SlippageExceeded);
+
+    let (amount_a, amount_b) = if share_supply == 0 {
+        (amount_a_max, amount_b_max)
+    } else {
+        (
+            get_amount_for_shares(deposit_shares, total_a, share_supply, true)?,
+            get_amount_for_shares(deposit_shares, total_b, share_supply, true)?,
+        )
+    };
+    require!(amount_a <= amount_a_max, SuniswapError::AmountAExceedsMax);
+    require!(amount_b <= amount_b_max, SuniswapError::AmountBExceedsMax);
+
+    // Transfer tokens
+    if amount_a > 0 {
+        transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.user_token_a.to_account_info(),
+                    mint: ctx.accounts.token_mint_a.to_account_info(),
+                    to: ctx.accounts.reserve_a.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            amount_a,
+            ctx.accounts.token_mint_a.decimals,
+        )?;
+    }
+
+    if amount_b > 0 {
+        transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.user_token_b.to_account_info(),
+                    mint: ctx.accounts.token_mint_b.to_account_info(),
+                    to: ctx.accounts.reserve_b.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            amount_b,
+            ctx.accounts.token_mint_b.decimals,
+        )?;
+    }
+
+    let vault = &ctx.accounts.vault;
+    let vault_seeds: &[&[u8]] = &[
+        seeds::MANAGED_VAULT_SEED,
+        vault.pool.as_ref(),
+        &vault.id.to_le_bytes(),
+        &[vault.bump],
+    ];
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.share_mint.to_account_info(),
+                to: ctx.accounts.user_shares.to_account_info(),
+                authority: vault.to_account_info(),
+            },
+            &[vault_seeds],
+        ),
+        shares,
+    )?;
+
+    emit!(VaultDepositEvent {
+        vault: vault.key(),
+        user: ctx.accounts.user.key(),
+        shares,
+        amount_a,
+        amount_b,
+    });
+
+    msg!("Vault deposit: {} shares for A={}, B={}", shares, amount_a, amount_b);
+
+    Ok(())
+}
@@ -0,0 +1,171 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token_interface::{TokenAccount, TokenInterface, Mint, TransferChecked, transfer_checked};
+use crate::state::{Pool, SuniswapConfig, FeeTier, ProtocolFeeConfig};
+use crate::constants::seeds;
+use crate::errors::SuniswapError;
+use crate::events::{ProtocolFeesCollectedEvent, ProtocolFeesDistributedEvent};
+
+/// Sweep a pool's protocol fees to the protocol fee recipients
+/// Permissionless; remaining accounts are each recipient's token A and token B
+/// associated token accounts, in recipient order
+#[derive(Accounts)]
+pub struct DistributeProtocolFees<'info> {
+    /// The global config
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, SuniswapConfig>,
+
+    /// The fee split
+    #[account(
+        seeds = [seeds::PROTOCOL_FEE_CONFIG_SEED],
+        bump = protocol_fee_config.bump,
+        constraint = protocol_fee_config.config == config.key() @ SuniswapError::InvalidConfig,
+    )]
+    pub protocol_fee_config: Account<'info, ProtocolFeeConfig>,
+
+    /// The pool to sweep (zero-copy)
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// The fee tier for this pool
+    pub fee_tier: Account<'info, FeeTier>,
+
+    /// Token A mint
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+
+    /// Token B mint
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    /// Pool vault for token A
+    #[account(mut)]
+    pub token_vault_a: InterfaceAccount<'info, TokenAccount>,
+
+    /// Pool vault for token B
+    #[account(mut)]
+    pub token_vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Distribute protocol fees handler
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, DistributeProtocolFees<'info>>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let fee_tier = &ctx.accounts.fee_tier;
+    let recipients = ctx.accounts.protocol_fee_config.active_recipients();
+
+    // Load and validate pool
+    let pool = ctx.accounts.pool.load()?;
+    require!(
+        pool.config == config.key().to_bytes(),
+        SuniswapError::InvalidConfig
+    );
+    require!(
+        pool.fee_tier == fee_tier.key().to_bytes(),
+        SuniswapError::InvalidFeeTier
+    );
+    require!(
+        pool.token_mint_a == ctx.accounts.token_mint_a.key().to_bytes(),
+        SuniswapError::InvalidTokenMint
+    );
+    require!(
+        pool.token_mint_b == ctx.accounts.token_mint_b.key().to_bytes(),
+        SuniswapError::InvalidTokenMint
+    );
+    require!(
+        pool.token_vault_a == ctx.accounts.token_vault_a.key().to_bytes(),
+        SuniswapError::InvalidVault
+    );
+    require!(
+        pool.token_vault_b == ctx.accounts.token_vault_b.key().to_bytes(),
+        SuniswapError::InvalidVault
+    );
+
+    let amount_a = pool.protocol_fees_a;
+    let amount_b = pool.protocol_fees_b;
+    let pool_bump = pool.bump;
+    let token_mint_a_bytes = pool.token_mint_a;
+    let token_mint_b_bytes = pool.token_mint_b;
+    drop(pool);
+
+    // Every recipient's pair of associated token accounts, in order
+    require!(
+        ctx.remaining_accounts.len() == recipients.len() * 2,
+        SuniswapError::InvalidProtocolFeeRecipientAccount
+    );
+    let token_program_key = ctx.accounts.token_program.key();
+    for (recipient, accounts) in recipients.iter().zip(ctx.remaining_accounts.chunks(2)) {
+        for (account, mint) in accounts.iter().zip([&ctx.accounts.token_mint_a, &ctx.accounts.token_mint_b]) {
+            require_keys_eq!(
+                account.key(),
+                get_associated_token_address_with_program_id(&recipient.wallet, &mint.key(), &token_program_key),
+                SuniswapError::InvalidProtocolFeeRecipientAccount
+            );
+        }
+    }
+
+    // Update pool state
+    {
+        let mut pool = ctx.accounts.pool.load_mut()?;
+        pool.protocol_fees_a = 0;
+        pool.protocol_fees_b = 0;
+    }
+
+    // Transfer each recipient's share
+    let pool_seeds: &[&[u8]] = &[
+        seeds::POOL_SEED,
+        &token_mint_a_bytes,
+        &token_mint_b_bytes,
+        &fee_tier.seed_bytes(),
+        &[pool_bump],
+    ];
+    let protocol_fee_config = &ctx.accounts.protocol_fee_config;
+    let shares_a = protocol_fee_config.split(amount_a);
+    let shares_b = protocol_fee_config.split(amount_b);
+
+    for (i, recipient) in recipients.iter().enumerate() {
+        let payouts = [
+            (&ctx.accounts.token_vault_a, &ctx.accounts.token_mint_a, shares_a[i]),
+            (&ctx.accounts.token_vault_b, &ctx.accounts.token_mint_b, shares_b[i]),
+        ];
+        for ((vault, mint, amount), to) in payouts.into_iter().zip(&ctx.remaining_accounts[i * 2..i * 2 + 2]) {
+            if amount == 0 {
+                continue;
+            }
+            transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: vault.to_account_info(),
+                        mint: mint.to_account_info(),
+                        to: to.clone(),
+                        authority: ctx.accounts.pool.to_account_info(),
+                    },
+                    &[pool_seeds],
+                ),
+                amount,
+                mint.decimals,
+            )?;
+        }
+
+        emit!(ProtocolFeesDistributedEvent {
+            pool: ctx.accounts.pool.key(),
+            recipient: recipient.wallet,
+            amount_a: shares_a[i],
+            amount_b: shares_b[i],
+        });
+    }
+
+    emit!(ProtocolFeesCollectedEvent {
+        pool: ctx.accounts.pool.key(),
+        amount_a,
+        amount_b,
+    });
+
+    msg!("Protocol fees distributed: A={}, B={}", amount_a, amount_b);
+
+    Ok(())
+}
@@ -1,7 +1,12 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{TokenAccount, TokenInterface, Mint, TransferChecked, transfer_checked};
-use crate::state::{Pool, Position, TickArray, Tick};
+use crate::state::{Pool, Position, TickArray, Tick, AllowedTrader};
+use crate::constants::position_permissions;
 use crate::errors::SuniswapError;
+use crate::events::LiquidityIncreasedEvent;
+use crate::instructions::deadline::check_deadline;
+use crate::instructions::trader_access::check_trader_access;
+use crate::instructions::native_sol::NativeSol;
 use crate::math::liquidity_math::{get_amounts_for_liquidity, add_liquidity_delta};
 
 /// Increase liquidity in an existing position
@@ -37,19 +42,31 @@ pub struct IncreaseLiquidity<'info> {
     #[account(mut)]
     pub token_vault_b: InterfaceAccount<'info, TokenAccount>,
 
-    /// User's token A account
+    /// User's token A account, omitted to pay native SOL
     #[account(mut)]
-    pub user_token_a: InterfaceAccount<'info, TokenAccount>,
+    pub user_token_a: Option<InterfaceAccount<'info, TokenAccount>>,
 
-    /// User's token B account
+    /// User's token B account, omitted to pay native SOL
     #[account(mut)]
-    pub user_token_b: InterfaceAccount<'info, TokenAccount>,
+    pub user_token_b: Option<InterfaceAccount<'info, TokenAccount>>,
 
-    /// Position owner
+    /// Position owner, or a delegate allowed to increase
+    #[account(mut)]
     pub owner: Signer<'info>,
 
     /// Token program
     pub token_program: Interface<'info, TokenInterface>,
+
+    /// The position owner's entry on the pool's trader allowlist, only needed while it is enabled
+    pub allowed_trader: Option<Account<'info, AllowedTrader>>,
+
+    /// CHECK: Temporary wSOL account, only needed when a user token account
+    /// is omitted; validated and created by the handler
+    #[account(mut)]
+    pub native_sol_account: Option<UncheckedAccount<'info>>,
+
+    /// System program, only needed with `native_sol_account`
+    pub system_program: Option<Program<'info, System>>,
 }
 
 /// Increase liquidity handler
@@ -58,14 +75,21 @@ pub fn handler(
     liquidity_delta: u128,
     amount_a_max: u64,
     amount_b_max: u64,
+    deadline_unix_ts: Option<i64>,
+    max_slot: Option<u64>,
 ) -> Result<()> {
-    require!(liquidity_delta > 0, SuniswapError::ZeroLiquidity);
+    check_deadline(deadline_unix_ts, max_slot)?;
 
-    // Load accounts
+    // Validate the token accounts against the pool; a delegate deposits on
+    // the owner's behalf, so the owner must be allowed
+    let position_owner = ctx.accounts.position.load()?.owner_pubkey();
     let pool = ctx.accounts.pool.load()?;
-
-    // Validate pool state
-    require!(pool.is_paused == 0, SuniswapError::PoolPaused);
+    check_trader_access(
+        &pool,
+        &ctx.accounts.pool.key(),
+        ctx.accounts.allowed_trader.as_ref(),
+        &position_owner,
+    )?;
     require!(
         pool.token_mint_a == ctx.accounts.token_mint_a.key().to_bytes(),
         SuniswapError::InvalidTokenMint
@@ -82,6 +106,93 @@ pub fn handler(
         pool.token_vault_b == ctx.accounts.token_vault_b.key().to_bytes(),
         SuniswapError::InvalidVault
     );
+    drop(pool);
+
+    let (amount_a, amount_b) = add_liquidity(
+        &ctx.accounts.pool,
+        &ctx.accounts.position,
+        ctx.accounts.tick_array_lower.as_ref(),
+        ctx.accounts.tick_array_upper.as_ref(),
+        ctx.accounts.owner.key(),
+        position_permissions::INCREASE,
+        liquidity_delta,
+        amount_a_max,
+        amount_b_max,
+    )?;
+
+    // Transfer tokens
+    let native_sol = NativeSol {
+        user: &ctx.accounts.owner,
+        native_account: ctx.accounts.native_sol_account.as_ref(),
+        system_program: ctx.accounts.system_program.as_ref(),
+        token_program: &ctx.accounts.token_program,
+    };
+    match &ctx.accounts.user_token_a {
+        Some(user_token_a) if amount_a > 0 => transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: user_token_a.to_account_info(),
+                    mint: ctx.accounts.token_mint_a.to_account_info(),
+                    to: ctx.accounts.token_vault_a.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            amount_a,
+            ctx.accounts.token_mint_a.decimals,
+        )?,
+        Some(_) => {}
+        None => native_sol.wrap(&ctx.accounts.token_mint_a, &ctx.accounts.token_vault_a, amount_a)?,
+    }
+
+    match &ctx.accounts.user_token_b {
+        Some(user_token_b) if amount_b > 0 => transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: user_token_b.to_account_info(),
+                    mint: ctx.accounts.token_mint_b.to_account_info(),
+                    to: ctx.accounts.token_vault_b.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            amount_b,
+            ctx.accounts.token_mint_b.decimals,
+        )?,
+        Some(_) => {}
+        None => native_sol.wrap(&ctx.accounts.token_mint_b, &ctx.accounts.token_vault_b, amount_b)?,
+    }
+
+    msg!("Liquidity increased: {}", liquidity_delta);
+    msg!("Amount A: {}, Amount B: {}", amount_a, amount_b);
+
+    Ok(())
+}
+
+/// Add liquidity to a position, returning the token amounts owed to the vaults
+/// `authority` must be the owner or a delegate holding `permissions`
+///
+/// Tick arrays are taken as plain accounts so that `open_position_with_liquidity`
+/// can pass arrays it created earlier in the same instruction
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn add_liquidity(
+    pool_loader: &AccountLoader<Pool>,
+    position_loader: &AccountLoader<Position>,
+    tick_array_lower_info: &AccountInfo,
+    tick_array_upper_info: &AccountInfo,
+    authority: Pubkey,
+    permissions: u8,
+    liquidity_delta: u128,
+    amount_a_max: u64,
+    amount_b_max: u64,
+) -> Result<(u64, u64)> {
+    require!(liquidity_delta > 0, SuniswapError::ZeroLiquidity);
+
+    // Load accounts
+    let pool = pool_loader.load()?;
+
+    // Validate pool state
+    require!(pool.is_paused == 0, SuniswapError::PoolPaused);
 
     // Get pool values we need
     let sqrt_price_x64 = pool.sqrt_price_x64;
@@ -89,34 +200,37 @@ pub fn handler(
     let tick_spacing = pool.tick_spacing;
     let fee_growth_global_a = pool.fee_growth_global_a_x128;
     let fee_growth_global_b = pool.fee_growth_global_b_x128;
-    let pool_key = ctx.accounts.pool.key();
+    let timestamp = Clock::get()?.unix_timestamp as u32;
+    let cumulatives = pool.observe(timestamp);
+    let pool_key = pool_loader.key();
 
     drop(pool);
 
     // Load position
-    let position = ctx.accounts.position.load()?;
+    let position = position_loader.load()?;
     require!(
         position.pool == pool_key.to_bytes(),
         SuniswapError::InvalidPosition
     );
     require!(
-        position.owner == ctx.accounts.owner.key().to_bytes(),
+        position.is_authorized(&authority, permissions),
         SuniswapError::InvalidPositionOwner
     );
+    let owner = position.owner_pubkey();
 
     let tick_lower = position.tick_lower;
     let tick_upper = position.tick_upper;
     drop(position);
 
     // Validate tick arrays
-    let tick_array_lower = ctx.accounts.tick_array_lower.load()?;
+    let tick_array_lower = TickArray::load_from(tick_array_lower_info)?;
     require!(
         tick_array_lower.pool == pool_key.to_bytes(),
         SuniswapError::InvalidTickArray
     );
     drop(tick_array_lower);
 
-    let tick_array_upper = ctx.accounts.tick_array_upper.load()?;
+    let tick_array_upper = TickArray::load_from(tick_array_upper_info)?;
     require!(
         tick_array_upper.pool == pool_key.to_bytes(),
         SuniswapError::InvalidTickArray
@@ -136,102 +250,85 @@ pub fn handler(
     require!(amount_a <= amount_a_max, SuniswapError::AmountAExceedsMax);
     require!(amount_b <= amount_b_max, SuniswapError::AmountBExceedsMax);
 
-    // Update fee growth and ticks
+    // Update ticks and position
     {
-        let mut tick_array_lower = ctx.accounts.tick_array_lower.load_mut()?;
-        let mut tick_array_upper = ctx.accounts.tick_array_upper.load_mut()?;
-
-        // Calculate fee growth inside
-        let (fee_growth_inside_a, fee_growth_inside_b) = calculate_fee_growth_inside(
-            &tick_array_lower,
-            &tick_array_upper,
-            tick_lower,
-            tick_upper,
-            tick_current,
-            fee_growth_global_a,
-            fee_growth_global_b,
-            tick_spacing,
-        )?;
-
-        // Update position
-        let mut position = ctx.accounts.position.load_mut()?;
-        position.update_owed_tokens(fee_growth_inside_a, fee_growth_inside_b)?;
-        position.liquidity = position.liquidity
-            .checked_add(liquidity_delta)
-            .ok_or(SuniswapError::LiquidityOverflow)?;
-        drop(position);
-
         // Safe conversion: validate liquidity_delta fits in i128
         let liquidity_delta_signed = i128::try_from(liquidity_delta)
             .map_err(|_| SuniswapError::LiquidityOverflow)?;
 
-        // Update ticks
-        let _flipped_lower = tick_array_lower.update_tick(
+        // Update ticks first: a bound initialized by this deposit snapshots its fee
+        // growth outside here, and the fee growth inside below must account for it.
+        // Both bounds may live in the same tick array, so only ever hold one
+        // mutable borrow at a time
+        let _flipped_lower = TickArray::load_mut_from(tick_array_lower_info)?.update_tick(
             tick_lower,
             tick_spacing,
             tick_current,
             liquidity_delta_signed,
             fee_growth_global_a,
             fee_growth_global_b,
+            &cumulatives,
             false,
         )?;
 
-        let _flipped_upper = tick_array_upper.update_tick(
+        let _flipped_upper = TickArray::load_mut_from(tick_array_upper_info)?.update_tick(
             tick_upper,
             tick_spacing,
             tick_current,
             liquidity_delta_signed,
             fee_growth_global_a,
             fee_growth_global_b,
+            &cumulatives,
             true,
         )?;
+
+        let tick_array_lower = TickArray::load_from(tick_array_lower_info)?;
+        let tick_array_upper = TickArray::load_from(tick_array_upper_info)?;
+
+        // Calculate fee growth inside
+        let (fee_growth_inside_a, fee_growth_inside_b) = calculate_fee_growth_inside(
+            &tick_array_lower,
+            &tick_array_upper,
+            tick_lower,
+            tick_upper,
+            tick_current,
+            fee_growth_global_a,
+            fee_growth_global_b,
+            tick_spacing,
+        )?;
+        drop(tick_array_lower);
+        drop(tick_array_upper);
+
+        // Update position
+        let mut position = position_loader.load_mut()?;
+        position.update_owed_tokens(fee_growth_inside_a, fee_growth_inside_b)?;
+        position.liquidity = position.liquidity
+            .checked_add(liquidity_delta)
+            .ok_or(SuniswapError::LiquidityOverflow)?;
     }
 
     // Update pool liquidity if in range
     if tick_current >= tick_lower && tick_current < tick_upper {
         let liquidity_delta_signed = i128::try_from(liquidity_delta)
             .map_err(|_| SuniswapError::LiquidityOverflow)?;
-        let mut pool = ctx.accounts.pool.load_mut()?;
+        let mut pool = pool_loader.load_mut()?;
+        pool.update_cumulatives(timestamp);
         pool.liquidity = add_liquidity_delta(pool.liquidity, liquidity_delta_signed)?;
     }
 
-    // Transfer tokens
-    if amount_a > 0 {
-        transfer_checked(
-            CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                TransferChecked {
-                    from: ctx.accounts.user_token_a.to_account_info(),
-                    mint: ctx.accounts.token_mint_a.to_account_info(),
-                    to: ctx.accounts.token_vault_a.to_account_info(),
-                    authority: ctx.accounts.owner.to_account_info(),
-                },
-            ),
-            amount_a,
-            ctx.accounts.token_mint_a.decimals,
-        )?;
-    }
-
-    if amount_b > 0 {
-        transfer_checked(
-            CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                TransferChecked {
-                    from: ctx.accounts.user_token_b.to_account_info(),
-                    mint: ctx.accounts.token_mint_b.to_account_info(),
-                    to: ctx.accounts.token_vault_b.to_account_info(),
-                    authority: ctx.accounts.owner.to_account_info(),
-                },
-            ),
-            amount_b,
-            ctx.accounts.token_mint_b.decimals,
-        )?;
-    }
-
-    msg!("Liquidity increased: {}", liquidity_delta);
-    msg!("Amount A: {}, Amount B: {}", amount_a, amount_b);
+    emit!(LiquidityIncreasedEvent {
+        pool: pool_key,
+        position: position_loader.key(),
+        owner,
+        tick_lower,
+        tick_upper,
+        liquidity_delta,
+        amount_a,
+        amount_b,
+        pool_liquidity: pool_loader.load()?.liquidity,
+    });
 
-    Ok(())
+    Ok((amount_a, amount_b))
 }
 
 /// Calculate fee growth inside a position's tick range
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use crate::errors::SuniswapError;
+use crate::instructions::increase_liquidity::{self, IncreaseLiquidity};
+use crate::math::liquidity_math::get_liquidity_for_amounts;
+use crate::math::tick_math::get_sqrt_price_at_tick;
+
+/// Parameters for depositing by token amounts
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct IncreaseLiquidityByAmountsParams {
+    /// Maximum amount of token A to deposit
+    pub amount_a_max: u64,
+    /// Maximum amount of token B to deposit
+    pub amount_b_max: u64,
+    /// Lowest acceptable pool sqrt price (Q64.64)
+    pub sqrt_price_min_x64: u128,
+    /// Highest acceptable pool sqrt price (Q64.64)
+    pub sqrt_price_max_x64: u128,
+    /// Unix timestamp after which the deposit is rejected
+    pub deadline_unix_ts: Option<i64>,
+    /// Slot after which the deposit is rejected
+    pub max_slot: Option<u64>,
+}
+
+/// Increase liquidity by amounts handler
+///
+/// Computes the largest liquidity the maximum amounts can fund at the current
+/// price, then deposits it exactly like `increase_liquidity`
+pub fn handler(
+    ctx: Context<IncreaseLiquidity>,
+    params: IncreaseLiquidityByAmountsParams,
+) -> Result<()> {
+    let sqrt_price_x64 = ctx.accounts.pool.load()?.sqrt_price_x64;
+    require!(
+        sqrt_price_x64 >= params.sqrt_price_min_x64 && sqrt_price_x64 <= params.sqrt_price_max_x64,
+        SuniswapError::PriceOutsideBand
+    );
+
+    // The position is validated against the pool and owner by the deposit below
+    let position = ctx.accounts.position.load()?;
+    let (tick_lower, tick_upper) = (position.tick_lower, position.tick_upper);
+    drop(position);
+
+    let liquidity_delta = get_liquidity_for_amounts(
+        sqrt_price_x64,
+        get_sqrt_price_at_tick(tick_lower)?,
+        get_sqrt_price_at_tick(tick_upper)?,
+        params.amount_a_max,
+        params.amount_b_max,
+    )?;
+
+    increase_liquidity::handler(
+        ctx,
+        liquidity_delta,
+        params.amount_a_max,
+        params.amount_b_max,
+        params.deadline_unix_ts,
+        params.max_slot,
+    )
+}
@@ -51,6 +51,9 @@ pub fn handler(
     config.pool_creation_paused = false;
     config.bump = ctx.bumps.config;
     config.fee_tier_count = 0;
+    config.max_referral_fee_rate = 0;
+    config.max_creator_fee_rate = 0;
+    config.pool_creation_mode = crate::constants::pool_creation::MODE_OPEN;
 
     msg!("SuniSwap config initialized");
     msg!("Protocol authority: {}", config.protocol_authority);
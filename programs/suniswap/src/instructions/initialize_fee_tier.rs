@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::state::{SuniswapConfig, FeeTier};
+use crate::state::{SuniswapConfig, FeeTier, FeeTierIndex};
 use crate::constants::{seeds, FEE_RATE_DENOMINATOR, MAX_TICK_SPACING};
 use crate::errors::SuniswapError;
 
@@ -25,6 +25,16 @@ pub struct InitializeFeeTier<'info> {
     )]
     pub fee_tier: Account<'info, FeeTier>,
 
+    /// Enumeration entry for the new tier, at the next free index
+    #[account(
+        init,
+        payer = payer,
+        space = FeeTierIndex::LEN,
+        seeds = [seeds::FEE_TIER_INDEX_SEED, &config.fee_tier_count.to_le_bytes()],
+        bump
+    )]
+    pub fee_tier_index: Account<'info, FeeTierIndex>,
+
     /// Authority that can create fee tiers (protocol authority)
     pub authority: Signer<'info>,
 
@@ -67,6 +77,15 @@ pub fn handler(
     fee_tier.fee_rate = fee_rate;
     fee_tier.tick_spacing = tick_spacing;
     fee_tier.bump = ctx.bumps.fee_tier;
+    fee_tier.index = config.fee_tier_count;
+    fee_tier.disabled = false;
+    fee_tier.seed_fee_rate = fee_rate;
+    fee_tier.seed_recorded = true;
+    fee_tier.index_registered = true;
+
+    let fee_tier_index = &mut ctx.accounts.fee_tier_index;
+    fee_tier_index.fee_tier = fee_tier.key();
+    fee_tier_index.bump = ctx.bumps.fee_tier_index;
 
     // Increment fee tier count
     config.fee_tier_count = config.fee_tier_count
@@ -76,6 +95,7 @@ pub fn handler(
     msg!("Fee tier initialized");
     msg!("Fee rate: {} ({}%)", fee_rate, fee_rate as f64 / 10000.0);
     msg!("Tick spacing: {}", tick_spacing);
+    msg!("Index: {}", fee_tier.index);
 
     Ok(())
 }
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use crate::state::{SuniswapConfig, Pool, Oracle};
+use crate::constants::{seeds, oracle::OBSERVATION_CAPACITY};
+use crate::errors::SuniswapError;
+use crate::events::PoolTwapGuardUpdatedEvent;
+
+/// Create a pool's TWAP oracle
+/// From then on every swap on the pool must pass the oracle so it records
+/// the pool's accumulators, at most `OBSERVATION_CAPACITY` times per TWAP
+/// window. Only callable by protocol authority
+#[derive(Accounts)]
+pub struct InitializeOracle<'info> {
+    /// The global config
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, SuniswapConfig>,
+
+    /// The pool to observe (zero-copy)
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// The oracle to initialize
+    #[account(
+        init,
+        payer = payer,
+        space = Oracle::size(OBSERVATION_CAPACITY as u16),
+        seeds = [seeds::ORACLE_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub oracle: Account<'info, Oracle>,
+
+    /// Protocol authority
+    #[account(
+        constraint = config.is_protocol_authority(&authority.key()) @ SuniswapError::NotProtocolAuthority,
+    )]
+    pub authority: Signer<'info>,
+
+    /// The payer for account creation
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+/// Initialize oracle handler
+pub fn handler(ctx: Context<InitializeOracle>, twap_window: u32) -> Result<()> {
+    require!(twap_window > 0, SuniswapError::InvalidTwapWindow);
+
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    require!(
+        pool.config == ctx.accounts.config.key().to_bytes(),
+        SuniswapError::InvalidConfig
+    );
+
+    let oracle = &mut ctx.accounts.oracle;
+    oracle.pool = ctx.accounts.pool.key();
+    oracle.bump = ctx.bumps.oracle;
+    oracle.initialize(pool.observe(Clock::get()?.unix_timestamp as u32));
+    oracle.grow(OBSERVATION_CAPACITY as u16);
+
+    pool.oracle = oracle.key().to_bytes();
+    pool.twap_window = twap_window;
+
+    emit!(PoolTwapGuardUpdatedEvent {
+        pool: oracle.pool,
+        oracle: oracle.key(),
+        twap_window,
+        max_twap_deviation_ticks: pool.max_twap_deviation_ticks,
+    });
+
+    msg!("Oracle initialized");
+    msg!("Pool: {}", oracle.pool);
+    msg!("TWAP window: {}s", twap_window);
+
+    Ok(())
+}
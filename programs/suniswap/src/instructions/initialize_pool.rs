@@ -1,8 +1,11 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
-use crate::state::{SuniswapConfig, FeeTier, Pool};
-use crate::constants::seeds;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked, transfer_checked};
+use crate::state::{SuniswapConfig, FeeTier, Pool, PoolCreationConfig};
+use crate::constants::{seeds, pool_creation};
 use crate::errors::SuniswapError;
+use crate::events::{PoolInitializedEvent, PoolCreationFeePaidEvent};
 use crate::math::tick_math::get_tick_at_sqrt_price;
 
 /// Initialize a new liquidity pool
@@ -18,9 +21,10 @@ pub struct InitializePool<'info> {
 
     /// The fee tier for this pool
     #[account(
-        seeds = [seeds::FEE_TIER_SEED, &fee_tier.fee_rate.to_le_bytes()],
+        seeds = [seeds::FEE_TIER_SEED, &fee_tier.seed_bytes()],
         bump = fee_tier.bump,
         constraint = fee_tier.config == config.key() @ SuniswapError::InvalidFeeTier,
+        constraint = !fee_tier.disabled @ SuniswapError::FeeTierDisabled,
     )]
     pub fee_tier: Account<'info, FeeTier>,
 
@@ -33,7 +37,7 @@ pub struct InitializePool<'info> {
             seeds::POOL_SEED,
             token_mint_a.key().as_ref(),
             token_mint_b.key().as_ref(),
-            &fee_tier.fee_rate.to_le_bytes()
+            &fee_tier.seed_bytes()
         ],
         bump
     )]
@@ -78,16 +82,157 @@ pub struct InitializePool<'info> {
 
     /// System program
     pub system_program: Program<'info, System>,
+
+    /// Pool creation allowlist and fee, required unless pool creation is open
+    #[account(
+        seeds = [seeds::POOL_CREATION_CONFIG_SEED],
+        bump = pool_creation_config.bump,
+    )]
+    pub pool_creation_config: Option<Account<'info, PoolCreationConfig>>,
+
+    /// Mint of the creation fee, when it is paid in a token
+    pub creation_fee_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Payer's token account the creation fee is paid from, when it is paid in a token
+    #[account(mut)]
+    pub creation_fee_source: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: The treasury wallet for a SOL fee, or its associated token
+    /// account for a token fee; checked against the pool creation config
+    #[account(mut)]
+    pub creation_fee_treasury: Option<UncheckedAccount<'info>>,
+
+    /// Token program owning the creation fee mint, when the fee is paid in a token
+    pub creation_fee_token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+impl InitializePool<'_> {
+    /// Enforce the config's pool creation mode on the payer
+    fn check_creation_access(&self) -> Result<()> {
+        let mode = self.config.pool_creation_mode;
+        if mode == pool_creation::MODE_OPEN {
+            return Ok(());
+        }
+
+        let Some(pool_creation_config) = self.pool_creation_config.as_ref() else {
+            return err!(SuniswapError::InvalidPoolCreationFeeAccount);
+        };
+        match mode {
+            pool_creation::MODE_ALLOWLIST => {
+                require!(
+                    pool_creation_config.is_allowed_creator(&self.payer.key()),
+                    SuniswapError::PoolCreatorNotAllowed
+                );
+                Ok(())
+            }
+            pool_creation::MODE_FEE => self.pay_creation_fee(pool_creation_config),
+            _ => err!(SuniswapError::InvalidPoolCreationMode),
+        }
+    }
+
+    /// Pay the creation fee from the payer to the treasury
+    fn pay_creation_fee(&self, pool_creation_config: &PoolCreationConfig) -> Result<()> {
+        let Some(treasury) = self.creation_fee_treasury.as_ref() else {
+            return err!(SuniswapError::InvalidPoolCreationFeeAccount);
+        };
+        let amount = pool_creation_config.fee_amount;
+
+        let fee_mint = if pool_creation_config.is_native_fee() {
+            require_keys_eq!(
+                treasury.key(),
+                pool_creation_config.treasury,
+                SuniswapError::InvalidPoolCreationFeeAccount
+            );
+            system_program::transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.payer.to_account_info(),
+                        to: treasury.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+            None
+        } else {
+            let (Some(mint), Some(source), Some(token_program)) = (
+                self.creation_fee_mint.as_ref(),
+                self.creation_fee_source.as_ref(),
+                self.creation_fee_token_program.as_ref(),
+            ) else {
+                return err!(SuniswapError::InvalidPoolCreationFeeAccount);
+            };
+            require_keys_eq!(
+                mint.key(),
+                pool_creation_config.fee_mint,
+                SuniswapError::InvalidPoolCreationFeeAccount
+            );
+            // The fee mint need not share the pool's token program
+            require_keys_eq!(
+                token_program.key(),
+                *mint.to_account_info().owner,
+                SuniswapError::InvalidPoolCreationFeeAccount
+            );
+            require_keys_eq!(
+                treasury.key(),
+                get_associated_token_address_with_program_id(
+                    &pool_creation_config.treasury,
+                    &mint.key(),
+                    token_program.key,
+                ),
+                SuniswapError::InvalidPoolCreationFeeAccount
+            );
+            transfer_checked(
+                CpiContext::new(
+                    token_program.to_account_info(),
+                    TransferChecked {
+                        from: source.to_account_info(),
+                        mint: mint.to_account_info(),
+                        to: treasury.to_account_info(),
+                        authority: self.payer.to_account_info(),
+                    },
+                ),
+                amount,
+                mint.decimals,
+            )?;
+            Some(mint.key())
+        };
+
+        emit!(PoolCreationFeePaidEvent {
+            pool: self.pool.key(),
+            payer: self.payer.key(),
+            fee_mint,
+            amount,
+            treasury: pool_creation_config.treasury,
+        });
+
+        Ok(())
+    }
 }
 
 /// Initialize pool handler
 pub fn handler(
     ctx: Context<InitializePool>,
     initial_sqrt_price_x64: u128,
+    creator: Option<Pubkey>,
+    creator_fee_rate: u8,
 ) -> Result<()> {
     let config = &ctx.accounts.config;
     let fee_tier = &ctx.accounts.fee_tier;
 
+    // Allowlist or creation fee, per the config's pool creation mode
+    ctx.accounts.check_creation_access()?;
+
+    // Validate the creator fee against the config cap
+    require!(
+        creator.is_some() || creator_fee_rate == 0,
+        SuniswapError::InvalidPoolCreator
+    );
+    require!(
+        creator_fee_rate <= config.max_creator_fee_rate,
+        SuniswapError::CreatorFeeTooHigh
+    );
+
     // Validate token ordering
     require!(
         ctx.accounts.token_mint_a.key() < ctx.accounts.token_mint_b.key(),
@@ -137,6 +282,49 @@ pub fn handler(
     pool.observation_cardinality = 0;
     pool.observation_cardinality_next = 0;
 
+    // Start the tick oracle accumulators
+    pool.seconds_per_liquidity_cumulative_x64 = 0;
+    pool.tick_cumulative = 0;
+    pool.cumulatives_timestamp = Clock::get()?.unix_timestamp as u32;
+
+    // Follow the tier's fee until the protocol authority overrides it
+    pool.fee_rate = fee_tier.fee_rate;
+    pool.fee_rate_override = 0;
+    pool.adaptive_fee_enabled = 0;
+
+    // Creator fees accrue alongside protocol fees from the first swap
+    pool.creator = creator.map_or([0u8; 32], |creator| creator.to_bytes());
+    pool.creator_fee_rate = creator_fee_rate;
+    pool.creator_fees_a = 0;
+    pool.creator_fees_b = 0;
+
+    // Trading is open to everyone until an allowlist admin switches it on
+    pool.trader_allowlist_enabled = 0;
+
+    // Price moves are uncapped until the protocol authority sets a circuit breaker
+    pool.circuit_breaker_slot = 0;
+    pool.circuit_breaker_tick = initial_tick;
+    pool.max_tick_move_per_swap = 0;
+    pool.max_tick_move_per_slot = 0;
+
+    // No TWAP guard without an oracle
+    pool.twap_window = 0;
+    pool.max_twap_deviation_ticks = 0;
+    drop(pool);
+
+    emit!(PoolInitializedEvent {
+        pool: ctx.accounts.pool.key(),
+        token_mint_a: ctx.accounts.token_mint_a.key(),
+        token_mint_b: ctx.accounts.token_mint_b.key(),
+        token_vault_a: ctx.accounts.token_vault_a.key(),
+        token_vault_b: ctx.accounts.token_vault_b.key(),
+        fee_tier: fee_tier.key(),
+        fee_rate: fee_tier.fee_rate,
+        tick_spacing: fee_tier.tick_spacing,
+        sqrt_price_x64: initial_sqrt_price_x64,
+        tick_current: initial_tick,
+    });
+
     msg!("Pool initialized");
     msg!("Token A: {}", ctx.accounts.token_mint_a.key());
     msg!("Token B: {}", ctx.accounts.token_mint_b.key());
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::state::{SuniswapConfig, PoolCreationConfig};
+use crate::constants::seeds;
+use crate::errors::SuniswapError;
+
+/// Create the pool creation allowlist and fee, both empty
+/// Only callable by protocol authority
+#[derive(Accounts)]
+pub struct InitializePoolCreationConfig<'info> {
+    /// The global config
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, SuniswapConfig>,
+
+    /// The pool creation config to initialize
+    #[account(
+        init,
+        payer = payer,
+        space = PoolCreationConfig::LEN,
+        seeds = [seeds::POOL_CREATION_CONFIG_SEED],
+        bump
+    )]
+    pub pool_creation_config: Account<'info, PoolCreationConfig>,
+
+    /// Protocol authority
+    #[account(
+        constraint = config.is_protocol_authority(&authority.key()) @ SuniswapError::NotProtocolAuthority,
+    )]
+    pub authority: Signer<'info>,
+
+    /// The payer for account creation
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+/// Initialize pool creation config handler
+pub fn handler(ctx: Context<InitializePoolCreationConfig>) -> Result<()> {
+    let pool_creation_config = &mut ctx.accounts.pool_creation_config;
+    pool_creation_config.config = ctx.accounts.config.key();
+    pool_creation_config.bump = ctx.bumps.pool_creation_config;
+
+    msg!("Pool creation config initialized");
+
+    Ok(())
+}
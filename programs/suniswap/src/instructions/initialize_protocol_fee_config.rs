@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::state::{SuniswapConfig, ProtocolFeeConfig, ProtocolFeeRecipient};
+use crate::constants::seeds;
+use crate::errors::SuniswapError;
+use crate::events::ProtocolFeeRecipientsUpdatedEvent;
+
+/// Create the protocol fee split
+/// Only callable by fee authority
+#[derive(Accounts)]
+pub struct InitializeProtocolFeeConfig<'info> {
+    /// The global config
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, SuniswapConfig>,
+
+    /// The fee split account to initialize
+    #[account(
+        init,
+        payer = payer,
+        space = ProtocolFeeConfig::LEN,
+        seeds = [seeds::PROTOCOL_FEE_CONFIG_SEED],
+        bump
+    )]
+    pub protocol_fee_config: Account<'info, ProtocolFeeConfig>,
+
+    /// Fee authority
+    #[account(
+        constraint = fee_authority.key() == config.fee_authority @ SuniswapError::InvalidFeeAuthority,
+    )]
+    pub fee_authority: Signer<'info>,
+
+    /// The payer for account creation
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+/// Initialize protocol fee config handler
+pub fn handler(
+    ctx: Context<InitializeProtocolFeeConfig>,
+    recipients: Vec<ProtocolFeeRecipient>,
+) -> Result<()> {
+    let protocol_fee_config = &mut ctx.accounts.protocol_fee_config;
+    protocol_fee_config.config = ctx.accounts.config.key();
+    protocol_fee_config.bump = ctx.bumps.protocol_fee_config;
+    protocol_fee_config.set_recipients(&recipients)?;
+
+    emit!(ProtocolFeeRecipientsUpdatedEvent { recipients });
+
+    msg!("Protocol fee config initialized");
+    msg!("Recipients: {}", protocol_fee_config.recipient_count);
+
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount, Transfer};
 use crate::state::{Pool, TickArray};
 use crate::constants::seeds;
 use crate::errors::SuniswapError;
@@ -50,6 +51,27 @@ pub fn handler(
     // Drop the pool borrow before loading tick_array
     drop(pool);
 
+    validate_start_tick_index(start_tick_index, tick_spacing)?;
+
+    // Initialize tick array using zero-copy
+    let mut tick_array = ctx.accounts.tick_array.load_init()?;
+    tick_array.pool = pool_key.to_bytes();
+    tick_array.start_tick_index = start_tick_index;
+    tick_array.initialized_bitmap = 0;
+    tick_array.bump = ctx.bumps.tick_array;
+
+    // Ticks are automatically zero-initialized
+
+    msg!("Tick array initialized");
+    msg!("Pool: {}", pool_key);
+    msg!("Start tick index: {}", start_tick_index);
+
+    Ok(())
+}
+
+/// Validate that a tick array starting at `start_tick_index` is aligned and
+/// fits within the tick range
+pub(crate) fn validate_start_tick_index(start_tick_index: i32, tick_spacing: u16) -> Result<()> {
     // Validate start_tick_index is aligned to tick array boundaries
     let ticks_per_array = (crate::constants::TICK_ARRAY_SIZE as i32) * (tick_spacing as i32);
 
@@ -68,17 +90,87 @@ pub fn handler(
         SuniswapError::TickAboveMaximum
     );
 
-    // Initialize tick array using zero-copy
-    let mut tick_array = ctx.accounts.tick_array.load_init()?;
-    tick_array.pool = pool_key.to_bytes();
-    tick_array.start_tick_index = start_tick_index;
-    tick_array.initialized_bitmap = 0;
-    tick_array.bump = ctx.bumps.tick_array;
+    Ok(())
+}
 
-    // Ticks are automatically zero-initialized
+/// Create the tick array PDA at `start_tick_index` unless it already exists
+///
+/// Equivalent to `initialize_tick_array` with `init_if_needed` semantics. The
+/// discriminator is written immediately, so the same account may be passed
+/// again (e.g. as both the lower and upper array) within the instruction
+pub(crate) fn initialize_tick_array_if_needed<'info>(
+    tick_array: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    pool_key: Pubkey,
+    tick_spacing: u16,
+    start_tick_index: i32,
+) -> Result<()> {
+    let start_bytes = start_tick_index.to_le_bytes();
+    let (expected, bump) = Pubkey::find_program_address(
+        &[seeds::TICK_ARRAY_SEED, pool_key.as_ref(), &start_bytes],
+        &crate::ID,
+    );
+    require_keys_eq!(tick_array.key(), expected, SuniswapError::InvalidTickArray);
+
+    if tick_array.owner == &crate::ID {
+        return Ok(());
+    }
+    validate_start_tick_index(start_tick_index, tick_spacing)?;
+
+    let signer_seeds: &[&[u8]] = &[seeds::TICK_ARRAY_SEED, pool_key.as_ref(), &start_bytes, &[bump]];
+    let rent = Rent::get()?.minimum_balance(TickArray::LEN);
+    let lamports = tick_array.lamports();
+
+    if lamports == 0 {
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount { from: payer.clone(), to: tick_array.clone() },
+                &[signer_seeds],
+            ),
+            rent,
+            TickArray::LEN as u64,
+            &crate::ID,
+        )?;
+    } else {
+        // Someone already sent lamports to the address: top up, then allocate and assign
+        if lamports < rent {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    Transfer { from: payer.clone(), to: tick_array.clone() },
+                ),
+                rent - lamports,
+            )?;
+        }
+        system_program::allocate(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                Allocate { account_to_allocate: tick_array.clone() },
+                &[signer_seeds],
+            ),
+            TickArray::LEN as u64,
+        )?;
+        system_program::assign(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                Assign { account_to_assign: tick_array.clone() },
+                &[signer_seeds],
+            ),
+            &crate::ID,
+        )?;
+    }
+
+    let mut data = tick_array.try_borrow_mut_data()?;
+    data[..8].copy_from_slice(TickArray::DISCRIMINATOR);
+    let array: &mut TickArray = bytemuck::from_bytes_mut(&mut data[8..TickArray::LEN]);
+    array.pool = pool_key.to_bytes();
+    array.start_tick_index = start_tick_index;
+    array.initialized_bitmap = 0;
+    array.bump = bump;
 
     msg!("Tick array initialized");
-    msg!("Pool: {}", pool_key);
     msg!("Start tick index: {}", start_tick_index);
 
     Ok(())
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use crate::state::{SuniswapConfig, Pool, TraderAllowlist};
+use crate::constants::seeds;
+use crate::errors::SuniswapError;
+use crate::events::TraderAllowlistUpdatedEvent;
+
+/// Create a pool's trader allowlist and hand it to an admin
+/// The allowlist starts empty and switched off, so the admin can fill it first
+/// Only callable by protocol authority
+#[derive(Accounts)]
+pub struct InitializeTraderAllowlist<'info> {
+    /// The global config
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, SuniswapConfig>,
+
+    /// The pool to gate (zero-copy)
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// The trader allowlist to initialize
+    #[account(
+        init,
+        payer = payer,
+        space = TraderAllowlist::LEN,
+        seeds = [seeds::TRADER_ALLOWLIST_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub trader_allowlist: Account<'info, TraderAllowlist>,
+
+    /// Protocol authority
+    #[account(
+        constraint = config.is_protocol_authority(&authority.key()) @ SuniswapError::NotProtocolAuthority,
+    )]
+    pub authority: Signer<'info>,
+
+    /// The payer for account creation
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+/// Initialize trader allowlist handler
+pub fn handler(ctx: Context<InitializeTraderAllowlist>, admin: Pubkey) -> Result<()> {
+    require!(
+        ctx.accounts.pool.load()?.config == ctx.accounts.config.key().to_bytes(),
+        SuniswapError::InvalidConfig
+    );
+
+    let trader_allowlist = &mut ctx.accounts.trader_allowlist;
+    trader_allowlist.pool = ctx.accounts.pool.key();
+    trader_allowlist.admin = admin;
+    trader_allowlist.bump = ctx.bumps.trader_allowlist;
+
+    emit!(TraderAllowlistUpdatedEvent {
+        pool: trader_allowlist.pool,
+        admin,
+        enabled: false,
+    });
+
+    msg!("Trader allowlist initialized");
+    msg!("Pool: {}", trader_allowlist.pool);
+    msg!("Admin: {}", admin);
+
+    Ok(())
+}
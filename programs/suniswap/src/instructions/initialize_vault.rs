@@ -0,0 +1,190 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{SuniswapConfig, Pool, ManagedVault};
+use crate::constants::seeds;
+use crate::constants::managed_vault::{MAX_PERFORMANCE_FEE_RATE, MAX_MANAGEMENT_FEE_RATE, SHARE_DECIMALS};
+use crate::errors::SuniswapError;
+use crate::events::{VaultInitializedEvent, VaultParamsUpdatedEvent};
+use crate::instructions::open_position::validate_tick_range;
+
+/// Create a managed liquidity vault over a pool
+/// Only callable by protocol authority
+#[derive(Accounts)]
+#[instruction(id: u16)]
+pub struct InitializeVault<'info> {
+    /// The global config
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, SuniswapConfig>,
+
+    /// The pool the vault provides liquidity to (zero-copy)
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// The vault account to initialize
+    #[account(
+        init,
+        payer = payer,
+        space = ManagedVault::LEN,
+        seeds = [seeds::MANAGED_VAULT_SEED, pool.key().as_ref(), &id.to_le_bytes()],
+        bump
+    )]
+    pub vault: Account<'info, ManagedVault>,
+
+    /// The vault's share mint
+    #[account(
+        init,
+        payer = payer,
+        seeds = [seeds::VAULT_SHARE_MINT_SEED, vault.key().as_ref()],
+        bump,
+        mint::decimals = SHARE_DECIMALS,
+        mint::authority = vault,
+        mint::token_program = token_program,
+    )]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+
+    /// Token A mint
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+
+    /// Token B mint
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    /// Vault reserve for token A
+    #[account(
+        init,
+        payer = payer,
+        seeds = [seeds::VAULT_RESERVE_SEED, vault.key().as_ref(), token_mint_a.key().as_ref()],
+        bump,
+        token::mint = token_mint_a,
+        token::authority = vault,
+        token::token_program = token_program,
+    )]
+    pub reserve_a: InterfaceAccount<'info, TokenAccount>,
+
+    /// Vault reserve for token B
+    #[account(
+        init,
+        payer = payer,
+        seeds = [seeds::VAULT_RESERVE_SEED, vault.key().as_ref(), token_mint_b.key().as_ref()],
+        bump,
+        token::mint = token_mint_b,
+        token::authority = vault,
+        token::token_program = token_program,
+    )]
+    pub reserve_b: InterfaceAccount<'info, TokenAccount>,
+
+    /// Protocol authority
+    pub authority: Signer<'info>,
+
+    /// The payer for account creation
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Token program
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+/// Managed vault parameters
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct VaultParams {
+    /// Account allowed to manage the vault's positions
+    pub strategist: Pubkey,
+    /// Owner of the accounts vault fees are paid to
+    pub treasury: Pubkey,
+    /// Share of harvested fees for the treasury, in basis points (at most 50%)
+    pub performance_fee_rate: u16,
+    /// Yearly fee on shares outstanding, in basis points (at most 5%)
+    pub management_fee_rate: u16,
+    /// Vault positions must lie within [tick_lower_limit, tick_upper_limit]
+    pub tick_lower_limit: i32,
+    pub tick_upper_limit: i32,
+}
+
+/// Check fee caps and that the tick limits form a valid range on the pool
+pub(crate) fn validate_vault_params(params: &VaultParams, tick_spacing: u16) -> Result<()> {
+    require!(
+        params.performance_fee_rate <= MAX_PERFORMANCE_FEE_RATE
+            && params.management_fee_rate <= MAX_MANAGEMENT_FEE_RATE,
+        SuniswapError::InvalidVaultParams
+    );
+    validate_tick_range(params.tick_lower_limit, params.tick_upper_limit, tick_spacing)
+        .map_err(|_| SuniswapError::InvalidVaultParams.into())
+}
+
+/// Store `params` on the vault and emit the update
+pub(crate) fn apply_vault_params(vault: &mut Account<ManagedVault>, params: VaultParams) {
+    vault.strategist = params.strategist;
+    vault.treasury = params.treasury;
+    vault.performance_fee_rate = params.performance_fee_rate;
+    vault.management_fee_rate = params.management_fee_rate;
+    vault.tick_lower_limit = params.tick_lower_limit;
+    vault.tick_upper_limit = params.tick_upper_limit;
+
+    emit!(VaultParamsUpdatedEvent {
+        vault: vault.key(),
+        strategist: params.strategist,
+        treasury: params.treasury,
+        performance_fee_rate: params.performance_fee_rate,
+        management_fee_rate: params.management_fee_rate,
+        tick_lower_limit: params.tick_lower_limit,
+        tick_upper_limit: params.tick_upper_limit,
+    });
+}
+
+/// Initialize vault handler
+pub fn handler(ctx: Context<InitializeVault>, id: u16, params: VaultParams) -> Result<()> {
+    let config = &ctx.accounts.config;
+    require!(
+        config.is_protocol_authority(&ctx.accounts.authority.key()),
+        SuniswapError::NotProtocolAuthority
+    );
+
+    let pool = ctx.accounts.pool.load()?;
+    require!(
+        pool.config == config.key().to_bytes(),
+        SuniswapError::InvalidConfig
+    );
+    require!(
+        pool.token_mint_a == ctx.accounts.token_mint_a.key().to_bytes(),
+        SuniswapError::InvalidTokenMint
+    );
+    require!(
+        pool.token_mint_b == ctx.accounts.token_mint_b.key().to_bytes(),
+        SuniswapError::InvalidTokenMint
+    );
+    validate_vault_params(&params, pool.tick_spacing)?;
+    drop(pool);
+
+    let vault = &mut ctx.accounts.vault;
+    vault.pool = ctx.accounts.pool.key();
+    vault.id = id;
+    vault.share_mint = ctx.accounts.share_mint.key();
+    vault.reserve_a = ctx.accounts.reserve_a.key();
+    vault.reserve_b = ctx.accounts.reserve_b.key();
+    vault.positions = Default::default();
+    vault.position_count = 0;
+    vault.treasury_fees_a = 0;
+    vault.treasury_fees_b = 0;
+    vault.treasury_shares = 0;
+    vault.fees_accrued_at = Clock::get()?.unix_timestamp;
+    vault.bump = ctx.bumps.vault;
+    vault.locked_shares = 0;
+    vault._reserved = [0u8; 24];
+
+    emit!(VaultInitializedEvent {
+        vault: vault.key(),
+        pool: vault.pool,
+        share_mint: vault.share_mint,
+        reserve_a: vault.reserve_a,
+        reserve_b: vault.reserve_b,
+    });
+    apply_vault_params(vault, params);
+
+    msg!("Vault {} initialized on pool {}", id, ctx.accounts.pool.key());
+
+    Ok(())
+}
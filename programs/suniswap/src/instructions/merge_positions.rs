@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use crate::state::{Pool, Position, TickArray, AllowedTrader};
+use crate::errors::SuniswapError;
+use crate::events::{PositionClosedEvent, PositionsMergedEvent};
+use crate::instructions::close_position::close_position_account;
+use crate::instructions::collect_fees::settle_position_fees;
+use crate::instructions::trader_access::check_trader_access;
+
+/// Combine two positions over the same range of a pool
+/// Everything in `merged_position` moves into `position` and the emptied
+/// account is closed; positions are unique per owner and range, so the two
+/// owners differ and both must sign
+#[derive(Accounts)]
+pub struct MergePositions<'info> {
+    /// The pool (zero-copy)
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// The position to merge into (zero-copy)
+    #[account(mut)]
+    pub position: AccountLoader<'info, Position>,
+
+    /// The position to merge and close (zero-copy)
+    #[account(mut)]
+    pub merged_position: AccountLoader<'info, Position>,
+
+    /// Tick array containing lower tick (zero-copy)
+    pub tick_array_lower: AccountLoader<'info, TickArray>,
+
+    /// Tick array containing upper tick (zero-copy)
+    pub tick_array_upper: AccountLoader<'info, TickArray>,
+
+    /// Owner of the position merged into
+    pub owner: Signer<'info>,
+
+    /// Owner of the merged position; receives its rent
+    #[account(mut)]
+    pub merged_owner: Signer<'info>,
+
+    /// The owner of `position`'s entry on the pool's trader allowlist, only needed while it is enabled
+    pub allowed_trader: Option<Account<'info, AllowedTrader>>,
+}
+
+/// Merge positions handler
+pub fn handler(ctx: Context<MergePositions>) -> Result<()> {
+    let pool_key = ctx.accounts.pool.key();
+    let owner_key = ctx.accounts.owner.key();
+    let merged_owner_key = ctx.accounts.merged_owner.key();
+    require_keys_neq!(
+        ctx.accounts.position.key(),
+        ctx.accounts.merged_position.key(),
+        SuniswapError::InvalidPosition
+    );
+    let position_owner = ctx.accounts.position.load()?.owner_pubkey();
+    let pool = ctx.accounts.pool.load()?;
+    require!(pool.is_paused == 0, SuniswapError::PoolPaused);
+    // Merging adds liquidity to `position`, so its owner must be allowed to deposit
+    check_trader_access(&pool, &pool_key, ctx.accounts.allowed_trader.as_ref(), &position_owner)?;
+    drop(pool);
+
+    // Settle both against the same fee growth so their checkpoints match
+    settle_position_fees(
+        &ctx.accounts.pool,
+        &ctx.accounts.position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        owner_key,
+        0,
+    )?;
+    settle_position_fees(
+        &ctx.accounts.pool,
+        &ctx.accounts.merged_position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        merged_owner_key,
+        0,
+    )?;
+
+    let merged = ctx.accounts.merged_position.load()?;
+    let mut position = ctx.accounts.position.load_mut()?;
+    require!(
+        merged.tick_lower == position.tick_lower && merged.tick_upper == position.tick_upper,
+        SuniswapError::InvalidTickRange
+    );
+
+    position.liquidity = position.liquidity
+        .checked_add(merged.liquidity)
+        .ok_or(SuniswapError::LiquidityOverflow)?;
+    position.tokens_owed_a = position.tokens_owed_a
+        .checked_add(merged.tokens_owed_a)
+        .ok_or(SuniswapError::MathOverflow)?;
+    position.tokens_owed_b = position.tokens_owed_b
+        .checked_add(merged.tokens_owed_b)
+        .ok_or(SuniswapError::MathOverflow)?;
+
+    let liquidity = merged.liquidity;
+    let tokens_owed_a = merged.tokens_owed_a;
+    let tokens_owed_b = merged.tokens_owed_b;
+    drop(position);
+    drop(merged);
+
+    close_position_account(
+        &ctx.accounts.merged_position.to_account_info(),
+        &ctx.accounts.merged_owner.to_account_info(),
+    )?;
+
+    emit!(PositionClosedEvent {
+        pool: pool_key,
+        position: ctx.accounts.merged_position.key(),
+        owner: merged_owner_key,
+    });
+
+    emit!(PositionsMergedEvent {
+        pool: pool_key,
+        position: ctx.accounts.position.key(),
+        merged_position: ctx.accounts.merged_position.key(),
+        owner: owner_key,
+        merged_owner: merged_owner_key,
+        liquidity,
+        tokens_owed_a,
+        tokens_owed_b,
+    });
+
+    msg!("Positions merged: {} liquidity added", liquidity);
+    msg!("Owed tokens moved: A={}, B={}", tokens_owed_a, tokens_owed_b);
+
+    Ok(())
+}
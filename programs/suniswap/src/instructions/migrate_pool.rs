@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use crate::state::{FeeTier, Pool};
+use crate::errors::SuniswapError;
+
+/// Grow a pool created before the fields after `oracle` were added to the
+/// current layout. Open to anyone; the payer covers the extra rent
+#[derive(Accounts)]
+pub struct MigratePool<'info> {
+    /// The pool to migrate (zero-copy, still at the legacy size)
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// The pool's fee tier, whose rate seeds the pool's own fee rate
+    pub fee_tier: Account<'info, FeeTier>,
+
+    /// Pays the rent for the added bytes
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+/// Migrate pool handler
+pub fn handler(ctx: Context<MigratePool>) -> Result<()> {
+    let pool_info = ctx.accounts.pool.to_account_info();
+    require!(
+        pool_info.data_len() == Pool::LEGACY_LEN,
+        SuniswapError::PoolAlreadyMigrated
+    );
+
+    let rent = Rent::get()?.minimum_balance(Pool::LEN);
+    let lamports = pool_info.lamports();
+    if lamports < rent {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: pool_info.clone(),
+                },
+            ),
+            rent - lamports,
+        )?;
+    }
+
+    // The added bytes are zeroed, which is already right for most new fields
+    pool_info.resize(Pool::LEN)?;
+
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    require!(
+        pool.fee_tier == ctx.accounts.fee_tier.key().to_bytes(),
+        SuniswapError::InvalidFeeTier
+    );
+
+    // Start the tick oracle accumulators now rather than at the epoch
+    pool.cumulatives_timestamp = Clock::get()?.unix_timestamp as u32;
+
+    // Follow the tier's fee until the protocol authority overrides it
+    pool.fee_rate = ctx.accounts.fee_tier.fee_rate;
+
+    // The circuit breaker measures per-slot moves from the current price
+    pool.circuit_breaker_tick = pool.tick_current;
+
+    msg!("Pool migrated to {} bytes", Pool::LEN);
+
+    Ok(())
+}
@@ -1,23 +1,106 @@
 pub mod initialize_config;
 pub mod initialize_fee_tier;
+pub mod set_fee_tier_rate;
+pub mod set_fee_tier_disabled;
+pub mod register_fee_tier_index;
+pub mod set_max_referral_fee_rate;
+pub mod set_max_creator_fee_rate;
+pub mod initialize_pool_creation_config;
+pub mod set_pool_creation_allowlist;
+pub mod set_pool_creation_fee;
+pub mod set_pool_creation_mode;
 pub mod initialize_pool;
+pub mod collect_creator_fees;
+pub mod migrate_pool;
 pub mod initialize_tick_array;
+pub mod set_pool_fee_rate;
+pub mod set_pool_adaptive_fee;
+pub mod set_pool_circuit_breaker;
+pub mod initialize_oracle;
+pub mod set_pool_twap_guard;
+pub mod initialize_trader_allowlist;
+pub mod set_trader_allowlist;
+pub mod add_allowed_trader;
+pub mod remove_allowed_trader;
 pub mod open_position;
+pub mod open_position_with_liquidity;
 pub mod close_position;
 pub mod increase_liquidity;
+pub mod increase_liquidity_by_amounts;
 pub mod decrease_liquidity;
 pub mod collect_fees;
+pub mod withdraw;
+pub mod compound_position;
+pub mod reposition;
+pub mod split_position;
+pub mod merge_positions;
+pub mod set_position_delegate;
+pub mod snapshot_cumulatives_inside;
 pub mod swap;
 pub mod collect_protocol_fees;
+pub mod initialize_protocol_fee_config;
+pub mod set_protocol_fee_recipients;
+pub mod distribute_protocol_fees;
+pub mod initialize_vault;
+pub mod set_vault_params;
+pub mod open_vault_position;
+pub mod rebalance_vault_position;
+pub mod close_vault_position;
+pub mod deposit_vault;
+pub mod withdraw_vault;
+pub mod collect_vault_fees;
+pub mod native_sol;
+pub mod deadline;
+pub mod trader_access;
 
 pub use initialize_config::*;
 pub use initialize_fee_tier::*;
+pub use set_fee_tier_rate::*;
+pub use set_fee_tier_disabled::*;
+pub use register_fee_tier_index::*;
+pub use set_max_referral_fee_rate::*;
+pub use set_max_creator_fee_rate::*;
+pub use initialize_pool_creation_config::*;
+pub use set_pool_creation_allowlist::*;
+pub use set_pool_creation_fee::*;
+pub use set_pool_creation_mode::*;
 pub use initialize_pool::*;
+pub use collect_creator_fees::*;
+pub use migrate_pool::*;
 pub use initialize_tick_array::*;
+pub use set_pool_fee_rate::*;
+pub use set_pool_adaptive_fee::*;
+pub use set_pool_circuit_breaker::*;
+pub use initialize_oracle::*;
+pub use set_pool_twap_guard::*;
+pub use initialize_trader_allowlist::*;
+pub use set_trader_allowlist::*;
+pub use add_allowed_trader::*;
+pub use remove_allowed_trader::*;
 pub use open_position::*;
+pub use open_position_with_liquidity::*;
 pub use close_position::*;
 pub use increase_liquidity::*;
+pub use increase_liquidity_by_amounts::*;
 pub use decrease_liquidity::*;
 pub use collect_fees::*;
+pub use withdraw::*;
+pub use compound_position::*;
+pub use reposition::*;
+pub use split_position::*;
+pub use merge_positions::*;
+pub use set_position_delegate::*;
+pub use snapshot_cumulatives_inside::*;
 pub use swap::*;
 pub use collect_protocol_fees::*;
+pub use initialize_protocol_fee_config::*;
+pub use set_protocol_fee_recipients::*;
+pub use distribute_protocol_fees::*;
+pub use initialize_vault::*;
+pub use set_vault_params::*;
+pub use open_vault_position::*;
+pub use rebalance_vault_position::*;
+pub use close_vault_position::*;
+pub use deposit_vault::*;
+pub use withdraw_vault::*;
+pub use collect_vault_fees::*;
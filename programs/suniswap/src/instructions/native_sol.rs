@@ -0,0 +1,186 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer};
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_interface::{
+    TokenAccount, TokenInterface, Mint, TransferChecked, transfer_checked,
+    InitializeAccount3, initialize_account3, CloseAccount, close_account,
+};
+use crate::constants::seeds;
+use crate::errors::SuniswapError;
+
+/// Whether `mint` is wrapped SOL under either token program
+pub(crate) fn is_native_mint(mint: &Pubkey) -> bool {
+    *mint == spl_token::native_mint::ID || *mint == spl_token_2022::native_mint::ID
+}
+
+/// Accounts for moving native SOL in place of an omitted user token account
+///
+/// Lamports pass through a temporary wSOL account at `[NATIVE_SOL_SEED, user]`
+/// that the instruction creates, owns on the user's behalf and closes back to
+/// the user before returning, so the client builds no wrap or unwrap
+/// instructions of its own
+pub(crate) struct NativeSol<'a, 'info> {
+    pub user: &'a Signer<'info>,
+    pub native_account: Option<&'a UncheckedAccount<'info>>,
+    pub system_program: Option<&'a Program<'info, System>>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+}
+
+impl<'info> NativeSol<'_, 'info> {
+    /// Pay `amount` of the user's lamports into `to` as wSOL
+    pub fn wrap(
+        &self,
+        mint: &InterfaceAccount<'info, Mint>,
+        to: &InterfaceAccount<'info, TokenAccount>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(is_native_mint(&mint.key()), SuniswapError::NotNativeMint);
+        if amount == 0 {
+            return Ok(());
+        }
+
+        // Lamports beyond the rent reserve become the account's balance on init
+        let temporary = self.open(mint, amount)?;
+        transfer_checked(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: temporary.clone(),
+                    mint: mint.to_account_info(),
+                    to: to.to_account_info(),
+                    authority: self.user.to_account_info(),
+                },
+            ),
+            amount,
+            mint.decimals,
+        )?;
+        self.close(temporary)
+    }
+
+    /// Pay `amount` of wSOL out of the program-owned `from` to the user as lamports
+    pub fn unwrap(
+        &self,
+        mint: &InterfaceAccount<'info, Mint>,
+        from: &InterfaceAccount<'info, TokenAccount>,
+        authority: AccountInfo<'info>,
+        signer_seeds: &[&[u8]],
+        amount: u64,
+    ) -> Result<()> {
+        require!(is_native_mint(&mint.key()), SuniswapError::NotNativeMint);
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let temporary = self.open(mint, 0)?;
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: from.to_account_info(),
+                    mint: mint.to_account_info(),
+                    to: temporary.clone(),
+                    authority,
+                },
+                &[signer_seeds],
+            ),
+            amount,
+            mint.decimals,
+        )?;
+        // Closing returns the balance and the rent reserve together
+        self.close(temporary)
+    }
+
+    /// Create and initialize the temporary wSOL account holding `amount`
+    fn open(&self, mint: &InterfaceAccount<'info, Mint>, amount: u64) -> Result<AccountInfo<'info>> {
+        let (Some(native_account), Some(system_program)) = (self.native_account, self.system_program) else {
+            return err!(SuniswapError::InvalidNativeSolAccount);
+        };
+        let user_key = self.user.key();
+        let (expected, bump) = Pubkey::find_program_address(
+            &[seeds::NATIVE_SOL_SEED, user_key.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(native_account.key(), expected, SuniswapError::InvalidNativeSolAccount);
+
+        let space = spl_token::state::Account::LEN;
+        let rent = Rent::get()?.minimum_balance(space);
+        let signer_seeds: &[&[u8]] = &[seeds::NATIVE_SOL_SEED, user_key.as_ref(), &[bump]];
+        let existing = native_account.lamports();
+
+        if existing == 0 {
+            let lamports = rent.checked_add(amount).ok_or(SuniswapError::MathOverflow)?;
+            create_account(
+                CpiContext::new_with_signer(
+                    system_program.to_account_info(),
+                    CreateAccount {
+                        from: self.user.to_account_info(),
+                        to: native_account.to_account_info(),
+                    },
+                    &[signer_seeds],
+                ),
+                lamports,
+                space as u64,
+                self.token_program.key,
+            )?;
+        } else {
+            // Someone already sent lamports to the address, which would make
+            // create_account fail: top up, then allocate and assign. Whatever
+            // was there is wrapped too and reaches the user on close
+            let lamports = rent
+                .saturating_sub(existing)
+                .checked_add(amount)
+                .ok_or(SuniswapError::MathOverflow)?;
+            if lamports > 0 {
+                transfer(
+                    CpiContext::new(
+                        system_program.to_account_info(),
+                        Transfer {
+                            from: self.user.to_account_info(),
+                            to: native_account.to_account_info(),
+                        },
+                    ),
+                    lamports,
+                )?;
+            }
+            allocate(
+                CpiContext::new_with_signer(
+                    system_program.to_account_info(),
+                    Allocate { account_to_allocate: native_account.to_account_info() },
+                    &[signer_seeds],
+                ),
+                space as u64,
+            )?;
+            assign(
+                CpiContext::new_with_signer(
+                    system_program.to_account_info(),
+                    Assign { account_to_assign: native_account.to_account_info() },
+                    &[signer_seeds],
+                ),
+                self.token_program.key,
+            )?;
+        }
+        initialize_account3(CpiContext::new(
+            self.token_program.to_account_info(),
+            InitializeAccount3 {
+                account: native_account.to_account_info(),
+                mint: mint.to_account_info(),
+                authority: self.user.to_account_info(),
+            },
+        ))?;
+        Ok(native_account.to_account_info())
+    }
+
+    /// Close the temporary account, sending all its lamports to the user
+    fn close(&self, temporary: AccountInfo<'info>) -> Result<()> {
+        close_account(CpiContext::new(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: temporary,
+                destination: self.user.to_account_info(),
+                authority: self.user.to_account_info(),
+            },
+        ))
+    }
+}
@@ -1,7 +1,9 @@
 use anchor_lang::prelude::*;
-use crate::state::{Pool, Position};
+use crate::state::{Pool, Position, AllowedTrader};
 use crate::constants::seeds;
 use crate::errors::SuniswapError;
+use crate::instructions::trader_access::check_trader_access;
+use crate::events::PositionOpenedEvent;
 use crate::math::tick_math::is_valid_tick;
 
 /// Open a new liquidity position
@@ -37,6 +39,9 @@ pub struct OpenPosition<'info> {
 
     /// System program
     pub system_program: Program<'info, System>,
+
+    /// The signer's entry on the pool's trader allowlist, only needed while it is enabled
+    pub allowed_trader: Option<Account<'info, AllowedTrader>>,
 }
 
 /// Open position handler
@@ -51,7 +56,32 @@ pub fn handler(
 
     // Check pool is not paused
     require!(pool.is_paused == 0, SuniswapError::PoolPaused);
+    check_trader_access(&pool, &pool_key, ctx.accounts.allowed_trader.as_ref(), &owner.key())?;
+
+    validate_tick_range(tick_lower, tick_upper, pool.tick_spacing)?;
+
+    // Drop the pool borrow before loading position
+    drop(pool);
+
+    initialize_position(
+        &ctx.accounts.position,
+        pool_key,
+        owner.key(),
+        tick_lower,
+        tick_upper,
+        ctx.bumps.position,
+    )?;
+
+    msg!("Position opened");
+    msg!("Pool: {}", pool_key);
+    msg!("Owner: {}", owner.key());
+    msg!("Tick range: [{}, {}]", tick_lower, tick_upper);
+
+    Ok(())
+}
 
+/// Validate that [tick_lower, tick_upper) is a non-empty range of usable ticks
+pub(crate) fn validate_tick_range(tick_lower: i32, tick_upper: i32, tick_spacing: u16) -> Result<()> {
     // Validate tick range
     require!(
         tick_lower < tick_upper,
@@ -60,21 +90,30 @@ pub fn handler(
 
     // Validate ticks are aligned to tick spacing
     require!(
-        is_valid_tick(tick_lower, pool.tick_spacing),
+        is_valid_tick(tick_lower, tick_spacing),
         SuniswapError::InvalidTickLower
     );
     require!(
-        is_valid_tick(tick_upper, pool.tick_spacing),
+        is_valid_tick(tick_upper, tick_spacing),
         SuniswapError::InvalidTickUpper
     );
 
-    // Drop the pool borrow before loading position
-    drop(pool);
+    Ok(())
+}
 
+/// Initialize a freshly created, empty position
+pub(crate) fn initialize_position(
+    position_loader: &AccountLoader<Position>,
+    pool_key: Pubkey,
+    owner: Pubkey,
+    tick_lower: i32,
+    tick_upper: i32,
+    bump: u8,
+) -> Result<()> {
     // Initialize position using zero-copy
-    let mut position = ctx.accounts.position.load_init()?;
+    let mut position = position_loader.load_init()?;
     position.pool = pool_key.to_bytes();
-    position.owner = owner.key().to_bytes();
+    position.owner = owner.to_bytes();
     position.tick_lower = tick_lower;
     position.tick_upper = tick_upper;
     position.liquidity = 0;
@@ -82,13 +121,21 @@ pub fn handler(
     position.fee_growth_inside_b_last_x128 = 0;
     position.tokens_owed_a = 0;
     position.tokens_owed_b = 0;
-    position.bump = ctx.bumps.position;
+    position.bump = bump;
     position.position_mint = [0u8; 32];
+    drop(position);
 
-    msg!("Position opened");
-    msg!("Pool: {}", pool_key);
-    msg!("Owner: {}", owner.key());
-    msg!("Tick range: [{}, {}]", tick_lower, tick_upper);
+    // Anchor only writes the discriminator on exit; write it now so the position
+    // can be loaded again within the same instruction
+    position_loader.as_ref().try_borrow_mut_data()?[..8].copy_from_slice(Position::DISCRIMINATOR);
+
+    emit!(PositionOpenedEvent {
+        pool: pool_key,
+        position: position_loader.key(),
+        owner,
+        tick_lower,
+        tick_upper,
+    });
 
     Ok(())
 }
@@ -0,0 +1,221 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenAccount, TokenInterface, Mint, TransferChecked, transfer_checked};
+use crate::state::{Pool, Position, TickArray, AllowedTrader};
+use crate::constants::seeds;
+use crate::errors::SuniswapError;
+use crate::instructions::deadline::check_deadline;
+use crate::instructions::trader_access::check_trader_access;
+use crate::instructions::increase_liquidity::add_liquidity;
+use crate::instructions::increase_liquidity_by_amounts::IncreaseLiquidityByAmountsParams;
+use crate::instructions::initialize_tick_array::initialize_tick_array_if_needed;
+use crate::instructions::open_position::{initialize_position, validate_tick_range};
+use crate::math::liquidity_math::get_liquidity_for_amounts;
+use crate::math::tick_math::get_sqrt_price_at_tick;
+
+/// Open a position and deposit into it in one instruction
+/// Creates the tick arrays holding the bounds if they do not exist yet
+#[derive(Accounts)]
+#[instruction(tick_lower: i32, tick_upper: i32)]
+pub struct OpenPositionWithLiquidity<'info> {
+    /// The pool (zero-copy)
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// The position account to create (zero-copy)
+    #[account(
+        init,
+        payer = payer,
+        space = Position::LEN,
+        seeds = [
+            seeds::POSITION_SEED,
+            pool.key().as_ref(),
+            owner.key().as_ref(),
+            &tick_lower.to_le_bytes(),
+            &tick_upper.to_le_bytes()
+        ],
+        bump
+    )]
+    pub position: AccountLoader<'info, Position>,
+
+    /// Tick array containing lower tick, created if needed
+    /// CHECK: Address is verified against the tick array PDA in the handler
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+
+    /// Tick array containing upper tick, created if needed (may equal the lower array)
+    /// CHECK: Address is verified against the tick array PDA in the handler
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    /// Token A mint
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+
+    /// Token B mint
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    /// Pool vault for token A
+    #[account(mut)]
+    pub token_vault_a: InterfaceAccount<'info, TokenAccount>,
+
+    /// Pool vault for token B
+    #[account(mut)]
+    pub token_vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    /// User's token A account
+    #[account(mut)]
+    pub user_token_a: InterfaceAccount<'info, TokenAccount>,
+
+    /// User's token B account
+    #[account(mut)]
+    pub user_token_b: InterfaceAccount<'info, TokenAccount>,
+
+    /// The position owner
+    pub owner: Signer<'info>,
+
+    /// The payer for account creation
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Token program
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+
+    /// The signer's entry on the pool's trader allowlist, only needed while it is enabled
+    pub allowed_trader: Option<Account<'info, AllowedTrader>>,
+}
+
+/// Open position with liquidity handler
+pub fn handler(
+    ctx: Context<OpenPositionWithLiquidity>,
+    tick_lower: i32,
+    tick_upper: i32,
+    params: IncreaseLiquidityByAmountsParams,
+) -> Result<()> {
+    check_deadline(params.deadline_unix_ts, params.max_slot)?;
+
+    let pool_key = ctx.accounts.pool.key();
+    let owner = ctx.accounts.owner.key();
+
+    // Load and validate pool
+    let pool = ctx.accounts.pool.load()?;
+    require!(pool.is_paused == 0, SuniswapError::PoolPaused);
+    check_trader_access(&pool, &pool_key, ctx.accounts.allowed_trader.as_ref(), &owner)?;
+    require!(
+        pool.token_mint_a == ctx.accounts.token_mint_a.key().to_bytes(),
+        SuniswapError::InvalidTokenMint
+    );
+    require!(
+        pool.token_mint_b == ctx.accounts.token_mint_b.key().to_bytes(),
+        SuniswapError::InvalidTokenMint
+    );
+    require!(
+        pool.token_vault_a == ctx.accounts.token_vault_a.key().to_bytes(),
+        SuniswapError::InvalidVault
+    );
+    require!(
+        pool.token_vault_b == ctx.accounts.token_vault_b.key().to_bytes(),
+        SuniswapError::InvalidVault
+    );
+    validate_tick_range(tick_lower, tick_upper, pool.tick_spacing)?;
+
+    let sqrt_price_x64 = pool.sqrt_price_x64;
+    let tick_spacing = pool.tick_spacing;
+    drop(pool);
+
+    require!(
+        sqrt_price_x64 >= params.sqrt_price_min_x64 && sqrt_price_x64 <= params.sqrt_price_max_x64,
+        SuniswapError::PriceOutsideBand
+    );
+
+    initialize_position(
+        &ctx.accounts.position,
+        pool_key,
+        owner,
+        tick_lower,
+        tick_upper,
+        ctx.bumps.position,
+    )?;
+
+    // Create the bounds' tick arrays; the second call is a no-op when both
+    // bounds share an array
+    let payer = ctx.accounts.payer.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    initialize_tick_array_if_needed(
+        &ctx.accounts.tick_array_lower.to_account_info(),
+        &payer,
+        &system_program,
+        pool_key,
+        tick_spacing,
+        TickArray::get_start_tick_index(tick_lower, tick_spacing),
+    )?;
+    initialize_tick_array_if_needed(
+        &ctx.accounts.tick_array_upper.to_account_info(),
+        &payer,
+        &system_program,
+        pool_key,
+        tick_spacing,
+        TickArray::get_start_tick_index(tick_upper, tick_spacing),
+    )?;
+
+    // Deposit the most liquidity the maximum amounts can fund
+    let liquidity_delta = get_liquidity_for_amounts(
+        sqrt_price_x64,
+        get_sqrt_price_at_tick(tick_lower)?,
+        get_sqrt_price_at_tick(tick_upper)?,
+        params.amount_a_max,
+        params.amount_b_max,
+    )?;
+
+    let (amount_a, amount_b) = add_liquidity(
+        &ctx.accounts.pool,
+        &ctx.accounts.position,
+        ctx.accounts.tick_array_lower.as_ref(),
+        ctx.accounts.tick_array_upper.as_ref(),
+        owner,
+        0,
+        liquidity_delta,
+        params.amount_a_max,
+        params.amount_b_max,
+    )?;
+
+    // Transfer tokens
+    if amount_a > 0 {
+        transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.user_token_a.to_account_info(),
+                    mint: ctx.accounts.token_mint_a.to_account_info(),
+                    to: ctx.accounts.token_vault_a.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            amount_a,
+            ctx.accounts.token_mint_a.decimals,
+        )?;
+    }
+
+    if amount_b > 0 {
+        transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.user_token_b.to_account_info(),
+                    mint: ctx.accounts.token_mint_b.to_account_info(),
+                    to: ctx.accounts.token_vault_b.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            amount_b,
+            ctx.accounts.token_mint_b.decimals,
+        )?;
+    }
+
+    msg!("Position opened with liquidity: {}", liquidity_delta);
+    msg!("Tick range: [{}, {}]", tick_lower, tick_upper);
+    msg!("Amount A: {}, Amount B: {}", amount_a, amount_b);
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use crate::state::{Pool, Position, ManagedVault};
+use crate::constants::seeds;
+use crate::constants::managed_vault::MAX_POSITIONS;
+use crate::errors::SuniswapError;
+use crate::instructions::open_position::{initialize_position, validate_tick_range};
+
+/// Open an empty position owned by a managed vault
+/// Only callable by the vault strategist, within the vault's tick limits
+#[derive(Accounts)]
+#[instruction(tick_lower: i32, tick_upper: i32)]
+pub struct OpenVaultPosition<'info> {
+    /// The vault's pool (zero-copy)
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// The vault to open the position for
+    #[account(
+        mut,
+        constraint = vault.pool == pool.key() @ SuniswapError::InvalidManagedVault,
+        constraint = vault.is_strategist(&strategist.key()) @ SuniswapError::NotVaultStrategist,
+    )]
+    pub vault: Account<'info, ManagedVault>,
+
+    /// The position account to create, owned by the vault (zero-copy)
+    #[account(
+        init,
+        payer = strategist,
+        space = Position::LEN,
+        seeds = [
+            seeds::POSITION_SEED,
+            pool.key().as_ref(),
+            vault.key().as_ref(),
+            &tick_lower.to_le_bytes(),
+            &tick_upper.to_le_bytes()
+        ],
+        bump
+    )]
+    pub position: AccountLoader<'info, Position>,
+
+    /// Vault strategist; pays for the position account
+    #[account(mut)]
+    pub strategist: Signer<'info>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+/// Open vault position handler
+pub fn handler(
+    ctx: Context<OpenVaultPosition>,
+    tick_lower: i32,
+    tick_upper: i32,
+) -> Result<()> {
+    let pool = ctx.accounts.pool.load()?;
+    require!(pool.is_paused == 0, SuniswapError::PoolPaused);
+    validate_tick_range(tick_lower, tick_upper, pool.tick_spacing)?;
+    drop(pool);
+
+    let vault = &mut ctx.accounts.vault;
+    require!(
+        tick_lower >= vault.tick_lower_limit && tick_upper <= vault.tick_upper_limit,
+        SuniswapError::VaultRangeOutsideLimits
+    );
+    let index = vault.position_count as usize;
+    require!(index < MAX_POSITIONS, SuniswapError::VaultPositionLimit);
+
+    initialize_position(
+        &ctx.accounts.position,
+        ctx.accounts.pool.key(),
+        vault.key(),
+        tick_lower,
+        tick_upper,
+        ctx.bumps.position,
+    )?;
+    vault.positions[index] = ctx.accounts.position.key();
+    vault.position_count += 1;
+
+    msg!("Vault position opened: [{}, {}]", tick_lower, tick_upper);
+
+    Ok(())
+}
@@ -0,0 +1,269 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenAccount, TokenInterface, Mint};
+use crate::state::{Pool, Position, TickArray, FeeTier, ManagedVault, Oracle};
+use crate::constants::seeds;
+use crate::errors::SuniswapError;
+use crate::events::VaultRebalancedEvent;
+use crate::instructions::decrease_liquidity::remove_liquidity;
+use crate::instructions::deposit_vault::{
+    check_vault_price, harvest_vault_position, idle_amounts, take_owed_tokens, transfer_signed,
+};
+use crate::instructions::increase_liquidity::add_liquidity;
+use crate::math::liquidity_math::get_liquidity_for_amounts;
+use crate::math::tick_math::get_sqrt_price_at_tick;
+
+/// Move liquidity between one vault position and the vault reserves
+/// Only callable by the vault strategist. Moving a vault from one range to
+/// another is a removal from the old position followed by a deployment into
+/// the new one. If the pool has an oracle, its price must be near the TWAP
+#[derive(Accounts)]
+pub struct RebalanceVaultPosition<'info> {
+    /// The vault's pool (zero-copy)
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// The fee tier for this pool
+    pub fee_tier: Account<'info, FeeTier>,
+
+    /// The vault holding the position
+    #[account(
+        mut,
+        constraint = vault.pool == pool.key() @ SuniswapError::InvalidManagedVault,
+        constraint = vault.is_strategist(&strategist.key()) @ SuniswapError::NotVaultStrategist,
+    )]
+    pub vault: Account<'info, ManagedVault>,
+
+    /// The vault position to rebalance (zero-copy)
+    #[account(mut)]
+    pub position: AccountLoader<'info, Position>,
+
+    /// Tick array containing lower tick (zero-copy)
+    #[account(mut)]
+    pub tick_array_lower: AccountLoader<'info, TickArray>,
+
+    /// Tick array containing upper tick (zero-copy)
+    #[account(mut)]
+    pub tick_array_upper: AccountLoader<'info, TickArray>,
+
+    /// Token A mint
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+
+    /// Token B mint
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    /// Pool vault for token A
+    #[account(mut)]
+    pub token_vault_a: InterfaceAccount<'info, TokenAccount>,
+
+    /// Pool vault for token B
+    #[account(mut)]
+    pub token_vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    /// Vault reserve for token A
+    #[account(
+        mut,
+        constraint = reserve_a.key() == vault.reserve_a @ SuniswapError::InvalidManagedVault,
+    )]
+    pub reserve_a: InterfaceAccount<'info, TokenAccount>,
+
+    /// Vault reserve for token B
+    #[account(
+        mut,
+        constraint = reserve_b.key() == vault.reserve_b @ SuniswapError::InvalidManagedVault,
+    )]
+    pub reserve_b: InterfaceAccount<'info, TokenAccount>,
+
+    /// Vault strategist
+    pub strategist: Signer<'info>,
+
+    /// Token program
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The pool's oracle, required once the pool has one
+    pub oracle: Option<Account<'info, Oracle>>,
+}
+
+/// Rebalance parameters
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct RebalanceVaultParams {
+    /// Liquidity to withdraw from the position into the reserves, 0 to skip
+    pub liquidity_removed: u128,
+    /// Most of each reserve to deploy into the position afterwards, 0 to skip
+    pub amount_a_max: u64,
+    pub amount_b_max: u64,
+    /// Least liquidity the deployment must add
+    pub liquidity_min: u128,
+}
+
+/// Rebalance vault position handler
+pub fn handler(ctx: Context<RebalanceVaultPosition>, params: RebalanceVaultParams) -> Result<()> {
+    let fee_tier = &ctx.accounts.fee_tier;
+    let pool_key = ctx.accounts.pool.key();
+    let vault_key = ctx.accounts.vault.key();
+    let position_key = ctx.accounts.position.key();
+    require!(
+        ctx.accounts.vault.active_positions().contains(&position_key),
+        SuniswapError::InvalidVaultPositions
+    );
+
+    // Load and validate pool
+    let pool = ctx.accounts.pool.load()?;
+    require!(pool.is_paused == 0, SuniswapError::PoolPaused);
+    require!(
+        pool.fee_tier == fee_tier.key().to_bytes(),
+        SuniswapError::InvalidFeeTier
+    );
+    require!(
+        pool.token_mint_a == ctx.accounts.token_mint_a.key().to_bytes(),
+        SuniswapError::InvalidTokenMint
+    );
+    require!(
+        pool.token_mint_b == ctx.accounts.token_mint_b.key().to_bytes(),
+        SuniswapError::InvalidTokenMint
+    );
+    require!(
+        pool.token_vault_a == ctx.accounts.token_vault_a.key().to_bytes(),
+        SuniswapError::InvalidVault
+    );
+    require!(
+        pool.token_vault_b == ctx.accounts.token_vault_b.key().to_bytes(),
+        SuniswapError::InvalidVault
+    );
+    // The strategist can move the price, so only deploy near the TWAP
+    if pool.oracle != [0u8; 32] {
+        check_vault_price(&pool, ctx.accounts.oracle.as_ref(), Clock::get()?.unix_timestamp as u32)?;
+    }
+
+    let sqrt_price_x64 = pool.sqrt_price_x64;
+    let pool_bump = pool.bump;
+    let token_mint_a_bytes = pool.token_mint_a;
+    let token_mint_b_bytes = pool.token_mint_b;
+    drop(pool);
+
+    // Fees and any withdrawn principal both land in the reserves
+    let (mut to_reserve_a, mut to_reserve_b) = harvest_vault_position(
+        &ctx.accounts.pool,
+        &mut ctx.accounts.vault,
+        &ctx.accounts.position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+    )?;
+    if params.liquidity_removed > 0 {
+        remove_liquidity(
+            &ctx.accounts.pool,
+            &ctx.accounts.position,
+            &ctx.accounts.tick_array_lower,
+            &ctx.accounts.tick_array_upper,
+            vault_key,
+            0,
+            params.liquidity_removed,
+            0,
+            0,
+        )?;
+        let (amount_a, amount_b) = take_owed_tokens(pool_key, &ctx.accounts.position)?;
+        to_reserve_a = to_reserve_a.checked_add(amount_a).ok_or(SuniswapError::MathOverflow)?;
+        to_reserve_b = to_reserve_b.checked_add(amount_b).ok_or(SuniswapError::MathOverflow)?;
+    }
+
+    let pool_seeds: &[&[u8]] = &[
+        seeds::POOL_SEED,
+        &token_mint_a_bytes,
+        &token_mint_b_bytes,
+        &fee_tier.seed_bytes(),
+        &[pool_bump],
+    ];
+    let pool_info = ctx.accounts.pool.to_account_info();
+    transfer_signed(
+        &ctx.accounts.token_program,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.reserve_a,
+        pool_info.clone(),
+        pool_seeds,
+        to_reserve_a,
+    )?;
+    transfer_signed(
+        &ctx.accounts.token_program,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.token_mint_b,
+        &ctx.accounts.reserve_b,
+        pool_info,
+        pool_seeds,
+        to_reserve_b,
+    )?;
+    ctx.accounts.reserve_a.reload()?;
+    ctx.accounts.reserve_b.reload()?;
+
+    // Deploy what the caps and the idle reserves allow
+    let (idle_a, idle_b) = idle_amounts(&ctx.accounts.vault, &ctx.accounts.reserve_a, &ctx.accounts.reserve_b)?;
+    let deploy_a = params.amount_a_max.min(idle_a);
+    let deploy_b = params.amount_b_max.min(idle_b);
+    let position = ctx.accounts.position.load()?;
+    let liquidity_added = get_liquidity_for_amounts(
+        sqrt_price_x64,
+        get_sqrt_price_at_tick(position.tick_lower)?,
+        get_sqrt_price_at_tick(position.tick_upper)?,
+        deploy_a,
+        deploy_b,
+    )?;
+    drop(position);
+    require!(
+        liquidity_added >= params.liquidity_min,
+        SuniswapError::SlippageExceeded
+    );
+
+    let (amount_a_added, amount_b_added) = if liquidity_added > 0 {
+        add_liquidity(
+            &ctx.accounts.pool,
+            &ctx.accounts.position,
+            ctx.accounts.tick_array_lower.as_ref(),
+            ctx.accounts.tick_array_upper.as_ref(),
+            vault_key,
+            0,
+            liquidity_added,
+            deploy_a,
+            deploy_b,
+        )?
+    } else {
+        (0, 0)
+    };
+
+    let vault = &ctx.accounts.vault;
+    let vault_seeds: &[&[u8]] = &[
+        seeds::MANAGED_VAULT_SEED,
+        vault.pool.as_ref(),
+        &vault.id.to_le_bytes(),
+        &[vault.bump],
+    ];
+    transfer_signed(
+        &ctx.accounts.token_program,
+        &ctx.accounts.reserve_a,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_vault_a,
+        vault.to_account_info(),
+        vault_seeds,
+        amount_a_added,
+    )?;
+    transfer_signed(
+        &ctx.accounts.token_program,
+        &ctx.accounts.reserve_b,
+        &ctx.accounts.token_mint_b,
+        &ctx.accounts.token_vault_b,
+        vault.to_account_info(),
+        vault_seeds,
+        amount_b_added,
+    )?;
+
+    emit!(VaultRebalancedEvent {
+        vault: vault_key,
+        position: position_key,
+        liquidity_removed: params.liquidity_removed,
+        liquidity_added,
+        amount_a_added,
+        amount_b_added,
+    });
+
+    msg!("Vault rebalanced: -{} +{} liquidity", params.liquidity_removed, liquidity_added);
+
+    Ok(())
+}
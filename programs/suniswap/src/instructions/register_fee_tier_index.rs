@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use crate::state::{SuniswapConfig, FeeTier, FeeTierIndex};
+use crate::constants::seeds;
+use crate::errors::SuniswapError;
+
+/// Give a fee tier created before tier enumeration the next free index
+/// Writes its `FeeTierIndex` entry so clients walking 0..fee_tier_count find
+/// it. Only callable by protocol authority
+#[derive(Accounts)]
+pub struct RegisterFeeTierIndex<'info> {
+    /// The global config
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, SuniswapConfig>,
+
+    /// The fee tier to register
+    #[account(
+        mut,
+        seeds = [seeds::FEE_TIER_SEED, &fee_tier.seed_bytes()],
+        bump = fee_tier.bump,
+        constraint = fee_tier.config == config.key() @ SuniswapError::InvalidFeeTier,
+    )]
+    pub fee_tier: Account<'info, FeeTier>,
+
+    /// Enumeration entry for the tier, at the next free index
+    #[account(
+        init,
+        payer = payer,
+        space = FeeTierIndex::LEN,
+        seeds = [seeds::FEE_TIER_INDEX_SEED, &config.fee_tier_count.to_le_bytes()],
+        bump
+    )]
+    pub fee_tier_index: Account<'info, FeeTierIndex>,
+
+    /// Protocol authority
+    pub authority: Signer<'info>,
+
+    /// The payer for account creation
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+/// Register fee tier index handler
+pub fn handler(ctx: Context<RegisterFeeTierIndex>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    require!(
+        config.is_protocol_authority(&ctx.accounts.authority.key()),
+        SuniswapError::NotProtocolAuthority
+    );
+
+    let fee_tier = &mut ctx.accounts.fee_tier;
+    require!(!fee_tier.index_registered, SuniswapError::InvalidFeeTier);
+    fee_tier.index = config.fee_tier_count;
+    fee_tier.index_registered = true;
+
+    let fee_tier_index = &mut ctx.accounts.fee_tier_index;
+    fee_tier_index.fee_tier = fee_tier.key();
+    fee_tier_index.bump = ctx.bumps.fee_tier_index;
+
+    config.fee_tier_count = config.fee_tier_count
+        .checked_add(1)
+        .ok_or(SuniswapError::MathOverflow)?;
+
+    msg!("Fee tier {} registered at index {}", fee_tier.key(), fee_tier.index);
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::state::{Pool, TraderAllowlist, AllowedTrader};
+use crate::constants::seeds;
+use crate::errors::SuniswapError;
+use crate::events::AllowedTraderUpdatedEvent;
+
+/// Remove a wallet from a pool's trader allowlist, returning the entry's rent to the admin
+/// The wallet keeps its positions and can still withdraw from them
+/// Only callable by the allowlist's admin
+#[derive(Accounts)]
+pub struct RemoveAllowedTrader<'info> {
+    /// The gated pool (zero-copy)
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// The pool's trader allowlist
+    #[account(
+        seeds = [seeds::TRADER_ALLOWLIST_SEED, pool.key().as_ref()],
+        bump = trader_allowlist.bump,
+        has_one = admin @ SuniswapError::NotTraderAllowlistAdmin,
+    )]
+    pub trader_allowlist: Account<'info, TraderAllowlist>,
+
+    /// The wallet's allowlist entry to close
+    #[account(
+        mut,
+        close = admin,
+        seeds = [seeds::ALLOWED_TRADER_SEED, pool.key().as_ref(), allowed_trader.wallet.as_ref()],
+        bump = allowed_trader.bump,
+    )]
+    pub allowed_trader: Account<'info, AllowedTrader>,
+
+    /// Allowlist admin
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+/// Remove allowed trader handler
+pub fn handler(ctx: Context<RemoveAllowedTrader>) -> Result<()> {
+    let wallet = ctx.accounts.allowed_trader.wallet;
+
+    emit!(AllowedTraderUpdatedEvent {
+        pool: ctx.accounts.pool.key(),
+        wallet,
+        allowed: false,
+    });
+
+    msg!("Allowed trader removed: {}", wallet);
+
+    Ok(())
+}
@@ -0,0 +1,415 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenAccount, TokenInterface, Mint, TransferChecked, transfer_checked};
+use crate::state::{Pool, Position, TickArray, FeeTier, Oracle, AllowedTrader};
+use crate::constants::{seeds, position_permissions};
+use crate::errors::SuniswapError;
+use crate::events::{PositionClosedEvent, PositionRepositionedEvent};
+use crate::instructions::close_position::close_position_account;
+use crate::instructions::collect_fees::{settle_position_fees, validate_payout_accounts};
+use crate::instructions::deadline::check_deadline;
+use crate::instructions::trader_access::check_trader_access;
+use crate::instructions::decrease_liquidity::remove_liquidity;
+use crate::instructions::increase_liquidity::add_liquidity;
+use crate::instructions::initialize_tick_array::initialize_tick_array_if_needed;
+use crate::instructions::open_position::{initialize_position, validate_tick_range};
+use crate::instructions::swap::{execute_swap, SwapParams};
+use crate::math::liquidity_math::get_liquidity_for_amounts;
+use crate::math::tick_math::get_sqrt_price_at_tick;
+
+/// Move all of a position's liquidity and fees into a new tick range
+/// The old position is closed; the withdrawn tokens never leave the vaults
+/// except for whatever the new range cannot use. The new position keeps the
+/// old one's owner and delegate
+#[derive(Accounts)]
+#[instruction(tick_lower: i32, tick_upper: i32)]
+pub struct Reposition<'info> {
+    /// The pool (zero-copy)
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// The fee tier for this pool
+    pub fee_tier: Account<'info, FeeTier>,
+
+    /// The position to move out of, closed afterwards (zero-copy)
+    #[account(mut)]
+    pub position: AccountLoader<'info, Position>,
+
+    /// Tick array containing the old lower tick (zero-copy)
+    #[account(mut)]
+    pub tick_array_lower: AccountLoader<'info, TickArray>,
+
+    /// Tick array containing the old upper tick (zero-copy)
+    #[account(mut)]
+    pub tick_array_upper: AccountLoader<'info, TickArray>,
+
+    /// The position account to create for the new range (zero-copy)
+    #[account(
+        init,
+        payer = payer,
+        space = Position::LEN,
+        seeds = [
+            seeds::POSITION_SEED,
+            pool.key().as_ref(),
+            position.load()?.owner.as_ref(),
+            &tick_lower.to_le_bytes(),
+            &tick_upper.to_le_bytes()
+        ],
+        bump
+    )]
+    pub new_position: AccountLoader<'info, Position>,
+
+    /// Tick array containing the new lower tick, created if needed
+    /// CHECK: Address is verified against the tick array PDA in the handler
+    #[account(mut)]
+    pub new_tick_array_lower: UncheckedAccount<'info>,
+
+    /// Tick array containing the new upper tick, created if needed
+    /// CHECK: Address is verified against the tick array PDA in the handler
+    #[account(mut)]
+    pub new_tick_array_upper: UncheckedAccount<'info>,
+
+    /// Current tick array for the rebalancing swap (zero-copy)
+    #[account(mut)]
+    pub swap_tick_array_0: Option<AccountLoader<'info, TickArray>>,
+
+    /// Adjacent tick array for the rebalancing swap (zero-copy)
+    #[account(mut)]
+    pub swap_tick_array_1: Option<AccountLoader<'info, TickArray>>,
+
+    /// Second adjacent tick array for the rebalancing swap (zero-copy)
+    #[account(mut)]
+    pub swap_tick_array_2: Option<AccountLoader<'info, TickArray>>,
+
+    /// Token A mint
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+
+    /// Token B mint
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    /// Pool vault for token A
+    #[account(mut)]
+    pub token_vault_a: InterfaceAccount<'info, TokenAccount>,
+
+    /// Pool vault for token B
+    #[account(mut)]
+    pub token_vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    /// User's token A account, receives any token A left over
+    #[account(mut)]
+    pub user_token_a: InterfaceAccount<'info, TokenAccount>,
+
+    /// User's token B account, receives any token B left over
+    #[account(mut)]
+    pub user_token_b: InterfaceAccount<'info, TokenAccount>,
+
+    /// Position owner, or a delegate allowed to reposition
+    pub owner: Signer<'info>,
+
+    /// The payer for account creation; receives the old position's rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Token program
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+
+    /// The position owner's entry on the pool's trader allowlist, only needed while it is enabled
+    pub allowed_trader: Option<Account<'info, AllowedTrader>>,
+
+    /// The pool's oracle, required for the rebalancing swap once the pool has one
+    #[account(mut)]
+    pub oracle: Option<Account<'info, Oracle>>,
+}
+
+/// Reposition parameters
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct RepositionParams {
+    /// Exact input swapped from the withdrawn tokens before depositing, 0 to skip
+    pub swap_amount: u64,
+    pub swap_a_to_b: bool,
+    pub sqrt_price_limit_x64: u128,
+    /// Least liquidity the new position must receive
+    pub liquidity_min: u128,
+    /// Unix timestamp after which the reposition is rejected
+    pub deadline_unix_ts: Option<i64>,
+    /// Slot after which the reposition is rejected
+    pub max_slot: Option<u64>,
+}
+
+/// Reposition handler
+pub fn handler(
+    ctx: Context<Reposition>,
+    tick_lower: i32,
+    tick_upper: i32,
+    params: RepositionParams,
+) -> Result<()> {
+    check_deadline(params.deadline_unix_ts, params.max_slot)?;
+
+    let fee_tier = &ctx.accounts.fee_tier;
+    let pool_key = ctx.accounts.pool.key();
+    let authority = ctx.accounts.owner.key();
+
+    // Load and validate pool; the new position goes to the owner, so the
+    // owner must be allowed even when a delegate signs
+    let position_owner = ctx.accounts.position.load()?.owner_pubkey();
+    let pool = ctx.accounts.pool.load()?;
+    require!(pool.is_paused == 0, SuniswapError::PoolPaused);
+    check_trader_access(&pool, &pool_key, ctx.accounts.allowed_trader.as_ref(), &position_owner)?;
+    require!(
+        pool.fee_tier == fee_tier.key().to_bytes(),
+        SuniswapError::InvalidFeeTier
+    );
+    require!(
+        pool.token_mint_a == ctx.accounts.token_mint_a.key().to_bytes(),
+        SuniswapError::InvalidTokenMint
+    );
+    require!(
+        pool.token_mint_b == ctx.accounts.token_mint_b.key().to_bytes(),
+        SuniswapError::InvalidTokenMint
+    );
+    require!(
+        pool.token_vault_a == ctx.accounts.token_vault_a.key().to_bytes(),
+        SuniswapError::InvalidVault
+    );
+    require!(
+        pool.token_vault_b == ctx.accounts.token_vault_b.key().to_bytes(),
+        SuniswapError::InvalidVault
+    );
+    validate_tick_range(tick_lower, tick_upper, pool.tick_spacing)?;
+
+    let tick_spacing = pool.tick_spacing;
+    let pool_bump = pool.bump;
+    let token_mint_a_bytes = pool.token_mint_a;
+    let token_mint_b_bytes = pool.token_mint_b;
+    drop(pool);
+
+    // Empty the old position; principal and fees become owed tokens
+    let liquidity = ctx.accounts.position.load()?.liquidity;
+    if liquidity > 0 {
+        remove_liquidity(
+            &ctx.accounts.pool,
+            &ctx.accounts.position,
+            &ctx.accounts.tick_array_lower,
+            &ctx.accounts.tick_array_upper,
+            authority,
+            position_permissions::REPOSITION,
+            liquidity,
+            0,
+            0,
+        )?;
+    } else {
+        settle_position_fees(
+            &ctx.accounts.pool,
+            &ctx.accounts.position,
+            &ctx.accounts.tick_array_lower,
+            &ctx.accounts.tick_array_upper,
+            authority,
+            position_permissions::REPOSITION,
+        )?;
+    }
+    validate_payout_accounts(
+        &ctx.accounts.position,
+        authority,
+        Some(&ctx.accounts.user_token_a),
+        Some(&ctx.accounts.user_token_b),
+    )?;
+
+    let position = ctx.accounts.position.load()?;
+    let mut amount_a = position.tokens_owed_a;
+    let mut amount_b = position.tokens_owed_b;
+    let owner_key = position.owner_pubkey();
+    let delegate = position.delegate;
+    let delegate_permissions = position.delegate_permissions;
+    drop(position);
+
+    close_position_account(
+        &ctx.accounts.position.to_account_info(),
+        &ctx.accounts.payer.to_account_info(),
+    )?;
+
+    emit!(PositionClosedEvent {
+        pool: pool_key,
+        position: ctx.accounts.position.key(),
+        owner: owner_key,
+    });
+
+    // Rebalance through the pool itself; the input is already in the vault
+    if params.swap_amount > 0 {
+        let (Some(tick_array_0), Some(tick_array_1), Some(tick_array_2)) = (
+            &ctx.accounts.swap_tick_array_0,
+            &ctx.accounts.swap_tick_array_1,
+            &ctx.accounts.swap_tick_array_2,
+        ) else {
+            return err!(SuniswapError::TickArrayNotFound);
+        };
+
+        let (balance_in, balance_out) = if params.swap_a_to_b {
+            (&mut amount_a, &mut amount_b)
+        } else {
+            (&mut amount_b, &mut amount_a)
+        };
+        require!(
+            params.swap_amount <= *balance_in,
+            SuniswapError::InputExceedsMaximum
+        );
+
+        let swap_params = SwapParams {
+            amount: i64::try_from(params.swap_amount)
+                .map_err(|_| SuniswapError::CastOverflow)?,
+            // Slippage is bounded by `liquidity_min` instead
+            other_amount_threshold: 0,
+            sqrt_price_limit_x64: params.sqrt_price_limit_x64,
+            a_to_b: params.swap_a_to_b,
+            referral_fee_rate: 0,
+            deadline_unix_ts: None,
+            max_slot: None,
+            max_twap_deviation_ticks: None,
+        };
+        let (amount_in, amount_out) = execute_swap(
+            &ctx.accounts.pool,
+            tick_array_0,
+            tick_array_1,
+            tick_array_2,
+            ctx.accounts.oracle.as_mut(),
+            fee_tier.fee_rate,
+            &swap_params,
+            owner_key,
+        )?;
+
+        *balance_in = balance_in
+            .checked_sub(amount_in)
+            .ok_or(SuniswapError::MathUnderflow)?;
+        *balance_out = balance_out
+            .checked_add(amount_out)
+            .ok_or(SuniswapError::MathOverflow)?;
+    }
+
+    // Open the new position and deposit as much as the balances fund
+    initialize_position(
+        &ctx.accounts.new_position,
+        pool_key,
+        owner_key,
+        tick_lower,
+        tick_upper,
+        ctx.bumps.new_position,
+    )?;
+    let mut new_position = ctx.accounts.new_position.load_mut()?;
+    new_position.delegate = delegate;
+    new_position.delegate_permissions = delegate_permissions;
+    drop(new_position);
+
+    let payer = ctx.accounts.payer.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    initialize_tick_array_if_needed(
+        &ctx.accounts.new_tick_array_lower.to_account_info(),
+        &payer,
+        &system_program,
+        pool_key,
+        tick_spacing,
+        TickArray::get_start_tick_index(tick_lower, tick_spacing),
+    )?;
+    initialize_tick_array_if_needed(
+        &ctx.accounts.new_tick_array_upper.to_account_info(),
+        &payer,
+        &system_program,
+        pool_key,
+        tick_spacing,
+        TickArray::get_start_tick_index(tick_upper, tick_spacing),
+    )?;
+
+    let sqrt_price_x64 = ctx.accounts.pool.load()?.sqrt_price_x64;
+    let liquidity_delta = get_liquidity_for_amounts(
+        sqrt_price_x64,
+        get_sqrt_price_at_tick(tick_lower)?,
+        get_sqrt_price_at_tick(tick_upper)?,
+        amount_a,
+        amount_b,
+    )?;
+    require!(
+        liquidity_delta >= params.liquidity_min,
+        SuniswapError::SlippageExceeded
+    );
+
+    let (deposit_a, deposit_b) = add_liquidity(
+        &ctx.accounts.pool,
+        &ctx.accounts.new_position,
+        ctx.accounts.new_tick_array_lower.as_ref(),
+        ctx.accounts.new_tick_array_upper.as_ref(),
+        authority,
+        position_permissions::REPOSITION,
+        liquidity_delta,
+        amount_a,
+        amount_b,
+    )?;
+
+    // Return what the new range could not use
+    let refund_a = amount_a
+        .checked_sub(deposit_a)
+        .ok_or(SuniswapError::MathUnderflow)?;
+    let refund_b = amount_b
+        .checked_sub(deposit_b)
+        .ok_or(SuniswapError::MathUnderflow)?;
+
+    let pool_seeds: &[&[u8]] = &[
+        seeds::POOL_SEED,
+        &token_mint_a_bytes,
+        &token_mint_b_bytes,
+        &fee_tier.seed_bytes(),
+        &[pool_bump],
+    ];
+
+    if refund_a > 0 {
+        transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.token_vault_a.to_account_info(),
+                    mint: ctx.accounts.token_mint_a.to_account_info(),
+                    to: ctx.accounts.user_token_a.to_account_info(),
+                    authority: ctx.accounts.pool.to_account_info(),
+                },
+                &[pool_seeds],
+            ),
+            refund_a,
+            ctx.accounts.token_mint_a.decimals,
+        )?;
+    }
+
+    if refund_b > 0 {
+        transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.token_vault_b.to_account_info(),
+                    mint: ctx.accounts.token_mint_b.to_account_info(),
+                    to: ctx.accounts.user_token_b.to_account_info(),
+                    authority: ctx.accounts.pool.to_account_info(),
+                },
+                &[pool_seeds],
+            ),
+            refund_b,
+            ctx.accounts.token_mint_b.decimals,
+        )?;
+    }
+
+    emit!(PositionRepositionedEvent {
+        pool: pool_key,
+        old_position: ctx.accounts.position.key(),
+        new_position: ctx.accounts.new_position.key(),
+        owner: owner_key,
+        tick_lower,
+        tick_upper,
+        liquidity: liquidity_delta,
+        amount_a: deposit_a,
+        amount_b: deposit_b,
+        refund_a,
+        refund_b,
+    });
+
+    msg!("Position repositioned to [{}, {}]", tick_lower, tick_upper);
+    msg!("Liquidity: {}, Refund A: {}, Refund B: {}", liquidity_delta, refund_a, refund_b);
+
+    Ok(())
+}
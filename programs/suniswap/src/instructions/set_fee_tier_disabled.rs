@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::state::{SuniswapConfig, FeeTier};
+use crate::constants::seeds;
+use crate::errors::SuniswapError;
+use crate::events::FeeTierUpdatedEvent;
+
+/// Disable or re-enable pool creation on a fee tier
+/// Existing pools on the tier are unaffected
+#[derive(Accounts)]
+pub struct SetFeeTierDisabled<'info> {
+    /// The global config
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, SuniswapConfig>,
+
+    /// The fee tier to update
+    #[account(
+        mut,
+        seeds = [seeds::FEE_TIER_SEED, &fee_tier.seed_bytes()],
+        bump = fee_tier.bump,
+        constraint = fee_tier.config == config.key() @ SuniswapError::InvalidFeeTier,
+    )]
+    pub fee_tier: Account<'info, FeeTier>,
+
+    /// Protocol authority
+    pub authority: Signer<'info>,
+}
+
+/// Set fee tier disabled handler
+pub fn handler(ctx: Context<SetFeeTierDisabled>, disabled: bool) -> Result<()> {
+    require!(
+        ctx.accounts.config.is_protocol_authority(&ctx.accounts.authority.key()),
+        SuniswapError::NotProtocolAuthority
+    );
+
+    let fee_tier = &mut ctx.accounts.fee_tier;
+    fee_tier.disabled = disabled;
+
+    emit!(FeeTierUpdatedEvent {
+        fee_tier: fee_tier.key(),
+        fee_rate: fee_tier.fee_rate,
+        disabled,
+    });
+
+    msg!("Fee tier {}", if disabled { "disabled" } else { "enabled" });
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::state::{SuniswapConfig, FeeTier};
+use crate::constants::{seeds, FEE_RATE_DENOMINATOR};
+use crate::errors::SuniswapError;
+use crate::events::FeeTierUpdatedEvent;
+
+/// Change the fee rate charged by a fee tier
+#[derive(Accounts)]
+pub struct SetFeeTierRate<'info> {
+    /// The global config
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, SuniswapConfig>,
+
+    /// The fee tier to update
+    #[account(
+        mut,
+        seeds = [seeds::FEE_TIER_SEED, &fee_tier.seed_bytes()],
+        bump = fee_tier.bump,
+        constraint = fee_tier.config == config.key() @ SuniswapError::InvalidFeeTier,
+    )]
+    pub fee_tier: Account<'info, FeeTier>,
+
+    /// Protocol authority
+    pub authority: Signer<'info>,
+}
+
+/// Set fee tier rate handler
+pub fn handler(ctx: Context<SetFeeTierRate>, fee_rate: u32) -> Result<()> {
+    require!(
+        ctx.accounts.config.is_protocol_authority(&ctx.accounts.authority.key()),
+        SuniswapError::NotProtocolAuthority
+    );
+
+    // Same bound as initialize_fee_tier (max 10% = 100000)
+    require!(
+        fee_rate <= FEE_RATE_DENOMINATOR / 10,
+        SuniswapError::FeeRateTooHigh
+    );
+
+    let fee_tier = &mut ctx.accounts.fee_tier;
+    let old_fee_rate = fee_tier.fee_rate;
+
+    // Tiers created before `seed_fee_rate` existed are seeded by their
+    // current rate; record it before the rate moves away from it
+    if !fee_tier.seed_recorded {
+        fee_tier.seed_fee_rate = old_fee_rate;
+        fee_tier.seed_recorded = true;
+    }
+    fee_tier.fee_rate = fee_rate;
+
+    emit!(FeeTierUpdatedEvent {
+        fee_tier: fee_tier.key(),
+        fee_rate,
+        disabled: fee_tier.disabled,
+    });
+
+    msg!("Fee tier rate updated: {} -> {}", old_fee_rate, fee_rate);
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::state::SuniswapConfig;
+use crate::constants::{seeds, MAX_CREATOR_FEE_RATE};
+use crate::errors::SuniswapError;
+use crate::events::MaxCreatorFeeRateUpdatedEvent;
+
+/// Change the highest creator fee share a new pool may set
+#[derive(Accounts)]
+pub struct SetMaxCreatorFeeRate<'info> {
+    /// The global config
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, SuniswapConfig>,
+
+    /// Protocol authority
+    pub authority: Signer<'info>,
+}
+
+/// Set max creator fee rate handler
+pub fn handler(ctx: Context<SetMaxCreatorFeeRate>, max_creator_fee_rate: u8) -> Result<()> {
+    require!(
+        ctx.accounts.config.is_protocol_authority(&ctx.accounts.authority.key()),
+        SuniswapError::NotProtocolAuthority
+    );
+    require!(
+        max_creator_fee_rate <= MAX_CREATOR_FEE_RATE,
+        SuniswapError::CreatorFeeTooHigh
+    );
+
+    let config = &mut ctx.accounts.config;
+    let old_rate = config.max_creator_fee_rate;
+    config.max_creator_fee_rate = max_creator_fee_rate;
+
+    emit!(MaxCreatorFeeRateUpdatedEvent {
+        max_creator_fee_rate,
+    });
+
+    msg!("Max creator fee rate updated: {} -> {}", old_rate, max_creator_fee_rate);
+
+    Ok(())
+}
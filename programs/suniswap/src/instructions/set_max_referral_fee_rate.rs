@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::state::SuniswapConfig;
+use crate::constants::{seeds, referral};
+use crate::errors::SuniswapError;
+use crate::events::MaxReferralFeeRateUpdatedEvent;
+
+/// Change the highest referral fee a swap may take
+#[derive(Accounts)]
+pub struct SetMaxReferralFeeRate<'info> {
+    /// The global config
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, SuniswapConfig>,
+
+    /// Protocol authority
+    pub authority: Signer<'info>,
+}
+
+/// Set max referral fee rate handler
+pub fn handler(ctx: Context<SetMaxReferralFeeRate>, max_referral_fee_rate: u16) -> Result<()> {
+    require!(
+        ctx.accounts.config.is_protocol_authority(&ctx.accounts.authority.key()),
+        SuniswapError::NotProtocolAuthority
+    );
+    require!(
+        max_referral_fee_rate <= referral::MAX_FEE_RATE,
+        SuniswapError::ReferralFeeTooHigh
+    );
+
+    let config = &mut ctx.accounts.config;
+    let old_rate = config.max_referral_fee_rate;
+    config.max_referral_fee_rate = max_referral_fee_rate;
+
+    emit!(MaxReferralFeeRateUpdatedEvent {
+        max_referral_fee_rate,
+    });
+
+    msg!("Max referral fee rate updated: {} -> {}", old_rate, max_referral_fee_rate);
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use crate::state::{SuniswapConfig, Pool};
+use crate::constants::{seeds, FEE_RATE_DENOMINATOR};
+use crate::errors::SuniswapError;
+use crate::events::PoolFeeUpdatedEvent;
+
+/// Enable, reconfigure or disable a pool's adaptive fee
+#[derive(Accounts)]
+pub struct SetPoolAdaptiveFee<'info> {
+    /// The global config
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, SuniswapConfig>,
+
+    /// The pool to update (zero-copy)
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// Protocol authority
+    pub authority: Signer<'info>,
+}
+
+/// Adaptive fee parameters
+///
+/// While enabled, each swap step charges
+/// `clamp(base_fee + volatility * volatility_fee_factor, min_fee_rate, max_fee_rate)`,
+/// where volatility is the number of ticks the price moved recently: the pool's
+/// accumulator, decayed linearly to zero over `volatility_decay_period` seconds,
+/// plus the ticks moved so far in the swap.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct AdaptiveFeeParams {
+    /// Lower bound of the fee rate
+    pub min_fee_rate: u32,
+    /// Upper bound of the fee rate (at most 10%)
+    pub max_fee_rate: u32,
+    /// Fee added per tick of volatility (hundredths of a bip)
+    pub volatility_fee_factor: u32,
+    /// Seconds for the volatility accumulator to decay to zero
+    pub volatility_decay_period: u32,
+}
+
+/// Set pool adaptive fee handler
+pub fn handler(ctx: Context<SetPoolAdaptiveFee>, params: Option<AdaptiveFeeParams>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    require!(
+        config.is_protocol_authority(&ctx.accounts.authority.key()),
+        SuniswapError::NotProtocolAuthority
+    );
+
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    require!(
+        pool.config == config.key().to_bytes(),
+        SuniswapError::InvalidConfig
+    );
+
+    match params {
+        Some(params) => {
+            require!(
+                params.max_fee_rate <= FEE_RATE_DENOMINATOR / 10,
+                SuniswapError::FeeRateTooHigh
+            );
+            require!(
+                params.min_fee_rate <= params.max_fee_rate && params.volatility_decay_period > 0,
+                SuniswapError::InvalidAdaptiveFeeParams
+            );
+            pool.min_fee_rate = params.min_fee_rate;
+            pool.max_fee_rate = params.max_fee_rate;
+            pool.volatility_fee_factor = params.volatility_fee_factor;
+            pool.volatility_decay_period = params.volatility_decay_period;
+            pool.adaptive_fee_enabled = 1;
+            msg!(
+                "Adaptive fee enabled: {}..{}, {} per tick, decay {}s",
+                params.min_fee_rate,
+                params.max_fee_rate,
+                params.volatility_fee_factor,
+                params.volatility_decay_period
+            );
+        }
+        None => {
+            pool.adaptive_fee_enabled = 0;
+            msg!("Adaptive fee disabled");
+        }
+    }
+
+    // Start measuring volatility afresh
+    pool.volatility_accumulator = 0;
+    pool.volatility_updated_at = Clock::get()?.unix_timestamp as u32;
+
+    emit!(PoolFeeUpdatedEvent {
+        pool: ctx.accounts.pool.key(),
+        fee_rate: pool.fee_rate,
+        fee_rate_override: pool.fee_rate_override != 0,
+        adaptive_fee_enabled: pool.adaptive_fee_enabled != 0,
+        min_fee_rate: pool.min_fee_rate,
+        max_fee_rate: pool.max_fee_rate,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use crate::state::{SuniswapConfig, Pool};
+use crate::constants::seeds;
+use crate::errors::SuniswapError;
+use crate::events::PoolCircuitBreakerUpdatedEvent;
+
+/// Cap how far a pool's price may move in one swap and within one slot
+#[derive(Accounts)]
+pub struct SetPoolCircuitBreaker<'info> {
+    /// The global config
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, SuniswapConfig>,
+
+    /// The pool to update (zero-copy)
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// Protocol authority
+    pub authority: Signer<'info>,
+}
+
+/// Set pool circuit breaker handler
+pub fn handler(
+    ctx: Context<SetPoolCircuitBreaker>,
+    max_tick_move_per_swap: u32,
+    max_tick_move_per_slot: u32,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+    require!(
+        config.is_protocol_authority(&ctx.accounts.authority.key()),
+        SuniswapError::NotProtocolAuthority
+    );
+
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    require!(
+        pool.config == config.key().to_bytes(),
+        SuniswapError::InvalidConfig
+    );
+
+    pool.max_tick_move_per_swap = max_tick_move_per_swap;
+    pool.max_tick_move_per_slot = max_tick_move_per_slot;
+
+    msg!(
+        "Pool circuit breaker: {} ticks per swap, {} ticks per slot",
+        max_tick_move_per_swap,
+        max_tick_move_per_slot
+    );
+
+    emit!(PoolCircuitBreakerUpdatedEvent {
+        pool: ctx.accounts.pool.key(),
+        max_tick_move_per_swap,
+        max_tick_move_per_slot,
+    });
+
+    Ok(())
+}
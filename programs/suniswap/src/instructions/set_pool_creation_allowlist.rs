@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::state::{SuniswapConfig, PoolCreationConfig};
+use crate::constants::seeds;
+use crate::errors::SuniswapError;
+use crate::events::PoolCreationAllowlistUpdatedEvent;
+
+/// Replace the keys allowed to create pools in allowlist mode
+/// Only callable by protocol authority
+#[derive(Accounts)]
+pub struct SetPoolCreationAllowlist<'info> {
+    /// The global config
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, SuniswapConfig>,
+
+    /// The pool creation config to update
+    #[account(
+        mut,
+        seeds = [seeds::POOL_CREATION_CONFIG_SEED],
+        bump = pool_creation_config.bump,
+    )]
+    pub pool_creation_config: Account<'info, PoolCreationConfig>,
+
+    /// Protocol authority
+    #[account(
+        constraint = config.is_protocol_authority(&authority.key()) @ SuniswapError::NotProtocolAuthority,
+    )]
+    pub authority: Signer<'info>,
+}
+
+/// Set pool creation allowlist handler
+pub fn handler(ctx: Context<SetPoolCreationAllowlist>, creators: Vec<Pubkey>) -> Result<()> {
+    let pool_creation_config = &mut ctx.accounts.pool_creation_config;
+    pool_creation_config.set_allowed_creators(&creators)?;
+
+    emit!(PoolCreationAllowlistUpdatedEvent { creators });
+
+    msg!("Pool creation allowlist updated: {}", pool_creation_config.allowed_creator_count);
+
+    Ok(())
+}
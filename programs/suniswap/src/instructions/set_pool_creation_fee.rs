@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::state::{SuniswapConfig, PoolCreationConfig};
+use crate::constants::{seeds, pool_creation};
+use crate::errors::SuniswapError;
+use crate::events::PoolCreationFeeUpdatedEvent;
+
+/// Set the fee charged for creating a pool in fee mode
+/// Only callable by protocol authority
+#[derive(Accounts)]
+pub struct SetPoolCreationFee<'info> {
+    /// The global config
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, SuniswapConfig>,
+
+    /// The pool creation config to update
+    #[account(
+        mut,
+        seeds = [seeds::POOL_CREATION_CONFIG_SEED],
+        bump = pool_creation_config.bump,
+    )]
+    pub pool_creation_config: Account<'info, PoolCreationConfig>,
+
+    /// Protocol authority
+    #[account(
+        constraint = config.is_protocol_authority(&authority.key()) @ SuniswapError::NotProtocolAuthority,
+    )]
+    pub authority: Signer<'info>,
+}
+
+/// Set pool creation fee handler
+pub fn handler(
+    ctx: Context<SetPoolCreationFee>,
+    fee_mint: Option<Pubkey>,
+    fee_amount: u64,
+    treasury: Pubkey,
+) -> Result<()> {
+    let pool_creation_config = &mut ctx.accounts.pool_creation_config;
+    pool_creation_config.fee_mint = fee_mint.unwrap_or_default();
+    pool_creation_config.fee_amount = fee_amount;
+    pool_creation_config.treasury = treasury;
+
+    // Fee mode can't be left without a fee to charge
+    require!(
+        ctx.accounts.config.pool_creation_mode != pool_creation::MODE_FEE || pool_creation_config.has_fee(),
+        SuniswapError::InvalidPoolCreationMode
+    );
+
+    emit!(PoolCreationFeeUpdatedEvent {
+        fee_mint,
+        fee_amount,
+        treasury,
+    });
+
+    msg!("Pool creation fee updated: {}", fee_amount);
+
+    Ok(())
+}
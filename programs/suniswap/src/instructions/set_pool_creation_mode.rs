@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use crate::state::{SuniswapConfig, PoolCreationConfig};
+use crate::constants::{seeds, pool_creation};
+use crate::errors::SuniswapError;
+use crate::events::PoolCreationModeUpdatedEvent;
+
+/// Switch who may create pools: anyone, allowlisted keys, or anyone paying the creation fee
+/// Only callable by protocol authority
+#[derive(Accounts)]
+pub struct SetPoolCreationMode<'info> {
+    /// The global config
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, SuniswapConfig>,
+
+    /// The pool creation config, required for allowlist and fee mode
+    #[account(
+        seeds = [seeds::POOL_CREATION_CONFIG_SEED],
+        bump = pool_creation_config.bump,
+    )]
+    pub pool_creation_config: Option<Account<'info, PoolCreationConfig>>,
+
+    /// Protocol authority
+    pub authority: Signer<'info>,
+}
+
+/// Set pool creation mode handler
+pub fn handler(ctx: Context<SetPoolCreationMode>, pool_creation_mode: u8) -> Result<()> {
+    require!(
+        ctx.accounts.config.is_protocol_authority(&ctx.accounts.authority.key()),
+        SuniswapError::NotProtocolAuthority
+    );
+
+    match pool_creation_mode {
+        pool_creation::MODE_OPEN => {}
+        pool_creation::MODE_ALLOWLIST => require!(
+            ctx.accounts.pool_creation_config.is_some(),
+            SuniswapError::InvalidPoolCreationMode
+        ),
+        pool_creation::MODE_FEE => require!(
+            ctx.accounts.pool_creation_config.as_ref().is_some_and(|c| c.has_fee()),
+            SuniswapError::InvalidPoolCreationMode
+        ),
+        _ => return err!(SuniswapError::InvalidPoolCreationMode),
+    }
+
+    let config = &mut ctx.accounts.config;
+    let old_mode = config.pool_creation_mode;
+    config.pool_creation_mode = pool_creation_mode;
+
+    emit!(PoolCreationModeUpdatedEvent { pool_creation_mode });
+
+    msg!("Pool creation mode updated: {} -> {}", old_mode, pool_creation_mode);
+
+    Ok(())
+}
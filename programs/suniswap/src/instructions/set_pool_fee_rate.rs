@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::state::{SuniswapConfig, Pool};
+use crate::constants::{seeds, FEE_RATE_DENOMINATOR};
+use crate::errors::SuniswapError;
+use crate::events::PoolFeeUpdatedEvent;
+
+/// Override a pool's fee rate, or return it to its fee tier's rate
+#[derive(Accounts)]
+pub struct SetPoolFeeRate<'info> {
+    /// The global config
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, SuniswapConfig>,
+
+    /// The pool to update (zero-copy)
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// Protocol authority
+    pub authority: Signer<'info>,
+}
+
+/// Set pool fee rate handler
+pub fn handler(ctx: Context<SetPoolFeeRate>, fee_rate: Option<u32>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    require!(
+        config.is_protocol_authority(&ctx.accounts.authority.key()),
+        SuniswapError::NotProtocolAuthority
+    );
+
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    require!(
+        pool.config == config.key().to_bytes(),
+        SuniswapError::InvalidConfig
+    );
+
+    match fee_rate {
+        Some(fee_rate) => {
+            // Same bound as fee tiers (max 10% = 100000)
+            require!(
+                fee_rate <= FEE_RATE_DENOMINATOR / 10,
+                SuniswapError::FeeRateTooHigh
+            );
+            pool.fee_rate = fee_rate;
+            pool.fee_rate_override = 1;
+            msg!("Pool fee rate overridden: {}", fee_rate);
+        }
+        None => {
+            pool.fee_rate_override = 0;
+            msg!("Pool fee rate follows fee tier");
+        }
+    }
+
+    emit!(PoolFeeUpdatedEvent {
+        pool: ctx.accounts.pool.key(),
+        fee_rate: pool.fee_rate,
+        fee_rate_override: pool.fee_rate_override != 0,
+        adaptive_fee_enabled: pool.adaptive_fee_enabled != 0,
+        min_fee_rate: pool.min_fee_rate,
+        max_fee_rate: pool.max_fee_rate,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::state::{SuniswapConfig, Pool};
+use crate::constants::seeds;
+use crate::errors::SuniswapError;
+use crate::events::PoolTwapGuardUpdatedEvent;
+
+/// Set how far swaps may leave a pool's price from its oracle TWAP
+#[derive(Accounts)]
+pub struct SetPoolTwapGuard<'info> {
+    /// The global config
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, SuniswapConfig>,
+
+    /// The pool to update (zero-copy)
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// Protocol authority
+    pub authority: Signer<'info>,
+}
+
+/// Set pool TWAP guard handler
+pub fn handler(
+    ctx: Context<SetPoolTwapGuard>,
+    twap_window: u32,
+    max_twap_deviation_ticks: u32,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+    require!(
+        config.is_protocol_authority(&ctx.accounts.authority.key()),
+        SuniswapError::NotProtocolAuthority
+    );
+
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    require!(
+        pool.config == config.key().to_bytes(),
+        SuniswapError::InvalidConfig
+    );
+    require!(pool.oracle != [0u8; 32], SuniswapError::OracleNotInitialized);
+    require!(twap_window > 0, SuniswapError::InvalidTwapWindow);
+
+    pool.twap_window = twap_window;
+    pool.max_twap_deviation_ticks = max_twap_deviation_ticks;
+
+    msg!(
+        "Pool TWAP guard: {} ticks over {}s",
+        max_twap_deviation_ticks,
+        twap_window
+    );
+
+    emit!(PoolTwapGuardUpdatedEvent {
+        pool: ctx.accounts.pool.key(),
+        oracle: pool.oracle_pubkey(),
+        twap_window,
+        max_twap_deviation_ticks,
+    });
+
+    Ok(())
+}
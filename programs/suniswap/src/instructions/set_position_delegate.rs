@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::state::Position;
+use crate::constants::position_permissions;
+use crate::errors::SuniswapError;
+use crate::events::PositionDelegateUpdatedEvent;
+
+/// Let another account manage a position within a set of permissions
+/// Payouts still only go to the owner's token accounts
+#[derive(Accounts)]
+pub struct SetPositionDelegate<'info> {
+    /// The position to delegate (zero-copy)
+    #[account(mut)]
+    pub position: AccountLoader<'info, Position>,
+
+    /// Position owner
+    pub owner: Signer<'info>,
+}
+
+/// Set position delegate handler
+pub fn handler(ctx: Context<SetPositionDelegate>, delegate: Pubkey, permissions: u8) -> Result<()> {
+    require!(
+        permissions & !position_permissions::ALL == 0,
+        SuniswapError::InvalidDelegatePermissions
+    );
+
+    let mut position = ctx.accounts.position.load_mut()?;
+    require!(
+        position.owner == ctx.accounts.owner.key().to_bytes(),
+        SuniswapError::InvalidPositionOwner
+    );
+
+    position.delegate = delegate.to_bytes();
+    position.delegate_permissions = permissions;
+    let pool_key = position.pool_pubkey();
+    drop(position);
+
+    emit!(PositionDelegateUpdatedEvent {
+        pool: pool_key,
+        position: ctx.accounts.position.key(),
+        owner: ctx.accounts.owner.key(),
+        delegate,
+        permissions,
+    });
+
+    msg!("Position delegate set: {} ({:#06b})", delegate, permissions);
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::state::{SuniswapConfig, ProtocolFeeConfig, ProtocolFeeRecipient};
+use crate::constants::seeds;
+use crate::errors::SuniswapError;
+use crate::events::ProtocolFeeRecipientsUpdatedEvent;
+
+/// Replace the protocol fee recipients and their weights
+/// Only callable by fee authority
+#[derive(Accounts)]
+pub struct SetProtocolFeeRecipients<'info> {
+    /// The global config
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, SuniswapConfig>,
+
+    /// The fee split to update
+    #[account(
+        mut,
+        seeds = [seeds::PROTOCOL_FEE_CONFIG_SEED],
+        bump = protocol_fee_config.bump,
+    )]
+    pub protocol_fee_config: Account<'info, ProtocolFeeConfig>,
+
+    /// Fee authority
+    #[account(
+        constraint = fee_authority.key() == config.fee_authority @ SuniswapError::InvalidFeeAuthority,
+    )]
+    pub fee_authority: Signer<'info>,
+}
+
+/// Set protocol fee recipients handler
+pub fn handler(
+    ctx: Context<SetProtocolFeeRecipients>,
+    recipients: Vec<ProtocolFeeRecipient>,
+) -> Result<()> {
+    let protocol_fee_config = &mut ctx.accounts.protocol_fee_config;
+    protocol_fee_config.set_recipients(&recipients)?;
+
+    emit!(ProtocolFeeRecipientsUpdatedEvent { recipients });
+
+    msg!("Protocol fee recipients updated: {}", protocol_fee_config.recipient_count);
+
+    Ok(())
+}
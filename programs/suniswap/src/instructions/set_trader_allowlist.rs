@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::state::{Pool, TraderAllowlist};
+use crate::constants::seeds;
+use crate::errors::SuniswapError;
+use crate::events::TraderAllowlistUpdatedEvent;
+
+/// Switch a pool's trader allowlist on or off, or hand it to a new admin
+/// Only callable by the allowlist's admin
+#[derive(Accounts)]
+pub struct SetTraderAllowlist<'info> {
+    /// The gated pool (zero-copy)
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// The pool's trader allowlist
+    #[account(
+        mut,
+        seeds = [seeds::TRADER_ALLOWLIST_SEED, pool.key().as_ref()],
+        bump = trader_allowlist.bump,
+        has_one = admin @ SuniswapError::NotTraderAllowlistAdmin,
+    )]
+    pub trader_allowlist: Account<'info, TraderAllowlist>,
+
+    /// Allowlist admin
+    pub admin: Signer<'info>,
+}
+
+/// Set trader allowlist handler
+pub fn handler(ctx: Context<SetTraderAllowlist>, admin: Pubkey, enabled: bool) -> Result<()> {
+    let mut pool = ctx.accounts.pool.load_mut()?;
+    pool.trader_allowlist_enabled = enabled as u8;
+    ctx.accounts.trader_allowlist.admin = admin;
+
+    emit!(TraderAllowlistUpdatedEvent {
+        pool: ctx.accounts.pool.key(),
+        admin,
+        enabled,
+    });
+
+    msg!("Trader allowlist {}", if enabled { "enabled" } else { "disabled" });
+    msg!("Admin: {}", admin);
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use crate::state::{SuniswapConfig, Pool, ManagedVault};
+use crate::constants::seeds;
+use crate::errors::SuniswapError;
+use crate::instructions::initialize_vault::{apply_vault_params, validate_vault_params, VaultParams};
+
+/// Update a managed vault's strategist, treasury, fees and tick limits
+/// Only callable by protocol authority
+#[derive(Accounts)]
+pub struct SetVaultParams<'info> {
+    /// The global config
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, SuniswapConfig>,
+
+    /// The vault's pool (zero-copy)
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// The vault to update
+    #[account(
+        mut,
+        constraint = vault.pool == pool.key() @ SuniswapError::InvalidManagedVault,
+    )]
+    pub vault: Account<'info, ManagedVault>,
+
+    /// The vault's share mint
+    #[account(
+        constraint = share_mint.key() == vault.share_mint @ SuniswapError::InvalidTokenMint,
+    )]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+
+    /// Protocol authority
+    pub authority: Signer<'info>,
+}
+
+/// Set vault params handler
+pub fn handler(ctx: Context<SetVaultParams>, params: VaultParams) -> Result<()> {
+    let config = &ctx.accounts.config;
+    require!(
+        config.is_protocol_authority(&ctx.accounts.authority.key()),
+        SuniswapError::NotProtocolAuthority
+    );
+
+    let pool = ctx.accounts.pool.load()?;
+    require!(
+        pool.config == config.key().to_bytes(),
+        SuniswapError::InvalidConfig
+    );
+    validate_vault_params(&params, pool.tick_spacing)?;
+    drop(pool);
+
+    // Charge the old management fee up to now before the rate changes
+    let vault = &mut ctx.accounts.vault;
+    vault.accrue_management_fee(ctx.accounts.share_mint.supply, Clock::get()?.unix_timestamp)?;
+    apply_vault_params(vault, params);
+
+    msg!("Vault params updated");
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use crate::state::{Pool, TickArray, Tick};
+use crate::errors::SuniswapError;
+use crate::math::tick_math::is_valid_tick;
+
+/// Read oracle values accumulated inside a tick range
+/// Read-only: intended to be simulated, or called via CPI by staking and
+/// incentive programs that measure time-in-range per position
+#[derive(Accounts)]
+pub struct SnapshotCumulativesInside<'info> {
+    /// The pool (zero-copy)
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// Tick array containing lower tick (zero-copy)
+    pub tick_array_lower: AccountLoader<'info, TickArray>,
+
+    /// Tick array containing upper tick (zero-copy)
+    pub tick_array_upper: AccountLoader<'info, TickArray>,
+}
+
+/// Snapshot of the oracle values inside a tick range
+/// Only differences between two snapshots of the same range are meaningful,
+/// and only while both ticks stayed initialized in between
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CumulativesInside {
+    /// Tick cumulative accumulated while the range was active
+    pub tick_cumulative_inside: i64,
+    /// Seconds per liquidity accumulated while the range was active (Q64.64)
+    pub seconds_per_liquidity_inside_x64: u128,
+    /// Seconds the range was active
+    pub seconds_inside: u32,
+}
+
+/// Snapshot cumulatives inside handler
+pub fn handler(
+    ctx: Context<SnapshotCumulativesInside>,
+    tick_lower: i32,
+    tick_upper: i32,
+) -> Result<CumulativesInside> {
+    let pool_key = ctx.accounts.pool.key();
+    let pool = ctx.accounts.pool.load()?;
+    let tick_spacing = pool.tick_spacing;
+
+    // Validate tick range
+    require!(tick_lower < tick_upper, SuniswapError::InvalidTickRange);
+    require!(
+        is_valid_tick(tick_lower, tick_spacing),
+        SuniswapError::InvalidTickLower
+    );
+    require!(
+        is_valid_tick(tick_upper, tick_spacing),
+        SuniswapError::InvalidTickUpper
+    );
+
+    // Validate tick arrays
+    let tick_array_lower = ctx.accounts.tick_array_lower.load()?;
+    require!(
+        tick_array_lower.pool == pool_key.to_bytes(),
+        SuniswapError::InvalidTickArray
+    );
+
+    let tick_array_upper = ctx.accounts.tick_array_upper.load()?;
+    require!(
+        tick_array_upper.pool == pool_key.to_bytes(),
+        SuniswapError::InvalidTickArray
+    );
+
+    // Outside values are only maintained while a tick is initialized
+    let lower = tick_array_lower.get_tick(tick_lower, tick_spacing)?;
+    let upper = tick_array_upper.get_tick(tick_upper, tick_spacing)?;
+    require!(lower.is_initialized(), SuniswapError::TickNotInitialized);
+    require!(upper.is_initialized(), SuniswapError::TickNotInitialized);
+
+    let cumulatives = pool.observe(Clock::get()?.unix_timestamp as u32);
+    let (tick_cumulative_inside, seconds_per_liquidity_inside_x64, seconds_inside) =
+        Tick::get_cumulatives_inside(
+            lower,
+            upper,
+            tick_lower,
+            tick_upper,
+            pool.tick_current,
+            &cumulatives,
+        );
+
+    Ok(CumulativesInside {
+        tick_cumulative_inside,
+        seconds_per_liquidity_inside_x64,
+        seconds_inside,
+    })
+}
@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+use crate::state::{Pool, Position, TickArray, AllowedTrader};
+use crate::constants::seeds;
+use crate::errors::SuniswapError;
+use crate::events::PositionSplitEvent;
+use crate::instructions::collect_fees::settle_position_fees;
+use crate::instructions::open_position::initialize_position;
+use crate::instructions::trader_access::check_trader_access;
+
+/// Move part of a position's liquidity into a new position over the same range
+/// The new position may belong to another owner; ticks and pool liquidity are
+/// unchanged since the range keeps the same total liquidity
+#[derive(Accounts)]
+pub struct SplitPosition<'info> {
+    /// The pool (zero-copy)
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// The position to split (zero-copy)
+    #[account(mut)]
+    pub position: AccountLoader<'info, Position>,
+
+    /// Tick array containing lower tick (zero-copy)
+    pub tick_array_lower: AccountLoader<'info, TickArray>,
+
+    /// Tick array containing upper tick (zero-copy)
+    pub tick_array_upper: AccountLoader<'info, TickArray>,
+
+    /// Owner of the new position
+    /// CHECK: Any account can own a position
+    pub new_owner: UncheckedAccount<'info>,
+
+    /// The position account to create, over the same range (zero-copy)
+    #[account(
+        init,
+        payer = payer,
+        space = Position::LEN,
+        seeds = [
+            seeds::POSITION_SEED,
+            pool.key().as_ref(),
+            new_owner.key().as_ref(),
+            &position.load()?.tick_lower.to_le_bytes(),
+            &position.load()?.tick_upper.to_le_bytes()
+        ],
+        bump
+    )]
+    pub new_position: AccountLoader<'info, Position>,
+
+    /// Owner of the position being split
+    pub owner: Signer<'info>,
+
+    /// The payer for account creation
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+
+    /// The new owner's entry on the pool's trader allowlist, only needed while it is enabled
+    pub allowed_trader: Option<Account<'info, AllowedTrader>>,
+}
+
+/// Split position handler
+pub fn handler(ctx: Context<SplitPosition>, liquidity_delta: u128) -> Result<()> {
+    require!(liquidity_delta > 0, SuniswapError::ZeroLiquidity);
+
+    let pool_key = ctx.accounts.pool.key();
+    let owner_key = ctx.accounts.owner.key();
+    let new_owner_key = ctx.accounts.new_owner.key();
+    let pool = ctx.accounts.pool.load()?;
+    require!(pool.is_paused == 0, SuniswapError::PoolPaused);
+    // The new owner receives liquidity, which is a deposit on their part
+    check_trader_access(&pool, &pool_key, ctx.accounts.allowed_trader.as_ref(), &new_owner_key)?;
+    drop(pool);
+
+    // Bring owed tokens up to date so fees earned so far are split too
+    settle_position_fees(
+        &ctx.accounts.pool,
+        &ctx.accounts.position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        owner_key,
+        0,
+    )?;
+
+    let mut position = ctx.accounts.position.load_mut()?;
+    require!(
+        position.liquidity >= liquidity_delta,
+        SuniswapError::InsufficientLiquidity
+    );
+
+    let (owed_a, owed_b) = position.owed_tokens_share(liquidity_delta)?;
+    position.liquidity -= liquidity_delta;
+    position.tokens_owed_a -= owed_a;
+    position.tokens_owed_b -= owed_b;
+
+    let tick_lower = position.tick_lower;
+    let tick_upper = position.tick_upper;
+    let fee_growth_inside_a = position.fee_growth_inside_a_last_x128;
+    let fee_growth_inside_b = position.fee_growth_inside_b_last_x128;
+    drop(position);
+
+    initialize_position(
+        &ctx.accounts.new_position,
+        pool_key,
+        new_owner_key,
+        tick_lower,
+        tick_upper,
+        ctx.bumps.new_position,
+    )?;
+
+    // The new position starts from the same fee checkpoint as the old one
+    let mut new_position = ctx.accounts.new_position.load_mut()?;
+    new_position.liquidity = liquidity_delta;
+    new_position.fee_growth_inside_a_last_x128 = fee_growth_inside_a;
+    new_position.fee_growth_inside_b_last_x128 = fee_growth_inside_b;
+    new_position.tokens_owed_a = owed_a;
+    new_position.tokens_owed_b = owed_b;
+    drop(new_position);
+
+    emit!(PositionSplitEvent {
+        pool: pool_key,
+        position: ctx.accounts.position.key(),
+        new_position: ctx.accounts.new_position.key(),
+        owner: owner_key,
+        new_owner: new_owner_key,
+        liquidity: liquidity_delta,
+        tokens_owed_a: owed_a,
+        tokens_owed_b: owed_b,
+    });
+
+    msg!("Position split: {} liquidity to {}", liquidity_delta, new_owner_key);
+    msg!("Owed tokens moved: A={}, B={}", owed_a, owed_b);
+
+    Ok(())
+}
@@ -1,8 +1,13 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{TokenAccount, TokenInterface, Mint, TransferChecked, transfer_checked};
-use crate::state::{Pool, TickArray, FeeTier};
-use crate::constants::seeds;
+use crate::state::{Pool, TickArray, FeeTier, Observation, Oracle, SuniswapConfig, AllowedTrader};
+use crate::constants::{seeds, referral};
 use crate::errors::SuniswapError;
+use crate::events::{SwapEvent, ReferralFeeEvent};
+use crate::instructions::deadline::check_deadline;
+use crate::instructions::trader_access::check_trader_access;
+use crate::instructions::native_sol::NativeSol;
+use crate::math::full_math::{mul_div, mul_div_round_up};
 use crate::math::swap_math::compute_swap_step;
 use crate::math::tick_math::{get_tick_at_sqrt_price, get_sqrt_price_at_tick};
 use crate::math::liquidity_math::add_liquidity_delta;
@@ -31,13 +36,13 @@ pub struct Swap<'info> {
     #[account(mut)]
     pub token_vault_b: InterfaceAccount<'info, TokenAccount>,
 
-    /// User's input token account
+    /// User's input token account, omitted to pay native SOL
     #[account(mut)]
-    pub user_token_input: InterfaceAccount<'info, TokenAccount>,
+    pub user_token_input: Option<InterfaceAccount<'info, TokenAccount>>,
 
-    /// User's output token account
+    /// User's output token account, omitted to receive native SOL
     #[account(mut)]
-    pub user_token_output: InterfaceAccount<'info, TokenAccount>,
+    pub user_token_output: Option<InterfaceAccount<'info, TokenAccount>>,
 
     /// Current tick array (zero-copy)
     #[account(mut)]
@@ -52,10 +57,38 @@ pub struct Swap<'info> {
     pub tick_array_2: AccountLoader<'info, TickArray>,
 
     /// The user performing the swap
+    #[account(mut)]
     pub user: Signer<'info>,
 
     /// Token program
     pub token_program: Interface<'info, TokenInterface>,
+
+    /// The signer's entry on the pool's trader allowlist, only needed while it is enabled
+    pub allowed_trader: Option<Account<'info, AllowedTrader>>,
+
+    /// The pool's oracle, required once the pool has one
+    #[account(mut)]
+    pub oracle: Option<Account<'info, Oracle>>,
+
+    /// The global config, only needed for a referral fee
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Option<Account<'info, SuniswapConfig>>,
+
+    /// Referrer's token account for the input or output mint, only needed
+    /// for a referral fee; the fee is taken in this account's token
+    #[account(mut)]
+    pub referrer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Temporary wSOL account, only needed when a user token account
+    /// is omitted; validated and created by the handler
+    #[account(mut)]
+    pub native_sol_account: Option<UncheckedAccount<'info>>,
+
+    /// System program, only needed with `native_sol_account`
+    pub system_program: Option<Program<'info, System>>,
 }
 
 /// Swap parameters
@@ -65,6 +98,15 @@ pub struct SwapParams {
     pub other_amount_threshold: u64,
     pub sqrt_price_limit_x64: u128,
     pub a_to_b: bool,
+    /// Referral fee in basis points of the user's input or output, 0 for none
+    pub referral_fee_rate: u16,
+    /// Unix timestamp after which the swap is rejected
+    pub deadline_unix_ts: Option<i64>,
+    /// Slot after which the swap is rejected
+    pub max_slot: Option<u64>,
+    /// Most ticks the swap may leave the price from the oracle TWAP, on top
+    /// of the pool's own cap
+    pub max_twap_deviation_ticks: Option<u32>,
 }
 
 /// Internal swap state to track progress through tick arrays
@@ -75,19 +117,25 @@ struct SwapState {
     tick: i32,
     liquidity: u128,
     fee_growth_global_x128: u128,
+    fee_amount: u64,
     protocol_fee: u64,
+    creator_fee: u64,
 }
 
 /// Swap handler with proper tick crossing (C-01, C-02 FIX)
-pub fn handler(ctx: Context<Swap>, params: SwapParams) -> Result<()> {
-    require!(params.amount != 0, SuniswapError::ZeroSwapAmount);
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, Swap<'info>>, params: SwapParams) -> Result<()> {
+    check_deadline(params.deadline_unix_ts, params.max_slot)?;
 
     let fee_tier = &ctx.accounts.fee_tier;
-    let pool_key = ctx.accounts.pool.key();
 
-    // Load and validate pool
+    // Validate the token accounts against the pool
     let pool = ctx.accounts.pool.load()?;
-    require!(pool.is_paused == 0, SuniswapError::PoolPaused);
+    check_trader_access(
+        &pool,
+        &ctx.accounts.pool.key(),
+        ctx.accounts.allowed_trader.as_ref(),
+        &ctx.accounts.user.key(),
+    )?;
     require!(
         pool.fee_tier == fee_tier.key().to_bytes(),
         SuniswapError::InvalidFeeTier
@@ -108,6 +156,238 @@ pub fn handler(ctx: Context<Swap>, params: SwapParams) -> Result<()> {
         pool.token_vault_b == ctx.accounts.token_vault_b.key().to_bytes(),
         SuniswapError::InvalidVault
     );
+    let pool_bump = pool.bump;
+    let token_mint_a_bytes = pool.token_mint_a;
+    let token_mint_b_bytes = pool.token_mint_b;
+    drop(pool);
+
+    let zero_for_one = params.a_to_b;
+    let exact_input = params.amount > 0;
+    let (input_mint, output_mint, input_decimals, output_decimals) = if zero_for_one {
+        (
+            &ctx.accounts.token_mint_a,
+            &ctx.accounts.token_mint_b,
+            ctx.accounts.token_mint_a.decimals,
+            ctx.accounts.token_mint_b.decimals,
+        )
+    } else {
+        (
+            &ctx.accounts.token_mint_b,
+            &ctx.accounts.token_mint_a,
+            ctx.accounts.token_mint_b.decimals,
+            ctx.accounts.token_mint_a.decimals,
+        )
+    };
+
+    let (input_vault, output_vault) = if zero_for_one {
+        (&ctx.accounts.token_vault_a, &ctx.accounts.token_vault_b)
+    } else {
+        (&ctx.accounts.token_vault_b, &ctx.accounts.token_vault_a)
+    };
+
+    // The referral fee is taken in whichever side's token the referrer holds
+    let referral_payout = if params.referral_fee_rate > 0 {
+        let (Some(config), Some(referrer)) = (&ctx.accounts.config, &ctx.accounts.referrer_token_account) else {
+            return err!(SuniswapError::InvalidReferralAccount);
+        };
+        require!(
+            params.referral_fee_rate <= config.max_referral_fee_rate,
+            SuniswapError::ReferralFeeTooHigh
+        );
+        let fee_on_input = referrer.mint == input_mint.key();
+        require!(
+            fee_on_input || referrer.mint == output_mint.key(),
+            SuniswapError::InvalidReferralAccount
+        );
+        Some((referrer, fee_on_input))
+    } else {
+        None
+    };
+
+    // Size the pool swap so the fee fits inside the user's amount, and leave
+    // slippage to be checked net of the fee below
+    let mut swap_params = params.clone();
+    if let Some((_, fee_on_input)) = referral_payout {
+        let denominator = referral::FEE_RATE_DENOMINATOR as u128;
+        let rate = params.referral_fee_rate as u128;
+        let amount = params.amount.unsigned_abs() as u128;
+        if fee_on_input && exact_input {
+            let fee = mul_div_round_up(amount, rate, denominator)?;
+            swap_params.amount = (amount - fee) as i64;
+        } else if !fee_on_input && !exact_input {
+            // Taken back off as the rate's share of the gross, this leaves the user exactly `amount`
+            let gross = amount + mul_div(amount, rate, denominator - rate)?;
+            swap_params.amount = -i64::try_from(gross).map_err(|_| SuniswapError::CastOverflow)?;
+        }
+        swap_params.other_amount_threshold = if exact_input { 0 } else { u64::MAX };
+    }
+
+    let (amount_in, amount_out) = execute_swap(
+        &ctx.accounts.pool,
+        &ctx.accounts.tick_array_0,
+        &ctx.accounts.tick_array_1,
+        &ctx.accounts.tick_array_2,
+        ctx.accounts.oracle.as_mut(),
+        fee_tier.fee_rate,
+        &swap_params,
+        ctx.accounts.user.key(),
+    )?;
+
+    // The input fee is the rate's share of everything the user pays, the
+    // output fee its share of everything the pool pays out
+    let (input_fee, output_fee) = match referral_payout {
+        Some((_, true)) => {
+            let rate = params.referral_fee_rate as u128;
+            let fee = mul_div(amount_in as u128, rate, referral::FEE_RATE_DENOMINATOR as u128 - rate)?;
+            (u64::try_from(fee).map_err(|_| SuniswapError::CastOverflow)?, 0)
+        }
+        Some((_, false)) => {
+            let fee = mul_div(
+                amount_out as u128,
+                params.referral_fee_rate as u128,
+                referral::FEE_RATE_DENOMINATOR as u128,
+            )?;
+            (0, fee as u64)
+        }
+        None => (0, 0),
+    };
+    let user_amount_in = amount_in.checked_add(input_fee).ok_or(SuniswapError::MathOverflow)?;
+    let user_amount_out = amount_out - output_fee;
+    if referral_payout.is_some() {
+        if exact_input {
+            require!(
+                user_amount_out >= params.other_amount_threshold,
+                SuniswapError::OutputBelowMinimum
+            );
+        } else {
+            require!(
+                user_amount_in <= params.other_amount_threshold,
+                SuniswapError::InputExceedsMaximum
+            );
+        }
+    }
+
+    // Execute token transfers
+    let native_sol = NativeSol {
+        user: &ctx.accounts.user,
+        native_account: ctx.accounts.native_sol_account.as_ref(),
+        system_program: ctx.accounts.system_program.as_ref(),
+        token_program: &ctx.accounts.token_program,
+    };
+    let pay_in = |to: &InterfaceAccount<'info, TokenAccount>, amount: u64| -> Result<()> {
+        match &ctx.accounts.user_token_input {
+            Some(user_token_input) => transfer_checked(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: user_token_input.to_account_info(),
+                        mint: input_mint.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: ctx.accounts.user.to_account_info(),
+                    },
+                ),
+                amount,
+                input_decimals,
+            ),
+            None => native_sol.wrap(input_mint, to, amount),
+        }
+    };
+
+    // Transfer input tokens
+    pay_in(input_vault, amount_in)?;
+
+    // Transfer output tokens
+    let pool_seeds: &[&[u8]] = &[
+        seeds::POOL_SEED,
+        &token_mint_a_bytes,
+        &token_mint_b_bytes,
+        &fee_tier.seed_bytes(),
+        &[pool_bump],
+    ];
+
+    match &ctx.accounts.user_token_output {
+        Some(user_token_output) => transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: output_vault.to_account_info(),
+                    mint: output_mint.to_account_info(),
+                    to: user_token_output.to_account_info(),
+                    authority: ctx.accounts.pool.to_account_info(),
+                },
+                &[pool_seeds],
+            ),
+            user_amount_out,
+            output_decimals,
+        )?,
+        None => native_sol.unwrap(
+            output_mint,
+            output_vault,
+            ctx.accounts.pool.to_account_info(),
+            pool_seeds,
+            user_amount_out,
+        )?,
+    }
+
+    // Pay the referrer
+    if let Some((referrer, fee_on_input)) = referral_payout {
+        let (mint, amount) = if fee_on_input {
+            pay_in(referrer, input_fee)?;
+            (input_mint.key(), input_fee)
+        } else {
+            if output_fee > 0 {
+                transfer_checked(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        TransferChecked {
+                            from: output_vault.to_account_info(),
+                            mint: output_mint.to_account_info(),
+                            to: referrer.to_account_info(),
+                            authority: ctx.accounts.pool.to_account_info(),
+                        },
+                        &[pool_seeds],
+                    ),
+                    output_fee,
+                    output_decimals,
+                )?;
+            }
+            (output_mint.key(), output_fee)
+        };
+
+        emit!(ReferralFeeEvent {
+            pool: ctx.accounts.pool.key(),
+            user: ctx.accounts.user.key(),
+            referrer_token_account: referrer.key(),
+            mint,
+            amount,
+            fee_rate: params.referral_fee_rate,
+        });
+    }
+
+    Ok(())
+}
+
+/// Run the swap loop against a pool and its tick arrays, updating the pool
+/// and crossed ticks and returning `(amount_in, amount_out)`
+///
+/// Moves no tokens: callers settle the amounts with the vaults themselves,
+/// which lets `reposition` swap balances that already sit in the vaults
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn execute_swap<'a>(
+    pool_loader: &AccountLoader<'a, Pool>,
+    tick_array_0: &AccountLoader<'a, TickArray>,
+    tick_array_1: &AccountLoader<'a, TickArray>,
+    tick_array_2: &AccountLoader<'a, TickArray>,
+    oracle: Option<&mut Account<'a, Oracle>>,
+    tier_fee_rate: u32,
+    params: &SwapParams,
+    user: Pubkey,
+) -> Result<(u64, u64)> {
+    require!(params.amount != 0, SuniswapError::ZeroSwapAmount);
+
+    let pool_key = pool_loader.key();
+    let pool = pool_loader.load()?;
+    require!(pool.is_paused == 0, SuniswapError::PoolPaused);
 
     let zero_for_one = params.a_to_b;
     let exact_input = params.amount > 0;
@@ -157,16 +437,46 @@ pub fn handler(ctx: Context<Swap>, params: SwapParams) -> Result<()> {
         } else {
             pool.fee_growth_global_b_x128
         },
+        fee_amount: 0,
         protocol_fee: 0,
+        creator_fee: 0,
     };
 
     let protocol_fee_rate = pool.protocol_fee_rate;
-    let pool_bump = pool.bump;
-    let token_mint_a_bytes = pool.token_mint_a;
-    let token_mint_b_bytes = pool.token_mint_b;
+    let creator_fee_rate = pool.creator_fee_rate;
     let fee_growth_global_a = pool.fee_growth_global_a_x128;
     let fee_growth_global_b = pool.fee_growth_global_b_x128;
 
+    // Oracle accumulators as of this swap; crossed ticks flip their outside values
+    // against these
+    let clock = Clock::get()?;
+    let timestamp = clock.unix_timestamp as u32;
+    let cumulatives = pool.observe(timestamp);
+
+    // The pool's own or its tier's fee, plus the adaptive component if enabled
+    let swap_fee = pool.swap_fee(tier_fee_rate, timestamp);
+    let tick_start = state.tick;
+
+    // Record the price the pool held up to now, and average it for the TWAP guard
+    match &oracle {
+        Some(oracle) => require_keys_eq!(oracle.key(), pool.oracle_pubkey(), SuniswapError::InvalidOracleAccount),
+        None => require!(pool.oracle == [0u8; 32], SuniswapError::InvalidOracleAccount),
+    }
+    let twap_limit = pool.twap_deviation_limit(params.max_twap_deviation_ticks);
+    let twap_guard = match oracle {
+        Some(oracle) => {
+            oracle.write(cumulatives, Oracle::write_interval(pool.twap_window));
+            match twap_limit {
+                Some(limit) => Some((limit, oracle.twap_tick(pool.twap_window, &pool, timestamp)?)),
+                None => None,
+            }
+        }
+        None => {
+            require!(twap_limit.is_none(), SuniswapError::OracleNotInitialized);
+            None
+        }
+    };
+
     drop(pool);
 
     // Validate tick arrays belong to this pool and are properly sequenced for swap direction
@@ -174,9 +484,9 @@ pub fn handler(ctx: Context<Swap>, params: SwapParams) -> Result<()> {
     let expected_start_0 = crate::state::TickArray::get_start_tick_index(state.tick, tick_spacing);
 
     let (start_0, start_1, start_2) = {
-        let tick_array_0 = ctx.accounts.tick_array_0.load()?;
-        let tick_array_1 = ctx.accounts.tick_array_1.load()?;
-        let tick_array_2 = ctx.accounts.tick_array_2.load()?;
+        let tick_array_0 = tick_array_0.load()?;
+        let tick_array_1 = tick_array_1.load()?;
+        let tick_array_2 = tick_array_2.load()?;
 
         // Validate all arrays belong to this pool
         require!(
@@ -230,15 +540,20 @@ pub fn handler(ctx: Context<Swap>, params: SwapParams) -> Result<()> {
     {
         iterations += 1;
 
-        // Find the next initialized tick in the swap direction
-        let (next_tick, next_tick_initialized) = find_next_initialized_tick(
-            &ctx.accounts.tick_array_0,
-            &ctx.accounts.tick_array_1,
-            &ctx.accounts.tick_array_2,
+        // Find the next initialized tick in the swap direction. Once the price has
+        // moved past the last provided tick array the swap ends here, since ticks
+        // beyond it cannot be crossed without their liquidity_net
+        let Some((next_tick, next_tick_initialized)) = find_next_initialized_tick(
+            tick_array_0,
+            tick_array_1,
+            tick_array_2,
             state.tick,
             tick_spacing,
             zero_for_one,
-        )?;
+        )?
+        else {
+            break;
+        };
 
         // Clamp to price limit
         let sqrt_price_next_tick = get_sqrt_price_at_tick(next_tick)?;
@@ -254,10 +569,11 @@ pub fn handler(ctx: Context<Swap>, params: SwapParams) -> Result<()> {
             sqrt_price_target,
             state.liquidity,
             state.amount_remaining,
-            fee_tier.fee_rate,
+            swap_fee.fee_rate(state.tick.abs_diff(tick_start)),
         )?;
 
         // Update state with step results
+        let sqrt_price_start_x64 = state.sqrt_price_x64;
         state.sqrt_price_x64 = step.sqrt_price_next_x64;
 
         // Safe conversion of swap step amounts to i64
@@ -288,37 +604,60 @@ pub fn handler(ctx: Context<Swap>, params: SwapParams) -> Result<()> {
                 .ok_or(SuniswapError::MathOverflow)?;
         }
 
-        // Update fee growth
+        state.fee_amount = state.fee_amount
+            .checked_add(step.fee_amount)
+            .ok_or(SuniswapError::MathOverflow)?;
+
+        // Update fee growth with the LP share of the fee (protocol and creator cuts taken first)
         if state.liquidity > 0 {
-            let fee_growth_delta = crate::math::swap_math::calculate_fee_growth(
-                step.fee_amount,
-                state.liquidity,
-            )?;
-            state.fee_growth_global_x128 = state.fee_growth_global_x128.wrapping_add(fee_growth_delta);
+            let mut lp_fee_amount = step.fee_amount;
 
             if protocol_fee_rate > 0 {
                 let protocol_fee_amount = crate::math::swap_math::calculate_protocol_fee(
                     step.fee_amount,
                     protocol_fee_rate,
                 )?;
+                lp_fee_amount = lp_fee_amount
+                    .checked_sub(protocol_fee_amount)
+                    .ok_or(SuniswapError::MathUnderflow)?;
                 state.protocol_fee = state.protocol_fee
                     .checked_add(protocol_fee_amount)
                     .ok_or(SuniswapError::MathOverflow)?;
             }
+
+            if creator_fee_rate > 0 {
+                let creator_fee_amount = crate::math::swap_math::calculate_creator_fee(
+                    step.fee_amount,
+                    creator_fee_rate,
+                )?;
+                lp_fee_amount = lp_fee_amount
+                    .checked_sub(creator_fee_amount)
+                    .ok_or(SuniswapError::MathUnderflow)?;
+                state.creator_fee = state.creator_fee
+                    .checked_add(creator_fee_amount)
+                    .ok_or(SuniswapError::MathOverflow)?;
+            }
+
+            let fee_growth_delta = crate::math::swap_math::calculate_fee_growth(
+                lp_fee_amount,
+                state.liquidity,
+            )?;
+            state.fee_growth_global_x128 = state.fee_growth_global_x128.wrapping_add(fee_growth_delta);
         }
 
         // C-01 FIX: Handle tick crossing when we reach the target tick
         if state.sqrt_price_x64 == sqrt_price_next_tick && next_tick_initialized {
             // Cross the tick - update liquidity
             let liquidity_net = cross_tick(
-                &ctx.accounts.tick_array_0,
-                &ctx.accounts.tick_array_1,
-                &ctx.accounts.tick_array_2,
+                tick_array_0,
+                tick_array_1,
+                tick_array_2,
                 next_tick,
                 tick_spacing,
                 fee_growth_global_a,
                 fee_growth_global_b,
                 state.fee_growth_global_x128,
+                &cumulatives,
                 zero_for_one,
             )?;
 
@@ -332,19 +671,15 @@ pub fn handler(ctx: Context<Swap>, params: SwapParams) -> Result<()> {
             };
         }
 
-        // Update tick based on new price
-        state.tick = if zero_for_one {
-            if state.sqrt_price_x64 == sqrt_price_next_tick {
-                next_tick - 1
-            } else {
-                get_tick_at_sqrt_price(state.sqrt_price_x64)?
-            }
+        // Update tick based on new price. A step that consumes input without moving
+        // the price keeps the tick, which matters right after crossing downwards:
+        // the price then sits on the crossed tick but the current tick is below it
+        state.tick = if state.sqrt_price_x64 == sqrt_price_next_tick {
+            if zero_for_one { next_tick - 1 } else { next_tick }
+        } else if state.sqrt_price_x64 != sqrt_price_start_x64 {
+            get_tick_at_sqrt_price(state.sqrt_price_x64)?
         } else {
-            if state.sqrt_price_x64 == sqrt_price_next_tick {
-                next_tick
-            } else {
-                get_tick_at_sqrt_price(state.sqrt_price_x64)?
-            }
+            state.tick
         };
     }
 
@@ -384,94 +719,66 @@ pub fn handler(ctx: Context<Swap>, params: SwapParams) -> Result<()> {
 
     // Update pool state
     {
-        let mut pool = ctx.accounts.pool.load_mut()?;
+        let mut pool = pool_loader.load_mut()?;
+        pool.check_circuit_breaker(clock.slot, tick_start, state.tick)?;
+        if let Some((limit, twap_tick)) = twap_guard {
+            require!(
+                state.tick.abs_diff(twap_tick) <= limit,
+                SuniswapError::TwapDeviationExceeded
+            );
+        }
+        pool.update_cumulatives(timestamp);
         pool.sqrt_price_x64 = state.sqrt_price_x64;
         pool.tick_current = state.tick;
         pool.liquidity = state.liquidity;  // C-02 FIX: Now properly updated
 
+        if swap_fee.adaptive {
+            pool.volatility_accumulator = swap_fee.volatility(state.tick.abs_diff(tick_start));
+            pool.volatility_updated_at = timestamp;
+        }
+
         if zero_for_one {
             pool.fee_growth_global_a_x128 = state.fee_growth_global_x128;
             pool.protocol_fees_a = pool.protocol_fees_a
                 .checked_add(state.protocol_fee)
                 .ok_or(SuniswapError::MathOverflow)?;
+            pool.creator_fees_a = pool.creator_fees_a
+                .checked_add(state.creator_fee)
+                .ok_or(SuniswapError::MathOverflow)?;
         } else {
             pool.fee_growth_global_b_x128 = state.fee_growth_global_x128;
             pool.protocol_fees_b = pool.protocol_fees_b
                 .checked_add(state.protocol_fee)
                 .ok_or(SuniswapError::MathOverflow)?;
+            pool.creator_fees_b = pool.creator_fees_b
+                .checked_add(state.creator_fee)
+                .ok_or(SuniswapError::MathOverflow)?;
         }
     }
 
-    // Execute token transfers
-    let (input_mint, output_mint, input_decimals, output_decimals) = if zero_for_one {
-        (
-            &ctx.accounts.token_mint_a,
-            &ctx.accounts.token_mint_b,
-            ctx.accounts.token_mint_a.decimals,
-            ctx.accounts.token_mint_b.decimals,
-        )
-    } else {
-        (
-            &ctx.accounts.token_mint_b,
-            &ctx.accounts.token_mint_a,
-            ctx.accounts.token_mint_b.decimals,
-            ctx.accounts.token_mint_a.decimals,
-        )
-    };
-
-    let (input_vault, output_vault) = if zero_for_one {
-        (&ctx.accounts.token_vault_a, &ctx.accounts.token_vault_b)
-    } else {
-        (&ctx.accounts.token_vault_b, &ctx.accounts.token_vault_a)
-    };
-
-    // Transfer input tokens
-    transfer_checked(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            TransferChecked {
-                from: ctx.accounts.user_token_input.to_account_info(),
-                mint: input_mint.to_account_info(),
-                to: input_vault.to_account_info(),
-                authority: ctx.accounts.user.to_account_info(),
-            },
-        ),
+    emit!(SwapEvent {
+        pool: pool_key,
+        user,
+        a_to_b: zero_for_one,
         amount_in,
-        input_decimals,
-    )?;
-
-    // Transfer output tokens
-    let pool_seeds: &[&[u8]] = &[
-        seeds::POOL_SEED,
-        &token_mint_a_bytes,
-        &token_mint_b_bytes,
-        &fee_tier.fee_rate.to_le_bytes(),
-        &[pool_bump],
-    ];
-
-    transfer_checked(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            TransferChecked {
-                from: output_vault.to_account_info(),
-                mint: output_mint.to_account_info(),
-                to: ctx.accounts.user_token_output.to_account_info(),
-                authority: ctx.accounts.pool.to_account_info(),
-            },
-            &[pool_seeds],
-        ),
         amount_out,
-        output_decimals,
-    )?;
+        fee_amount: state.fee_amount,
+        protocol_fee: state.protocol_fee,
+        creator_fee: state.creator_fee,
+        sqrt_price_x64: state.sqrt_price_x64,
+        tick_current: state.tick,
+        liquidity: state.liquidity,
+    });
 
     msg!("Swap: {} -> {}", if zero_for_one { "A" } else { "B" }, if zero_for_one { "B" } else { "A" });
     msg!("In: {}, Out: {}, Ticks crossed: {}", amount_in, amount_out, iterations);
 
-    Ok(())
+    Ok((amount_in, amount_out))
 }
 
 /// Find the next initialized tick in the given direction
-/// Returns (next_tick, is_initialized)
+/// Returns (next_tick, is_initialized), or None if no provided array covers the
+/// ticks ahead of `current_tick`
 fn find_next_initialized_tick<'a>(
     tick_array_0: &AccountLoader<'a, TickArray>,
     tick_array_1: &AccountLoader<'a, TickArray>,
@@ -479,7 +786,7 @@ fn find_next_initialized_tick<'a>(
     current_tick: i32,
     tick_spacing: u16,
     zero_for_one: bool,
-) -> Result<(i32, bool)> {
+) -> Result<Option<(i32, bool)>> {
     // Search for next initialized tick across all provided tick arrays.
     // For zero_for_one (going left): search in the array containing current tick first,
     // then continue to arrays to the left.
@@ -496,7 +803,15 @@ fn find_next_initialized_tick<'a>(
         [tick_array_0, tick_array_1, tick_array_2]
     };
 
-    let mut boundary = current_tick;
+    // Going left the current tick itself may still be crossed (it is <= current price).
+    // Going right it has already been crossed, so start at the next spaced tick above it.
+    let first_candidate = if zero_for_one {
+        current_tick
+    } else {
+        current_tick.div_euclid(tick_spacing as i32) * tick_spacing as i32 + tick_spacing as i32
+    };
+
+    let mut boundary = None;
 
     for array_loader in arrays.iter() {
         let array = array_loader.load()?;
@@ -507,10 +822,10 @@ fn find_next_initialized_tick<'a>(
 
         let should_search = if zero_for_one {
             // Search if array covers ticks <= current_tick
-            array.start_tick_index <= current_tick
+            array.start_tick_index <= first_candidate
         } else {
-            // Search if array covers ticks >= current_tick
-            array_end > current_tick
+            // Search if array covers ticks above current_tick
+            array_end > first_candidate
         };
 
         if !should_search {
@@ -520,10 +835,10 @@ fn find_next_initialized_tick<'a>(
         // Determine the search start tick within this array
         let search_from = if zero_for_one {
             // Start from current_tick or array end (whichever is smaller)
-            current_tick.min(array_end - tick_spacing as i32)
+            first_candidate.min(array_end - tick_spacing as i32)
         } else {
-            // Start from current_tick or array start (whichever is larger)
-            current_tick.max(array.start_tick_index)
+            // Start from the next tick above current or array start (whichever is larger)
+            first_candidate.max(array.start_tick_index)
         };
 
         if array.is_tick_in_array(search_from, tick_spacing) {
@@ -534,20 +849,20 @@ fn find_next_initialized_tick<'a>(
             )?;
 
             if initialized {
-                return Ok((next_tick, true));
+                return Ok(Some((next_tick, true)));
             }
 
             // Update boundary to this array's boundary
-            boundary = if zero_for_one {
+            boundary = Some(if zero_for_one {
                 array.start_tick_index
             } else {
                 array_end - tick_spacing as i32
-            };
+            });
         }
     }
 
     // No initialized tick found in any array, return boundary
-    Ok((boundary, false))
+    Ok(boundary.map(|boundary| (boundary, false)))
 }
 
 /// Cross a tick and return the liquidity_net to apply
@@ -560,6 +875,7 @@ fn cross_tick<'a>(
     fee_growth_global_a: u128,
     fee_growth_global_b: u128,
     current_fee_growth: u128,
+    cumulatives: &Observation,
     zero_for_one: bool,
 ) -> Result<i128> {
     // Compute fee values for crossing
@@ -574,7 +890,7 @@ fn cross_tick<'a>(
         let mut array = tick_array_0.load_mut()?;
         if array.is_tick_in_array(tick_index, tick_spacing) {
             let tick = array.get_tick_mut(tick_index, tick_spacing)?;
-            tick.cross(fee_a, fee_b);
+            tick.cross(fee_a, fee_b, cumulatives);
             return Ok(tick.liquidity_net);
         }
     }
@@ -584,7 +900,7 @@ fn cross_tick<'a>(
         let mut array = tick_array_1.load_mut()?;
         if array.is_tick_in_array(tick_index, tick_spacing) {
             let tick = array.get_tick_mut(tick_index, tick_spacing)?;
-            tick.cross(fee_a, fee_b);
+            tick.cross(fee_a, fee_b, cumulatives);
             return Ok(tick.liquidity_net);
         }
     }
@@ -594,7 +910,7 @@ fn cross_tick<'a>(
         let mut array = tick_array_2.load_mut()?;
         if array.is_tick_in_array(tick_index, tick_spacing) {
             let tick = array.get_tick_mut(tick_index, tick_spacing)?;
-            tick.cross(fee_a, fee_b);
+            tick.cross(fee_a, fee_b, cumulatives);
             return Ok(tick.liquidity_net);
         }
     }
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::state::{Pool, AllowedTrader};
+use crate::errors::SuniswapError;
+
+/// Reject a swap or deposit by `wallet` in an allowlisted pool unless
+/// `allowed_trader` is the wallet's entry for the pool
+///
+/// Only entry points call this: decreasing, collecting and closing stay open
+/// to every position owner, so removing a wallet never traps its funds
+pub(crate) fn check_trader_access(
+    pool: &Pool,
+    pool_key: &Pubkey,
+    allowed_trader: Option<&Account<AllowedTrader>>,
+    wallet: &Pubkey,
+) -> Result<()> {
+    if !pool.is_trader_allowlist_enabled() {
+        return Ok(());
+    }
+
+    let Some(allowed_trader) = allowed_trader else {
+        return err!(SuniswapError::TraderNotAllowed);
+    };
+    require!(
+        allowed_trader.pool == *pool_key && allowed_trader.wallet == *wallet,
+        SuniswapError::TraderNotAllowed
+    );
+    Ok(())
+}
@@ -0,0 +1,224 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenAccount, TokenInterface, Mint, TransferChecked, transfer_checked};
+use crate::state::{Pool, Position, TickArray, FeeTier};
+use crate::constants::{seeds, position_permissions};
+use crate::errors::SuniswapError;
+use crate::events::{FeesCollectedEvent, PositionClosedEvent};
+use crate::instructions::close_position::close_position_account;
+use crate::instructions::collect_fees::{settle_position_fees, validate_payout_accounts};
+use crate::instructions::deadline::check_deadline;
+use crate::instructions::decrease_liquidity::remove_liquidity;
+
+/// Remove liquidity and pay out principal and fees in one instruction
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    /// The pool (zero-copy)
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// The fee tier for this pool
+    pub fee_tier: Account<'info, FeeTier>,
+
+    /// The position to withdraw from (zero-copy)
+    #[account(mut)]
+    pub position: AccountLoader<'info, Position>,
+
+    /// Tick array containing lower tick (zero-copy)
+    #[account(mut)]
+    pub tick_array_lower: AccountLoader<'info, TickArray>,
+
+    /// Tick array containing upper tick (zero-copy)
+    #[account(mut)]
+    pub tick_array_upper: AccountLoader<'info, TickArray>,
+
+    /// Token A mint
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+
+    /// Token B mint
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    /// Pool vault for token A
+    #[account(mut)]
+    pub token_vault_a: InterfaceAccount<'info, TokenAccount>,
+
+    /// Pool vault for token B
+    #[account(mut)]
+    pub token_vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    /// User's token A account
+    #[account(mut)]
+    pub user_token_a: InterfaceAccount<'info, TokenAccount>,
+
+    /// User's token B account
+    #[account(mut)]
+    pub user_token_b: InterfaceAccount<'info, TokenAccount>,
+
+    /// Position owner, or a delegate allowed to collect and decrease;
+    /// only the owner may close the position and receives its rent
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Token program
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Withdraw handler
+pub fn handler(
+    ctx: Context<Withdraw>,
+    liquidity_delta: u128,
+    amount_a_min: u64,
+    amount_b_min: u64,
+    close_position: bool,
+    deadline_unix_ts: Option<i64>,
+    max_slot: Option<u64>,
+) -> Result<()> {
+    check_deadline(deadline_unix_ts, max_slot)?;
+
+    let fee_tier = &ctx.accounts.fee_tier;
+    let pool_key = ctx.accounts.pool.key();
+    let authority = ctx.accounts.owner.key();
+
+    // Load and validate pool
+    let pool = ctx.accounts.pool.load()?;
+    require!(pool.is_paused == 0, SuniswapError::PoolPaused);
+    require!(
+        pool.fee_tier == fee_tier.key().to_bytes(),
+        SuniswapError::InvalidFeeTier
+    );
+    require!(
+        pool.token_mint_a == ctx.accounts.token_mint_a.key().to_bytes(),
+        SuniswapError::InvalidTokenMint
+    );
+    require!(
+        pool.token_mint_b == ctx.accounts.token_mint_b.key().to_bytes(),
+        SuniswapError::InvalidTokenMint
+    );
+    require!(
+        pool.token_vault_a == ctx.accounts.token_vault_a.key().to_bytes(),
+        SuniswapError::InvalidVault
+    );
+    require!(
+        pool.token_vault_b == ctx.accounts.token_vault_b.key().to_bytes(),
+        SuniswapError::InvalidVault
+    );
+
+    let pool_bump = pool.bump;
+    let token_mint_a_bytes = pool.token_mint_a;
+    let token_mint_b_bytes = pool.token_mint_b;
+    drop(pool);
+
+    if liquidity_delta > 0 {
+        // Credits the principal to the position and settles its fees
+        remove_liquidity(
+            &ctx.accounts.pool,
+            &ctx.accounts.position,
+            &ctx.accounts.tick_array_lower,
+            &ctx.accounts.tick_array_upper,
+            authority,
+            position_permissions::COLLECT | position_permissions::DECREASE,
+            liquidity_delta,
+            amount_a_min,
+            amount_b_min,
+        )?;
+    } else {
+        // Nothing to remove, only settle the fees earned so far
+        settle_position_fees(
+            &ctx.accounts.pool,
+            &ctx.accounts.position,
+            &ctx.accounts.tick_array_lower,
+            &ctx.accounts.tick_array_upper,
+            authority,
+            position_permissions::COLLECT,
+        )?;
+    }
+    validate_payout_accounts(
+        &ctx.accounts.position,
+        authority,
+        Some(&ctx.accounts.user_token_a),
+        Some(&ctx.accounts.user_token_b),
+    )?;
+
+    // Pay out everything owed: the principal just removed plus all fees
+    let mut position = ctx.accounts.position.load_mut()?;
+    let amount_a = position.tokens_owed_a;
+    let amount_b = position.tokens_owed_b;
+    position.tokens_owed_a = 0;
+    position.tokens_owed_b = 0;
+    let remaining_liquidity = position.liquidity;
+    let owner_key = position.owner_pubkey();
+    drop(position);
+
+    let pool_seeds: &[&[u8]] = &[
+        seeds::POOL_SEED,
+        &token_mint_a_bytes,
+        &token_mint_b_bytes,
+        &fee_tier.seed_bytes(),
+        &[pool_bump],
+    ];
+
+    if amount_a > 0 {
+        transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.token_vault_a.to_account_info(),
+                    mint: ctx.accounts.token_mint_a.to_account_info(),
+                    to: ctx.accounts.user_token_a.to_account_info(),
+                    authority: ctx.accounts.pool.to_account_info(),
+                },
+                &[pool_seeds],
+            ),
+            amount_a,
+            ctx.accounts.token_mint_a.decimals,
+        )?;
+    }
+
+    if amount_b > 0 {
+        transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.token_vault_b.to_account_info(),
+                    mint: ctx.accounts.token_mint_b.to_account_info(),
+                    to: ctx.accounts.user_token_b.to_account_info(),
+                    authority: ctx.accounts.pool.to_account_info(),
+                },
+                &[pool_seeds],
+            ),
+            amount_b,
+            ctx.accounts.token_mint_b.decimals,
+        )?;
+    }
+
+    emit!(FeesCollectedEvent {
+        pool: pool_key,
+        position: ctx.accounts.position.key(),
+        owner: owner_key,
+        amount_a,
+        amount_b,
+    });
+
+    msg!("Withdrawn: A={}, B={}", amount_a, amount_b);
+
+    if close_position {
+        require_keys_eq!(authority, owner_key, SuniswapError::InvalidPositionOwner);
+        require!(
+            remaining_liquidity == 0,
+            SuniswapError::PositionHasLiquidity
+        );
+        close_position_account(
+            &ctx.accounts.position.to_account_info(),
+            &ctx.accounts.owner.to_account_info(),
+        )?;
+
+        emit!(PositionClosedEvent {
+            pool: pool_key,
+            position: ctx.accounts.position.key(),
+            owner: owner_key,
+        });
+
+        msg!("Position closed");
+    }
+
+    Ok(())
+}
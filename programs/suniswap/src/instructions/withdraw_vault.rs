@@ -0,0 +1,259 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenAccount, TokenInterface, Mint, Burn, burn};
+use crate::state::{Pool, FeeTier, ManagedVault, Oracle};
+use crate::constants::seeds;
+use crate::errors::SuniswapError;
+use crate::events::VaultWithdrawEvent;
+use crate::instructions::decrease_liquidity::remove_liquidity;
+use crate::instructions::deposit_vault::{
+    check_vault_price, harvest_vault_positions, idle_amounts, load_vault_positions, take_owed_tokens,
+    transfer_signed,
+};
+use crate::math::full_math::mul_div;
+use crate::math::share_math::get_amount_for_shares;
+
+/// Burn vault shares for their part of every vault position and of the
+/// reserves, fees included
+/// If the pool has an oracle, its price must be near the TWAP
+///
+/// Remaining accounts: (position, tick_array_lower, tick_array_upper) for each
+/// vault position, in vault order, all writable
+#[derive(Accounts)]
+pub struct WithdrawVault<'info> {
+    /// The vault's pool (zero-copy)
+    #[account(mut)]
+    pub pool: AccountLoader<'info, Pool>,
+
+    /// The fee tier for this pool
+    pub fee_tier: Account<'info, FeeTier>,
+
+    /// The vault to withdraw from
+    #[account(
+        mut,
+        constraint = vault.pool == pool.key() @ SuniswapError::InvalidManagedVault,
+    )]
+    pub vault: Account<'info, ManagedVault>,
+
+    /// The vault's share mint
+    #[account(
+        mut,
+        constraint = share_mint.key() == vault.share_mint @ SuniswapError::InvalidTokenMint,
+    )]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+
+    /// Token A mint
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+
+    /// Token B mint
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    /// Pool vault for token A
+    #[account(mut)]
+    pub token_vault_a: InterfaceAccount<'info, TokenAccount>,
+
+    /// Pool vault for token B
+    #[account(mut)]
+    pub token_vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    /// Vault reserve for token A
+    #[account(
+        mut,
+        constraint = reserve_a.key() == vault.reserve_a @ SuniswapError::InvalidManagedVault,
+    )]
+    pub reserve_a: InterfaceAccount<'info, TokenAccount>,
+
+    /// Vault reserve for token B
+    #[account(
+        mut,
+        constraint = reserve_b.key() == vault.reserve_b @ SuniswapError::InvalidManagedVault,
+    )]
+    pub reserve_b: InterfaceAccount<'info, TokenAccount>,
+
+    /// User's token A account
+    #[account(mut)]
+    pub user_token_a: InterfaceAccount<'info, TokenAccount>,
+
+    /// User's token B account
+    #[account(mut)]
+    pub user_token_b: InterfaceAccount<'info, TokenAccount>,
+
+    /// User's share token account, shares are burned from it
+    #[account(mut)]
+    pub user_shares: InterfaceAccount<'info, TokenAccount>,
+
+    /// Share holder
+    pub user: Signer<'info>,
+
+    /// Token program
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The pool's oracle, required once the pool has one
+    pub oracle: Option<Account<'info, Oracle>>,
+}
+
+/// Withdraw from vault handler
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, WithdrawVault<'info>>,
+    shares: u64,
+    amount_a_min: u64,
+    amount_b_min: u64,
+) -> Result<()> {
+    require!(shares > 0, SuniswapError::ZeroShares);
+    let fee_tier = &ctx.accounts.fee_tier;
+    let pool_key = ctx.accounts.pool.key();
+    let vault_key = ctx.accounts.vault.key();
+
+    // Load and validate pool
+    let pool = ctx.accounts.pool.load()?;
+    require!(pool.is_paused == 0, SuniswapError::PoolPaused);
+    require!(
+        pool.fee_tier == fee_tier.key().to_bytes(),
+        SuniswapError::InvalidFeeTier
+    );
+    require!(
+        pool.token_mint_a == ctx.accounts.token_mint_a.key().to_bytes(),
+        SuniswapError::InvalidTokenMint
+    );
+    require!(
+        pool.token_mint_b == ctx.accounts.token_mint_b.key().to_bytes(),
+        SuniswapError::InvalidTokenMint
+    );
+    require!(
+        pool.token_vault_a == ctx.accounts.token_vault_a.key().to_bytes(),
+        SuniswapError::InvalidVault
+    );
+    require!(
+        pool.token_vault_b == ctx.accounts.token_vault_b.key().to_bytes(),
+        SuniswapError::InvalidVault
+    );
+    // Vaults on pools without an oracle can still be left, bounded by the
+    // amount minimums
+    let now = Clock::get()?.unix_timestamp;
+    if pool.oracle != [0u8; 32] {
+        check_vault_price(&pool, ctx.accounts.oracle.as_ref(), now as u32)?;
+    }
+
+    let pool_bump = pool.bump;
+    let token_mint_a_bytes = pool.token_mint_a;
+    let token_mint_b_bytes = pool.token_mint_b;
+    drop(pool);
+
+    // Fees go to the reserves first, where the withdrawal takes its share
+    let positions = load_vault_positions(&ctx.accounts.vault, ctx.remaining_accounts)?;
+    let (harvested_a, harvested_b) = harvest_vault_positions(&ctx.accounts.pool, &mut ctx.accounts.vault, &positions)?;
+
+    let pool_seeds: &[&[u8]] = &[
+        seeds::POOL_SEED,
+        &token_mint_a_bytes,
+        &token_mint_b_bytes,
+        &fee_tier.seed_bytes(),
+        &[pool_bump],
+    ];
+    let pool_info = ctx.accounts.pool.to_account_info();
+    transfer_signed(
+        &ctx.accounts.token_program,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.reserve_a,
+        pool_info.clone(),
+        pool_seeds,
+        harvested_a,
+    )?;
+    transfer_signed(
+        &ctx.accounts.token_program,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.token_mint_b,
+        &ctx.accounts.reserve_b,
+        pool_info.clone(),
+        pool_seeds,
+        harvested_b,
+    )?;
+    ctx.accounts.reserve_a.reload()?;
+    ctx.accounts.reserve_b.reload()?;
+
+    let minted = ctx.accounts.share_mint.supply;
+    let vault = &mut ctx.accounts.vault;
+    vault.accrue_management_fee(minted, now)?;
+    let share_supply = vault.share_supply(minted)?;
+    let (idle_a, idle_b) = idle_amounts(vault, &ctx.accounts.reserve_a, &ctx.accounts.reserve_b)?;
+    let vault_pool = vault.pool;
+    let vault_id_bytes = vault.id.to_le_bytes();
+    let vault_bump = vault.bump;
+
+    // Burn first so the user can't withdraw more shares than they hold
+    burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.share_mint.to_account_info(),
+                from: ctx.accounts.user_shares.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        shares,
+    )?;
+
+    // Remove the same share of every position's liquidity
+    let mut position_a: u64 = 0;
+    let mut position_b: u64 = 0;
+    for accounts in &positions {
+        let liquidity = accounts.position.load()?.liquidity;
+        let liquidity_delta = mul_div(liquidity, shares as u128, share_supply as u128)?;
+        if liquidity_delta == 0 {
+            continue;
+        }
+        remove_liquidity(
+            &ctx.accounts.pool,
+            &accounts.position,
+            &accounts.tick_array_lower,
+            &accounts.tick_array_upper,
+            vault_key,
+            0,
+            liquidity_delta,
+            0,
+            0,
+        )?;
+        let (amount_a, amount_b) = take_owed_tokens(pool_key, &accounts.position)?;
+        position_a = position_a.checked_add(amount_a).ok_or(SuniswapError::MathOverflow)?;
+        position_b = position_b.checked_add(amount_b).ok_or(SuniswapError::MathOverflow)?;
+    }
+
+    // And the same share of the reserves, fees included
+    let reserve_out_a = get_amount_for_shares(shares, idle_a, share_supply, false)?;
+    let reserve_out_b = get_amount_for_shares(shares, idle_b, share_supply, false)?;
+
+    let amount_a = position_a.checked_add(reserve_out_a).ok_or(SuniswapError::MathOverflow)?;
+    let amount_b = position_b.checked_add(reserve_out_b).ok_or(SuniswapError::MathOverflow)?;
+    require!(amount_a >= amount_a_min, SuniswapError::AmountABelowMin);
+    require!(amount_b >= amount_b_min, SuniswapError::AmountBBelowMin);
+
+    // Paid from the pool vaults for the principal and the reserves for the rest
+    let vault_seeds: &[&[u8]] = &[
+        seeds::MANAGED_VAULT_SEED,
+        vault_pool.as_ref(),
+        &vault_id_bytes,
+        &[vault_bump],
+    ];
+    let vault_info = ctx.accounts.vault.to_account_info();
+    let accounts = &ctx.accounts;
+    for (from, mint, to, authority, signer_seeds, amount) in [
+        (&accounts.token_vault_a, &accounts.token_mint_a, &accounts.user_token_a, &pool_info, pool_seeds, position_a),
+        (&accounts.token_vault_b, &accounts.token_mint_b, &accounts.user_token_b, &pool_info, pool_seeds, position_b),
+        (&accounts.reserve_a, &accounts.token_mint_a, &accounts.user_token_a, &vault_info, vault_seeds, reserve_out_a),
+        (&accounts.reserve_b, &accounts.token_mint_b, &accounts.user_token_b, &vault_info, vault_seeds, reserve_out_b),
+    ] {
+        transfer_signed(&accounts.token_program, from, mint, to, authority.clone(), signer_seeds, amount)?;
+    }
+
+    emit!(VaultWithdrawEvent {
+        vault: vault_key,
+        user: accounts.user.key(),
+        shares,
+        amount_a,
+        amount_b,
+    });
+
+    msg!("Vault withdraw: {} shares for A={}, B={}", shares, amount_a, amount_b);
+
+    Ok(())
+}
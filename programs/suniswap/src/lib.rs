@@ -22,6 +22,7 @@ use anchor_lang::prelude::*;
 
 pub mod constants;
 pub mod errors;
+pub mod events;
 pub mod hooks;
 pub mod instructions;
 pub mod math;
@@ -29,6 +30,7 @@ pub mod state;
 pub mod utils;
 
 use instructions::*;
+use state::ProtocolFeeRecipient;
 
 declare_id!("D3mEetFkLuB1sia8Bvvv2nmt9k6RsJPAGR2PE6tj7EFq");
 
@@ -66,19 +68,139 @@ pub mod suniswap {
         instructions::initialize_fee_tier::handler(ctx, fee_rate, tick_spacing)
     }
 
+    /// Change the fee rate of a fee tier
+    /// Applies to swaps on every pool of the tier; only protocol authority can call this
+    ///
+    /// # Arguments
+    /// * `fee_rate` - New fee rate in hundredths of a bip (3000 = 0.3%)
+    pub fn set_fee_tier_rate(ctx: Context<SetFeeTierRate>, fee_rate: u32) -> Result<()> {
+        instructions::set_fee_tier_rate::handler(ctx, fee_rate)
+    }
+
+    /// Disable or re-enable pool creation on a fee tier
+    /// Only protocol authority can call this
+    ///
+    /// # Arguments
+    /// * `disabled` - Whether `initialize_pool` is blocked for this tier
+    pub fn set_fee_tier_disabled(ctx: Context<SetFeeTierDisabled>, disabled: bool) -> Result<()> {
+        instructions::set_fee_tier_disabled::handler(ctx, disabled)
+    }
+
+    /// Give a fee tier created before tier enumeration its index entry
+    /// Only protocol authority can call this
+    pub fn register_fee_tier_index(ctx: Context<RegisterFeeTierIndex>) -> Result<()> {
+        instructions::register_fee_tier_index::handler(ctx)
+    }
+
+    /// Set the highest referral fee swaps may take
+    /// Only protocol authority can call this
+    ///
+    /// # Arguments
+    /// * `max_referral_fee_rate` - Cap in basis points, 0 to disable referral fees
+    pub fn set_max_referral_fee_rate(
+        ctx: Context<SetMaxReferralFeeRate>,
+        max_referral_fee_rate: u16,
+    ) -> Result<()> {
+        instructions::set_max_referral_fee_rate::handler(ctx, max_referral_fee_rate)
+    }
+
+    /// Set the highest creator fee share new pools may take
+    /// Only protocol authority can call this
+    ///
+    /// # Arguments
+    /// * `max_creator_fee_rate` - Cap in percent of swap fees, 0 to disable creator fees
+    pub fn set_max_creator_fee_rate(
+        ctx: Context<SetMaxCreatorFeeRate>,
+        max_creator_fee_rate: u8,
+    ) -> Result<()> {
+        instructions::set_max_creator_fee_rate::handler(ctx, max_creator_fee_rate)
+    }
+
+    /// Create the pool creation allowlist and fee, both empty
+    /// Only protocol authority can call this
+    pub fn initialize_pool_creation_config(ctx: Context<InitializePoolCreationConfig>) -> Result<()> {
+        instructions::initialize_pool_creation_config::handler(ctx)
+    }
+
+    /// Replace the keys allowed to create pools in allowlist mode
+    /// Only protocol authority can call this
+    ///
+    /// # Arguments
+    /// * `creators` - Up to 16 keys; checked against the pool creation payer
+    pub fn set_pool_creation_allowlist(
+        ctx: Context<SetPoolCreationAllowlist>,
+        creators: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_pool_creation_allowlist::handler(ctx, creators)
+    }
+
+    /// Set the fee charged for creating a pool in fee mode
+    /// Only protocol authority can call this
+    ///
+    /// # Arguments
+    /// * `fee_mint` - Token the fee is paid in, None for native SOL
+    /// * `fee_amount` - Fee in lamports or base units of `fee_mint`
+    /// * `treasury` - Wallet receiving the fee (its associated token account for tokens)
+    pub fn set_pool_creation_fee(
+        ctx: Context<SetPoolCreationFee>,
+        fee_mint: Option<Pubkey>,
+        fee_amount: u64,
+        treasury: Pubkey,
+    ) -> Result<()> {
+        instructions::set_pool_creation_fee::handler(ctx, fee_mint, fee_amount, treasury)
+    }
+
+    /// Switch who may create pools
+    /// Only protocol authority can call this
+    ///
+    /// # Arguments
+    /// * `pool_creation_mode` - 0 = anyone, 1 = allowlisted keys, 2 = anyone paying the creation fee
+    pub fn set_pool_creation_mode(
+        ctx: Context<SetPoolCreationMode>,
+        pool_creation_mode: u8,
+    ) -> Result<()> {
+        instructions::set_pool_creation_mode::handler(ctx, pool_creation_mode)
+    }
+
     // ═══════════════════════════════════════════════════════════════════════════
     // POOL INSTRUCTIONS
     // ═══════════════════════════════════════════════════════════════════════════
 
     /// Initialize a new liquidity pool
+    /// Open to anyone, to allowlisted payers, or to payers of the creation fee,
+    /// per the config's pool creation mode
     ///
     /// # Arguments
     /// * `initial_sqrt_price_x64` - Initial sqrt(price) in Q64.64 format
+    /// * `creator` - Optional wallet that collects the creator's share of swap fees
+    /// * `creator_fee_rate` - Creator's share of swap fees in percent, up to the config cap
     pub fn initialize_pool(
         ctx: Context<InitializePool>,
         initial_sqrt_price_x64: u128,
+        creator: Option<Pubkey>,
+        creator_fee_rate: u8,
     ) -> Result<()> {
-        instructions::initialize_pool::handler(ctx, initial_sqrt_price_x64)
+        instructions::initialize_pool::handler(ctx, initial_sqrt_price_x64, creator, creator_fee_rate)
+    }
+
+    /// Collect accumulated creator fees from a pool
+    /// Only callable by the pool's creator
+    ///
+    /// # Arguments
+    /// * `amount_a_requested` - Maximum amount of token A to collect
+    /// * `amount_b_requested` - Maximum amount of token B to collect
+    pub fn collect_creator_fees(
+        ctx: Context<CollectCreatorFees>,
+        amount_a_requested: u64,
+        amount_b_requested: u64,
+    ) -> Result<()> {
+        instructions::collect_creator_fees::handler(ctx, amount_a_requested, amount_b_requested)
+    }
+
+    /// Grow a pool created at the legacy 384-byte layout to the current size
+    /// Open to anyone; the payer covers the extra rent
+    pub fn migrate_pool(ctx: Context<MigratePool>) -> Result<()> {
+        instructions::migrate_pool::handler(ctx)
     }
 
     /// Initialize a tick array for a pool
@@ -92,6 +214,98 @@ pub mod suniswap {
         instructions::initialize_tick_array::handler(ctx, start_tick_index)
     }
 
+    /// Override a pool's fee rate
+    /// Only protocol authority can call this
+    ///
+    /// # Arguments
+    /// * `fee_rate` - Fee rate to charge instead of the tier's, or None to follow the tier again
+    pub fn set_pool_fee_rate(ctx: Context<SetPoolFeeRate>, fee_rate: Option<u32>) -> Result<()> {
+        instructions::set_pool_fee_rate::handler(ctx, fee_rate)
+    }
+
+    /// Configure a pool's adaptive (volatility-based) fee
+    /// Only protocol authority can call this
+    ///
+    /// # Arguments
+    /// * `params` - Fee bounds and volatility settings, or None to disable
+    pub fn set_pool_adaptive_fee(
+        ctx: Context<SetPoolAdaptiveFee>,
+        params: Option<AdaptiveFeeParams>,
+    ) -> Result<()> {
+        instructions::set_pool_adaptive_fee::handler(ctx, params)
+    }
+
+    /// Cap a pool's price movement per swap and per slot
+    /// Only protocol authority can call this
+    ///
+    /// # Arguments
+    /// * `max_tick_move_per_swap` - Most ticks one swap may move the price (0 = unlimited)
+    /// * `max_tick_move_per_slot` - Most ticks the price may move within a slot (0 = unlimited)
+    pub fn set_pool_circuit_breaker(
+        ctx: Context<SetPoolCircuitBreaker>,
+        max_tick_move_per_swap: u32,
+        max_tick_move_per_slot: u32,
+    ) -> Result<()> {
+        instructions::set_pool_circuit_breaker::handler(ctx, max_tick_move_per_swap, max_tick_move_per_slot)
+    }
+
+    /// Create a pool's TWAP oracle; every later swap on the pool must pass it
+    /// Only protocol authority can call this
+    ///
+    /// # Arguments
+    /// * `twap_window` - Seconds the TWAP deviation guard averages over
+    pub fn initialize_oracle(ctx: Context<InitializeOracle>, twap_window: u32) -> Result<()> {
+        instructions::initialize_oracle::handler(ctx, twap_window)
+    }
+
+    /// Set how far swaps may leave a pool's price from its oracle TWAP
+    /// Only protocol authority can call this
+    ///
+    /// # Arguments
+    /// * `twap_window` - Seconds the TWAP is averaged over
+    /// * `max_twap_deviation_ticks` - Most ticks a swap may leave the price from the TWAP (0 = only when a swap asks)
+    pub fn set_pool_twap_guard(
+        ctx: Context<SetPoolTwapGuard>,
+        twap_window: u32,
+        max_twap_deviation_ticks: u32,
+    ) -> Result<()> {
+        instructions::set_pool_twap_guard::handler(ctx, twap_window, max_twap_deviation_ticks)
+    }
+
+    /// Create a pool's trader allowlist, empty and switched off
+    /// Only protocol authority can call this
+    ///
+    /// # Arguments
+    /// * `admin` - Wallet that manages the allowlist
+    pub fn initialize_trader_allowlist(ctx: Context<InitializeTraderAllowlist>, admin: Pubkey) -> Result<()> {
+        instructions::initialize_trader_allowlist::handler(ctx, admin)
+    }
+
+    /// Switch a pool's trader allowlist on or off, or hand it to a new admin
+    /// Only the allowlist's admin can call this
+    ///
+    /// # Arguments
+    /// * `admin` - Wallet that manages the allowlist from now on
+    /// * `enabled` - Whether only allowed wallets may swap and add liquidity
+    pub fn set_trader_allowlist(ctx: Context<SetTraderAllowlist>, admin: Pubkey, enabled: bool) -> Result<()> {
+        instructions::set_trader_allowlist::handler(ctx, admin, enabled)
+    }
+
+    /// Allow a wallet to swap and add liquidity in an allowlisted pool
+    /// Only the allowlist's admin can call this
+    ///
+    /// # Arguments
+    /// * `wallet` - Wallet to allow
+    pub fn add_allowed_trader(ctx: Context<AddAllowedTrader>, wallet: Pubkey) -> Result<()> {
+        instructions::add_allowed_trader::handler(ctx, wallet)
+    }
+
+    /// Remove a wallet from a pool's trader allowlist
+    /// Only the allowlist's admin can call this
+    pub fn remove_allowed_trader(ctx: Context<RemoveAllowedTrader>) -> Result<()> {
+        instructions::remove_allowed_trader::handler(ctx)
+    }
+
     // ═══════════════════════════════════════════════════════════════════════════
     // POSITION INSTRUCTIONS
     // ═══════════════════════════════════════════════════════════════════════════
@@ -109,24 +323,56 @@ pub mod suniswap {
         instructions::open_position::handler(ctx, tick_lower, tick_upper)
     }
 
+    /// Open a position and deposit by token amounts in one instruction
+    /// Creates the tick arrays holding the bounds if they do not exist yet
+    ///
+    /// # Arguments
+    /// * `tick_lower` - Lower tick bound of position
+    /// * `tick_upper` - Upper tick bound of position
+    /// * `params` - Maximum amounts and the sqrt price band the deposit accepts
+    pub fn open_position_with_liquidity(
+        ctx: Context<OpenPositionWithLiquidity>,
+        tick_lower: i32,
+        tick_upper: i32,
+        params: IncreaseLiquidityByAmountsParams,
+    ) -> Result<()> {
+        instructions::open_position_with_liquidity::handler(ctx, tick_lower, tick_upper, params)
+    }
+
     /// Close an empty position and reclaim rent
     pub fn close_position(ctx: Context<ClosePosition>) -> Result<()> {
         instructions::close_position::handler(ctx)
     }
 
     /// Add liquidity to an existing position
+    /// Omitting the token account for a native SOL side pays it from the owner's lamports
     ///
     /// # Arguments
     /// * `liquidity_delta` - Amount of liquidity to add
     /// * `amount_a_max` - Maximum amount of token A to deposit
     /// * `amount_b_max` - Maximum amount of token B to deposit
+    /// * `deadline_unix_ts` - Optional unix timestamp after which the deposit is rejected
+    /// * `max_slot` - Optional slot after which the deposit is rejected
     pub fn increase_liquidity(
         ctx: Context<IncreaseLiquidity>,
         liquidity_delta: u128,
         amount_a_max: u64,
         amount_b_max: u64,
+        deadline_unix_ts: Option<i64>,
+        max_slot: Option<u64>,
     ) -> Result<()> {
-        instructions::increase_liquidity::handler(ctx, liquidity_delta, amount_a_max, amount_b_max)
+        instructions::increase_liquidity::handler(ctx, liquidity_delta, amount_a_max, amount_b_max, deadline_unix_ts, max_slot)
+    }
+
+    /// Add the most liquidity the given token amounts can fund at the current price
+    ///
+    /// # Arguments
+    /// * `params` - Maximum amounts, the sqrt price band and the deadline the deposit accepts
+    pub fn increase_liquidity_by_amounts(
+        ctx: Context<IncreaseLiquidity>,
+        params: IncreaseLiquidityByAmountsParams,
+    ) -> Result<()> {
+        instructions::increase_liquidity_by_amounts::handler(ctx, params)
     }
 
     /// Remove liquidity from an existing position
@@ -135,16 +381,21 @@ pub mod suniswap {
     /// * `liquidity_delta` - Amount of liquidity to remove
     /// * `amount_a_min` - Minimum amount of token A to receive
     /// * `amount_b_min` - Minimum amount of token B to receive
+    /// * `deadline_unix_ts` - Optional unix timestamp after which the removal is rejected
+    /// * `max_slot` - Optional slot after which the removal is rejected
     pub fn decrease_liquidity(
         ctx: Context<DecreaseLiquidity>,
         liquidity_delta: u128,
         amount_a_min: u64,
         amount_b_min: u64,
+        deadline_unix_ts: Option<i64>,
+        max_slot: Option<u64>,
     ) -> Result<()> {
-        instructions::decrease_liquidity::handler(ctx, liquidity_delta, amount_a_min, amount_b_min)
+        instructions::decrease_liquidity::handler(ctx, liquidity_delta, amount_a_min, amount_b_min, deadline_unix_ts, max_slot)
     }
 
     /// Collect accumulated fees from a position
+    /// Omitting the token account for a native SOL side pays it out as lamports
     ///
     /// # Arguments
     /// * `amount_a_requested` - Maximum amount of token A fees to collect
@@ -157,15 +408,112 @@ pub mod suniswap {
         instructions::collect_fees::handler(ctx, amount_a_requested, amount_b_requested)
     }
 
+    /// Remove liquidity and transfer the principal and all fees owed to the owner
+    ///
+    /// # Arguments
+    /// * `liquidity_delta` - Amount of liquidity to remove (may be 0 to only collect)
+    /// * `amount_a_min` - Minimum amount of token A principal to receive
+    /// * `amount_b_min` - Minimum amount of token B principal to receive
+    /// * `close_position` - Close the position afterwards, returning its rent to the owner
+    /// * `deadline_unix_ts` - Optional unix timestamp after which the withdrawal is rejected
+    /// * `max_slot` - Optional slot after which the withdrawal is rejected
+    pub fn withdraw(
+        ctx: Context<Withdraw>,
+        liquidity_delta: u128,
+        amount_a_min: u64,
+        amount_b_min: u64,
+        close_position: bool,
+        deadline_unix_ts: Option<i64>,
+        max_slot: Option<u64>,
+    ) -> Result<()> {
+        instructions::withdraw::handler(
+            ctx,
+            liquidity_delta,
+            amount_a_min,
+            amount_b_min,
+            close_position,
+            deadline_unix_ts,
+            max_slot,
+        )
+    }
+
+    /// Reinvest a position's fees and other owed tokens as liquidity
+    /// Adds as much liquidity as the owed amounts fund at the current price;
+    /// the remainder stays owed
+    pub fn compound_position(ctx: Context<CompoundPosition>) -> Result<()> {
+        instructions::compound_position::handler(ctx)
+    }
+
+    /// Move a position's liquidity and fees into a new tick range
+    /// Empties and closes the position, optionally swaps part of the proceeds
+    /// through the pool, then opens and funds the new range; leftovers are refunded
+    ///
+    /// # Arguments
+    /// * `tick_lower` - Lower tick bound of the new position
+    /// * `tick_upper` - Upper tick bound of the new position
+    /// * `params` - Rebalancing swap, the least liquidity to accept and the deadline
+    pub fn reposition(
+        ctx: Context<Reposition>,
+        tick_lower: i32,
+        tick_upper: i32,
+        params: RepositionParams,
+    ) -> Result<()> {
+        instructions::reposition::handler(ctx, tick_lower, tick_upper, params)
+    }
+
+    /// Move part of a position into a new position over the same range
+    /// Owed tokens are split in proportion to the liquidity moved
+    ///
+    /// # Arguments
+    /// * `liquidity_delta` - Liquidity to move into the new position
+    pub fn split_position(ctx: Context<SplitPosition>, liquidity_delta: u128) -> Result<()> {
+        instructions::split_position::handler(ctx, liquidity_delta)
+    }
+
+    /// Fold one position into another over the same range and close it
+    pub fn merge_positions(ctx: Context<MergePositions>) -> Result<()> {
+        instructions::merge_positions::handler(ctx)
+    }
+
+    /// Let a delegate act on a position; payouts still go to the owner
+    ///
+    /// # Arguments
+    /// * `delegate` - Account allowed to act on the position
+    /// * `permissions` - Bitmask of `constants::position_permissions`, 0 to revoke
+    pub fn set_position_delegate(
+        ctx: Context<SetPositionDelegate>,
+        delegate: Pubkey,
+        permissions: u8,
+    ) -> Result<()> {
+        instructions::set_position_delegate::handler(ctx, delegate, permissions)
+    }
+
+    /// Snapshot the oracle values accumulated inside a tick range
+    /// Read-only; the snapshot is returned as instruction return data
+    ///
+    /// # Arguments
+    /// * `tick_lower` - Lower tick bound of the range (must be initialized)
+    /// * `tick_upper` - Upper tick bound of the range (must be initialized)
+    pub fn snapshot_cumulatives_inside(
+        ctx: Context<SnapshotCumulativesInside>,
+        tick_lower: i32,
+        tick_upper: i32,
+    ) -> Result<CumulativesInside> {
+        instructions::snapshot_cumulatives_inside::handler(ctx, tick_lower, tick_upper)
+    }
+
     // ═══════════════════════════════════════════════════════════════════════════
     // SWAP INSTRUCTIONS
     // ═══════════════════════════════════════════════════════════════════════════
 
     /// Execute a swap on a pool
+    /// Omitting the input or output token account for native SOL wraps or
+    /// unwraps it within the instruction. A referral fee is paid to the
+    /// referrer token account in its token, the input or output
     ///
     /// # Arguments
-    /// * `params` - Swap parameters including amount, direction, and slippage limits
-    pub fn swap(ctx: Context<Swap>, params: SwapParams) -> Result<()> {
+    /// * `params` - Swap parameters including amount, direction, slippage limits, referral fee and deadline
+    pub fn swap<'info>(ctx: Context<'_, '_, '_, 'info, Swap<'info>>, params: SwapParams) -> Result<()> {
         instructions::swap::handler(ctx, params)
     }
 
@@ -186,6 +534,128 @@ pub mod suniswap {
     ) -> Result<()> {
         instructions::collect_protocol_fees::handler(ctx, amount_a_requested, amount_b_requested)
     }
+
+    /// Create the split `distribute_protocol_fees` pays protocol fees out by
+    /// Only callable by fee authority
+    ///
+    /// # Arguments
+    /// * `recipients` - Wallets and their weights in basis points, summing to 10000
+    pub fn initialize_protocol_fee_config(
+        ctx: Context<InitializeProtocolFeeConfig>,
+        recipients: Vec<ProtocolFeeRecipient>,
+    ) -> Result<()> {
+        instructions::initialize_protocol_fee_config::handler(ctx, recipients)
+    }
+
+    /// Replace the protocol fee recipients and their weights
+    /// Only callable by fee authority
+    ///
+    /// # Arguments
+    /// * `recipients` - Wallets and their weights in basis points, summing to 10000
+    pub fn set_protocol_fee_recipients(
+        ctx: Context<SetProtocolFeeRecipients>,
+        recipients: Vec<ProtocolFeeRecipient>,
+    ) -> Result<()> {
+        instructions::set_protocol_fee_recipients::handler(ctx, recipients)
+    }
+
+    /// Sweep all of a pool's protocol fees to the recipients' associated
+    /// token accounts by weight
+    /// Permissionless
+    pub fn distribute_protocol_fees<'info>(
+        ctx: Context<'_, '_, '_, 'info, DistributeProtocolFees<'info>>,
+    ) -> Result<()> {
+        instructions::distribute_protocol_fees::handler(ctx)
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // MANAGED VAULT INSTRUCTIONS
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    /// Create a managed vault over a pool with its share mint and reserves
+    /// Only callable by protocol authority
+    ///
+    /// # Arguments
+    /// * `id` - Distinguishes vaults on the same pool
+    /// * `params` - Strategist, treasury, fee rates and tick limits
+    pub fn initialize_vault(ctx: Context<InitializeVault>, id: u16, params: VaultParams) -> Result<()> {
+        instructions::initialize_vault::handler(ctx, id, params)
+    }
+
+    /// Update a vault's strategist, treasury, fee rates and tick limits
+    /// Only callable by protocol authority
+    pub fn set_vault_params(ctx: Context<SetVaultParams>, params: VaultParams) -> Result<()> {
+        instructions::set_vault_params::handler(ctx, params)
+    }
+
+    /// Open an empty position owned by a vault
+    /// Only callable by the vault strategist
+    ///
+    /// # Arguments
+    /// * `tick_lower` - Lower tick bound, within the vault's limits
+    /// * `tick_upper` - Upper tick bound, within the vault's limits
+    pub fn open_vault_position(
+        ctx: Context<OpenVaultPosition>,
+        tick_lower: i32,
+        tick_upper: i32,
+    ) -> Result<()> {
+        instructions::open_vault_position::handler(ctx, tick_lower, tick_upper)
+    }
+
+    /// Move liquidity between a vault position and the vault reserves
+    /// Only callable by the vault strategist
+    ///
+    /// # Arguments
+    /// * `params` - Liquidity to remove, then reserves to deploy
+    pub fn rebalance_vault_position(
+        ctx: Context<RebalanceVaultPosition>,
+        params: RebalanceVaultParams,
+    ) -> Result<()> {
+        instructions::rebalance_vault_position::handler(ctx, params)
+    }
+
+    /// Close an empty vault position
+    /// Only callable by the vault strategist
+    pub fn close_vault_position(ctx: Context<CloseVaultPosition>) -> Result<()> {
+        instructions::close_vault_position::handler(ctx)
+    }
+
+    /// Deposit tokens into a vault for shares, in the vault's current ratio
+    /// Remaining accounts list each vault position with its tick arrays
+    ///
+    /// # Arguments
+    /// * `amount_a_max` - Maximum amount of token A to deposit
+    /// * `amount_b_max` - Maximum amount of token B to deposit
+    /// * `shares_min` - Least shares to accept; a vault's first deposit locks `MINIMUM_SHARES` of them
+    pub fn deposit_vault<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DepositVault<'info>>,
+        amount_a_max: u64,
+        amount_b_max: u64,
+        shares_min: u64,
+    ) -> Result<()> {
+        instructions::deposit_vault::handler(ctx, amount_a_max, amount_b_max, shares_min)
+    }
+
+    /// Burn vault shares for their part of the vault, fees included
+    /// Remaining accounts list each vault position with its tick arrays
+    ///
+    /// # Arguments
+    /// * `shares` - Shares to burn
+    /// * `amount_a_min` - Minimum amount of token A to receive
+    /// * `amount_b_min` - Minimum amount of token B to receive
+    pub fn withdraw_vault<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawVault<'info>>,
+        shares: u64,
+        amount_a_min: u64,
+        amount_b_min: u64,
+    ) -> Result<()> {
+        instructions::withdraw_vault::handler(ctx, shares, amount_a_min, amount_b_min)
+    }
+
+    /// Pay a vault's accrued fees to its treasury
+    pub fn collect_vault_fees(ctx: Context<CollectVaultFees>) -> Result<()> {
+        instructions::collect_vault_fees::handler(ctx)
+    }
 }
 
 #[cfg(test)]
@@ -13,6 +13,16 @@ pub const Q64: u128 = 1u128 << 64;
 /// 2^128 = 340282366920938463463374607431768211456
 pub const Q128: u128 = u128::MAX / 2 + 1;
 
+// Declared in its own module so the macro expansion does not see Anchor's Result alias
+#[allow(clippy::manual_div_ceil)]
+mod u256 {
+    uint::construct_uint! {
+        /// 256-bit unsigned integer for intermediates that can exceed 128 bits
+        pub struct U256(4);
+    }
+}
+pub use u256::U256;
+
 /// Multiply two u128 numbers and divide by a third, with full precision
 /// Handles cases where intermediate value would overflow u128
 ///
@@ -31,47 +41,19 @@ pub fn mul_div(a: u128, b: u128, denominator: u128) -> Result<u128> {
         return Err(SuniswapError::DivisionByZero.into());
     }
 
-    // Split into high and low 64-bit parts
-    let a_hi = a >> 64;
-    let a_lo = a & ((1u128 << 64) - 1);
-    let b_hi = b >> 64;
-    let b_lo = b & ((1u128 << 64) - 1);
-
-    // Compute partial products
-    let p0 = a_lo * b_lo;
-    let p1 = a_lo * b_hi;
-    let p2 = a_hi * b_lo;
-    let p3 = a_hi * b_hi;
-
-    // Sum the partial products
-    let carry = (((p0 >> 64) + (p1 & ((1u128 << 64) - 1)) + (p2 & ((1u128 << 64) - 1))) >> 64) as u128;
-    let mid = ((p0 >> 64) + p1 + p2) & ((1u128 << 64) - 1);
-
-    let result_lo = (p0 & ((1u128 << 64) - 1)) | (mid << 64);
-    let result_hi = p3 + (p1 >> 64) + (p2 >> 64) + carry;
+    let (result_hi, result_lo) = mul_128(a, b)?;
 
     // If result_hi is 0, we can do simple division
     if result_hi == 0 {
-        return result_lo.checked_div(denominator)
-            .ok_or(SuniswapError::DivisionByZero.into());
+        return Ok(result_lo / denominator);
     }
 
-    // Full 256-bit division
-    // This is a simplified version - for production, use a proper bigint library
+    // The quotient only fits in 128 bits if the high word is below the denominator
     if result_hi >= denominator {
         return Err(SuniswapError::MulDivOverflow.into());
     }
 
-    // Newton-Raphson division approximation for 256/128
-    let mut quotient = div_256_by_128(result_hi, result_lo, denominator)?;
-
-    // Verify and adjust
-    let product = mul_128(quotient, denominator)?;
-    if product.0 > result_hi || (product.0 == result_hi && product.1 > result_lo) {
-        quotient = quotient.saturating_sub(1);
-    }
-
-    Ok(quotient)
+    div_256_by_128(result_hi, result_lo, denominator)
 }
 
 /// Multiply two u128 numbers and divide by a third, rounding up
@@ -122,9 +104,12 @@ fn div_256_by_128(hi: u128, lo: u128, denominator: u128) -> Result<u128> {
     let mut quotient = 0u128;
 
     for i in (0..128).rev() {
+        // The remainder is below the denominator but may still use the top bit,
+        // in which case the shifted value exceeds 128 bits and always divides
+        let carry = remainder >> 127;
         remainder = (remainder << 1) | ((lo >> i) & 1);
-        if remainder >= denominator {
-            remainder -= denominator;
+        if carry == 1 || remainder >= denominator {
+            remainder = remainder.wrapping_sub(denominator);
             quotient |= 1u128 << i;
         }
     }
@@ -194,4 +179,51 @@ mod tests {
     fn test_mul_div_zero_denominator() {
         assert!(mul_div(10, 20, 0).is_err());
     }
+
+    mod properties {
+        use super::*;
+        use num_bigint::BigUint;
+        use proptest::prelude::*;
+
+        /// (a * b) / d computed with arbitrary precision, None if it does not fit in u128
+        fn reference(a: u128, b: u128, d: u128, round_up: bool) -> Option<u128> {
+            let product = BigUint::from(a) * BigUint::from(b);
+            let d = BigUint::from(d);
+            let mut quotient = &product / &d;
+            if round_up && &quotient * &d != product {
+                quotient += 1u32;
+            }
+            u128::try_from(quotient).ok()
+        }
+
+        /// Mix of full-range values and values near the 64 and 128 bit boundaries
+        fn operand() -> impl Strategy<Value = u128> {
+            prop_oneof![
+                any::<u128>(),
+                any::<u64>().prop_map(u128::from),
+                (0u32..128).prop_map(|shift| 1u128 << shift),
+                (0u32..128, 0u128..4).prop_map(|(shift, delta)| (u128::MAX >> shift).saturating_sub(delta)),
+            ]
+        }
+
+        proptest! {
+            #[test]
+            fn mul_div_matches_big_int(a in operand(), b in operand(), d in operand()) {
+                prop_assume!(d > 0);
+                match reference(a, b, d, false) {
+                    Some(expected) => prop_assert_eq!(mul_div(a, b, d).unwrap(), expected),
+                    None => prop_assert!(mul_div(a, b, d).is_err()),
+                }
+            }
+
+            #[test]
+            fn mul_div_round_up_matches_big_int(a in operand(), b in operand(), d in operand()) {
+                prop_assume!(d > 0);
+                match reference(a, b, d, true) {
+                    Some(expected) => prop_assert_eq!(mul_div_round_up(a, b, d).unwrap(), expected),
+                    None => prop_assert!(mul_div_round_up(a, b, d).is_err()),
+                }
+            }
+        }
+    }
 }
@@ -3,7 +3,7 @@
 //! Functions for computing liquidity deltas and token amounts.
 
 use crate::errors::SuniswapError;
-use crate::math::full_math::{mul_div, mul_div_round_up, Q64};
+use crate::math::full_math::{mul_div, mul_div_round_up, Q64, U256};
 use anchor_lang::prelude::*;
 
 /// Add a signed liquidity delta to existing liquidity
@@ -42,23 +42,34 @@ pub fn get_amount_a_delta(
 
     // Formula: amount_a = L * Q64 * (sp_upper - sp_lower) / (sp_upper * sp_lower)
     //
-    // Since sp_upper * sp_lower overflows u128 (~2^64 * 2^64 = 2^128), we split into two steps:
-    // step1 = L * (sp_upper - sp_lower) / sp_upper
-    // step2 = step1 * Q64 / sp_lower
-    //
-    // This gives: L * (sp_upper - sp_lower) * Q64 / (sp_upper * sp_lower)
+    // Since sp_upper * sp_lower overflows u128 (~2^64 * 2^64 = 2^128), we split into two steps.
+    // Dividing by sp_upper and then by sp_lower rounds exactly like a single division,
+    // as long as the Q64 factor is folded into the first step:
+    // step1 = (L << 64) * (sp_upper - sp_lower) / sp_upper
+    // step2 = step1 / sp_lower
     let diff = sqrt_price_upper - sqrt_price_lower;
 
-    let intermediate = if round_up {
-        mul_div_round_up(liquidity, diff, sqrt_price_upper)?
-    } else {
-        mul_div(liquidity, diff, sqrt_price_upper)?
-    };
-
-    let result = if round_up {
-        mul_div_round_up(intermediate, Q64, sqrt_price_lower)?
+    let result = if liquidity <= u64::MAX as u128 {
+        let numerator = liquidity << 64;
+        if round_up {
+            let intermediate = mul_div_round_up(numerator, diff, sqrt_price_upper)?;
+            intermediate.div_ceil(sqrt_price_lower)
+        } else {
+            mul_div(numerator, diff, sqrt_price_upper)? / sqrt_price_lower
+        }
     } else {
-        mul_div(intermediate, Q64, sqrt_price_lower)?
+        // L << 64 no longer fits: apply the Q64 factor in the second step instead,
+        // which can be off by a few units in the rounding direction
+        let intermediate = if round_up {
+            mul_div_round_up(liquidity, diff, sqrt_price_upper)?
+        } else {
+            mul_div(liquidity, diff, sqrt_price_upper)?
+        };
+        if round_up {
+            mul_div_round_up(intermediate, Q64, sqrt_price_lower)?
+        } else {
+            mul_div(intermediate, Q64, sqrt_price_lower)?
+        }
     };
 
     if result > u64::MAX as u128 {
@@ -117,14 +128,21 @@ pub fn get_liquidity_for_amount_a(
         (sqrt_price_b_x64, sqrt_price_a_x64)
     };
 
-    // Formula: L = amount_a * sp_upper * sp_lower / (Q64 * (sp_upper - sp_lower))
+    // Formula: L = amount_a * (sp_upper * sp_lower / Q64) / (sp_upper - sp_lower)
     //
-    // Since sp_upper * sp_lower overflows u128, we split into two steps:
-    // step1 = amount_a * sp_upper / (sp_upper - sp_lower)
-    // step2 = step1 * sp_lower / Q64
-    let diff = sqrt_price_upper - sqrt_price_lower;
-    let intermediate = mul_div(amount_a as u128, sqrt_price_upper, diff)?;
-    mul_div(intermediate, sqrt_price_lower, Q64)
+    // sp_upper * sp_lower / Q64 can exceed u128 near the top of the price range,
+    // so the whole expression is evaluated in 256 bits with the same rounding as
+    // the Uniswap V3 LiquidityAmounts library
+    if sqrt_price_upper == sqrt_price_lower {
+        return Err(SuniswapError::DivisionByZero.into());
+    }
+    let intermediate = (U256::from(sqrt_price_lower) * U256::from(sqrt_price_upper)) >> 64;
+    let liquidity =
+        U256::from(amount_a) * intermediate / U256::from(sqrt_price_upper - sqrt_price_lower);
+    if liquidity > U256::from(u128::MAX) {
+        return Err(SuniswapError::MathOverflow.into());
+    }
+    Ok(liquidity.as_u128())
 }
 
 /// Calculate the liquidity amount for a given amount of token B
@@ -232,4 +250,70 @@ mod tests {
     fn test_add_liquidity_delta_underflow() {
         assert!(add_liquidity_delta(50, -100).is_err());
     }
+
+    mod properties {
+        use super::*;
+        use crate::constants::{MAX_TICK, MIN_TICK};
+        use crate::math::tick_math::get_sqrt_price_at_tick;
+        use num_bigint::BigUint;
+        use proptest::prelude::*;
+
+        /// Exact (floor, ceil) of numerator / denominator
+        fn floor_ceil(numerator: BigUint, denominator: BigUint) -> (BigUint, BigUint) {
+            let floor = &numerator / &denominator;
+            let ceil = if &floor * &denominator == numerator { floor.clone() } else { &floor + 1u32 };
+            (floor, ceil)
+        }
+
+        fn sqrt_price() -> impl Strategy<Value = u128> {
+            (MIN_TICK..=MAX_TICK).prop_map(|tick| get_sqrt_price_at_tick(tick).unwrap())
+        }
+
+        fn liquidity() -> impl Strategy<Value = u128> {
+            prop_oneof![0u128..1_000_000, any::<u64>().prop_map(u128::from), 0u128..(1u128 << 96)]
+        }
+
+        proptest! {
+            #[test]
+            fn amount_a_delta_brackets_exact_value(a in sqrt_price(), b in sqrt_price(), l in liquidity()) {
+                let (lower, upper) = if a <= b { (a, b) } else { (b, a) };
+                let (floor, ceil) = floor_ceil(
+                    (BigUint::from(l) * BigUint::from(upper - lower)) << 64,
+                    BigUint::from(upper) * BigUint::from(lower),
+                );
+                let down = get_amount_a_delta(a, b, l, false);
+                let up = get_amount_a_delta(a, b, l, true);
+                if let (Ok(down), Ok(up)) = (&down, &up) {
+                    if l <= u64::MAX as u128 {
+                        prop_assert_eq!(BigUint::from(*down), floor);
+                        prop_assert_eq!(BigUint::from(*up), ceil);
+                    } else {
+                        prop_assert!(BigUint::from(*down) <= floor);
+                        prop_assert!(BigUint::from(*up) >= ceil);
+                    }
+                } else {
+                    prop_assert!(ceil > BigUint::from(u64::MAX));
+                }
+                prop_assert_eq!(down.ok(), get_amount_a_delta(b, a, l, false).ok());
+            }
+
+            #[test]
+            fn amount_b_delta_rounds_exactly(a in sqrt_price(), b in sqrt_price(), l in liquidity()) {
+                let (lower, upper) = if a <= b { (a, b) } else { (b, a) };
+                let (floor, ceil) = floor_ceil(
+                    BigUint::from(l) * BigUint::from(upper - lower),
+                    BigUint::from(1u32) << 64,
+                );
+                match get_amount_b_delta(a, b, l, false) {
+                    Ok(down) => prop_assert_eq!(BigUint::from(down), floor),
+                    Err(_) => prop_assert!(floor > BigUint::from(u64::MAX)),
+                }
+                match get_amount_b_delta(a, b, l, true) {
+                    Ok(up) => prop_assert_eq!(BigUint::from(up), ceil),
+                    Err(_) => prop_assert!(ceil > BigUint::from(u64::MAX)),
+                }
+                prop_assert_eq!(get_amount_b_delta(a, b, l, true).ok(), get_amount_b_delta(b, a, l, true).ok());
+            }
+        }
+    }
 }
@@ -4,6 +4,7 @@ pub mod liquidity_math;
 pub mod sqrt_price_math;
 pub mod swap_math;
 pub mod bit_math;
+pub mod share_math;
 
 pub use full_math::*;
 pub use tick_math::*;
@@ -11,3 +12,4 @@ pub use liquidity_math::*;
 pub use sqrt_price_math::*;
 pub use swap_math::*;
 pub use bit_math::*;
+pub use share_math::*;
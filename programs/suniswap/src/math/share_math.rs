@@ -0,0 +1,113 @@
+//! Share Math
+//!
+//! Functions for converting between managed vault shares and token amounts.
+
+use crate::constants::managed_vault::{FEE_RATE_DENOMINATOR, SECONDS_PER_YEAR};
+use crate::errors::SuniswapError;
+use crate::math::full_math::{mul_div, mul_div_round_up};
+use anchor_lang::prelude::*;
+
+/// Shares minted for depositing up to `amount_a` and `amount_b` into a vault
+/// holding `total_a` and `total_b` against `share_supply` shares
+///
+/// The deposit is limited by whichever token it covers least, so the vault's
+/// ratio is kept; a token the vault holds none of doesn't limit it.
+/// The first deposit into an empty vault mints the larger amount as shares.
+pub fn get_shares_for_amounts(
+    amount_a: u64,
+    amount_b: u64,
+    total_a: u64,
+    total_b: u64,
+    share_supply: u64,
+) -> Result<u64> {
+    if share_supply == 0 {
+        return Ok(amount_a.max(amount_b));
+    }
+
+    let shares_a = if total_a > 0 {
+        Some(mul_div(amount_a as u128, share_supply as u128, total_a as u128)?)
+    } else {
+        None
+    };
+    let shares_b = if total_b > 0 {
+        Some(mul_div(amount_b as u128, share_supply as u128, total_b as u128)?)
+    } else {
+        None
+    };
+
+    let shares = match (shares_a, shares_b) {
+        (Some(a), Some(b)) => a.min(b),
+        (Some(a), None) => a,
+        (None, Some(b)) => b,
+        // Shares outstanding but nothing backing them
+        (None, None) => 0,
+    };
+    u64::try_from(shares).map_err(|_| SuniswapError::CastOverflow.into())
+}
+
+/// Amount of a token `shares` are worth out of a vault `total`
+/// Rounds up for deposits and down for withdrawals, in the vault's favour
+pub fn get_amount_for_shares(shares: u64, total: u64, share_supply: u64, round_up: bool) -> Result<u64> {
+    require!(share_supply > 0, SuniswapError::DivisionByZero);
+    let amount = if round_up {
+        mul_div_round_up(shares as u128, total as u128, share_supply as u128)?
+    } else {
+        mul_div(shares as u128, total as u128, share_supply as u128)?
+    };
+    u64::try_from(amount).map_err(|_| SuniswapError::CastOverflow.into())
+}
+
+/// Shares owed to the treasury for `elapsed` seconds of management fees at
+/// `fee_rate` basis points per year, charged on `share_supply`
+pub fn get_management_fee_shares(share_supply: u64, fee_rate: u16, elapsed: u64) -> Result<u64> {
+    let numerator = (share_supply as u128)
+        .checked_mul(fee_rate as u128)
+        .ok_or(SuniswapError::MathOverflow)?;
+    let shares = mul_div(
+        numerator,
+        elapsed as u128,
+        FEE_RATE_DENOMINATOR as u128 * SECONDS_PER_YEAR as u128,
+    )?;
+    u64::try_from(shares).map_err(|_| SuniswapError::CastOverflow.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_deposit_mints_larger_amount() {
+        assert_eq!(get_shares_for_amounts(1_000, 4_000, 0, 0, 0).unwrap(), 4_000);
+        assert_eq!(get_shares_for_amounts(0, 0, 0, 0, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_deposit_limited_by_scarcer_token() {
+        // Vault holds 1:2, so 100 A only matches 200 B
+        let shares = get_shares_for_amounts(100, 1_000, 1_000, 2_000, 500).unwrap();
+        assert_eq!(shares, 50);
+        assert_eq!(get_amount_for_shares(shares, 1_000, 500, true).unwrap(), 100);
+        assert_eq!(get_amount_for_shares(shares, 2_000, 500, true).unwrap(), 200);
+
+        // A token the vault holds none of is not needed
+        assert_eq!(get_shares_for_amounts(0, 100, 0, 1_000, 1_000).unwrap(), 100);
+        assert_eq!(get_shares_for_amounts(100, 100, 0, 0, 1_000).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_amount_for_shares_rounding() {
+        assert_eq!(get_amount_for_shares(1, 10, 3, false).unwrap(), 3);
+        assert_eq!(get_amount_for_shares(1, 10, 3, true).unwrap(), 4);
+        assert_eq!(get_amount_for_shares(3, 10, 3, false).unwrap(), 10);
+        assert!(get_amount_for_shares(1, 10, 0, false).is_err());
+    }
+
+    #[test]
+    fn test_management_fee_shares() {
+        // 2% a year on 1M shares
+        assert_eq!(get_management_fee_shares(1_000_000, 200, SECONDS_PER_YEAR).unwrap(), 20_000);
+        assert_eq!(get_management_fee_shares(1_000_000, 200, SECONDS_PER_YEAR / 2).unwrap(), 10_000);
+        assert_eq!(get_management_fee_shares(1_000_000, 200, 0).unwrap(), 0);
+        assert_eq!(get_management_fee_shares(0, 200, SECONDS_PER_YEAR).unwrap(), 0);
+    }
+}
@@ -3,7 +3,7 @@
 //! Functions for computing the next sqrt price given token deltas.
 
 use crate::errors::SuniswapError;
-use crate::math::full_math::{mul_div, mul_div_round_up, Q64};
+use crate::math::full_math::{mul_div, mul_div_round_up, Q64, U256};
 use anchor_lang::prelude::*;
 
 /// Get the next sqrt price after swapping a specified amount of token A
@@ -22,28 +22,44 @@ pub fn get_next_sqrt_price_from_amount_a_rounding_up(
         return Ok(sqrt_price_x64);
     }
 
-    // C-04 FIX: Use checked_shl to prevent overflow when liquidity > 2^64
-    let numerator = liquidity
-        .checked_shl(64)
-        .ok_or(SuniswapError::MathOverflow)?;
-    let product = (amount as u128)
-        .checked_mul(sqrt_price_x64)
-        .ok_or(SuniswapError::MathOverflow)?;
+    // L << 64 only fits while liquidity is below 2^64. Above that fall back to the
+    // equivalent L * Q64 / (L * Q64 / sqrt_price ± amount), rounding the inner
+    // quotient so that the result still rounds up
+    let numerator = (liquidity <= u64::MAX as u128).then_some(liquidity << 64);
+    let product = (amount as u128).checked_mul(sqrt_price_x64);
 
     if add {
         // Selling token A (price goes down)
-        let denominator = numerator
-            .checked_add(product)
+        if let Some(numerator) = numerator {
+            if let Some(denominator) = product.and_then(|product| numerator.checked_add(product)) {
+                return mul_div_round_up(numerator, sqrt_price_x64, denominator);
+            }
+            // The denominator exceeds 128 bits, so the result is well below sqrt_price
+            // and can be computed exactly in 256 bits
+            let denominator = U256::from(numerator) + U256::from(amount) * U256::from(sqrt_price_x64);
+            let (quotient, remainder) =
+                (U256::from(numerator) * U256::from(sqrt_price_x64)).div_mod(denominator);
+            let quotient = quotient.as_u128();
+            return Ok(if remainder.is_zero() { quotient } else { quotient + 1 });
+        }
+        let denominator = mul_div(liquidity, Q64, sqrt_price_x64)?
+            .checked_add(amount as u128)
             .ok_or(SuniswapError::MathOverflow)?;
-
-        mul_div_round_up(numerator, sqrt_price_x64, denominator)
+        mul_div_round_up(liquidity, Q64, denominator)
     } else {
         // Buying token A (price goes up)
-        if product >= numerator {
-            return Err(SuniswapError::InsufficientLiquidity.into());
+        if let Some(numerator) = numerator {
+            let product = product.ok_or(SuniswapError::InsufficientLiquidity)?;
+            if product >= numerator {
+                return Err(SuniswapError::InsufficientLiquidity.into());
+            }
+            return mul_div_round_up(numerator, sqrt_price_x64, numerator - product);
         }
-        let denominator = numerator - product;
-        mul_div_round_up(numerator, sqrt_price_x64, denominator)
+        let denominator = mul_div(liquidity, Q64, sqrt_price_x64)?
+            .checked_sub(amount as u128)
+            .filter(|denominator| *denominator > 0)
+            .ok_or(SuniswapError::InsufficientLiquidity)?;
+        mul_div_round_up(liquidity, Q64, denominator)
     }
 }
 
@@ -63,16 +79,17 @@ pub fn get_next_sqrt_price_from_amount_b_rounding_down(
         return Ok(sqrt_price_x64);
     }
 
-    // quotient = amount * 2^64 / liquidity
-    let quotient = mul_div(amount as u128, Q64, liquidity)?;
-
     if add {
         // Selling token B (price goes up)
+        // quotient = amount * 2^64 / liquidity
+        let quotient = mul_div(amount as u128, Q64, liquidity)?;
         sqrt_price_x64.checked_add(quotient)
             .ok_or(SuniswapError::SqrtPriceAboveMaximum.into())
     } else {
         // Buying token B (price goes down)
-        if quotient > sqrt_price_x64 {
+        // Round the quotient up so the price moves at least far enough to release `amount`
+        let quotient = mul_div_round_up(amount as u128, Q64, liquidity)?;
+        if quotient >= sqrt_price_x64 {
             return Err(SuniswapError::SqrtPriceBelowMinimum.into());
         }
         Ok(sqrt_price_x64 - quotient)
@@ -204,4 +221,68 @@ mod tests {
             sqrt_price
         );
     }
+
+    mod properties {
+        use super::*;
+        use crate::constants::{MAX_TICK, MIN_TICK};
+        use crate::math::liquidity_math::{get_amount_a_delta, get_amount_b_delta};
+        use crate::math::tick_math::get_sqrt_price_at_tick;
+        use proptest::prelude::*;
+
+        fn sqrt_price() -> impl Strategy<Value = u128> {
+            (MIN_TICK..=MAX_TICK).prop_map(|tick| get_sqrt_price_at_tick(tick).unwrap())
+        }
+
+        fn liquidity() -> impl Strategy<Value = u128> {
+            prop_oneof![1u128..1_000_000, any::<u64>().prop_map(u128::from), 1u128..(1u128 << 96)]
+        }
+
+        proptest! {
+            /// An exact input never moves the price further than the input pays for
+            #[test]
+            fn next_price_from_input_favors_pool(
+                sqrt_price in sqrt_price(),
+                liquidity in liquidity(),
+                amount in any::<u64>(),
+                zero_for_one in any::<bool>(),
+            ) {
+                let Ok(next) = get_next_sqrt_price_from_input(sqrt_price, liquidity, amount, zero_for_one) else {
+                    return Ok(());
+                };
+                let required = if zero_for_one {
+                    prop_assert!(next <= sqrt_price);
+                    get_amount_a_delta(next, sqrt_price, liquidity, true)
+                } else {
+                    prop_assert!(next >= sqrt_price);
+                    get_amount_b_delta(sqrt_price, next, liquidity, true)
+                };
+                if let Ok(required) = required {
+                    prop_assert!(required <= amount);
+                }
+            }
+
+            /// An exact output always moves the price at least far enough to release it
+            #[test]
+            fn next_price_from_output_favors_pool(
+                sqrt_price in sqrt_price(),
+                liquidity in liquidity(),
+                amount in any::<u64>(),
+                zero_for_one in any::<bool>(),
+            ) {
+                let Ok(next) = get_next_sqrt_price_from_output(sqrt_price, liquidity, amount, zero_for_one) else {
+                    return Ok(());
+                };
+                let released = if zero_for_one {
+                    prop_assert!(next <= sqrt_price);
+                    get_amount_b_delta(next, sqrt_price, liquidity, false)
+                } else {
+                    prop_assert!(next >= sqrt_price);
+                    get_amount_a_delta(sqrt_price, next, liquidity, false)
+                };
+                if let Ok(released) = released {
+                    prop_assert!(released >= amount);
+                }
+            }
+        }
+    }
 }
@@ -66,27 +66,37 @@ pub fn compute_swap_step(
             FEE_RATE_DENOMINATOR as u128,
         )? as u64;
 
-        // Calculate amount needed to reach target price
+        // Calculate amount needed to reach target price. This may not fit in a u64
+        // when the target is far away, in which case the remaining amount is limiting
         let amount_in_max = if zero_for_one {
-            get_amount_a_delta(sqrt_price_target_x64, sqrt_price_current_x64, liquidity, true)?
+            get_amount_a_delta(sqrt_price_target_x64, sqrt_price_current_x64, liquidity, true)
         } else {
-            get_amount_b_delta(sqrt_price_current_x64, sqrt_price_target_x64, liquidity, true)?
+            get_amount_b_delta(sqrt_price_current_x64, sqrt_price_target_x64, liquidity, true)
         };
 
         // Determine if we can reach the target or if amount is limiting
-        if amount_remaining_less_fee >= amount_in_max {
-            // Can reach target
-            sqrt_price_next_x64 = sqrt_price_target_x64;
-            amount_in = amount_in_max;
-        } else {
-            // Amount is limiting - calculate new sqrt price
-            sqrt_price_next_x64 = get_next_sqrt_price_from_input(
-                sqrt_price_current_x64,
-                liquidity,
-                amount_remaining_less_fee,
-                zero_for_one,
-            )?;
-            amount_in = amount_remaining_less_fee;
+        match amount_in_max {
+            Ok(amount_in_max) if amount_remaining_less_fee >= amount_in_max => {
+                // Can reach target
+                sqrt_price_next_x64 = sqrt_price_target_x64;
+                amount_in = amount_in_max;
+            }
+            _ => {
+                // Amount is limiting - calculate new sqrt price
+                sqrt_price_next_x64 = get_next_sqrt_price_from_input(
+                    sqrt_price_current_x64,
+                    liquidity,
+                    amount_remaining_less_fee,
+                    zero_for_one,
+                )?;
+                // The price rounds in the pool's favour, so the amount actually needed
+                // for the move can be slightly below what is available
+                amount_in = if zero_for_one {
+                    get_amount_a_delta(sqrt_price_next_x64, sqrt_price_current_x64, liquidity, true)?
+                } else {
+                    get_amount_b_delta(sqrt_price_current_x64, sqrt_price_next_x64, liquidity, true)?
+                };
+            }
         }
 
         // Calculate output amount
@@ -97,27 +107,36 @@ pub fn compute_swap_step(
         };
     } else {
         // Exact output
-        // Calculate maximum output available to target price
+        // Calculate maximum output available to target price, which may not fit in a u64
         let amount_out_max = if zero_for_one {
-            get_amount_b_delta(sqrt_price_target_x64, sqrt_price_current_x64, liquidity, false)?
+            get_amount_b_delta(sqrt_price_target_x64, sqrt_price_current_x64, liquidity, false)
         } else {
-            get_amount_a_delta(sqrt_price_current_x64, sqrt_price_target_x64, liquidity, false)?
+            get_amount_a_delta(sqrt_price_current_x64, sqrt_price_target_x64, liquidity, false)
         };
 
         // Determine if we can satisfy the output or if target is limiting
-        if amount_remaining_abs >= amount_out_max {
-            // Can reach target
-            sqrt_price_next_x64 = sqrt_price_target_x64;
-            amount_out = amount_out_max;
-        } else {
-            // Output amount is limiting - calculate new sqrt price
-            sqrt_price_next_x64 = get_next_sqrt_price_from_output(
-                sqrt_price_current_x64,
-                liquidity,
-                amount_remaining_abs,
-                zero_for_one,
-            )?;
-            amount_out = amount_remaining_abs;
+        match amount_out_max {
+            Ok(amount_out_max) if amount_remaining_abs >= amount_out_max => {
+                // Can reach target
+                sqrt_price_next_x64 = sqrt_price_target_x64;
+                amount_out = amount_out_max;
+            }
+            _ => {
+                // Output amount is limiting - calculate new sqrt price
+                sqrt_price_next_x64 = get_next_sqrt_price_from_output(
+                    sqrt_price_current_x64,
+                    liquidity,
+                    amount_remaining_abs,
+                    zero_for_one,
+                )?;
+                // Never pay out more than was requested
+                let amount_out_at_next = if zero_for_one {
+                    get_amount_b_delta(sqrt_price_next_x64, sqrt_price_current_x64, liquidity, false)?
+                } else {
+                    get_amount_a_delta(sqrt_price_current_x64, sqrt_price_next_x64, liquidity, false)?
+                };
+                amount_out = amount_out_at_next.min(amount_remaining_abs);
+            }
         }
 
         // Calculate input amount required
@@ -132,7 +151,7 @@ pub fn compute_swap_step(
     // For exact input: fee is the remaining amount minus what was used
     // For exact output: fee is calculated on top of amount_in
     let fee_amount = if exact_input && sqrt_price_next_x64 != sqrt_price_target_x64 {
-        // Didn't reach target, fee is remaining after amount_in
+        // Didn't reach target, the fee absorbs everything not used to move the price
         amount_remaining_abs.saturating_sub(amount_in)
     } else {
         // Reached target or exact output, calculate fee based on amount_in
@@ -170,6 +189,12 @@ pub fn calculate_protocol_fee(fee_amount: u64, protocol_fee_rate: u8) -> Result<
     u64::try_from(result).map_err(|_| SuniswapError::CastOverflow.into())
 }
 
+/// Calculate the pool creator's portion of total fees
+/// `creator_fee_rate` is a percentage like the protocol fee rate
+pub fn calculate_creator_fee(fee_amount: u64, creator_fee_rate: u8) -> Result<u64> {
+    calculate_protocol_fee(fee_amount, creator_fee_rate)
+}
+
 /// Calculate fee growth per unit of liquidity
 /// fee_growth = fee_amount * 2^128 / liquidity
 ///
@@ -227,4 +252,71 @@ mod tests {
         // Result should be at or between current and target
         assert!(result.sqrt_price_next_x64 > 0);
     }
+
+    mod properties {
+        use super::*;
+        use crate::constants::{MAX_TICK, MIN_TICK};
+        use crate::math::tick_math::get_sqrt_price_at_tick;
+        use proptest::prelude::*;
+
+        fn sqrt_price() -> impl Strategy<Value = u128> {
+            (MIN_TICK..=MAX_TICK).prop_map(|tick| get_sqrt_price_at_tick(tick).unwrap())
+        }
+
+        fn liquidity() -> impl Strategy<Value = u128> {
+            prop_oneof![1u128..1_000_000, any::<u64>().prop_map(u128::from), 1u128..(1u128 << 96)]
+        }
+
+        fn fee_rate() -> impl Strategy<Value = u32> {
+            prop_oneof![Just(0u32), Just(100), Just(500), Just(3000), Just(10_000), 0u32..=100_000]
+        }
+
+        proptest! {
+            #[test]
+            fn swap_step_respects_amount_remaining(
+                current in sqrt_price(),
+                target in sqrt_price(),
+                liquidity in liquidity(),
+                amount_remaining in any::<i64>(),
+                fee_rate in fee_rate(),
+            ) {
+                prop_assume!(amount_remaining != 0 && amount_remaining != i64::MIN);
+                let Ok(step) = compute_swap_step(current, target, liquidity, amount_remaining, fee_rate) else {
+                    return Ok(());
+                };
+                let zero_for_one = current >= target;
+
+                if amount_remaining > 0 {
+                    let total_in = step.amount_in as u128 + step.fee_amount as u128;
+                    prop_assert!(total_in <= amount_remaining as u128);
+                } else {
+                    prop_assert!(step.amount_out <= amount_remaining.unsigned_abs());
+                }
+
+                // The price moves toward the target without passing it
+                if zero_for_one {
+                    prop_assert!(step.sqrt_price_next_x64 <= current && step.sqrt_price_next_x64 >= target);
+                } else {
+                    prop_assert!(step.sqrt_price_next_x64 >= current && step.sqrt_price_next_x64 <= target);
+                }
+
+                // The pool never pays out more, or takes in less, than the price move implies
+                let (output_for_move, input_for_move) = if zero_for_one {
+                    (
+                        get_amount_b_delta(step.sqrt_price_next_x64, current, liquidity, false),
+                        get_amount_a_delta(step.sqrt_price_next_x64, current, liquidity, true),
+                    )
+                } else {
+                    (
+                        get_amount_a_delta(current, step.sqrt_price_next_x64, liquidity, false),
+                        get_amount_b_delta(current, step.sqrt_price_next_x64, liquidity, true),
+                    )
+                };
+                if let (Ok(output_for_move), Ok(input_for_move)) = (output_for_move, input_for_move) {
+                    prop_assert!(step.amount_out <= output_for_move);
+                    prop_assert!(step.amount_in >= input_for_move);
+                }
+            }
+        }
+    }
 }
@@ -277,8 +277,9 @@ pub fn get_tick_at_sqrt_price(sqrt_price_x64: u128) -> Result<i32> {
 
     // Use a simplified approach: binary search for the tick
     // that gives a sqrt price closest to the target
+    // The upper bound is exclusive so that MAX_SQRT_PRICE_X64 resolves to MAX_TICK
     let mut low = MIN_TICK;
-    let mut high = MAX_TICK;
+    let mut high = MAX_TICK + 1;
 
     while low < high {
         let mid = low + (high - low) / 2;
@@ -459,4 +460,59 @@ mod tests {
         let ratio_neg200 = sp_neg200 as f64 / q64 as f64;
         assert!((ratio_neg200 - 0.99).abs() < 0.01);
     }
+
+    #[test]
+    fn test_sqrt_price_bounds_match_tick_bounds() {
+        assert_eq!(get_sqrt_price_at_tick(MIN_TICK).unwrap(), MIN_SQRT_PRICE_X64);
+        assert_eq!(get_sqrt_price_at_tick(MAX_TICK).unwrap(), MAX_SQRT_PRICE_X64);
+        assert_eq!(get_tick_at_sqrt_price(MIN_SQRT_PRICE_X64).unwrap(), MIN_TICK);
+        assert_eq!(get_tick_at_sqrt_price(MAX_SQRT_PRICE_X64).unwrap(), MAX_TICK);
+    }
+
+    #[test]
+    fn test_sqrt_price_strictly_increasing_over_full_range() {
+        let mut previous = get_sqrt_price_at_tick(MIN_TICK).unwrap();
+        for tick in MIN_TICK + 1..=MAX_TICK {
+            let current = get_sqrt_price_at_tick(tick).unwrap();
+            assert!(current > previous, "not increasing at tick {}", tick);
+            previous = current;
+        }
+    }
+
+    mod properties {
+        use super::*;
+        use proptest::prelude::*;
+
+        proptest! {
+            #[test]
+            fn tick_round_trips_through_sqrt_price(tick in MIN_TICK..=MAX_TICK) {
+                let sqrt_price = get_sqrt_price_at_tick(tick).unwrap();
+                prop_assert_eq!(get_tick_at_sqrt_price(sqrt_price).unwrap(), tick);
+            }
+
+            #[test]
+            fn tick_at_sqrt_price_is_floor(sqrt_price in MIN_SQRT_PRICE_X64..=MAX_SQRT_PRICE_X64) {
+                let tick = get_tick_at_sqrt_price(sqrt_price).unwrap();
+                prop_assert!(get_sqrt_price_at_tick(tick).unwrap() <= sqrt_price);
+                if tick < MAX_TICK {
+                    prop_assert!(get_sqrt_price_at_tick(tick + 1).unwrap() > sqrt_price);
+                }
+            }
+
+            #[test]
+            fn sqrt_price_monotonic(a in MIN_TICK..=MAX_TICK, b in MIN_TICK..=MAX_TICK) {
+                let (price_a, price_b) = (get_sqrt_price_at_tick(a).unwrap(), get_sqrt_price_at_tick(b).unwrap());
+                prop_assert_eq!(a.cmp(&b), price_a.cmp(&price_b));
+            }
+
+            #[test]
+            fn tick_at_sqrt_price_monotonic(
+                a in MIN_SQRT_PRICE_X64..=MAX_SQRT_PRICE_X64,
+                b in MIN_SQRT_PRICE_X64..=MAX_SQRT_PRICE_X64,
+            ) {
+                let (low, high) = if a <= b { (a, b) } else { (b, a) };
+                prop_assert!(get_tick_at_sqrt_price(low).unwrap() <= get_tick_at_sqrt_price(high).unwrap());
+            }
+        }
+    }
 }
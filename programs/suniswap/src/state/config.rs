@@ -24,8 +24,17 @@ pub struct SuniswapConfig {
     /// Number of fee tiers created
     pub fee_tier_count: u16,
 
+    /// Highest referral fee a swap may take, in basis points (0 disables referrals)
+    pub max_referral_fee_rate: u16,
+
+    /// Highest creator fee share a new pool may set, in percent (0 disables creator fees)
+    pub max_creator_fee_rate: u8,
+
+    /// Who may create pools, one of `pool_creation::MODE_*`
+    pub pool_creation_mode: u8,
+
     /// Reserved for future use
-    pub _reserved: [u8; 32],
+    pub _reserved: [u8; 28],
 }
 
 impl SuniswapConfig {
@@ -36,7 +45,10 @@ impl SuniswapConfig {
         1 +                      // pool_creation_paused
         1 +                      // bump
         2 +                      // fee_tier_count
-        32;                      // reserved
+        2 +                      // max_referral_fee_rate
+        1 +                      // max_creator_fee_rate
+        1 +                      // pool_creation_mode
+        28;                      // reserved
 
     pub fn is_protocol_authority(&self, signer: &Pubkey) -> bool {
         self.protocol_authority == *signer
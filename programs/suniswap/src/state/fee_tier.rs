@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 
 /// Fee tier configuration
-/// PDA: ["fee_tier", fee_rate.to_le_bytes()]
+/// PDA: ["fee_tier", seed_fee_rate.to_le_bytes()]
 #[account]
 #[derive(Debug)]
 pub struct FeeTier {
@@ -9,6 +9,7 @@ pub struct FeeTier {
     pub config: Pubkey,
 
     /// Fee rate in hundredths of a basis point (100 = 0.01%)
+    /// Read at swap time, so updating it applies to every pool on this tier
     pub fee_rate: u32,
 
     /// Tick spacing for this fee tier
@@ -18,8 +19,27 @@ pub struct FeeTier {
     /// Bump seed for PDA derivation
     pub bump: u8,
 
+    /// Creation order of this tier; its `FeeTierIndex` entry uses the same index
+    /// Only meaningful once `index_registered` is set
+    pub index: u16,
+
+    /// Whether new pools are blocked on this tier
+    pub disabled: bool,
+
+    /// Fee rate the tier was created with
+    /// Seeds the PDAs of this tier and its pools, so it never changes.
+    /// Only meaningful once `seed_recorded` is set; use `seed_bytes`
+    pub seed_fee_rate: u32,
+
+    /// Whether `seed_fee_rate` holds the seed; false on tiers created before it existed
+    pub seed_recorded: bool,
+
+    /// Whether the tier has a `FeeTierIndex` entry; tiers created before
+    /// enumeration get one from `register_fee_tier_index`
+    pub index_registered: bool,
+
     /// Reserved for future use
-    pub _reserved: [u8; 32],
+    pub _reserved: [u8; 23],
 }
 
 impl FeeTier {
@@ -28,7 +48,24 @@ impl FeeTier {
         4 +                      // fee_rate
         2 +                      // tick_spacing
         1 +                      // bump
-        32;                      // reserved
+        2 +                      // index
+        1 +                      // disabled
+        4 +                      // seed_fee_rate
+        1 +                      // seed_recorded
+        1 +                      // index_registered
+        23;                      // reserved
+
+    /// PDA seed of this tier and its pools
+    /// Older tiers have no recorded seed; their rate is still the one they
+    /// were created with, since `set_fee_tier_rate` records the seed first
+    pub fn seed_bytes(&self) -> [u8; 4] {
+        let seed_fee_rate = if self.seed_recorded {
+            self.seed_fee_rate
+        } else {
+            self.fee_rate
+        };
+        seed_fee_rate.to_le_bytes()
+    }
 
     /// Calculate fee amount from input amount
     pub fn calculate_fee(&self, amount: u64) -> Option<u64> {
@@ -39,3 +76,22 @@ impl FeeTier {
         Some(fee as u64)
     }
 }
+
+/// Enumeration entry for the fee tier created at `index`
+/// Clients walk indices 0..config.fee_tier_count to discover every tier
+/// PDA: ["fee_tier_index", index.to_le_bytes()]
+#[account]
+#[derive(Debug)]
+pub struct FeeTierIndex {
+    /// The fee tier at this index
+    pub fee_tier: Pubkey,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl FeeTierIndex {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +                     // fee_tier
+        1;                       // bump
+}
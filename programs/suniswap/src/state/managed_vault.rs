@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use crate::constants::managed_vault::{MAX_POSITIONS, FEE_RATE_DENOMINATOR};
+use crate::errors::SuniswapError;
+use crate::math::full_math::mul_div;
+use crate::math::share_math::get_management_fee_shares;
+
+/// Managed liquidity vault over one pool
+/// Owns up to `MAX_POSITIONS` positions plus idle reserves of both tokens, and
+/// issues fungible share tokens against them
+/// PDA: ["managed_vault", pool, id.to_le_bytes()]
+#[account]
+#[derive(Debug)]
+pub struct ManagedVault {
+    /// The pool this vault provides liquidity to
+    pub pool: Pubkey,
+
+    /// Distinguishes vaults on the same pool
+    pub id: u16,
+
+    /// Mint of the vault's share token, with the vault as mint authority
+    pub share_mint: Pubkey,
+
+    /// Vault-owned token A account holding idle tokens and treasury fees
+    pub reserve_a: Pubkey,
+
+    /// Vault-owned token B account holding idle tokens and treasury fees
+    pub reserve_b: Pubkey,
+
+    /// Account allowed to open, rebalance and close the vault's positions
+    pub strategist: Pubkey,
+
+    /// Owner of the accounts fees are paid to
+    pub treasury: Pubkey,
+
+    /// Share of harvested fees taken for the treasury, in basis points
+    pub performance_fee_rate: u16,
+
+    /// Yearly fee on shares outstanding, in basis points, paid as new shares
+    pub management_fee_rate: u16,
+
+    /// Lowest tick a vault position may start at
+    pub tick_lower_limit: i32,
+
+    /// Highest tick a vault position may end at
+    pub tick_upper_limit: i32,
+
+    /// The vault's positions, first `position_count` entries are in use
+    pub positions: [Pubkey; MAX_POSITIONS],
+
+    /// Number of positions in use
+    pub position_count: u8,
+
+    /// Performance fees held in `reserve_a` for the treasury
+    pub treasury_fees_a: u64,
+
+    /// Performance fees held in `reserve_b` for the treasury
+    pub treasury_fees_b: u64,
+
+    /// Management fee shares accrued but not yet minted to the treasury
+    pub treasury_shares: u64,
+
+    /// Last time management fees were accrued
+    pub fees_accrued_at: i64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+
+    /// Shares locked by the first deposit, never minted
+    pub locked_shares: u64,
+
+    /// Reserved for future use
+    pub _reserved: [u8; 24],
+}
+
+impl ManagedVault {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +                     // pool
+        2 +                      // id
+        32 +                     // share_mint
+        32 +                     // reserve_a
+        32 +                     // reserve_b
+        32 +                     // strategist
+        32 +                     // treasury
+        2 +                      // performance_fee_rate
+        2 +                      // management_fee_rate
+        4 +                      // tick_lower_limit
+        4 +                      // tick_upper_limit
+        32 * MAX_POSITIONS +     // positions
+        1 +                      // position_count
+        8 +                      // treasury_fees_a
+        8 +                      // treasury_fees_b
+        8 +                      // treasury_shares
+        8 +                      // fees_accrued_at
+        1 +                      // bump
+        8 +                      // locked_shares
+        24;                      // reserved
+
+    /// Positions currently held
+    pub fn active_positions(&self) -> &[Pubkey] {
+        &self.positions[..self.position_count as usize]
+    }
+
+    pub fn is_strategist(&self, signer: &Pubkey) -> bool {
+        self.strategist == *signer
+    }
+
+    /// Share supply including management fee shares not yet minted and the
+    /// locked shares
+    pub fn share_supply(&self, minted: u64) -> Result<u64> {
+        minted
+            .checked_add(self.treasury_shares)
+            .and_then(|supply| supply.checked_add(self.locked_shares))
+            .ok_or(SuniswapError::MathOverflow.into())
+    }
+
+    /// Accrue management fee shares up to `now`
+    pub fn accrue_management_fee(&mut self, minted: u64, now: i64) -> Result<()> {
+        let elapsed = now.saturating_sub(self.fees_accrued_at).max(0) as u64;
+        let shares = get_management_fee_shares(self.share_supply(minted)?, self.management_fee_rate, elapsed)?;
+        self.treasury_shares = self.treasury_shares
+            .checked_add(shares)
+            .ok_or(SuniswapError::MathOverflow)?;
+        self.fees_accrued_at = now;
+        Ok(())
+    }
+
+    /// Take the performance fee out of harvested fees, returning what is left
+    /// for shareholders
+    pub fn take_performance_fee(&mut self, fees_a: u64, fees_b: u64) -> Result<(u64, u64)> {
+        let rate = self.performance_fee_rate as u128;
+        let denominator = FEE_RATE_DENOMINATOR as u128;
+        let cut_a = mul_div(fees_a as u128, rate, denominator)? as u64;
+        let cut_b = mul_div(fees_b as u128, rate, denominator)? as u64;
+        self.treasury_fees_a = self.treasury_fees_a
+            .checked_add(cut_a)
+            .ok_or(SuniswapError::MathOverflow)?;
+        self.treasury_fees_b = self.treasury_fees_b
+            .checked_add(cut_b)
+            .ok_or(SuniswapError::MathOverflow)?;
+        Ok((fees_a - cut_a, fees_b - cut_b))
+    }
+}
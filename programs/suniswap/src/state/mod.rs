@@ -5,6 +5,10 @@ pub mod position;
 pub mod tick;
 pub mod tick_array;
 pub mod oracle;
+pub mod managed_vault;
+pub mod protocol_fee_config;
+pub mod pool_creation_config;
+pub mod trader_allowlist;
 
 pub use config::*;
 pub use fee_tier::*;
@@ -13,3 +17,7 @@ pub use position::*;
 pub use tick::*;
 pub use tick_array::*;
 pub use oracle::*;
+pub use managed_vault::*;
+pub use protocol_fee_config::*;
+pub use pool_creation_config::*;
+pub use trader_allowlist::*;
@@ -1,4 +1,7 @@
 use anchor_lang::prelude::*;
+use crate::constants::oracle::OBSERVATION_CAPACITY;
+use crate::math::full_math::mul_div;
+use crate::state::Pool;
 
 /// Oracle observation - stores TWAP data points
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, Debug, Copy)]
@@ -22,6 +25,28 @@ impl Observation {
         8 +     // tick_cumulative
         16 +    // seconds_per_liquidity_cumulative_x64
         1;      // initialized
+
+    /// Extend the cumulatives to `target_timestamp`, assuming `tick` and `liquidity`
+    /// held since this observation
+    pub fn transform(&self, target_timestamp: u32, tick: i32, liquidity: u128) -> Observation {
+        let time_delta = target_timestamp.wrapping_sub(self.block_timestamp);
+
+        let tick_cumulative = self.tick_cumulative
+            .wrapping_add((tick as i64).wrapping_mul(time_delta as i64));
+
+        let seconds_per_liquidity_cumulative_x64 = match ((time_delta as u128) << 64).checked_div(liquidity) {
+            Some(seconds_per_liquidity) => self.seconds_per_liquidity_cumulative_x64
+                .wrapping_add(seconds_per_liquidity),
+            None => self.seconds_per_liquidity_cumulative_x64,
+        };
+
+        Observation {
+            block_timestamp: target_timestamp,
+            tick_cumulative,
+            seconds_per_liquidity_cumulative_x64,
+            initialized: true,
+        }
+    }
 }
 
 /// Oracle account - stores multiple observations for TWAP calculations
@@ -45,7 +70,7 @@ pub struct Oracle {
 
     /// Array of observations (reduced for Solana stack limits)
     /// For production, use zero-copy accounts to support larger arrays (256+ standard)
-    pub observations: [Observation; 32],
+    pub observations: [Observation; OBSERVATION_CAPACITY],
 }
 
 impl Oracle {
@@ -61,57 +86,33 @@ impl Oracle {
         Self::BASE_LEN + (Observation::LEN * cardinality as usize)
     }
 
-    /// Initialize the oracle with first observation
-    pub fn initialize(&mut self, timestamp: u32) {
-        self.observations[0] = Observation {
-            block_timestamp: timestamp,
-            tick_cumulative: 0,
-            seconds_per_liquidity_cumulative_x64: 0,
-            initialized: true,
-        };
+    /// Initialize the oracle with the pool's accumulators as its first observation
+    pub fn initialize(&mut self, observation: Observation) {
+        self.observations[0] = observation;
         self.observation_cardinality = 1;
         self.observation_cardinality_next = 1;
     }
 
-    /// Write a new observation
-    pub fn write(
-        &mut self,
-        timestamp: u32,
-        tick: i32,
-        liquidity: u128,
-    ) -> (u16, u16) {
-        let last = &self.observations[self.observation_index as usize];
+    /// Seconds between observations so a full ring of them spans `window`
+    pub fn write_interval(window: u32) -> u32 {
+        window.div_ceil(OBSERVATION_CAPACITY as u32 - 1).max(1)
+    }
+
+    /// Write a new observation, unless the last one is less than
+    /// `min_interval` seconds old
+    pub fn write(&mut self, observation: Observation, min_interval: u32) -> (u16, u16) {
+        let last = self.observations[self.observation_index as usize];
 
-        // Early return if same timestamp
-        if timestamp == last.block_timestamp {
+        // Early return if the last observation is too recent
+        if observation.block_timestamp.wrapping_sub(last.block_timestamp) < min_interval.max(1) {
             return (self.observation_index, self.observation_cardinality);
         }
 
-        let time_delta = timestamp.wrapping_sub(last.block_timestamp);
-
-        // Calculate new cumulative values
-        let tick_cumulative = last.tick_cumulative
-            .wrapping_add((tick as i64).wrapping_mul(time_delta as i64));
-
-        let seconds_per_liquidity_cumulative_x64 = if liquidity > 0 {
-            last.seconds_per_liquidity_cumulative_x64
-                .wrapping_add(
-                    ((time_delta as u128) << 64) / liquidity
-                )
-        } else {
-            last.seconds_per_liquidity_cumulative_x64
-        };
-
         // Determine new index (wrap around)
         let new_index = (self.observation_index + 1) % self.observation_cardinality_next;
 
         // Write observation
-        self.observations[new_index as usize] = Observation {
-            block_timestamp: timestamp,
-            tick_cumulative,
-            seconds_per_liquidity_cumulative_x64,
-            initialized: true,
-        };
+        self.observations[new_index as usize] = observation;
 
         // Update cardinality if expanding
         let new_cardinality = if new_index + 1 > self.observation_cardinality {
@@ -150,6 +151,31 @@ impl Oracle {
         Ok((observation.tick_cumulative, observation.seconds_per_liquidity_cumulative_x64))
     }
 
+    /// Time-weighted average tick over the `window` seconds up to `timestamp`,
+    /// or since the oldest observation if the oracle doesn't reach that far back
+    pub fn twap_tick(&self, window: u32, pool: &Pool, timestamp: u32) -> Result<i32> {
+        let oldest = &self.observations[self.oldest_index() as usize];
+        let start = timestamp.saturating_sub(window).max(oldest.block_timestamp);
+        let elapsed = timestamp.saturating_sub(start);
+        if elapsed == 0 {
+            return Ok(pool.tick_current);
+        }
+
+        let tick_cumulative_end = pool.observe(timestamp).tick_cumulative;
+        let (tick_cumulative_start, _) = self.observe_single(start, pool.tick_current, pool.liquidity, timestamp)?;
+
+        // Round towards negative infinity, like the tick of a price
+        let average = tick_cumulative_end
+            .wrapping_sub(tick_cumulative_start)
+            .div_euclid(elapsed as i64);
+        Ok(average as i32)
+    }
+
+    /// Index of the oldest observation
+    fn oldest_index(&self) -> u16 {
+        (self.observation_index + 1) % self.observation_cardinality
+    }
+
     /// Binary search for observation at or before target timestamp
     fn get_observation_at_or_before(
         &self,
@@ -165,11 +191,11 @@ impl Oracle {
             if target == last.block_timestamp {
                 return Ok(*last);
             }
-            return Ok(self.transform(last, target, tick, liquidity));
+            return Ok(last.transform(target, tick, liquidity));
         }
 
         // Binary search through observations
-        let oldest_index = (self.observation_index + 1) % self.observation_cardinality;
+        let oldest_index = self.oldest_index();
         let oldest = &self.observations[oldest_index as usize];
 
         if target < oldest.block_timestamp {
@@ -177,36 +203,27 @@ impl Oracle {
         }
 
         // Perform binary search
-        let (before_or_at, _at_or_after) = self.binary_search(target, oldest_index)?;
-        Ok(before_or_at)
-    }
-
-    /// Transform an observation to a target timestamp
-    fn transform(
-        &self,
-        observation: &Observation,
-        target_timestamp: u32,
-        tick: i32,
-        liquidity: u128,
-    ) -> Observation {
-        let time_delta = target_timestamp.wrapping_sub(observation.block_timestamp);
-
-        let tick_cumulative = observation.tick_cumulative
-            .wrapping_add((tick as i64).wrapping_mul(time_delta as i64));
-
-        let seconds_per_liquidity_cumulative_x64 = if liquidity > 0 {
-            observation.seconds_per_liquidity_cumulative_x64
-                .wrapping_add(((time_delta as u128) << 64) / liquidity)
-        } else {
-            observation.seconds_per_liquidity_cumulative_x64
-        };
+        let (before_or_at, at_or_after) = self.binary_search(target, oldest_index)?;
+        if target == before_or_at.block_timestamp {
+            return Ok(before_or_at);
+        }
 
-        Observation {
-            block_timestamp: target_timestamp,
-            tick_cumulative,
-            seconds_per_liquidity_cumulative_x64,
+        // Interpolate; the tick held between the two observations
+        let elapsed = target.wrapping_sub(before_or_at.block_timestamp);
+        let span = at_or_after.block_timestamp.wrapping_sub(before_or_at.block_timestamp);
+        let tick_cumulative_delta = at_or_after.tick_cumulative.wrapping_sub(before_or_at.tick_cumulative);
+        let seconds_per_liquidity_delta = at_or_after
+            .seconds_per_liquidity_cumulative_x64
+            .wrapping_sub(before_or_at.seconds_per_liquidity_cumulative_x64);
+
+        Ok(Observation {
+            block_timestamp: target,
+            tick_cumulative: before_or_at.tick_cumulative
+                .wrapping_add(tick_cumulative_delta / span as i64 * elapsed as i64),
+            seconds_per_liquidity_cumulative_x64: before_or_at.seconds_per_liquidity_cumulative_x64
+                .wrapping_add(mul_div(seconds_per_liquidity_delta, elapsed as u128, span as u128)?),
             initialized: true,
-        }
+        })
     }
 
     /// Binary search for surrounding observations
@@ -1,7 +1,8 @@
 use anchor_lang::prelude::*;
+use crate::state::oracle::Observation;
 
 /// Pool state - the core AMM state for a token pair
-/// PDA: ["pool", token_mint_a, token_mint_b, fee_rate.to_le_bytes()]
+/// PDA: ["pool", token_mint_a, token_mint_b, fee_tier.seed_fee_rate.to_le_bytes()]
 ///
 /// Using zero-copy for efficient memory access and reduced stack usage
 /// All fields are carefully ordered to avoid implicit padding
@@ -90,14 +91,105 @@ pub struct Pool {
     /// Oracle account for TWAP (optional)
     pub oracle: [u8; 32],                         // 32 bytes, offset 320
 
+    // === Oracle accumulators ===
+
+    /// Cumulative seconds per unit of in-range liquidity (Q64.64)
+    pub seconds_per_liquidity_cumulative_x64: u128, // 16 bytes, offset 352
+
+    /// Cumulative tick value (tick * seconds elapsed)
+    pub tick_cumulative: i64,                     // 8 bytes, offset 368
+
+    /// Timestamp the accumulators were last advanced to
+    pub cumulatives_timestamp: u32,               // 4 bytes, offset 376
+
+    // === Fee settings ===
+
+    /// Pool's own fee rate, initialized from the fee tier
+    /// Charged instead of the tier's rate while `fee_rate_override` is set
+    pub fee_rate: u32,                            // 4 bytes, offset 380
+
+    /// Lower bound of the adaptive fee
+    pub min_fee_rate: u32,                        // 4 bytes, offset 384
+
+    /// Upper bound of the adaptive fee
+    pub max_fee_rate: u32,                        // 4 bytes, offset 388
+
+    /// Adaptive fee added per tick of volatility (hundredths of a bip)
+    pub volatility_fee_factor: u32,               // 4 bytes, offset 392
+
+    /// Seconds for the volatility accumulator to decay linearly to zero
+    pub volatility_decay_period: u32,             // 4 bytes, offset 396
+
+    /// Ticks the price moved recently, decayed over time
+    pub volatility_accumulator: u32,              // 4 bytes, offset 400
+
+    /// Timestamp the volatility accumulator was last updated
+    pub volatility_updated_at: u32,               // 4 bytes, offset 404
+
+    /// Whether swaps charge `fee_rate` instead of the tier's rate
+    pub fee_rate_override: u8,                    // 1 byte, offset 408
+
+    /// Whether the fee rises with the volatility accumulator
+    pub adaptive_fee_enabled: u8,                 // 1 byte, offset 409
+
+    // === Creator fees ===
+
+    /// Creator's share of swap fees, in percent like `protocol_fee_rate`
+    pub creator_fee_rate: u8,                     // 1 byte, offset 410
+
+    // === Trader access ===
+
+    /// Whether only wallets on the pool's trader allowlist may swap and add liquidity
+    pub trader_allowlist_enabled: u8,             // 1 byte, offset 411
+
+    /// Padding for alignment
+    pub _padding: [u8; 4],                        // 4 bytes, offset 412
+
+    /// Creator fees accumulated for token A
+    pub creator_fees_a: u64,                      // 8 bytes, offset 416
+
+    /// Creator fees accumulated for token B
+    pub creator_fees_b: u64,                      // 8 bytes, offset 424
+
+    /// Wallet that may collect creator fees (zero if none)
+    pub creator: [u8; 32],                        // 32 bytes, offset 432
+
+    // === Circuit breaker ===
+
+    /// Slot of the pool's first swap since `circuit_breaker_tick` was recorded
+    pub circuit_breaker_slot: u64,                // 8 bytes, offset 464
+
+    /// `tick_current` before the first swap in `circuit_breaker_slot`
+    pub circuit_breaker_tick: i32,                // 4 bytes, offset 472
+
+    /// Most ticks a single swap may move the price (0 = unlimited)
+    pub max_tick_move_per_swap: u32,              // 4 bytes, offset 476
+
+    /// Most ticks the price may move from `circuit_breaker_tick` within a slot (0 = unlimited)
+    pub max_tick_move_per_slot: u32,              // 4 bytes, offset 480
+
+    // === TWAP guard ===
+
+    /// Seconds the TWAP deviation guard averages over (0 until an oracle is initialized)
+    pub twap_window: u32,                         // 4 bytes, offset 484
+
+    /// Most ticks a swap may leave the price from the TWAP (0 = unchecked)
+    pub max_twap_deviation_ticks: u32,            // 4 bytes, offset 488
+
     /// Reserved for future use
-    pub _reserved: [u8; 32],                      // 32 bytes, offset 352
+    pub _reserved: [u8; 20],                      // 20 bytes, offset 492
 }
-// Total: 384 bytes (divisible by 16)
+// Total: 512 bytes (divisible by 16)
+
+const _: () = assert!(std::mem::size_of::<Pool>() == 512);
 
 impl Pool {
     pub const LEN: usize = 8 + std::mem::size_of::<Pool>();
 
+    /// Size of pools created before the fields after `oracle` were added;
+    /// `migrate_pool` grows them to `LEN`
+    pub const LEGACY_LEN: usize = 8 + 384;
+
     /// Get config as Pubkey
     pub fn config_pubkey(&self) -> Pubkey {
         Pubkey::new_from_array(self.config)
@@ -138,11 +230,21 @@ impl Pool {
         Pubkey::new_from_array(self.oracle)
     }
 
+    /// Get creator as Pubkey
+    pub fn creator_pubkey(&self) -> Pubkey {
+        Pubkey::new_from_array(self.creator)
+    }
+
     /// Check if pool is paused
     pub fn is_pool_paused(&self) -> bool {
         self.is_paused != 0
     }
 
+    /// Check if swaps and deposits are limited to allowlisted wallets
+    pub fn is_trader_allowlist_enabled(&self) -> bool {
+        self.trader_allowlist_enabled != 0
+    }
+
     /// Check if a specific hook is enabled
     pub fn is_hook_enabled(&self, flag: u8) -> bool {
         self.hook_program != [0u8; 32] && (self.hook_flags & flag) != 0
@@ -153,6 +255,90 @@ impl Pool {
         self.hook_program != [0u8; 32] && self.hook_flags != 0
     }
 
+    /// Oracle accumulators extended to `timestamp` at the current tick and liquidity
+    pub fn observe(&self, timestamp: u32) -> Observation {
+        Observation {
+            block_timestamp: self.cumulatives_timestamp,
+            tick_cumulative: self.tick_cumulative,
+            seconds_per_liquidity_cumulative_x64: self.seconds_per_liquidity_cumulative_x64,
+            initialized: true,
+        }
+        .transform(timestamp, self.tick_current, self.liquidity)
+    }
+
+    /// Advance the oracle accumulators to `timestamp`
+    /// Must be called before `tick_current` or `liquidity` change
+    pub fn update_cumulatives(&mut self, timestamp: u32) {
+        let observation = self.observe(timestamp);
+        self.tick_cumulative = observation.tick_cumulative;
+        self.seconds_per_liquidity_cumulative_x64 = observation.seconds_per_liquidity_cumulative_x64;
+        self.cumulatives_timestamp = timestamp;
+    }
+
+    /// Fee rate charged before any adaptive component
+    pub fn base_fee_rate(&self, tier_fee_rate: u32) -> u32 {
+        if self.fee_rate_override != 0 {
+            self.fee_rate
+        } else {
+            tier_fee_rate
+        }
+    }
+
+    /// Volatility accumulator decayed to `timestamp`
+    pub fn decayed_volatility(&self, timestamp: u32) -> u32 {
+        let elapsed = timestamp.saturating_sub(self.volatility_updated_at);
+        if elapsed >= self.volatility_decay_period {
+            return 0;
+        }
+        let remaining = (self.volatility_decay_period - elapsed) as u64;
+        (self.volatility_accumulator as u64 * remaining / self.volatility_decay_period as u64) as u32
+    }
+
+    /// Fee settings for a swap starting at `timestamp`
+    pub fn swap_fee(&self, tier_fee_rate: u32, timestamp: u32) -> SwapFee {
+        let adaptive = self.adaptive_fee_enabled != 0;
+        SwapFee {
+            base_fee_rate: self.base_fee_rate(tier_fee_rate),
+            adaptive,
+            min_fee_rate: self.min_fee_rate,
+            max_fee_rate: self.max_fee_rate,
+            volatility_fee_factor: self.volatility_fee_factor,
+            volatility_start: if adaptive { self.decayed_volatility(timestamp) } else { 0 },
+        }
+    }
+
+    /// Record the slot's starting tick and check a swap's move from
+    /// `tick_before` to `tick_after` against the circuit breaker caps
+    pub fn check_circuit_breaker(&mut self, slot: u64, tick_before: i32, tick_after: i32) -> Result<()> {
+        if self.circuit_breaker_slot != slot {
+            self.circuit_breaker_slot = slot;
+            self.circuit_breaker_tick = tick_before;
+        }
+        if self.max_tick_move_per_swap > 0 {
+            require!(
+                tick_after.abs_diff(tick_before) <= self.max_tick_move_per_swap,
+                crate::errors::SuniswapError::PriceMoveLimitExceeded
+            );
+        }
+        if self.max_tick_move_per_slot > 0 {
+            require!(
+                tick_after.abs_diff(self.circuit_breaker_tick) <= self.max_tick_move_per_slot,
+                crate::errors::SuniswapError::PriceMoveLimitExceeded
+            );
+        }
+        Ok(())
+    }
+
+    /// TWAP deviation cap for a swap: the tighter of the pool's own cap and
+    /// `requested`, or None if neither applies
+    pub fn twap_deviation_limit(&self, requested: Option<u32>) -> Option<u32> {
+        let pool_limit = (self.max_twap_deviation_ticks > 0).then_some(self.max_twap_deviation_ticks);
+        match (pool_limit, requested) {
+            (Some(pool_limit), Some(requested)) => Some(pool_limit.min(requested)),
+            (pool_limit, requested) => pool_limit.or(requested),
+        }
+    }
+
     /// Update liquidity, handling the signed delta
     pub fn update_liquidity(&mut self, delta: i128) -> Result<()> {
         if delta >= 0 {
@@ -167,3 +353,35 @@ impl Pool {
         Ok(())
     }
 }
+
+/// Fee settings copied out of the pool for the duration of a swap
+#[derive(Clone, Copy, Debug)]
+pub struct SwapFee {
+    pub base_fee_rate: u32,
+    pub adaptive: bool,
+    pub min_fee_rate: u32,
+    pub max_fee_rate: u32,
+    pub volatility_fee_factor: u32,
+    /// Decayed volatility accumulator when the swap started
+    pub volatility_start: u32,
+}
+
+impl SwapFee {
+    /// Volatility after the swap has moved the price `ticks_moved` ticks
+    pub fn volatility(&self, ticks_moved: u32) -> u32 {
+        self.volatility_start.saturating_add(ticks_moved)
+    }
+
+    /// Fee rate for a swap step once the price has moved `ticks_moved` ticks
+    pub fn fee_rate(&self, ticks_moved: u32) -> u32 {
+        if !self.adaptive {
+            return self.base_fee_rate;
+        }
+        let variable_fee = (self.volatility(ticks_moved) as u64)
+            .saturating_mul(self.volatility_fee_factor as u64);
+        let fee_rate = (self.base_fee_rate as u64)
+            .saturating_add(variable_fee)
+            .min(u32::MAX as u64) as u32;
+        fee_rate.max(self.min_fee_rate).min(self.max_fee_rate)
+    }
+}
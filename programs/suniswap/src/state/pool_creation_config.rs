@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use crate::constants::pool_creation::MAX_ALLOWED_CREATORS;
+use crate::errors::SuniswapError;
+
+/// Allowlist and creation fee applied to `initialize_pool` by the config's
+/// `pool_creation_mode`
+/// PDA: ["pool_creation_config"]
+#[account]
+#[derive(Debug)]
+pub struct PoolCreationConfig {
+    /// The config this policy belongs to
+    pub config: Pubkey,
+
+    /// Keys allowed to create pools, first `allowed_creator_count` entries are in use
+    pub allowed_creators: [Pubkey; MAX_ALLOWED_CREATORS],
+
+    /// Number of allowlisted keys in use
+    pub allowed_creator_count: u8,
+
+    /// Mint the creation fee is paid in (default pubkey = native SOL)
+    pub fee_mint: Pubkey,
+
+    /// Creation fee, in lamports or base units of `fee_mint`
+    pub fee_amount: u64,
+
+    /// Wallet receiving creation fees (its associated token account for `fee_mint`)
+    pub treasury: Pubkey,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+
+    /// Reserved for future use
+    pub _reserved: [u8; 32],
+}
+
+impl PoolCreationConfig {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +                     // config
+        32 * MAX_ALLOWED_CREATORS + // allowed_creators
+        1 +                      // allowed_creator_count
+        32 +                     // fee_mint
+        8 +                      // fee_amount
+        32 +                     // treasury
+        1 +                      // bump
+        32;                      // reserved
+
+    /// The allowlisted keys in use
+    pub fn active_creators(&self) -> &[Pubkey] {
+        &self.allowed_creators[..self.allowed_creator_count as usize]
+    }
+
+    pub fn is_allowed_creator(&self, key: &Pubkey) -> bool {
+        self.active_creators().contains(key)
+    }
+
+    /// Replace the allowlist
+    pub fn set_allowed_creators(&mut self, creators: &[Pubkey]) -> Result<()> {
+        require!(
+            creators.len() <= MAX_ALLOWED_CREATORS,
+            SuniswapError::InvalidPoolCreationAllowlist
+        );
+
+        self.allowed_creators = [Pubkey::default(); MAX_ALLOWED_CREATORS];
+        self.allowed_creators[..creators.len()].copy_from_slice(creators);
+        self.allowed_creator_count = creators.len() as u8;
+        Ok(())
+    }
+
+    /// Whether the fee is paid in lamports rather than a token
+    pub fn is_native_fee(&self) -> bool {
+        self.fee_mint == Pubkey::default()
+    }
+
+    /// Whether a fee and treasury have been set
+    pub fn has_fee(&self) -> bool {
+        self.fee_amount > 0 && self.treasury != Pubkey::default()
+    }
+}
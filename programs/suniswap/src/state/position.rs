@@ -6,7 +6,7 @@ use anchor_lang::prelude::*;
 /// Fields ordered by alignment requirements to avoid padding
 #[account(zero_copy)]
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct Position {
     // === 16-byte aligned fields (u128) ===
 
@@ -40,8 +40,11 @@ pub struct Position {
     /// Bump seed for PDA derivation
     pub bump: u8,                                 // 1 byte, offset 72
 
+    /// What the delegate may do, see `constants::position_permissions`
+    pub delegate_permissions: u8,                 // 1 byte, offset 73
+
     /// Padding for 8-byte alignment before [u8; 32] arrays
-    pub _padding: [u8; 7],                        // 7 bytes, offset 73
+    pub _padding: [u8; 6],                        // 6 bytes, offset 74
 
     // === Pubkey-sized fields (32 bytes) ===
 
@@ -54,31 +57,12 @@ pub struct Position {
     /// Position NFT mint (optional, for NFT-based positions)
     pub position_mint: [u8; 32],                  // 32 bytes, offset 144
 
-    /// Reserved for future use
-    pub _reserved: [u8; 32],                      // 32 bytes, offset 176
+    /// Account allowed to manage the position for the owner, zero if none
+    /// (formerly reserved space)
+    pub delegate: [u8; 32],                       // 32 bytes, offset 176
 }
 // Total: 208 bytes (divisible by 16)
 
-impl Default for Position {
-    fn default() -> Self {
-        Self {
-            liquidity: 0,
-            fee_growth_inside_a_last_x128: 0,
-            fee_growth_inside_b_last_x128: 0,
-            tokens_owed_a: 0,
-            tokens_owed_b: 0,
-            tick_lower: 0,
-            tick_upper: 0,
-            bump: 0,
-            _padding: [0u8; 7],
-            pool: [0u8; 32],
-            owner: [0u8; 32],
-            position_mint: [0u8; 32],
-            _reserved: [0u8; 32],
-        }
-    }
-}
-
 impl Position {
     pub const LEN: usize = 8 + std::mem::size_of::<Position>();
 
@@ -97,6 +81,20 @@ impl Position {
         Pubkey::new_from_array(self.position_mint)
     }
 
+    /// Get delegate as Pubkey
+    pub fn delegate_pubkey(&self) -> Pubkey {
+        Pubkey::new_from_array(self.delegate)
+    }
+
+    /// Whether `authority` may act on the position: the owner always may, the
+    /// delegate only if it holds every bit of `permissions` (never for 0)
+    pub fn is_authorized(&self, authority: &Pubkey, permissions: u8) -> bool {
+        self.owner == authority.to_bytes()
+            || (permissions != 0
+                && self.delegate == authority.to_bytes()
+                && self.delegate_permissions & permissions == permissions)
+    }
+
     /// Check if position is empty (no liquidity and no owed tokens)
     pub fn is_empty(&self) -> bool {
         self.liquidity == 0 && self.tokens_owed_a == 0 && self.tokens_owed_b == 0
@@ -170,6 +168,19 @@ impl Position {
 
         Ok(())
     }
+
+    /// Owed tokens that go with `liquidity` out of this position's liquidity,
+    /// in proportion and rounded down
+    pub fn owed_tokens_share(&self, liquidity: u128) -> Result<(u64, u64)> {
+        if self.liquidity == 0 {
+            return Ok((0, 0));
+        }
+        let share = |owed: u64| -> Result<u64> {
+            let amount = crate::math::full_math::mul_div(owed as u128, liquidity, self.liquidity)?;
+            u64::try_from(amount).map_err(|_| crate::errors::SuniswapError::CastOverflow.into())
+        };
+        Ok((share(self.tokens_owed_a)?, share(self.tokens_owed_b)?))
+    }
 }
 
 /// Position bundle - allows managing multiple positions in one account
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use crate::constants::protocol_fee_split::{MAX_RECIPIENTS, WEIGHT_DENOMINATOR};
+use crate::errors::SuniswapError;
+
+/// A wallet paid a weighted share of distributed protocol fees
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ProtocolFeeRecipient {
+    /// Wallet whose associated token accounts receive the share
+    pub wallet: Pubkey,
+
+    /// Share of protocol fees in basis points
+    pub weight: u16,
+}
+
+impl ProtocolFeeRecipient {
+    pub const LEN: usize = 32 + 2;
+}
+
+/// How `distribute_protocol_fees` splits a pool's protocol fees
+/// PDA: ["protocol_fee_config"]
+#[account]
+#[derive(Debug)]
+pub struct ProtocolFeeConfig {
+    /// The config this split belongs to
+    pub config: Pubkey,
+
+    /// Fee recipients, first `recipient_count` entries are in use
+    pub recipients: [ProtocolFeeRecipient; MAX_RECIPIENTS],
+
+    /// Number of recipients in use
+    pub recipient_count: u8,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+
+    /// Reserved for future use
+    pub _reserved: [u8; 32],
+}
+
+impl ProtocolFeeConfig {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +                     // config
+        ProtocolFeeRecipient::LEN * MAX_RECIPIENTS + // recipients
+        1 +                      // recipient_count
+        1 +                      // bump
+        32;                      // reserved
+
+    /// The recipients in use
+    pub fn active_recipients(&self) -> &[ProtocolFeeRecipient] {
+        &self.recipients[..self.recipient_count as usize]
+    }
+
+    /// Replace the recipients; weights must be nonzero and sum to 100%
+    pub fn set_recipients(&mut self, recipients: &[ProtocolFeeRecipient]) -> Result<()> {
+        require!(
+            !recipients.is_empty() && recipients.len() <= MAX_RECIPIENTS,
+            SuniswapError::InvalidProtocolFeeRecipients
+        );
+        let mut total_weight = 0u32;
+        for recipient in recipients {
+            require!(recipient.weight > 0, SuniswapError::InvalidProtocolFeeRecipients);
+            total_weight += recipient.weight as u32;
+        }
+        require!(
+            total_weight == WEIGHT_DENOMINATOR as u32,
+            SuniswapError::InvalidProtocolFeeRecipients
+        );
+
+        self.recipients = [ProtocolFeeRecipient::default(); MAX_RECIPIENTS];
+        self.recipients[..recipients.len()].copy_from_slice(recipients);
+        self.recipient_count = recipients.len() as u8;
+        Ok(())
+    }
+
+    /// Split `amount` by weight, the last recipient taking the rounding remainder
+    pub fn split(&self, amount: u64) -> Vec<u64> {
+        let recipients = self.active_recipients();
+        let mut remaining = amount;
+        recipients
+            .iter()
+            .enumerate()
+            .map(|(i, recipient)| {
+                let share = if i + 1 == recipients.len() {
+                    remaining
+                } else {
+                    (amount as u128 * recipient.weight as u128 / WEIGHT_DENOMINATOR as u128) as u64
+                };
+                remaining -= share;
+                share
+            })
+            .collect()
+    }
+}
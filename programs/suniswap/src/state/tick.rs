@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 use bytemuck::{Pod, Zeroable};
+use crate::state::oracle::Observation;
 
 /// Tick data - stored within tick arrays
 /// Each tick represents a price point where liquidity can change
@@ -23,7 +24,7 @@ pub struct Tick {
     /// Fee growth on the other side of this tick (token B)
     pub fee_growth_outside_b_x128: u128,  // 16 bytes, offset 48
 
-    /// Seconds per liquidity outside (Q32.32)
+    /// Seconds per liquidity on the other side of this tick (Q64.64)
     pub seconds_per_liquidity_outside_x64: u128,  // 16 bytes, offset 64
 
     /// Cumulative tick value (for TWAP calculations)
@@ -53,6 +54,8 @@ impl Tick {
     }
 
     /// Update tick when liquidity is added/removed
+    /// `cumulatives` are the pool's oracle accumulators at the current time
+    #[allow(clippy::too_many_arguments)]
     pub fn update(
         &mut self,
         tick_current: i32,
@@ -60,6 +63,7 @@ impl Tick {
         liquidity_delta: i128,
         fee_growth_global_a_x128: u128,
         fee_growth_global_b_x128: u128,
+        cumulatives: &Observation,
         upper: bool,
     ) -> Result<bool> {
         let liquidity_gross_before = self.liquidity_gross;
@@ -81,10 +85,14 @@ impl Tick {
             // Initialize tick
             self.initialized = 1;
 
-            // By convention, we assume fees accumulated below current tick
+            // By convention, we assume fees and time accumulated below current tick
             if tick_index <= tick_current {
                 self.fee_growth_outside_a_x128 = fee_growth_global_a_x128;
                 self.fee_growth_outside_b_x128 = fee_growth_global_b_x128;
+                self.seconds_per_liquidity_outside_x64 =
+                    cumulatives.seconds_per_liquidity_cumulative_x64;
+                self.tick_cumulative_outside = cumulatives.tick_cumulative;
+                self.seconds_outside = cumulatives.block_timestamp;
             }
         }
 
@@ -110,12 +118,24 @@ impl Tick {
         &mut self,
         fee_growth_global_a_x128: u128,
         fee_growth_global_b_x128: u128,
+        cumulatives: &Observation,
     ) {
         // Flip fee growth outside
         self.fee_growth_outside_a_x128 = fee_growth_global_a_x128
             .wrapping_sub(self.fee_growth_outside_a_x128);
         self.fee_growth_outside_b_x128 = fee_growth_global_b_x128
             .wrapping_sub(self.fee_growth_outside_b_x128);
+
+        // Flip oracle values outside
+        self.seconds_per_liquidity_outside_x64 = cumulatives
+            .seconds_per_liquidity_cumulative_x64
+            .wrapping_sub(self.seconds_per_liquidity_outside_x64);
+        self.tick_cumulative_outside = cumulatives
+            .tick_cumulative
+            .wrapping_sub(self.tick_cumulative_outside);
+        self.seconds_outside = cumulatives
+            .block_timestamp
+            .wrapping_sub(self.seconds_outside);
     }
 
     /// Clear tick when it's no longer needed
@@ -163,5 +183,49 @@ impl Tick {
                 .wrapping_sub(fee_growth_above_b),
         )
     }
-}
 
+    /// Oracle values accumulated while the current tick was inside a tick range
+    ///
+    /// Returns (tick_cumulative_inside, seconds_per_liquidity_inside_x64, seconds_inside).
+    /// Only differences between two snapshots of the same range are meaningful.
+    pub fn get_cumulatives_inside(
+        tick_lower: &Tick,
+        tick_upper: &Tick,
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+        tick_current: i32,
+        cumulatives: &Observation,
+    ) -> (i64, u128, u32) {
+        if tick_current < tick_lower_index {
+            (
+                tick_lower.tick_cumulative_outside
+                    .wrapping_sub(tick_upper.tick_cumulative_outside),
+                tick_lower.seconds_per_liquidity_outside_x64
+                    .wrapping_sub(tick_upper.seconds_per_liquidity_outside_x64),
+                tick_lower.seconds_outside
+                    .wrapping_sub(tick_upper.seconds_outside),
+            )
+        } else if tick_current < tick_upper_index {
+            (
+                cumulatives.tick_cumulative
+                    .wrapping_sub(tick_lower.tick_cumulative_outside)
+                    .wrapping_sub(tick_upper.tick_cumulative_outside),
+                cumulatives.seconds_per_liquidity_cumulative_x64
+                    .wrapping_sub(tick_lower.seconds_per_liquidity_outside_x64)
+                    .wrapping_sub(tick_upper.seconds_per_liquidity_outside_x64),
+                cumulatives.block_timestamp
+                    .wrapping_sub(tick_lower.seconds_outside)
+                    .wrapping_sub(tick_upper.seconds_outside),
+            )
+        } else {
+            (
+                tick_upper.tick_cumulative_outside
+                    .wrapping_sub(tick_lower.tick_cumulative_outside),
+                tick_upper.seconds_per_liquidity_outside_x64
+                    .wrapping_sub(tick_lower.seconds_per_liquidity_outside_x64),
+                tick_upper.seconds_outside
+                    .wrapping_sub(tick_lower.seconds_outside),
+            )
+        }
+    }
+}
@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
+use anchor_lang::error::ErrorCode;
+use std::cell::{Ref, RefMut};
 use crate::state::tick::Tick;
+use crate::state::oracle::Observation;
 use crate::constants::TICK_ARRAY_SIZE;
 use crate::errors::SuniswapError;
 
@@ -44,6 +47,36 @@ impl TickArray {
         Pubkey::new_from_array(self.pool)
     }
 
+    /// Borrow the tick array held by a plain account
+    ///
+    /// For accounts that may be created earlier in the same instruction and so
+    /// cannot be wrapped in an `AccountLoader`. Performs the same owner and
+    /// discriminator checks as `AccountLoader::load`
+    pub fn load_from<'a>(account_info: &'a AccountInfo) -> Result<Ref<'a, TickArray>> {
+        Self::check_account(account_info)?;
+        let data = account_info.try_borrow_data()?;
+        Ok(Ref::map(data, |data| bytemuck::from_bytes(&data[8..Self::LEN])))
+    }
+
+    /// Mutably borrow the tick array held by a plain account, see `load_from`
+    pub fn load_mut_from<'a>(account_info: &'a AccountInfo) -> Result<RefMut<'a, TickArray>> {
+        require!(account_info.is_writable, ErrorCode::AccountNotMutable);
+        Self::check_account(account_info)?;
+        let data = account_info.try_borrow_mut_data()?;
+        Ok(RefMut::map(data, |data| bytemuck::from_bytes_mut(&mut data[8..Self::LEN])))
+    }
+
+    fn check_account(account_info: &AccountInfo) -> Result<()> {
+        require_keys_eq!(*account_info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+        let data = account_info.try_borrow_data()?;
+        require!(data.len() >= Self::LEN, ErrorCode::AccountDidNotDeserialize);
+        require!(
+            data[..8] == *TickArray::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        Ok(())
+    }
+
     /// Calculate the start index for a tick array containing a given tick
     pub fn get_start_tick_index(tick_index: i32, tick_spacing: u16) -> i32 {
         let ticks_per_array = (TICK_ARRAY_SIZE as i32) * (tick_spacing as i32);
@@ -144,6 +177,7 @@ impl TickArray {
         liquidity_delta: i128,
         fee_growth_global_a_x128: u128,
         fee_growth_global_b_x128: u128,
+        cumulatives: &Observation,
         upper: bool,
     ) -> Result<bool> {
         let tick = self.get_tick_mut(tick_index, tick_spacing)?;
@@ -153,14 +187,17 @@ impl TickArray {
             liquidity_delta,
             fee_growth_global_a_x128,
             fee_growth_global_b_x128,
+            cumulatives,
             upper,
         )?;
 
         if flipped {
-            if tick.is_initialized() {
-                self.set_tick_initialized(tick_index, tick_spacing)?;
-            } else {
+            if tick.liquidity_gross == 0 {
+                // No position references this tick anymore; drop its fee snapshots too
+                tick.clear();
                 self.clear_tick_initialized(tick_index, tick_spacing)?;
+            } else {
+                self.set_tick_initialized(tick_index, tick_spacing)?;
             }
         }
 
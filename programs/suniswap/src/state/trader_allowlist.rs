@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+/// Admin of a pool's trader allowlist
+/// PDA: ["trader_allowlist", pool]
+#[account]
+#[derive(Debug)]
+pub struct TraderAllowlist {
+    /// The pool this allowlist gates
+    pub pool: Pubkey,
+
+    /// Wallet that adds and removes allowed traders and switches the allowlist on and off
+    pub admin: Pubkey,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+
+    /// Reserved for future use
+    pub _reserved: [u8; 32],
+}
+
+impl TraderAllowlist {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +                     // pool
+        32 +                     // admin
+        1 +                      // bump
+        32;                      // reserved
+}
+
+/// A wallet allowed to swap and add liquidity in an allowlisted pool
+/// PDA: ["allowed_trader", pool, wallet]
+#[account]
+#[derive(Debug)]
+pub struct AllowedTrader {
+    /// The pool the wallet may trade in
+    pub pool: Pubkey,
+
+    /// The allowed wallet
+    pub wallet: Pubkey,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl AllowedTrader {
+    pub const LEN: usize = 8 +  // discriminator
+        32 +                     // pool
+        32 +                     // wallet
+        1;                       // bump
+}
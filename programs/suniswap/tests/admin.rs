@@ -0,0 +1,654 @@
+mod common;
+
+use anchor_lang::prelude::{Pubkey, Rent};
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use common::*;
+use suniswap::constants::pool_creation;
+use suniswap::errors::SuniswapError;
+use suniswap::events::{
+    FeeTierUpdatedEvent, MaxCreatorFeeRateUpdatedEvent, MaxReferralFeeRateUpdatedEvent, PoolCircuitBreakerUpdatedEvent,
+    PoolCreationFeePaidEvent, PoolInitializedEvent,
+};
+use suniswap::instructions::AdaptiveFeeParams;
+use suniswap::state::{FeeTier, FeeTierIndex, Pool, PoolCreationConfig, SuniswapConfig};
+
+// ═══════════════════════════════════════════════════════════════════════════
+// CONFIG
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_initialize_config() {
+    let ctx = TestContext::with_config(20);
+
+    let config = ctx.config();
+    assert_eq!(config.protocol_authority, ctx.authority);
+    assert_eq!(config.fee_authority, ctx.authority);
+    assert_eq!(config.default_protocol_fee_rate, 20);
+    assert!(!config.pool_creation_paused);
+    assert_eq!(config.fee_tier_count, 0);
+}
+
+#[test]
+fn test_initialize_config_only_once() {
+    let mut ctx = TestContext::with_config(20);
+    assert!(ctx.initialize_config(10).is_err());
+    assert_eq!(ctx.config().default_protocol_fee_rate, 20);
+}
+
+#[test]
+fn test_initialize_config_rejects_high_protocol_fee() {
+    let mut ctx = TestContext::new();
+    assert_error(ctx.initialize_config(26), SuniswapError::ProtocolFeeTooHigh);
+    assert!(ctx.initialize_config(25).is_ok());
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// FEE TIERS
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_initialize_fee_tiers() {
+    let mut ctx = TestContext::with_config(10);
+    ctx.initialize_fee_tier(500, 10).unwrap();
+    ctx.initialize_fee_tier(3000, 60).unwrap();
+
+    let tier = ctx.fee_tier(&fee_tier_pda(3000));
+    assert_eq!(tier.config, config_pda());
+    assert_eq!(tier.fee_rate, 3000);
+    assert_eq!(tier.seed_fee_rate, 3000);
+    assert_eq!(tier.tick_spacing, 60);
+    assert_eq!(tier.index, 1);
+    assert!(!tier.disabled);
+    assert!(tier.seed_recorded && tier.index_registered);
+    assert_eq!(ctx.config().fee_tier_count, 2);
+
+    // Tiers are discoverable by index without knowing their rates
+    let tiers: Vec<Pubkey> = (0..ctx.config().fee_tier_count)
+        .map(|index| ctx.account::<FeeTierIndex>(&fee_tier_index_pda(index)).fee_tier)
+        .collect();
+    assert_eq!(tiers, vec![fee_tier_pda(500), fee_tier_pda(3000)]);
+}
+
+#[test]
+fn test_initialize_fee_tier_requires_protocol_authority() {
+    let mut ctx = TestContext::with_config(10);
+    let intruder = Pubkey::new_unique();
+    ctx.bank.airdrop(intruder, SOL);
+
+    let mut ix = ctx.ix_initialize_fee_tier(3000, 60);
+    replace_account(&mut ix, ctx.authority, intruder);
+    assert_error(ctx.bank.process(&ix), SuniswapError::NotProtocolAuthority);
+}
+
+#[test]
+fn test_initialize_fee_tier_validates_parameters() {
+    let mut ctx = TestContext::with_config(10);
+    assert_error(ctx.initialize_fee_tier(100_001, 60), SuniswapError::FeeRateTooHigh);
+    assert_error(ctx.initialize_fee_tier(3000, 0), SuniswapError::InvalidFeeTier);
+    assert_error(
+        ctx.initialize_fee_tier(3000, suniswap::constants::MAX_TICK_SPACING + 1),
+        SuniswapError::InvalidFeeTier,
+    );
+    assert!(ctx.initialize_fee_tier(100_000, 1).is_ok());
+}
+
+#[test]
+fn test_set_fee_tier_rate() {
+    let mut ctx = TestContext::with_config(10);
+    ctx.initialize_fee_tier(3000, 60).unwrap();
+    let tier = fee_tier_pda(3000);
+
+    ctx.set_fee_tier_rate(&tier, 2500).unwrap();
+    let state = ctx.fee_tier(&tier);
+    assert_eq!(state.fee_rate, 2500);
+    assert_eq!(state.seed_fee_rate, 3000);
+    let event = ctx.bank.events::<FeeTierUpdatedEvent>().pop().unwrap();
+    assert_eq!((event.fee_tier, event.fee_rate, event.disabled), (tier, 2500, false));
+
+    // The tier keeps its address, and so do pools created on it afterwards
+    let pool = ctx.create_pool(3000, sqrt_price_at(0)).unwrap();
+    assert_eq!(ctx.pool(&pool).fee_tier_pubkey(), tier);
+    assert_eq!(ctx.bank.events::<PoolInitializedEvent>().pop().unwrap().fee_rate, 2500);
+
+    // A tier with the new rate can still be created alongside it
+    ctx.initialize_fee_tier(2500, 50).unwrap();
+}
+
+#[test]
+fn test_set_fee_tier_rate_validation() {
+    let mut ctx = TestContext::with_config(10);
+    ctx.initialize_fee_tier(3000, 60).unwrap();
+    let tier = fee_tier_pda(3000);
+
+    assert_error(ctx.set_fee_tier_rate(&tier, 100_001), SuniswapError::FeeRateTooHigh);
+
+    let intruder = Pubkey::new_unique();
+    let mut ix = ctx.ix_set_fee_tier_rate(&tier, 500);
+    replace_account(&mut ix, ctx.authority, intruder);
+    assert_error(ctx.bank.process(&ix), SuniswapError::NotProtocolAuthority);
+
+    ctx.modify_account::<FeeTier>(&tier, |t| t.config = Pubkey::new_unique());
+    assert_error(ctx.set_fee_tier_rate(&tier, 500), SuniswapError::InvalidFeeTier);
+    assert_eq!(ctx.fee_tier(&tier).fee_rate, 3000);
+}
+
+#[test]
+fn test_fee_tier_from_before_seed_fee_rate() {
+    let (mut ctx, pool) = TestContext::with_pool(3000, 60, 0);
+    let lp = ctx.create_user(&pool, 1_000_000_000);
+    let trader = ctx.create_user(&pool, 1_000_000_000);
+    ctx.add_liquidity(&pool, &lp, -600, 600, 1_000_000_000);
+
+    // Tiers created before index, disabled and seed_fee_rate existed have
+    // zeroed reserved bytes from the bump onwards
+    ctx.bank.poke(&pool.fee_tier, |data| data[8 + 32 + 4 + 2 + 1..].fill(0));
+    assert_eq!(ctx.fee_tier(&pool.fee_tier).seed_fee_rate, 0);
+
+    // The pool still signs with the rate its tier was created with
+    ctx.swap(&pool, &trader, exact_in(10_000, true)).unwrap();
+
+    // Changing the rate records the seed first
+    ctx.set_fee_tier_rate(&pool.fee_tier, 2500).unwrap();
+    let tier = ctx.fee_tier(&pool.fee_tier);
+    assert_eq!((tier.fee_rate, tier.seed_fee_rate), (2500, 3000));
+    ctx.swap(&pool, &trader, exact_in(10_000, false)).unwrap();
+    ctx.set_fee_tier_disabled(&pool.fee_tier, true).unwrap();
+
+    // Zero-fee tiers are repriced like any other, with or without a recorded seed
+    ctx.initialize_fee_tier(0, 1).unwrap();
+    ctx.set_fee_tier_rate(&fee_tier_pda(0), 100).unwrap();
+    ctx.set_fee_tier_rate(&fee_tier_pda(0), 0).unwrap();
+    ctx.bank.poke(&fee_tier_pda(0), |data| data[8 + 32 + 4 + 2 + 1..].fill(0));
+    ctx.set_fee_tier_rate(&fee_tier_pda(0), 100).unwrap();
+    let tier = ctx.fee_tier(&fee_tier_pda(0));
+    assert_eq!((tier.fee_rate, tier.seed_fee_rate, tier.seed_recorded), (100, 0, true));
+    ctx.set_fee_tier_rate(&fee_tier_pda(0), 200).unwrap();
+}
+
+#[test]
+fn test_register_legacy_fee_tier_index() {
+    let mut ctx = TestContext::with_config(10);
+
+    // Tiers created before enumeration have zeroes from the bump onwards, no
+    // index entry, and aren't counted by the config
+    ctx.initialize_fee_tier(500, 10).unwrap();
+    let legacy = fee_tier_pda(500);
+    ctx.bank.poke(&legacy, |data| data[8 + 32 + 4 + 2 + 1..].fill(0));
+    ctx.bank.set_account(fee_tier_index_pda(0), AccountState::default());
+    ctx.modify_account::<SuniswapConfig>(&config_pda(), |config| config.fee_tier_count = 0);
+
+    // The first new tier takes index 0, which the legacy tier also reads
+    ctx.initialize_fee_tier(3000, 60).unwrap();
+    assert_eq!(ctx.fee_tier(&legacy).index, 0);
+
+    let intruder = Pubkey::new_unique();
+    ctx.bank.airdrop(intruder, SOL);
+    let mut ix = ctx.ix_register_fee_tier_index(&legacy);
+    replace_account(&mut ix, ctx.authority, intruder);
+    assert_error(ctx.bank.process(&ix), SuniswapError::NotProtocolAuthority);
+
+    // Registering gives it the next index
+    ctx.register_fee_tier_index(&legacy).unwrap();
+    let tier = ctx.fee_tier(&legacy);
+    assert_eq!((tier.index, tier.index_registered), (1, true));
+    let tiers: Vec<Pubkey> = (0..ctx.config().fee_tier_count)
+        .map(|index| ctx.account::<FeeTierIndex>(&fee_tier_index_pda(index)).fee_tier)
+        .collect();
+    assert_eq!(tiers, vec![fee_tier_pda(3000), legacy]);
+
+    // Once
+    assert_error(ctx.register_fee_tier_index(&legacy), SuniswapError::InvalidFeeTier);
+    assert_error(ctx.register_fee_tier_index(&fee_tier_pda(3000)), SuniswapError::InvalidFeeTier);
+}
+
+#[test]
+fn test_set_max_referral_fee_rate() {
+    let mut ctx = TestContext::with_config(10);
+    assert_eq!(ctx.config().max_referral_fee_rate, 0);
+
+    ctx.set_max_referral_fee_rate(100).unwrap();
+    assert_eq!(ctx.config().max_referral_fee_rate, 100);
+    let event = ctx.bank.events::<MaxReferralFeeRateUpdatedEvent>().pop().unwrap();
+    assert_eq!(event.max_referral_fee_rate, 100);
+
+    assert_error(
+        ctx.set_max_referral_fee_rate(suniswap::constants::referral::MAX_FEE_RATE + 1),
+        SuniswapError::ReferralFeeTooHigh,
+    );
+    let mut ix = ctx.ix_set_max_referral_fee_rate(0);
+    replace_account(&mut ix, ctx.authority, Pubkey::new_unique());
+    assert_error(ctx.bank.process(&ix), SuniswapError::NotProtocolAuthority);
+    assert_eq!(ctx.config().max_referral_fee_rate, 100);
+}
+
+#[test]
+fn test_set_max_creator_fee_rate() {
+    let mut ctx = TestContext::with_config(10);
+    assert_eq!(ctx.config().max_creator_fee_rate, 0);
+
+    ctx.set_max_creator_fee_rate(15).unwrap();
+    assert_eq!(ctx.config().max_creator_fee_rate, 15);
+    let event = ctx.bank.events::<MaxCreatorFeeRateUpdatedEvent>().pop().unwrap();
+    assert_eq!(event.max_creator_fee_rate, 15);
+
+    assert_error(
+        ctx.set_max_creator_fee_rate(suniswap::constants::MAX_CREATOR_FEE_RATE + 1),
+        SuniswapError::CreatorFeeTooHigh,
+    );
+    let mut ix = ctx.ix_set_max_creator_fee_rate(0);
+    replace_account(&mut ix, ctx.authority, Pubkey::new_unique());
+    assert_error(ctx.bank.process(&ix), SuniswapError::NotProtocolAuthority);
+    assert_eq!(ctx.config().max_creator_fee_rate, 15);
+}
+
+#[test]
+fn test_disabled_fee_tier_blocks_pool_creation() {
+    let (mut ctx, pool) = TestContext::with_pool(3000, 60, 0);
+
+    ctx.set_fee_tier_disabled(&pool.fee_tier, true).unwrap();
+    assert!(ctx.fee_tier(&pool.fee_tier).disabled);
+    assert!(ctx.bank.events::<FeeTierUpdatedEvent>().pop().unwrap().disabled);
+    assert_error(ctx.create_pool(3000, sqrt_price_at(0)).map(|_| ()), SuniswapError::FeeTierDisabled);
+
+    // Existing pools keep working
+    ctx.initialize_tick_array(&pool, 0).unwrap();
+
+    ctx.set_fee_tier_disabled(&pool.fee_tier, false).unwrap();
+    assert!(ctx.create_pool(3000, sqrt_price_at(0)).is_ok());
+}
+
+#[test]
+fn test_set_fee_tier_disabled_requires_protocol_authority() {
+    let (mut ctx, pool) = TestContext::with_pool(3000, 60, 0);
+    let intruder = Pubkey::new_unique();
+
+    let mut ix = ctx.ix_set_fee_tier_disabled(&pool.fee_tier, true);
+    replace_account(&mut ix, ctx.authority, intruder);
+    assert_error(ctx.bank.process(&ix), SuniswapError::NotProtocolAuthority);
+    assert!(!ctx.fee_tier(&pool.fee_tier).disabled);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// POOLS
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_initialize_pool() {
+    let (ctx, pool) = TestContext::with_pool(3000, 60, 1234);
+
+    let state = ctx.pool(&pool);
+    assert_eq!(state.sqrt_price_x64, sqrt_price_at(1234));
+    assert_eq!(state.tick_current, 1234);
+    assert_eq!(state.tick_spacing, 60);
+    assert_eq!(state.liquidity, 0);
+    assert_eq!(state.protocol_fee_rate, 10);
+    assert_eq!(state.config_pubkey(), config_pda());
+    assert_eq!(state.fee_tier_pubkey(), pool.fee_tier);
+    assert_eq!(state.token_vault_a_pubkey(), pool.vault_a);
+    assert_eq!(state.token_vault_b_pubkey(), pool.vault_b);
+    assert_eq!(ctx.token_balance(&pool.vault_a), 0);
+
+    let event = ctx.bank.events::<PoolInitializedEvent>().pop().unwrap();
+    assert_eq!(event.pool, pool.pool);
+    assert_eq!(event.tick_current, 1234);
+    assert_eq!(event.fee_rate, 3000);
+}
+
+#[test]
+fn test_initialize_pool_rejects_unordered_mints() {
+    let mut ctx = TestContext::with_config(10);
+    ctx.initialize_fee_tier(3000, 60).unwrap();
+    let (mint_a, mint_b) = ctx.create_mint_pair();
+
+    let ix = ctx.ix_initialize_pool(&mint_b, &mint_a, 3000, sqrt_price_at(0));
+    assert_error(ctx.bank.process(&ix), SuniswapError::InvalidTokenOrder);
+}
+
+#[test]
+fn test_initialize_pool_rejects_out_of_range_price() {
+    let mut ctx = TestContext::with_config(10);
+    ctx.initialize_fee_tier(3000, 60).unwrap();
+
+    assert_error(
+        ctx.create_pool(3000, suniswap::constants::MIN_SQRT_PRICE_X64 - 1).map(|_| ()),
+        SuniswapError::SqrtPriceBelowMinimum,
+    );
+    assert_error(
+        ctx.create_pool(3000, suniswap::constants::MAX_SQRT_PRICE_X64 + 1).map(|_| ()),
+        SuniswapError::SqrtPriceAboveMaximum,
+    );
+}
+
+#[test]
+fn test_initialize_pool_blocked_while_creation_paused() {
+    let mut ctx = TestContext::with_config(10);
+    ctx.initialize_fee_tier(3000, 60).unwrap();
+    ctx.modify_account::<SuniswapConfig>(&config_pda(), |c| c.pool_creation_paused = true);
+
+    assert_error(ctx.create_pool(3000, sqrt_price_at(0)).map(|_| ()), SuniswapError::PoolPaused);
+}
+
+#[test]
+fn test_initialize_pool_rejects_foreign_fee_tier() {
+    let mut ctx = TestContext::with_config(10);
+    ctx.initialize_fee_tier(3000, 60).unwrap();
+    ctx.modify_account::<FeeTier>(&fee_tier_pda(3000), |t| t.config = Pubkey::new_unique());
+
+    assert_error(ctx.create_pool(3000, sqrt_price_at(0)).map(|_| ()), SuniswapError::InvalidFeeTier);
+}
+
+#[test]
+fn test_migrate_legacy_pool() {
+    let (mut ctx, pool) = TestContext::with_pool(3000, 60, 0);
+    let lp = ctx.create_user(&pool, 1_000_000_000);
+    let trader = ctx.create_user(&pool, 1_000_000_000);
+    ctx.add_liquidity(&pool, &lp, -600, 600, 1_000_000_000);
+    ctx.swap(&pool, &trader, exact_in(10_000, true)).unwrap();
+    let before = ctx.pool(&pool);
+
+    // A pool created at the legacy layout: nothing after `oracle`, rent for that size only
+    let mut account = ctx.bank.account(&pool.pool).unwrap().clone();
+    account.data.truncate(Pool::LEGACY_LEN);
+    account.data[8 + 352..].fill(0);
+    account.lamports = Rent::default().minimum_balance(Pool::LEGACY_LEN);
+    ctx.bank.set_account(pool.pool, account);
+
+    let payer = Pubkey::new_unique();
+    ctx.bank.airdrop(payer, SOL);
+    let now = ctx.bank.clock().unix_timestamp + 100;
+    ctx.bank.warp_to(10, now);
+    ctx.migrate_pool(&pool, &payer).unwrap();
+
+    let account = ctx.bank.account(&pool.pool).unwrap();
+    assert_eq!(account.data.len(), Pool::LEN);
+    assert_eq!(account.lamports, Rent::default().minimum_balance(Pool::LEN));
+
+    let state = ctx.pool(&pool);
+    assert_eq!(state.sqrt_price_x64, before.sqrt_price_x64);
+    assert_eq!(state.liquidity, before.liquidity);
+    assert_eq!(state.fee_growth_global_a_x128, before.fee_growth_global_a_x128);
+    assert_eq!(state.protocol_fees_a, before.protocol_fees_a);
+    assert_eq!(state.fee_rate, 3000);
+    assert_eq!(state.fee_rate_override, 0);
+    assert_eq!(state.cumulatives_timestamp, now as u32);
+    assert_eq!(state.circuit_breaker_tick, state.tick_current);
+    assert_eq!(state.creator_pubkey(), Pubkey::default());
+
+    // The pool trades again, and only migrates once
+    ctx.swap(&pool, &trader, exact_in(10_000, false)).unwrap();
+    assert_error(ctx.migrate_pool(&pool, &payer), SuniswapError::PoolAlreadyMigrated);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// POOL CREATION ACCESS
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// `initialize_pool` for a new mint pair on the 0.3% tier, paid by `payer`
+fn ix_create_pool_as(ctx: &mut TestContext, payer: Pubkey) -> Instruction {
+    let (mint_a, mint_b) = ctx.create_mint_pair();
+    let mut ix = ctx.ix_initialize_pool(&mint_a, &mint_b, 3000, sqrt_price_at(0));
+    replace_account(&mut ix, ctx.authority, payer);
+    ctx.bank.airdrop(payer, 10 * SOL);
+    ix
+}
+
+#[test]
+fn test_pool_creation_allowlist() {
+    let mut ctx = TestContext::with_config(10);
+    ctx.initialize_fee_tier(3000, 60).unwrap();
+    let (listed, unlisted) = (Pubkey::new_unique(), Pubkey::new_unique());
+    ctx.initialize_pool_creation_config().unwrap();
+    ctx.set_pool_creation_allowlist(vec![listed]).unwrap();
+    ctx.set_pool_creation_mode(pool_creation::MODE_ALLOWLIST).unwrap();
+    assert_eq!(ctx.config().pool_creation_mode, pool_creation::MODE_ALLOWLIST);
+
+    // The allowlist has to be passed, and hold the payer
+    let ix = ix_create_pool_as(&mut ctx, listed);
+    assert_error(ctx.bank.process(&ix), SuniswapError::InvalidPoolCreationFeeAccount);
+    let mut ix = ix_create_pool_as(&mut ctx, unlisted);
+    use_pool_creation_config(&mut ix);
+    assert_error(ctx.bank.process(&ix), SuniswapError::PoolCreatorNotAllowed);
+    let mut ix = ix_create_pool_as(&mut ctx, listed);
+    use_pool_creation_config(&mut ix);
+    ctx.bank.process(&ix).unwrap();
+
+    // Reopening lets anyone create pools without it
+    ctx.set_pool_creation_mode(pool_creation::MODE_OPEN).unwrap();
+    let ix = ix_create_pool_as(&mut ctx, unlisted);
+    ctx.bank.process(&ix).unwrap();
+}
+
+#[test]
+fn test_pool_creation_fee_in_sol() {
+    let mut ctx = TestContext::with_config(10);
+    ctx.initialize_fee_tier(3000, 60).unwrap();
+    let treasury = Pubkey::new_unique();
+    ctx.initialize_pool_creation_config().unwrap();
+    ctx.set_pool_creation_fee(None, SOL, treasury).unwrap();
+    ctx.set_pool_creation_mode(pool_creation::MODE_FEE).unwrap();
+    let payer = Pubkey::new_unique();
+
+    // Only to the configured treasury
+    let mut ix = ix_create_pool_as(&mut ctx, payer);
+    use_creation_fee(&mut ix, Pubkey::new_unique(), None);
+    assert_error(ctx.bank.process(&ix), SuniswapError::InvalidPoolCreationFeeAccount);
+
+    let mut ix = ix_create_pool_as(&mut ctx, payer);
+    use_creation_fee(&mut ix, treasury, None);
+    ctx.bank.process(&ix).unwrap();
+    assert_eq!(ctx.bank.account(&treasury).unwrap().lamports, SOL);
+
+    let event = ctx.bank.events::<PoolCreationFeePaidEvent>().pop().unwrap();
+    assert_eq!(event.payer, payer);
+    assert_eq!(event.fee_mint, None);
+    assert_eq!(event.amount, SOL);
+    assert_eq!(event.treasury, treasury);
+}
+
+#[test]
+fn test_pool_creation_fee_in_token() {
+    let mut ctx = TestContext::with_config(10);
+    ctx.initialize_fee_tier(3000, 60).unwrap();
+    let (fee_mint, treasury) = (ctx.create_mint(), Pubkey::new_unique());
+    let treasury_account = ctx.create_associated_token_account(&fee_mint, &treasury);
+    ctx.initialize_pool_creation_config().unwrap();
+    ctx.set_pool_creation_fee(Some(fee_mint), 5_000, treasury).unwrap();
+    ctx.set_pool_creation_mode(pool_creation::MODE_FEE).unwrap();
+    let payer = Pubkey::new_unique();
+    let source = ctx.create_token_account(&fee_mint, &payer, 10_000);
+
+    // The fee token and the treasury's associated token account are required
+    let mut ix = ix_create_pool_as(&mut ctx, payer);
+    use_creation_fee(&mut ix, treasury_account, None);
+    assert_error(ctx.bank.process(&ix), SuniswapError::InvalidPoolCreationFeeAccount);
+    let other_account = ctx.create_token_account(&fee_mint, &treasury, 0);
+    let mut ix = ix_create_pool_as(&mut ctx, payer);
+    use_creation_fee(&mut ix, other_account, Some((fee_mint, source)));
+    assert_error(ctx.bank.process(&ix), SuniswapError::InvalidPoolCreationFeeAccount);
+
+    // The fee is paid through the fee mint's own token program
+    let mut ix = ix_create_pool_as(&mut ctx, payer);
+    use_creation_fee(&mut ix, treasury_account, Some((fee_mint, source)));
+    ix.accounts.pop();
+    ix.accounts.push(AccountMeta::new_readonly(suniswap::ID, false));
+    assert_error(ctx.bank.process(&ix), SuniswapError::InvalidPoolCreationFeeAccount);
+    ctx.bank.set_account(
+        anchor_spl::token_2022::ID,
+        AccountState { lamports: 1, executable: true, ..AccountState::default() },
+    );
+    let mut ix = ix_create_pool_as(&mut ctx, payer);
+    use_creation_fee(&mut ix, treasury_account, Some((fee_mint, source)));
+    ix.accounts.pop();
+    ix.accounts.push(AccountMeta::new_readonly(anchor_spl::token_2022::ID, false));
+    assert_error(ctx.bank.process(&ix), SuniswapError::InvalidPoolCreationFeeAccount);
+
+    let mut ix = ix_create_pool_as(&mut ctx, payer);
+    use_creation_fee(&mut ix, treasury_account, Some((fee_mint, source)));
+    ctx.bank.process(&ix).unwrap();
+    assert_eq!(ctx.token_balance(&treasury_account), 5_000);
+    assert_eq!(ctx.token_balance(&source), 5_000);
+    let event = ctx.bank.events::<PoolCreationFeePaidEvent>().pop().unwrap();
+    assert_eq!(event.fee_mint, Some(fee_mint));
+}
+
+#[test]
+fn test_pool_creation_mode_validation() {
+    let mut ctx = TestContext::with_config(10);
+
+    // Allowlist and fee modes need the pool creation config, fee mode a fee
+    assert_error(
+        ctx.set_pool_creation_mode(pool_creation::MODE_ALLOWLIST),
+        SuniswapError::InvalidPoolCreationMode,
+    );
+    ctx.initialize_pool_creation_config().unwrap();
+    assert_error(ctx.set_pool_creation_mode(pool_creation::MODE_FEE), SuniswapError::InvalidPoolCreationMode);
+    assert_error(ctx.set_pool_creation_mode(3), SuniswapError::InvalidPoolCreationMode);
+
+    let treasury = Pubkey::new_unique();
+    ctx.set_pool_creation_fee(None, SOL, treasury).unwrap();
+    ctx.set_pool_creation_mode(pool_creation::MODE_FEE).unwrap();
+    assert_error(ctx.set_pool_creation_fee(None, 0, treasury), SuniswapError::InvalidPoolCreationMode);
+
+    let too_many = vec![Pubkey::new_unique(); pool_creation::MAX_ALLOWED_CREATORS + 1];
+    assert_error(ctx.set_pool_creation_allowlist(too_many), SuniswapError::InvalidPoolCreationAllowlist);
+    let policy = ctx.account::<PoolCreationConfig>(&pool_creation_config_pda());
+    assert_eq!(policy.allowed_creator_count, 0);
+    assert_eq!(policy.fee_amount, SOL);
+
+    // All of it is the protocol authority's
+    let intruder = Pubkey::new_unique();
+    for mut ix in [
+        ctx.ix_set_pool_creation_mode(pool_creation::MODE_OPEN),
+        ctx.ix_set_pool_creation_allowlist(vec![intruder]),
+        ctx.ix_set_pool_creation_fee(None, 1, intruder),
+    ] {
+        replace_account(&mut ix, ctx.authority, intruder);
+        assert_error(ctx.bank.process(&ix), SuniswapError::NotProtocolAuthority);
+    }
+    assert_eq!(ctx.config().pool_creation_mode, pool_creation::MODE_FEE);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// TICK ARRAYS
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_initialize_tick_array() {
+    let (mut ctx, pool) = TestContext::with_pool(3000, 60, 0);
+    ctx.initialize_tick_array(&pool, -480).unwrap();
+
+    let array = ctx.tick_array(&tick_array_pda(&pool.pool, -480));
+    assert_eq!(array.pool_pubkey(), pool.pool);
+    assert_eq!(array.start_tick_index, -480);
+    assert_eq!(array.initialized_bitmap, 0);
+}
+
+#[test]
+fn test_initialize_tick_array_validates_start() {
+    // 8 ticks of spacing 60 per array
+    let (mut ctx, pool) = TestContext::with_pool(3000, 60, 0);
+
+    assert_error(ctx.initialize_tick_array(&pool, 60), SuniswapError::InvalidTickArrayStart);
+    assert_error(ctx.initialize_tick_array(&pool, -444_000), SuniswapError::TickBelowMinimum);
+    assert_error(ctx.initialize_tick_array(&pool, 443_520), SuniswapError::TickAboveMaximum);
+    assert!(ctx.initialize_tick_array(&pool, -443_520).is_ok());
+    assert!(ctx.initialize_tick_array(&pool, 443_040).is_ok());
+}
+
+#[test]
+fn test_initialize_tick_array_blocked_while_pool_paused() {
+    let (mut ctx, pool) = TestContext::with_pool(3000, 60, 0);
+    ctx.modify_zero_copy::<Pool>(&pool.pool, |p| p.is_paused = 1);
+    assert_error(ctx.initialize_tick_array(&pool, 0), SuniswapError::PoolPaused);
+}
+
+#[test]
+fn test_set_pool_fee_rate_validation() {
+    let (mut ctx, pool) = TestContext::with_pool(3000, 60, 0);
+
+    assert_error(ctx.set_pool_fee_rate(&pool, Some(100_001)), SuniswapError::FeeRateTooHigh);
+
+    let intruder = Pubkey::new_unique();
+    let mut ix = ctx.ix_set_pool_fee_rate(&pool, Some(500));
+    replace_account(&mut ix, ctx.authority, intruder);
+    assert_error(ctx.bank.process(&ix), SuniswapError::NotProtocolAuthority);
+
+    ctx.set_pool_fee_rate(&pool, Some(100_000)).unwrap();
+    let state = ctx.pool(&pool);
+    assert_eq!(state.fee_rate, 100_000);
+    assert_eq!(state.fee_rate_override, 1);
+}
+
+#[test]
+fn test_set_pool_adaptive_fee_validation() {
+    let (mut ctx, pool) = TestContext::with_pool(3000, 60, 0);
+    let params = AdaptiveFeeParams {
+        min_fee_rate: 1000,
+        max_fee_rate: 10_000,
+        volatility_fee_factor: 10,
+        volatility_decay_period: 60,
+    };
+
+    let inverted = AdaptiveFeeParams { min_fee_rate: 20_000, ..params.clone() };
+    assert_error(ctx.set_pool_adaptive_fee(&pool, Some(inverted)), SuniswapError::InvalidAdaptiveFeeParams);
+    let no_decay = AdaptiveFeeParams { volatility_decay_period: 0, ..params.clone() };
+    assert_error(ctx.set_pool_adaptive_fee(&pool, Some(no_decay)), SuniswapError::InvalidAdaptiveFeeParams);
+    let too_high = AdaptiveFeeParams { max_fee_rate: 100_001, ..params.clone() };
+    assert_error(ctx.set_pool_adaptive_fee(&pool, Some(too_high)), SuniswapError::FeeRateTooHigh);
+
+    let intruder = Pubkey::new_unique();
+    let mut ix = ctx.ix_set_pool_adaptive_fee(&pool, Some(params.clone()));
+    replace_account(&mut ix, ctx.authority, intruder);
+    assert_error(ctx.bank.process(&ix), SuniswapError::NotProtocolAuthority);
+
+    ctx.set_pool_adaptive_fee(&pool, Some(params)).unwrap();
+    let state = ctx.pool(&pool);
+    assert_eq!(state.adaptive_fee_enabled, 1);
+    assert_eq!((state.min_fee_rate, state.max_fee_rate), (1000, 10_000));
+    assert_eq!((state.volatility_fee_factor, state.volatility_decay_period), (10, 60));
+}
+
+#[test]
+fn test_set_pool_circuit_breaker() {
+    let (mut ctx, pool) = TestContext::with_pool(3000, 60, 0);
+    let state = ctx.pool(&pool);
+    assert_eq!((state.max_tick_move_per_swap, state.max_tick_move_per_slot), (0, 0));
+
+    let intruder = Pubkey::new_unique();
+    let mut ix = ctx.ix_set_pool_circuit_breaker(&pool, 100, 300);
+    replace_account(&mut ix, ctx.authority, intruder);
+    assert_error(ctx.bank.process(&ix), SuniswapError::NotProtocolAuthority);
+
+    ctx.set_pool_circuit_breaker(&pool, 100, 300).unwrap();
+    let event = ctx.bank.events::<PoolCircuitBreakerUpdatedEvent>().pop().unwrap();
+    assert_eq!(
+        (event.pool, event.max_tick_move_per_swap, event.max_tick_move_per_slot),
+        (pool.pool, 100, 300)
+    );
+    let state = ctx.pool(&pool);
+    assert_eq!((state.max_tick_move_per_swap, state.max_tick_move_per_slot), (100, 300));
+}
+
+#[test]
+fn test_set_pool_twap_guard_validation() {
+    let (mut ctx, pool) = TestContext::with_pool(3000, 60, 0);
+
+    assert_error(ctx.set_pool_twap_guard(&pool, 100, 50), SuniswapError::OracleNotInitialized);
+    assert_error(ctx.initialize_oracle(&pool, 0), SuniswapError::InvalidTwapWindow);
+
+    let intruder = Pubkey::new_unique();
+    ctx.bank.airdrop(intruder, 10 * SOL);
+    let mut ix = ctx.ix_initialize_oracle(&pool, 100);
+    replace_account(&mut ix, ctx.authority, intruder);
+    assert_error(ctx.bank.process(&ix), SuniswapError::NotProtocolAuthority);
+    ctx.initialize_oracle(&pool, 100).unwrap();
+
+    assert_error(ctx.set_pool_twap_guard(&pool, 0, 50), SuniswapError::InvalidTwapWindow);
+    let mut ix = ctx.ix_set_pool_twap_guard(&pool, 300, 50);
+    replace_account(&mut ix, ctx.authority, intruder);
+    assert_error(ctx.bank.process(&ix), SuniswapError::NotProtocolAuthority);
+
+    ctx.set_pool_twap_guard(&pool, 300, 50).unwrap();
+    let state = ctx.pool(&pool);
+    assert_eq!((state.twap_window, state.max_twap_deviation_ticks), (300, 50));
+}
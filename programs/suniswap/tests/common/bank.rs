@@ -0,0 +1,401 @@
+//! In-process runtime for integration tests
+//!
+//! Executes instructions by calling the program's `entry` directly, with
+//! account memory laid out the way the BPF loader serializes it so that
+//! `realloc`, `assign` and zero-copy loads behave as on chain.
+//! CPIs are routed through the syscall stubs to the real SPL Token
+//! processor and to a minimal System Program.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::Once;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_lang::solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+use anchor_lang::system_program;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+
+/// Bytes before account data in a serialized account region:
+/// 4 padding, 4 original_data_len, 32 key, 32 owner, 8 lamports, 8 data_len
+const HEADER_LEN: usize = 88;
+
+/// Owner recorded for program accounts (token program, system program)
+const LOADER_ID: Pubkey = pubkey!("BPFLoaderUpgradeab1e11111111111111111111111");
+
+thread_local! {
+    static CLOCK: RefCell<Clock> = RefCell::new(Clock::default());
+    static LOGS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    static EVENTS: RefCell<Vec<Vec<u8>>> = const { RefCell::new(Vec::new()) };
+    static RETURN_DATA: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Stored state of a single account
+#[derive(Clone, Debug, Default)]
+pub struct AccountState {
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub owner: Pubkey,
+    pub executable: bool,
+}
+
+/// Accounts plus the sysvars the program can read
+pub struct Bank {
+    accounts: HashMap<Pubkey, AccountState>,
+    /// Log lines from the last processed instruction
+    pub logs: Vec<String>,
+    events: Vec<Vec<u8>>,
+    return_data: Vec<u8>,
+}
+
+impl Default for Bank {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Bank {
+    pub fn new() -> Self {
+        static STUBS: Once = Once::new();
+        STUBS.call_once(|| {
+            set_syscall_stubs(Box::new(TestStubs));
+        });
+
+        let mut bank = Self {
+            accounts: HashMap::new(),
+            logs: Vec::new(),
+            events: Vec::new(),
+            return_data: Vec::new(),
+        };
+        for program in [system_program::ID, spl_token::ID, suniswap::ID] {
+            bank.set_account(
+                program,
+                AccountState {
+                    lamports: 1,
+                    data: Vec::new(),
+                    owner: LOADER_ID,
+                    executable: true,
+                },
+            );
+        }
+        bank.warp_to(1, 1_700_000_000);
+        bank
+    }
+
+    pub fn account(&self, key: &Pubkey) -> Option<&AccountState> {
+        self.accounts.get(key)
+    }
+
+    pub fn set_account(&mut self, key: Pubkey, account: AccountState) {
+        self.accounts.insert(key, account);
+    }
+
+    /// Mutate an account's raw data in place, for forcing states no instruction can reach
+    pub fn poke(&mut self, key: &Pubkey, f: impl FnOnce(&mut Vec<u8>)) {
+        f(&mut self.accounts.get_mut(key).expect("account exists").data);
+    }
+
+    /// Fund a system-owned wallet
+    pub fn airdrop(&mut self, key: Pubkey, lamports: u64) {
+        let account = self.accounts.entry(key).or_default();
+        account.lamports += lamports;
+    }
+
+    /// Set the clock seen by `Clock::get()`
+    pub fn warp_to(&mut self, slot: u64, unix_timestamp: i64) {
+        CLOCK.with(|clock| {
+            let mut clock = clock.borrow_mut();
+            clock.slot = slot;
+            clock.unix_timestamp = unix_timestamp;
+        });
+    }
+
+    pub fn clock(&self) -> Clock {
+        CLOCK.with(|clock| clock.borrow().clone())
+    }
+
+    /// Decode every event of type `T` emitted by the last instruction
+    pub fn events<T: AnchorDeserialize + Discriminator>(&self) -> Vec<T> {
+        self.events
+            .iter()
+            .filter(|data| data.starts_with(T::DISCRIMINATOR))
+            .map(|data| T::deserialize(&mut &data[T::DISCRIMINATOR.len()..]).unwrap())
+            .collect()
+    }
+
+    /// Decode the return data set by the last instruction
+    pub fn return_data<T: AnchorDeserialize>(&self) -> T {
+        T::deserialize(&mut self.return_data.as_slice()).unwrap()
+    }
+
+    /// Execute a top-level instruction against the program
+    /// Account changes are committed only if the instruction succeeds
+    pub fn process(&mut self, ix: &Instruction) -> std::result::Result<(), ProgramError> {
+        assert_eq!(ix.program_id, suniswap::ID, "only the program under test is executable");
+        LOGS.with(|logs| logs.borrow_mut().clear());
+        EVENTS.with(|events| events.borrow_mut().clear());
+        RETURN_DATA.with(|data| data.borrow_mut().clear());
+
+        // One region per unique key; duplicate metas share the region like the runtime does
+        let mut order: Vec<Pubkey> = Vec::new();
+        let mut privileges: HashMap<Pubkey, (bool, bool)> = HashMap::new();
+        for meta in &ix.accounts {
+            let entry = privileges.entry(meta.pubkey).or_insert_with(|| {
+                order.push(meta.pubkey);
+                (false, false)
+            });
+            entry.0 |= meta.is_signer;
+            entry.1 |= meta.is_writable;
+        }
+
+        let mut regions: Vec<Region> = order
+            .iter()
+            .map(|key| Region::new(key, self.accounts.get(key).cloned().unwrap_or_default()))
+            .collect();
+
+        let result = {
+            let infos_by_key: Vec<AccountInfo> = regions
+                .iter_mut()
+                .zip(order.iter())
+                .map(|(region, key)| {
+                    let (is_signer, is_writable) = privileges[key];
+                    // SAFETY: regions outlive the infos, which are dropped at the end of this block
+                    unsafe { region.account_info(is_signer, is_writable) }
+                })
+                .collect();
+            let infos: Vec<AccountInfo> = ix
+                .accounts
+                .iter()
+                .map(|meta| {
+                    let index = order.iter().position(|key| *key == meta.pubkey).unwrap();
+                    infos_by_key[index].clone()
+                })
+                .collect();
+
+            suniswap::entry(&ix.program_id, &infos, &ix.data)
+        };
+
+        self.logs = LOGS.with(|logs| logs.take());
+        self.events = EVENTS.with(|events| events.take());
+        self.return_data = RETURN_DATA.with(|data| data.take());
+        result?;
+
+        for (region, key) in regions.iter().zip(order.iter()) {
+            let after = region.read();
+            let before = self.accounts.get(key).cloned().unwrap_or_default();
+            if !privileges[key].1 {
+                assert_eq!(before.data, after.data, "read-only account {key} was modified");
+                assert_eq!(before.lamports, after.lamports, "read-only account {key} lamports changed");
+            }
+            // The runtime drops accounts left without lamports, whatever their data
+            if after.lamports == 0 {
+                self.accounts.remove(key);
+            } else {
+                self.accounts.insert(*key, after);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Serialized account memory, 16-byte aligned so zero-copy loads of `u128`
+/// fields succeed on the host (data starts 8 bytes before a 16-byte boundary,
+/// putting the post-discriminator struct on one)
+struct Region {
+    memory: Vec<u128>,
+    executable: bool,
+}
+
+impl Region {
+    fn new(key: &Pubkey, account: AccountState) -> Self {
+        let len = HEADER_LEN + account.data.len() + MAX_PERMITTED_DATA_INCREASE;
+        let mut region = Self {
+            memory: vec![0u128; len.div_ceil(16)],
+            executable: account.executable,
+        };
+        let bytes = region.bytes_mut();
+        bytes[4..8].copy_from_slice(&(account.data.len() as u32).to_le_bytes());
+        bytes[8..40].copy_from_slice(key.as_ref());
+        bytes[40..72].copy_from_slice(account.owner.as_ref());
+        bytes[72..80].copy_from_slice(&account.lamports.to_le_bytes());
+        bytes[80..88].copy_from_slice(&(account.data.len() as u64).to_le_bytes());
+        bytes[HEADER_LEN..HEADER_LEN + account.data.len()].copy_from_slice(&account.data);
+        region
+    }
+
+    fn bytes_mut(&mut self) -> &mut [u8] {
+        let len = self.memory.len() * 16;
+        // SAFETY: u128 storage reinterpreted as bytes of the same length
+        unsafe { std::slice::from_raw_parts_mut(self.memory.as_mut_ptr() as *mut u8, len) }
+    }
+
+    fn bytes(&self) -> &[u8] {
+        let len = self.memory.len() * 16;
+        // SAFETY: u128 storage reinterpreted as bytes of the same length
+        unsafe { std::slice::from_raw_parts(self.memory.as_ptr() as *const u8, len) }
+    }
+
+    /// # Safety
+    /// The returned info aliases this region and must not outlive it
+    unsafe fn account_info<'a>(&mut self, is_signer: bool, is_writable: bool) -> AccountInfo<'a> {
+        let base = self.memory.as_mut_ptr() as *mut u8;
+        let data_len = u64::from_le_bytes(self.bytes()[80..88].try_into().unwrap()) as usize;
+        AccountInfo {
+            key: &*(base.add(8) as *const Pubkey),
+            owner: &*(base.add(40) as *const Pubkey),
+            lamports: Rc::new(RefCell::new(&mut *(base.add(72) as *mut u64))),
+            data: Rc::new(RefCell::new(std::slice::from_raw_parts_mut(
+                base.add(HEADER_LEN),
+                data_len,
+            ))),
+            is_signer,
+            is_writable,
+            executable: self.executable,
+            rent_epoch: u64::MAX,
+        }
+    }
+
+    fn read(&self) -> AccountState {
+        let bytes = self.bytes();
+        let data_len = u64::from_le_bytes(bytes[80..88].try_into().unwrap()) as usize;
+        AccountState {
+            owner: Pubkey::try_from(&bytes[40..72]).unwrap(),
+            lamports: u64::from_le_bytes(bytes[72..80].try_into().unwrap()),
+            data: bytes[HEADER_LEN..HEADER_LEN + data_len].to_vec(),
+            executable: self.executable,
+        }
+    }
+}
+
+/// Syscall stubs: sysvars, log and return data capture, and CPI dispatch
+struct TestStubs;
+
+impl SyscallStubs for TestStubs {
+    fn sol_log(&self, message: &str) {
+        LOGS.with(|logs| logs.borrow_mut().push(format!("Program log: {message}")));
+    }
+
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        EVENTS.with(|events| events.borrow_mut().push(fields.concat()));
+    }
+
+    fn sol_set_return_data(&self, data: &[u8]) {
+        RETURN_DATA.with(|return_data| *return_data.borrow_mut() = data.to_vec());
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let clock = CLOCK.with(|clock| clock.borrow().clone());
+        // SAFETY: the caller passes a pointer to a Clock
+        unsafe { *(var_addr as *mut Clock) = clock };
+        0
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        // SAFETY: the caller passes a pointer to a Rent
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        0
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> std::result::Result<(), ProgramError> {
+        let pda_signers: Vec<Pubkey> = signers_seeds
+            .iter()
+            .map(|seeds| Pubkey::create_program_address(seeds, &suniswap::ID).unwrap())
+            .collect();
+
+        let mut infos = Vec::with_capacity(instruction.accounts.len());
+        for meta in &instruction.accounts {
+            let caller = account_infos
+                .iter()
+                .find(|info| *info.key == meta.pubkey)
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            if meta.is_signer && !caller.is_signer && !pda_signers.contains(&meta.pubkey) {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if meta.is_writable && !caller.is_writable {
+                return Err(ProgramError::InvalidArgument);
+            }
+            let mut info = caller.clone();
+            info.is_signer = meta.is_signer;
+            info.is_writable = meta.is_writable;
+            infos.push(info);
+        }
+
+        if instruction.program_id == spl_token::ID {
+            spl_token::processor::Processor::process(&spl_token::ID, &infos, &instruction.data)
+        } else if instruction.program_id == system_program::ID {
+            process_system_instruction(&infos, &instruction.data)
+        } else {
+            Err(ProgramError::IncorrectProgramId)
+        }
+    }
+}
+
+/// The subset of the System Program that Anchor's `init` uses
+fn process_system_instruction(
+    infos: &[AccountInfo],
+    data: &[u8],
+) -> std::result::Result<(), ProgramError> {
+    let read_u64 = |at: usize| u64::from_le_bytes(data[at..at + 8].try_into().unwrap());
+    let read_key = |at: usize| Pubkey::try_from(&data[at..at + 32]).unwrap();
+    let tag = u32::from_le_bytes(data[0..4].try_into().unwrap());
+
+    let require_signer = |info: &AccountInfo| {
+        if info.is_signer {
+            Ok(())
+        } else {
+            Err(ProgramError::MissingRequiredSignature)
+        }
+    };
+    let transfer = |from: &AccountInfo, to: &AccountInfo, lamports: u64| {
+        require_signer(from)?;
+        if *from.owner != system_program::ID {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        let remaining = from
+            .lamports()
+            .checked_sub(lamports)
+            .ok_or(ProgramError::InsufficientFunds)?;
+        **from.try_borrow_mut_lamports()? = remaining;
+        **to.try_borrow_mut_lamports()? += lamports;
+        Ok(())
+    };
+    let allocate = |info: &AccountInfo, space: u64| {
+        require_signer(info)?;
+        if !info.data_is_empty() || *info.owner != system_program::ID {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+        info.resize(space as usize)
+    };
+    let assign = |info: &AccountInfo, owner: &Pubkey| {
+        require_signer(info)?;
+        info.assign(owner);
+        Ok(())
+    };
+
+    match tag {
+        // CreateAccount { lamports, space, owner }
+        0 => {
+            if infos[1].lamports() != 0 {
+                return Err(ProgramError::AccountAlreadyInitialized);
+            }
+            transfer(&infos[0], &infos[1], read_u64(4))?;
+            allocate(&infos[1], read_u64(12))?;
+            assign(&infos[1], &read_key(20))
+        }
+        // Assign { owner }
+        1 => assign(&infos[0], &read_key(4)),
+        // Transfer { lamports }
+        2 => transfer(&infos[0], &infos[1], read_u64(4)),
+        // Allocate { space }
+        8 => allocate(&infos[0], read_u64(4)),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
@@ -0,0 +1,2296 @@
+//! Shared fixtures for the integration tests
+//!
+//! `TestContext` wraps a `Bank` with the protocol authority, mints and token
+//! accounts that most scenarios need. Every instruction has an `ix_*` builder
+//! (so error tests can swap accounts before sending) and a method that builds
+//! and processes it in one step.
+
+#![allow(dead_code)]
+
+pub mod bank;
+
+pub use bank::{AccountState, Bank};
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_error::ProgramError;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::system_program;
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use spl_token::state as token_state;
+
+use suniswap::constants::seeds;
+use suniswap::errors::SuniswapError;
+use suniswap::instructions::{
+    AdaptiveFeeParams, CumulativesInside, IncreaseLiquidityByAmountsParams, RebalanceVaultParams,
+    RepositionParams, SwapParams, VaultParams,
+};
+use suniswap::state::{FeeTier, ManagedVault, Pool, Position, ProtocolFeeRecipient, SuniswapConfig, TickArray};
+
+pub type TxResult = std::result::Result<(), ProgramError>;
+
+pub const DECIMALS: u8 = 6;
+pub const SOL: u64 = 1_000_000_000;
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey = pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+/// Assert that an instruction failed with the given program error
+pub fn assert_error(result: TxResult, expected: SuniswapError) {
+    let expected_code = ProgramError::from(anchor_lang::error::Error::from(expected));
+    assert_eq!(result, Err(expected_code), "expected {expected:?}");
+}
+
+/// Replace every occurrence of `from` in an instruction's accounts
+pub fn replace_account(ix: &mut Instruction, from: Pubkey, to: Pubkey) {
+    for meta in ix.accounts.iter_mut().filter(|meta| meta.pubkey == from) {
+        meta.pubkey = to;
+    }
+}
+
+/// Omit `token_account` from an instruction that ends with the optional
+/// native SOL accounts, moving native SOL for `user` in its place
+pub fn use_native_sol(ix: &mut Instruction, token_account: Pubkey, user: &Pubkey) {
+    let meta = ix.accounts.iter_mut().find(|meta| meta.pubkey == token_account).unwrap();
+    *meta = AccountMeta::new_readonly(suniswap::ID, false);
+    let len = ix.accounts.len();
+    ix.accounts[len - 2] = AccountMeta::new(native_sol_pda(user), false);
+    ix.accounts[len - 1] = AccountMeta::new_readonly(system_program::ID, false);
+}
+
+/// Take a swap's referral fee into `referrer_token_account`, passing the
+/// config and referrer accounts ahead of the native SOL accounts
+pub fn use_referral(ix: &mut Instruction, referrer_token_account: Pubkey) {
+    let len = ix.accounts.len();
+    ix.accounts[len - 4] = AccountMeta::new_readonly(config_pda(), false);
+    ix.accounts[len - 3] = AccountMeta::new(referrer_token_account, false);
+}
+
+/// Pass the pool creation config to `initialize_pool`, ahead of the creation fee accounts
+pub fn use_pool_creation_config(ix: &mut Instruction) {
+    let len = ix.accounts.len();
+    ix.accounts[len - 5] = AccountMeta::new_readonly(pool_creation_config_pda(), false);
+}
+
+/// Pay `initialize_pool`'s creation fee to `treasury`: the treasury wallet for
+/// a SOL fee, or with `token = (mint, source)` its associated token account
+pub fn use_creation_fee(ix: &mut Instruction, treasury: Pubkey, token: Option<(Pubkey, Pubkey)>) {
+    use_pool_creation_config(ix);
+    let len = ix.accounts.len();
+    if let Some((mint, source)) = token {
+        ix.accounts[len - 4] = AccountMeta::new_readonly(mint, false);
+        ix.accounts[len - 3] = AccountMeta::new(source, false);
+        ix.accounts[len - 1] = AccountMeta::new_readonly(spl_token::ID, false);
+    }
+    ix.accounts[len - 2] = AccountMeta::new(treasury, false);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// PDAs
+// ═══════════════════════════════════════════════════════════════════════════
+
+pub fn config_pda() -> Pubkey {
+    Pubkey::find_program_address(&[seeds::CONFIG_SEED], &suniswap::ID).0
+}
+
+pub fn fee_tier_pda(fee_rate: u32) -> Pubkey {
+    Pubkey::find_program_address(&[seeds::FEE_TIER_SEED, &fee_rate.to_le_bytes()], &suniswap::ID).0
+}
+
+pub fn fee_tier_index_pda(index: u16) -> Pubkey {
+    Pubkey::find_program_address(&[seeds::FEE_TIER_INDEX_SEED, &index.to_le_bytes()], &suniswap::ID).0
+}
+
+pub fn pool_pda(mint_a: &Pubkey, mint_b: &Pubkey, fee_rate: u32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[seeds::POOL_SEED, mint_a.as_ref(), mint_b.as_ref(), &fee_rate.to_le_bytes()],
+        &suniswap::ID,
+    )
+    .0
+}
+
+pub fn vault_pda(pool: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[seeds::POOL_VAULT_SEED, pool.as_ref(), mint.as_ref()], &suniswap::ID).0
+}
+
+pub fn tick_array_pda(pool: &Pubkey, start_tick_index: i32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[seeds::TICK_ARRAY_SEED, pool.as_ref(), &start_tick_index.to_le_bytes()],
+        &suniswap::ID,
+    )
+    .0
+}
+
+pub fn position_pda(pool: &Pubkey, owner: &Pubkey, tick_lower: i32, tick_upper: i32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            seeds::POSITION_SEED,
+            pool.as_ref(),
+            owner.as_ref(),
+            &tick_lower.to_le_bytes(),
+            &tick_upper.to_le_bytes(),
+        ],
+        &suniswap::ID,
+    )
+    .0
+}
+
+pub fn managed_vault_pda(pool: &Pubkey, id: u16) -> Pubkey {
+    Pubkey::find_program_address(&[seeds::MANAGED_VAULT_SEED, pool.as_ref(), &id.to_le_bytes()], &suniswap::ID).0
+}
+
+pub fn vault_share_mint_pda(vault: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[seeds::VAULT_SHARE_MINT_SEED, vault.as_ref()], &suniswap::ID).0
+}
+
+pub fn native_sol_pda(user: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[seeds::NATIVE_SOL_SEED, user.as_ref()], &suniswap::ID).0
+}
+
+pub fn vault_reserve_pda(vault: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[seeds::VAULT_RESERVE_SEED, vault.as_ref(), mint.as_ref()], &suniswap::ID).0
+}
+
+pub fn associated_token_address(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[owner.as_ref(), spl_token::ID.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
+
+pub fn protocol_fee_config_pda() -> Pubkey {
+    Pubkey::find_program_address(&[seeds::PROTOCOL_FEE_CONFIG_SEED], &suniswap::ID).0
+}
+
+pub fn pool_creation_config_pda() -> Pubkey {
+    Pubkey::find_program_address(&[seeds::POOL_CREATION_CONFIG_SEED], &suniswap::ID).0
+}
+
+pub fn trader_allowlist_pda(pool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[seeds::TRADER_ALLOWLIST_SEED, pool.as_ref()], &suniswap::ID).0
+}
+
+pub fn allowed_trader_pda(pool: &Pubkey, wallet: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[seeds::ALLOWED_TRADER_SEED, pool.as_ref(), wallet.as_ref()], &suniswap::ID).0
+}
+
+pub fn oracle_pda(pool: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[seeds::ORACLE_SEED, pool.as_ref()], &suniswap::ID).0
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// FIXTURES
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// A pool and the accounts derived from it
+#[derive(Clone, Debug)]
+pub struct PoolFixture {
+    pub pool: Pubkey,
+    pub fee_tier: Pubkey,
+    pub fee_rate: u32,
+    pub tick_spacing: u16,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub vault_a: Pubkey,
+    pub vault_b: Pubkey,
+}
+
+impl PoolFixture {
+    pub fn tick_array_start(&self, tick: i32) -> i32 {
+        TickArray::get_start_tick_index(tick, self.tick_spacing)
+    }
+
+    pub fn ticks_per_array(&self) -> i32 {
+        suniswap::constants::TICK_ARRAY_SIZE as i32 * self.tick_spacing as i32
+    }
+
+    pub fn tick_array(&self, tick: i32) -> Pubkey {
+        tick_array_pda(&self.pool, self.tick_array_start(tick))
+    }
+}
+
+/// A managed vault with its strategist and treasury
+#[derive(Clone, Debug)]
+pub struct VaultFixture {
+    pub vault: Pubkey,
+    pub share_mint: Pubkey,
+    pub reserve_a: Pubkey,
+    pub reserve_b: Pubkey,
+    pub strategist: User,
+    pub treasury: User,
+    /// Treasury's share token account
+    pub treasury_shares: Pubkey,
+}
+
+/// A funded wallet with token accounts for both pool mints
+#[derive(Clone, Debug)]
+pub struct User {
+    pub key: Pubkey,
+    pub token_a: Pubkey,
+    pub token_b: Pubkey,
+}
+
+pub struct TestContext {
+    pub bank: Bank,
+    /// Protocol and fee authority
+    pub authority: Pubkey,
+}
+
+impl Default for TestContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestContext {
+    /// A bank with a funded authority and no config
+    pub fn new() -> Self {
+        let mut bank = Bank::new();
+        let authority = Pubkey::new_unique();
+        bank.airdrop(authority, 100 * SOL);
+        Self { bank, authority }
+    }
+
+    /// A bank with the config initialized
+    pub fn with_config(protocol_fee_rate: u8) -> Self {
+        let mut ctx = Self::new();
+        ctx.initialize_config(protocol_fee_rate).unwrap();
+        ctx
+    }
+
+    /// Config, fee tier and an initialized pool at `initial_tick`
+    pub fn with_pool(fee_rate: u32, tick_spacing: u16, initial_tick: i32) -> (Self, PoolFixture) {
+        let mut ctx = Self::with_config(10);
+        ctx.initialize_fee_tier(fee_rate, tick_spacing).unwrap();
+        let pool = ctx.create_pool(fee_rate, sqrt_price_at(initial_tick)).unwrap();
+        (ctx, pool)
+    }
+
+    // ─── Accounts ────────────────────────────────────────────────────────────
+
+    pub fn create_mint(&mut self) -> Pubkey {
+        let mint = Pubkey::new_unique();
+        let mut data = vec![0u8; token_state::Mint::LEN];
+        token_state::Mint::pack(
+            token_state::Mint {
+                mint_authority: COption::Some(self.authority),
+                supply: u64::MAX / 2,
+                decimals: DECIMALS,
+                is_initialized: true,
+                freeze_authority: COption::None,
+            },
+            &mut data,
+        )
+        .unwrap();
+        self.set_token_program_account(mint, data);
+        mint
+    }
+
+    /// The wrapped SOL mint, created on first use
+    pub fn create_native_mint(&mut self) -> Pubkey {
+        let mint = spl_token::native_mint::ID;
+        if !self.exists(&mint) {
+            let mut data = vec![0u8; token_state::Mint::LEN];
+            token_state::Mint::pack(
+                token_state::Mint {
+                    mint_authority: COption::None,
+                    supply: 0,
+                    decimals: spl_token::native_mint::DECIMALS,
+                    is_initialized: true,
+                    freeze_authority: COption::None,
+                },
+                &mut data,
+            )
+            .unwrap();
+            self.set_token_program_account(mint, data);
+        }
+        mint
+    }
+
+    /// Two mints ordered so that the first sorts before the second
+    pub fn create_mint_pair(&mut self) -> (Pubkey, Pubkey) {
+        let (a, b) = (self.create_mint(), self.create_mint());
+        if a < b {
+            (a, b)
+        } else {
+            (b, a)
+        }
+    }
+
+    /// Native SOL accounts hold their balance as lamports above the rent reserve
+    pub fn create_token_account(&mut self, mint: &Pubkey, owner: &Pubkey, amount: u64) -> Pubkey {
+        self.create_token_account_at(Pubkey::new_unique(), mint, owner, amount)
+    }
+
+    /// Create `owner`'s associated token account for `mint`, empty
+    pub fn create_associated_token_account(&mut self, mint: &Pubkey, owner: &Pubkey) -> Pubkey {
+        self.create_token_account_at(associated_token_address(owner, mint), mint, owner, 0)
+    }
+
+    fn create_token_account_at(&mut self, account: Pubkey, mint: &Pubkey, owner: &Pubkey, amount: u64) -> Pubkey {
+        let rent = Rent::default().minimum_balance(token_state::Account::LEN);
+        let native = *mint == spl_token::native_mint::ID;
+        let mut data = vec![0u8; token_state::Account::LEN];
+        token_state::Account::pack(
+            token_state::Account {
+                mint: *mint,
+                owner: *owner,
+                amount,
+                delegate: COption::None,
+                state: token_state::AccountState::Initialized,
+                is_native: if native { COption::Some(rent) } else { COption::None },
+                delegated_amount: 0,
+                close_authority: COption::None,
+            },
+            &mut data,
+        )
+        .unwrap();
+        self.set_token_program_account(account, data);
+        if native {
+            self.bank.airdrop(account, amount);
+        }
+        account
+    }
+
+    fn set_token_program_account(&mut self, key: Pubkey, data: Vec<u8>) {
+        self.bank.set_account(
+            key,
+            AccountState {
+                lamports: Rent::default().minimum_balance(data.len()),
+                data,
+                owner: spl_token::ID,
+                executable: false,
+            },
+        );
+    }
+
+    pub fn create_user(&mut self, pool: &PoolFixture, amount: u64) -> User {
+        let key = Pubkey::new_unique();
+        self.bank.airdrop(key, 10 * SOL);
+        User {
+            key,
+            token_a: self.create_token_account(&pool.mint_a, &key, amount),
+            token_b: self.create_token_account(&pool.mint_b, &key, amount),
+        }
+    }
+
+    // ─── State ───────────────────────────────────────────────────────────────
+
+    pub fn token_balance(&self, account: &Pubkey) -> u64 {
+        let data = &self.bank.account(account).expect("token account").data;
+        token_state::Account::unpack(data).unwrap().amount
+    }
+
+    /// Add `amount` to a token account's balance, as if sent to it directly
+    pub fn credit_tokens(&mut self, account: &Pubkey, amount: u64) {
+        self.bank.poke(account, |data| {
+            let mut state = token_state::Account::unpack(data).unwrap();
+            state.amount += amount;
+            token_state::Account::pack(state, data).unwrap();
+        });
+    }
+
+    pub fn exists(&self, key: &Pubkey) -> bool {
+        self.bank.account(key).is_some()
+    }
+
+    pub fn account<T: AccountDeserialize>(&self, key: &Pubkey) -> T {
+        let data = &self.bank.account(key).expect("account exists").data;
+        T::try_deserialize(&mut data.as_slice()).unwrap()
+    }
+
+    /// Overwrite a Borsh account in place
+    pub fn modify_account<T: AccountSerialize + AccountDeserialize>(
+        &mut self,
+        key: &Pubkey,
+        f: impl FnOnce(&mut T),
+    ) {
+        let mut value: T = self.account(key);
+        f(&mut value);
+        let mut bytes = Vec::new();
+        value.try_serialize(&mut bytes).unwrap();
+        self.bank.poke(key, |data| data[..bytes.len()].copy_from_slice(&bytes));
+    }
+
+    pub fn zero_copy<T: bytemuck::Pod>(&self, key: &Pubkey) -> T {
+        let data = &self.bank.account(key).expect("account exists").data;
+        bytemuck::pod_read_unaligned(&data[8..8 + std::mem::size_of::<T>()])
+    }
+
+    /// Overwrite a zero-copy account in place
+    pub fn modify_zero_copy<T: bytemuck::Pod>(&mut self, key: &Pubkey, f: impl FnOnce(&mut T)) {
+        let mut value: T = self.zero_copy(key);
+        f(&mut value);
+        self.bank.poke(key, |data| {
+            data[8..8 + std::mem::size_of::<T>()].copy_from_slice(bytemuck::bytes_of(&value));
+        });
+    }
+
+    pub fn config(&self) -> SuniswapConfig {
+        self.account(&config_pda())
+    }
+
+    pub fn fee_tier(&self, key: &Pubkey) -> FeeTier {
+        self.account(key)
+    }
+
+    pub fn pool(&self, pool: &PoolFixture) -> Pool {
+        self.zero_copy(&pool.pool)
+    }
+
+    pub fn position(&self, key: &Pubkey) -> Position {
+        self.zero_copy(key)
+    }
+
+    pub fn vault(&self, vault: &VaultFixture) -> ManagedVault {
+        self.account(&vault.vault)
+    }
+
+    pub fn tick_array(&self, key: &Pubkey) -> TickArray {
+        self.zero_copy(key)
+    }
+
+    /// `wallet`'s entry on the pool's trader allowlist, if it has one
+    pub fn allowed_trader(&self, pool: &Pubkey, wallet: &Pubkey) -> Option<Pubkey> {
+        let entry = allowed_trader_pda(pool, wallet);
+        self.exists(&entry).then_some(entry)
+    }
+
+    /// The pool's oracle, if it has one
+    pub fn oracle(&self, pool: &Pubkey) -> Option<Pubkey> {
+        let oracle = oracle_pda(pool);
+        self.exists(&oracle).then_some(oracle)
+    }
+
+    // ─── Admin ───────────────────────────────────────────────────────────────
+
+    pub fn ix_initialize_config(&self, protocol_fee_rate: u8) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::InitializeConfig {
+                config: config_pda(),
+                protocol_authority: self.authority,
+                fee_authority: self.authority,
+                payer: self.authority,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::InitializeConfig {
+                default_protocol_fee_rate: protocol_fee_rate,
+            }
+            .data(),
+        }
+    }
+
+    pub fn initialize_config(&mut self, protocol_fee_rate: u8) -> TxResult {
+        let ix = self.ix_initialize_config(protocol_fee_rate);
+        self.bank.process(&ix)
+    }
+
+    pub fn ix_initialize_fee_tier(&self, fee_rate: u32, tick_spacing: u16) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::InitializeFeeTier {
+                config: config_pda(),
+                fee_tier: fee_tier_pda(fee_rate),
+                fee_tier_index: fee_tier_index_pda(self.config().fee_tier_count),
+                authority: self.authority,
+                payer: self.authority,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::InitializeFeeTier {
+                fee_rate,
+                tick_spacing,
+            }
+            .data(),
+        }
+    }
+
+    pub fn initialize_fee_tier(&mut self, fee_rate: u32, tick_spacing: u16) -> TxResult {
+        let ix = self.ix_initialize_fee_tier(fee_rate, tick_spacing);
+        self.bank.process(&ix)
+    }
+
+    pub fn ix_set_fee_tier_rate(&self, fee_tier: &Pubkey, fee_rate: u32) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::SetFeeTierRate {
+                config: config_pda(),
+                fee_tier: *fee_tier,
+                authority: self.authority,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::SetFeeTierRate { fee_rate }.data(),
+        }
+    }
+
+    pub fn set_fee_tier_rate(&mut self, fee_tier: &Pubkey, fee_rate: u32) -> TxResult {
+        let ix = self.ix_set_fee_tier_rate(fee_tier, fee_rate);
+        self.bank.process(&ix)
+    }
+
+    pub fn ix_register_fee_tier_index(&self, fee_tier: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::RegisterFeeTierIndex {
+                config: config_pda(),
+                fee_tier: *fee_tier,
+                fee_tier_index: fee_tier_index_pda(self.config().fee_tier_count),
+                authority: self.authority,
+                payer: self.authority,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::RegisterFeeTierIndex {}.data(),
+        }
+    }
+
+    pub fn register_fee_tier_index(&mut self, fee_tier: &Pubkey) -> TxResult {
+        let ix = self.ix_register_fee_tier_index(fee_tier);
+        self.bank.process(&ix)
+    }
+
+    pub fn ix_set_fee_tier_disabled(&self, fee_tier: &Pubkey, disabled: bool) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::SetFeeTierDisabled {
+                config: config_pda(),
+                fee_tier: *fee_tier,
+                authority: self.authority,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::SetFeeTierDisabled { disabled }.data(),
+        }
+    }
+
+    pub fn set_fee_tier_disabled(&mut self, fee_tier: &Pubkey, disabled: bool) -> TxResult {
+        let ix = self.ix_set_fee_tier_disabled(fee_tier, disabled);
+        self.bank.process(&ix)
+    }
+
+    pub fn ix_set_max_referral_fee_rate(&self, max_referral_fee_rate: u16) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::SetMaxReferralFeeRate {
+                config: config_pda(),
+                authority: self.authority,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::SetMaxReferralFeeRate { max_referral_fee_rate }.data(),
+        }
+    }
+
+    pub fn set_max_referral_fee_rate(&mut self, max_referral_fee_rate: u16) -> TxResult {
+        let ix = self.ix_set_max_referral_fee_rate(max_referral_fee_rate);
+        self.bank.process(&ix)
+    }
+
+    pub fn ix_set_max_creator_fee_rate(&self, max_creator_fee_rate: u8) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::SetMaxCreatorFeeRate {
+                config: config_pda(),
+                authority: self.authority,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::SetMaxCreatorFeeRate { max_creator_fee_rate }.data(),
+        }
+    }
+
+    pub fn set_max_creator_fee_rate(&mut self, max_creator_fee_rate: u8) -> TxResult {
+        let ix = self.ix_set_max_creator_fee_rate(max_creator_fee_rate);
+        self.bank.process(&ix)
+    }
+
+    pub fn ix_initialize_pool_creation_config(&self) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::InitializePoolCreationConfig {
+                config: config_pda(),
+                pool_creation_config: pool_creation_config_pda(),
+                authority: self.authority,
+                payer: self.authority,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::InitializePoolCreationConfig {}.data(),
+        }
+    }
+
+    pub fn initialize_pool_creation_config(&mut self) -> TxResult {
+        let ix = self.ix_initialize_pool_creation_config();
+        self.bank.process(&ix)
+    }
+
+    pub fn ix_set_pool_creation_allowlist(&self, creators: Vec<Pubkey>) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::SetPoolCreationAllowlist {
+                config: config_pda(),
+                pool_creation_config: pool_creation_config_pda(),
+                authority: self.authority,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::SetPoolCreationAllowlist { creators }.data(),
+        }
+    }
+
+    pub fn set_pool_creation_allowlist(&mut self, creators: Vec<Pubkey>) -> TxResult {
+        let ix = self.ix_set_pool_creation_allowlist(creators);
+        self.bank.process(&ix)
+    }
+
+    pub fn ix_set_pool_creation_fee(&self, fee_mint: Option<Pubkey>, fee_amount: u64, treasury: Pubkey) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::SetPoolCreationFee {
+                config: config_pda(),
+                pool_creation_config: pool_creation_config_pda(),
+                authority: self.authority,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::SetPoolCreationFee { fee_mint, fee_amount, treasury }.data(),
+        }
+    }
+
+    pub fn set_pool_creation_fee(&mut self, fee_mint: Option<Pubkey>, fee_amount: u64, treasury: Pubkey) -> TxResult {
+        let ix = self.ix_set_pool_creation_fee(fee_mint, fee_amount, treasury);
+        self.bank.process(&ix)
+    }
+
+    /// Switch the pool creation mode, passing the pool creation config if it exists
+    pub fn ix_set_pool_creation_mode(&self, pool_creation_mode: u8) -> Instruction {
+        let pool_creation_config = self.exists(&pool_creation_config_pda()).then(pool_creation_config_pda);
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::SetPoolCreationMode {
+                config: config_pda(),
+                pool_creation_config,
+                authority: self.authority,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::SetPoolCreationMode { pool_creation_mode }.data(),
+        }
+    }
+
+    pub fn set_pool_creation_mode(&mut self, pool_creation_mode: u8) -> TxResult {
+        let ix = self.ix_set_pool_creation_mode(pool_creation_mode);
+        self.bank.process(&ix)
+    }
+
+    // ─── Pool ────────────────────────────────────────────────────────────────
+
+    pub fn ix_initialize_pool(
+        &self,
+        mint_a: &Pubkey,
+        mint_b: &Pubkey,
+        fee_rate: u32,
+        sqrt_price_x64: u128,
+    ) -> Instruction {
+        self.ix_initialize_pool_with_creator(mint_a, mint_b, fee_rate, sqrt_price_x64, None, 0)
+    }
+
+    pub fn ix_initialize_pool_with_creator(
+        &self,
+        mint_a: &Pubkey,
+        mint_b: &Pubkey,
+        fee_rate: u32,
+        sqrt_price_x64: u128,
+        creator: Option<Pubkey>,
+        creator_fee_rate: u8,
+    ) -> Instruction {
+        let pool = pool_pda(mint_a, mint_b, fee_rate);
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::InitializePool {
+                config: config_pda(),
+                fee_tier: fee_tier_pda(fee_rate),
+                pool,
+                token_mint_a: *mint_a,
+                token_mint_b: *mint_b,
+                token_vault_a: vault_pda(&pool, mint_a),
+                token_vault_b: vault_pda(&pool, mint_b),
+                payer: self.authority,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+                pool_creation_config: None,
+                creation_fee_mint: None,
+                creation_fee_source: None,
+                creation_fee_treasury: None,
+                creation_fee_token_program: None,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::InitializePool {
+                initial_sqrt_price_x64: sqrt_price_x64,
+                creator,
+                creator_fee_rate,
+            }
+            .data(),
+        }
+    }
+
+    /// Create a mint pair and initialize a pool for it on an existing fee tier
+    pub fn create_pool(
+        &mut self,
+        fee_rate: u32,
+        sqrt_price_x64: u128,
+    ) -> std::result::Result<PoolFixture, ProgramError> {
+        let (mint_a, mint_b) = self.create_mint_pair();
+        self.create_pool_with_mints(mint_a, mint_b, fee_rate, sqrt_price_x64)
+    }
+
+    /// Create a mint pair and initialize a pool that pays `creator` a share of swap fees
+    pub fn create_pool_with_creator(
+        &mut self,
+        fee_rate: u32,
+        sqrt_price_x64: u128,
+        creator: Pubkey,
+        creator_fee_rate: u8,
+    ) -> std::result::Result<PoolFixture, ProgramError> {
+        let (mint_a, mint_b) = self.create_mint_pair();
+        let ix = self.ix_initialize_pool_with_creator(
+            &mint_a, &mint_b, fee_rate, sqrt_price_x64, Some(creator), creator_fee_rate,
+        );
+        self.bank.process(&ix)?;
+        Ok(self.pool_fixture(mint_a, mint_b, fee_rate))
+    }
+
+    /// Pool pairing native SOL with a new mint
+    pub fn create_native_pool(
+        &mut self,
+        fee_rate: u32,
+        sqrt_price_x64: u128,
+    ) -> std::result::Result<PoolFixture, ProgramError> {
+        let (native, mint) = (self.create_native_mint(), self.create_mint());
+        let (mint_a, mint_b) = if native < mint { (native, mint) } else { (mint, native) };
+        self.create_pool_with_mints(mint_a, mint_b, fee_rate, sqrt_price_x64)
+    }
+
+    fn create_pool_with_mints(
+        &mut self,
+        mint_a: Pubkey,
+        mint_b: Pubkey,
+        fee_rate: u32,
+        sqrt_price_x64: u128,
+    ) -> std::result::Result<PoolFixture, ProgramError> {
+        let ix = self.ix_initialize_pool(&mint_a, &mint_b, fee_rate, sqrt_price_x64);
+        self.bank.process(&ix)?;
+        Ok(self.pool_fixture(mint_a, mint_b, fee_rate))
+    }
+
+    fn pool_fixture(&self, mint_a: Pubkey, mint_b: Pubkey, fee_rate: u32) -> PoolFixture {
+        let fee_tier = fee_tier_pda(fee_rate);
+        let pool = pool_pda(&mint_a, &mint_b, fee_rate);
+        PoolFixture {
+            pool,
+            fee_tier,
+            fee_rate,
+            tick_spacing: self.fee_tier(&fee_tier).tick_spacing,
+            mint_a,
+            mint_b,
+            vault_a: vault_pda(&pool, &mint_a),
+            vault_b: vault_pda(&pool, &mint_b),
+        }
+    }
+
+    // ─── Trader allowlist ────────────────────────────────────────────────────
+
+    pub fn ix_initialize_trader_allowlist(&self, pool: &PoolFixture, admin: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::InitializeTraderAllowlist {
+                config: config_pda(),
+                pool: pool.pool,
+                trader_allowlist: trader_allowlist_pda(&pool.pool),
+                authority: self.authority,
+                payer: self.authority,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::InitializeTraderAllowlist { admin: *admin }.data(),
+        }
+    }
+
+    pub fn initialize_trader_allowlist(&mut self, pool: &PoolFixture, admin: &Pubkey) -> TxResult {
+        let ix = self.ix_initialize_trader_allowlist(pool, admin);
+        self.bank.process(&ix)
+    }
+
+    /// Hand the allowlist to `admin` and switch it, signed by `signer`
+    pub fn ix_set_trader_allowlist(&self, pool: &PoolFixture, signer: &Pubkey, admin: &Pubkey, enabled: bool) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::SetTraderAllowlist {
+                pool: pool.pool,
+                trader_allowlist: trader_allowlist_pda(&pool.pool),
+                admin: *signer,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::SetTraderAllowlist { admin: *admin, enabled }.data(),
+        }
+    }
+
+    pub fn set_trader_allowlist(&mut self, pool: &PoolFixture, signer: &Pubkey, admin: &Pubkey, enabled: bool) -> TxResult {
+        let ix = self.ix_set_trader_allowlist(pool, signer, admin, enabled);
+        self.bank.process(&ix)
+    }
+
+    pub fn ix_add_allowed_trader(&self, pool: &PoolFixture, admin: &Pubkey, wallet: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::AddAllowedTrader {
+                pool: pool.pool,
+                trader_allowlist: trader_allowlist_pda(&pool.pool),
+                allowed_trader: allowed_trader_pda(&pool.pool, wallet),
+                admin: *admin,
+                payer: *admin,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::AddAllowedTrader { wallet: *wallet }.data(),
+        }
+    }
+
+    pub fn add_allowed_trader(&mut self, pool: &PoolFixture, admin: &Pubkey, wallet: &Pubkey) -> TxResult {
+        let ix = self.ix_add_allowed_trader(pool, admin, wallet);
+        self.bank.process(&ix)
+    }
+
+    pub fn ix_remove_allowed_trader(&self, pool: &PoolFixture, admin: &Pubkey, wallet: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::RemoveAllowedTrader {
+                pool: pool.pool,
+                trader_allowlist: trader_allowlist_pda(&pool.pool),
+                allowed_trader: allowed_trader_pda(&pool.pool, wallet),
+                admin: *admin,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::RemoveAllowedTrader {}.data(),
+        }
+    }
+
+    pub fn remove_allowed_trader(&mut self, pool: &PoolFixture, admin: &Pubkey, wallet: &Pubkey) -> TxResult {
+        let ix = self.ix_remove_allowed_trader(pool, admin, wallet);
+        self.bank.process(&ix)
+    }
+
+    pub fn ix_migrate_pool(&self, pool: &PoolFixture, payer: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::MigratePool {
+                pool: pool.pool,
+                fee_tier: pool.fee_tier,
+                payer: *payer,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::MigratePool {}.data(),
+        }
+    }
+
+    pub fn migrate_pool(&mut self, pool: &PoolFixture, payer: &Pubkey) -> TxResult {
+        let ix = self.ix_migrate_pool(pool, payer);
+        self.bank.process(&ix)
+    }
+
+    pub fn ix_initialize_tick_array(&self, pool: &PoolFixture, start_tick_index: i32) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::InitializeTickArray {
+                pool: pool.pool,
+                tick_array: tick_array_pda(&pool.pool, start_tick_index),
+                payer: self.authority,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::InitializeTickArray { start_tick_index }.data(),
+        }
+    }
+
+    pub fn initialize_tick_array(&mut self, pool: &PoolFixture, start_tick_index: i32) -> TxResult {
+        let ix = self.ix_initialize_tick_array(pool, start_tick_index);
+        self.bank.process(&ix)
+    }
+
+    /// Initialize the tick array containing `tick` if it does not exist yet
+    pub fn ensure_tick_array(&mut self, pool: &PoolFixture, tick: i32) -> Pubkey {
+        let key = pool.tick_array(tick);
+        if !self.exists(&key) {
+            self.initialize_tick_array(pool, pool.tick_array_start(tick)).unwrap();
+        }
+        key
+    }
+
+    pub fn ix_set_pool_fee_rate(&self, pool: &PoolFixture, fee_rate: Option<u32>) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::SetPoolFeeRate {
+                config: config_pda(),
+                pool: pool.pool,
+                authority: self.authority,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::SetPoolFeeRate { fee_rate }.data(),
+        }
+    }
+
+    pub fn set_pool_fee_rate(&mut self, pool: &PoolFixture, fee_rate: Option<u32>) -> TxResult {
+        let ix = self.ix_set_pool_fee_rate(pool, fee_rate);
+        self.bank.process(&ix)
+    }
+
+    pub fn ix_set_pool_adaptive_fee(
+        &self,
+        pool: &PoolFixture,
+        params: Option<AdaptiveFeeParams>,
+    ) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::SetPoolAdaptiveFee {
+                config: config_pda(),
+                pool: pool.pool,
+                authority: self.authority,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::SetPoolAdaptiveFee { params }.data(),
+        }
+    }
+
+    pub fn set_pool_adaptive_fee(
+        &mut self,
+        pool: &PoolFixture,
+        params: Option<AdaptiveFeeParams>,
+    ) -> TxResult {
+        let ix = self.ix_set_pool_adaptive_fee(pool, params);
+        self.bank.process(&ix)
+    }
+
+    pub fn ix_set_pool_circuit_breaker(
+        &self,
+        pool: &PoolFixture,
+        max_tick_move_per_swap: u32,
+        max_tick_move_per_slot: u32,
+    ) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::SetPoolCircuitBreaker {
+                config: config_pda(),
+                pool: pool.pool,
+                authority: self.authority,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::SetPoolCircuitBreaker {
+                max_tick_move_per_swap,
+                max_tick_move_per_slot,
+            }
+            .data(),
+        }
+    }
+
+    pub fn set_pool_circuit_breaker(
+        &mut self,
+        pool: &PoolFixture,
+        max_tick_move_per_swap: u32,
+        max_tick_move_per_slot: u32,
+    ) -> TxResult {
+        let ix = self.ix_set_pool_circuit_breaker(pool, max_tick_move_per_swap, max_tick_move_per_slot);
+        self.bank.process(&ix)
+    }
+
+    pub fn ix_initialize_oracle(&self, pool: &PoolFixture, twap_window: u32) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::InitializeOracle {
+                config: config_pda(),
+                pool: pool.pool,
+                oracle: oracle_pda(&pool.pool),
+                authority: self.authority,
+                payer: self.authority,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::InitializeOracle { twap_window }.data(),
+        }
+    }
+
+    pub fn initialize_oracle(&mut self, pool: &PoolFixture, twap_window: u32) -> TxResult {
+        let ix = self.ix_initialize_oracle(pool, twap_window);
+        self.bank.process(&ix)
+    }
+
+    pub fn ix_set_pool_twap_guard(
+        &self,
+        pool: &PoolFixture,
+        twap_window: u32,
+        max_twap_deviation_ticks: u32,
+    ) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::SetPoolTwapGuard {
+                config: config_pda(),
+                pool: pool.pool,
+                authority: self.authority,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::SetPoolTwapGuard {
+                twap_window,
+                max_twap_deviation_ticks,
+            }
+            .data(),
+        }
+    }
+
+    pub fn set_pool_twap_guard(
+        &mut self,
+        pool: &PoolFixture,
+        twap_window: u32,
+        max_twap_deviation_ticks: u32,
+    ) -> TxResult {
+        let ix = self.ix_set_pool_twap_guard(pool, twap_window, max_twap_deviation_ticks);
+        self.bank.process(&ix)
+    }
+
+    // ─── Positions ───────────────────────────────────────────────────────────
+
+    pub fn ix_open_position(
+        &self,
+        pool: &PoolFixture,
+        owner: &User,
+        tick_lower: i32,
+        tick_upper: i32,
+    ) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::OpenPosition {
+                pool: pool.pool,
+                position: position_pda(&pool.pool, &owner.key, tick_lower, tick_upper),
+                owner: owner.key,
+                payer: owner.key,
+                system_program: system_program::ID,
+                allowed_trader: self.allowed_trader(&pool.pool, &owner.key),
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::OpenPosition {
+                tick_lower,
+                tick_upper,
+            }
+            .data(),
+        }
+    }
+
+    pub fn open_position(
+        &mut self,
+        pool: &PoolFixture,
+        owner: &User,
+        tick_lower: i32,
+        tick_upper: i32,
+    ) -> TxResult {
+        let ix = self.ix_open_position(pool, owner, tick_lower, tick_upper);
+        self.bank.process(&ix)
+    }
+
+    pub fn ix_open_position_with_liquidity(
+        &self,
+        pool: &PoolFixture,
+        owner: &User,
+        tick_lower: i32,
+        tick_upper: i32,
+        params: IncreaseLiquidityByAmountsParams,
+    ) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::OpenPositionWithLiquidity {
+                pool: pool.pool,
+                position: position_pda(&pool.pool, &owner.key, tick_lower, tick_upper),
+                tick_array_lower: pool.tick_array(tick_lower),
+                tick_array_upper: pool.tick_array(tick_upper),
+                token_mint_a: pool.mint_a,
+                token_mint_b: pool.mint_b,
+                token_vault_a: pool.vault_a,
+                token_vault_b: pool.vault_b,
+                user_token_a: owner.token_a,
+                user_token_b: owner.token_b,
+                owner: owner.key,
+                payer: owner.key,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+                allowed_trader: self.allowed_trader(&pool.pool, &owner.key),
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::OpenPositionWithLiquidity {
+                tick_lower,
+                tick_upper,
+                params,
+            }
+            .data(),
+        }
+    }
+
+    pub fn open_position_with_liquidity(
+        &mut self,
+        pool: &PoolFixture,
+        owner: &User,
+        tick_lower: i32,
+        tick_upper: i32,
+        params: IncreaseLiquidityByAmountsParams,
+    ) -> TxResult {
+        let ix = self.ix_open_position_with_liquidity(pool, owner, tick_lower, tick_upper, params);
+        self.bank.process(&ix)
+    }
+
+    pub fn ix_close_position(
+        &self,
+        pool: &PoolFixture,
+        owner: &User,
+        tick_lower: i32,
+        tick_upper: i32,
+    ) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::ClosePosition {
+                pool: pool.pool,
+                position: position_pda(&pool.pool, &owner.key, tick_lower, tick_upper),
+                owner: owner.key,
+                receiver: owner.key,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::ClosePosition {}.data(),
+        }
+    }
+
+    pub fn close_position(
+        &mut self,
+        pool: &PoolFixture,
+        owner: &User,
+        tick_lower: i32,
+        tick_upper: i32,
+    ) -> TxResult {
+        let ix = self.ix_close_position(pool, owner, tick_lower, tick_upper);
+        self.bank.process(&ix)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn ix_increase_liquidity(
+        &self,
+        pool: &PoolFixture,
+        owner: &User,
+        tick_lower: i32,
+        tick_upper: i32,
+        liquidity_delta: u128,
+        amount_a_max: u64,
+        amount_b_max: u64,
+    ) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::IncreaseLiquidity {
+                pool: pool.pool,
+                position: position_pda(&pool.pool, &owner.key, tick_lower, tick_upper),
+                tick_array_lower: pool.tick_array(tick_lower),
+                tick_array_upper: pool.tick_array(tick_upper),
+                token_mint_a: pool.mint_a,
+                token_mint_b: pool.mint_b,
+                token_vault_a: pool.vault_a,
+                token_vault_b: pool.vault_b,
+                user_token_a: Some(owner.token_a),
+                user_token_b: Some(owner.token_b),
+                owner: owner.key,
+                token_program: spl_token::ID,
+                allowed_trader: self.allowed_trader(&pool.pool, &owner.key),
+                native_sol_account: None,
+                system_program: None,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::IncreaseLiquidity {
+                liquidity_delta,
+                amount_a_max,
+                amount_b_max,
+                deadline_unix_ts: None,
+                max_slot: None,
+            }
+            .data(),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn increase_liquidity(
+        &mut self,
+        pool: &PoolFixture,
+        owner: &User,
+        tick_lower: i32,
+        tick_upper: i32,
+        liquidity_delta: u128,
+        amount_a_max: u64,
+        amount_b_max: u64,
+    ) -> TxResult {
+        let ix = self.ix_increase_liquidity(
+            pool,
+            owner,
+            tick_lower,
+            tick_upper,
+            liquidity_delta,
+            amount_a_max,
+            amount_b_max,
+        );
+        self.bank.process(&ix)
+    }
+
+    /// Same accounts as `increase_liquidity`, with the liquidity computed on-chain
+    pub fn ix_increase_liquidity_by_amounts(
+        &self,
+        pool: &PoolFixture,
+        owner: &User,
+        tick_lower: i32,
+        tick_upper: i32,
+        params: IncreaseLiquidityByAmountsParams,
+    ) -> Instruction {
+        Instruction {
+            data: suniswap::instruction::IncreaseLiquidityByAmounts { params }.data(),
+            ..self.ix_increase_liquidity(pool, owner, tick_lower, tick_upper, 0, 0, 0)
+        }
+    }
+
+    pub fn increase_liquidity_by_amounts(
+        &mut self,
+        pool: &PoolFixture,
+        owner: &User,
+        tick_lower: i32,
+        tick_upper: i32,
+        params: IncreaseLiquidityByAmountsParams,
+    ) -> TxResult {
+        let ix = self.ix_increase_liquidity_by_amounts(pool, owner, tick_lower, tick_upper, params);
+        self.bank.process(&ix)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn ix_decrease_liquidity(
+        &self,
+        pool: &PoolFixture,
+        owner: &User,
+        tick_lower: i32,
+        tick_upper: i32,
+        liquidity_delta: u128,
+        amount_a_min: u64,
+        amount_b_min: u64,
+    ) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::DecreaseLiquidity {
+                pool: pool.pool,
+                position: position_pda(&pool.pool, &owner.key, tick_lower, tick_upper),
+                tick_array_lower: pool.tick_array(tick_lower),
+                tick_array_upper: pool.tick_array(tick_upper),
+                owner: owner.key,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::DecreaseLiquidity {
+                liquidity_delta,
+                amount_a_min,
+                amount_b_min,
+                deadline_unix_ts: None,
+                max_slot: None,
+            }
+            .data(),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn decrease_liquidity(
+        &mut self,
+        pool: &PoolFixture,
+        owner: &User,
+        tick_lower: i32,
+        tick_upper: i32,
+        liquidity_delta: u128,
+        amount_a_min: u64,
+        amount_b_min: u64,
+    ) -> TxResult {
+        let ix = self.ix_decrease_liquidity(
+            pool,
+            owner,
+            tick_lower,
+            tick_upper,
+            liquidity_delta,
+            amount_a_min,
+            amount_b_min,
+        );
+        self.bank.process(&ix)
+    }
+
+    /// Initialize tick arrays, open the position and deposit `liquidity`
+    pub fn add_liquidity(
+        &mut self,
+        pool: &PoolFixture,
+        owner: &User,
+        tick_lower: i32,
+        tick_upper: i32,
+        liquidity: u128,
+    ) -> Pubkey {
+        self.ensure_tick_array(pool, tick_lower);
+        self.ensure_tick_array(pool, tick_upper);
+        let position = position_pda(&pool.pool, &owner.key, tick_lower, tick_upper);
+        if !self.exists(&position) {
+            self.open_position(pool, owner, tick_lower, tick_upper).unwrap();
+        }
+        self.increase_liquidity(pool, owner, tick_lower, tick_upper, liquidity, u64::MAX, u64::MAX)
+            .unwrap();
+        position
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn ix_collect_fees(
+        &self,
+        pool: &PoolFixture,
+        owner: &User,
+        tick_lower: i32,
+        tick_upper: i32,
+        amount_a_requested: u64,
+        amount_b_requested: u64,
+    ) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::CollectFees {
+                pool: pool.pool,
+                fee_tier: pool.fee_tier,
+                position: position_pda(&pool.pool, &owner.key, tick_lower, tick_upper),
+                tick_array_lower: pool.tick_array(tick_lower),
+                tick_array_upper: pool.tick_array(tick_upper),
+                token_mint_a: pool.mint_a,
+                token_mint_b: pool.mint_b,
+                token_vault_a: pool.vault_a,
+                token_vault_b: pool.vault_b,
+                user_token_a: Some(owner.token_a),
+                user_token_b: Some(owner.token_b),
+                owner: owner.key,
+                token_program: spl_token::ID,
+                native_sol_account: None,
+                system_program: None,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::CollectFees {
+                amount_a_requested,
+                amount_b_requested,
+            }
+            .data(),
+        }
+    }
+
+    pub fn collect_fees(
+        &mut self,
+        pool: &PoolFixture,
+        owner: &User,
+        tick_lower: i32,
+        tick_upper: i32,
+        amount_a_requested: u64,
+        amount_b_requested: u64,
+    ) -> TxResult {
+        let ix = self.ix_collect_fees(
+            pool,
+            owner,
+            tick_lower,
+            tick_upper,
+            amount_a_requested,
+            amount_b_requested,
+        );
+        self.bank.process(&ix)
+    }
+
+    pub fn ix_compound_position(
+        &self,
+        pool: &PoolFixture,
+        owner: &User,
+        tick_lower: i32,
+        tick_upper: i32,
+    ) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::CompoundPosition {
+                pool: pool.pool,
+                position: position_pda(&pool.pool, &owner.key, tick_lower, tick_upper),
+                tick_array_lower: pool.tick_array(tick_lower),
+                tick_array_upper: pool.tick_array(tick_upper),
+                owner: owner.key,
+                allowed_trader: self.allowed_trader(&pool.pool, &owner.key),
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::CompoundPosition {}.data(),
+        }
+    }
+
+    pub fn compound_position(
+        &mut self,
+        pool: &PoolFixture,
+        owner: &User,
+        tick_lower: i32,
+        tick_upper: i32,
+    ) -> TxResult {
+        let ix = self.ix_compound_position(pool, owner, tick_lower, tick_upper);
+        self.bank.process(&ix)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn ix_withdraw(
+        &self,
+        pool: &PoolFixture,
+        owner: &User,
+        tick_lower: i32,
+        tick_upper: i32,
+        liquidity_delta: u128,
+        amount_a_min: u64,
+        amount_b_min: u64,
+        close_position: bool,
+    ) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::Withdraw {
+                pool: pool.pool,
+                fee_tier: pool.fee_tier,
+                position: position_pda(&pool.pool, &owner.key, tick_lower, tick_upper),
+                tick_array_lower: pool.tick_array(tick_lower),
+                tick_array_upper: pool.tick_array(tick_upper),
+                token_mint_a: pool.mint_a,
+                token_mint_b: pool.mint_b,
+                token_vault_a: pool.vault_a,
+                token_vault_b: pool.vault_b,
+                user_token_a: owner.token_a,
+                user_token_b: owner.token_b,
+                owner: owner.key,
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::Withdraw {
+                liquidity_delta,
+                amount_a_min,
+                amount_b_min,
+                close_position,
+                deadline_unix_ts: None,
+                max_slot: None,
+            }
+            .data(),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn withdraw(
+        &mut self,
+        pool: &PoolFixture,
+        owner: &User,
+        tick_lower: i32,
+        tick_upper: i32,
+        liquidity_delta: u128,
+        amount_a_min: u64,
+        amount_b_min: u64,
+        close_position: bool,
+    ) -> TxResult {
+        let ix = self.ix_withdraw(
+            pool,
+            owner,
+            tick_lower,
+            tick_upper,
+            liquidity_delta,
+            amount_a_min,
+            amount_b_min,
+            close_position,
+        );
+        self.bank.process(&ix)
+    }
+
+    /// `range` is the position being moved, `new_range` where it goes; the swap
+    /// tick arrays are left out when `swap_tick_arrays` is None
+    pub fn ix_reposition(
+        &self,
+        pool: &PoolFixture,
+        owner: &User,
+        range: (i32, i32),
+        new_range: (i32, i32),
+        params: RepositionParams,
+        swap_tick_arrays: Option<[Pubkey; 3]>,
+    ) -> Instruction {
+        let (tick_lower, tick_upper) = new_range;
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::Reposition {
+                pool: pool.pool,
+                fee_tier: pool.fee_tier,
+                position: position_pda(&pool.pool, &owner.key, range.0, range.1),
+                tick_array_lower: pool.tick_array(range.0),
+                tick_array_upper: pool.tick_array(range.1),
+                new_position: position_pda(&pool.pool, &owner.key, tick_lower, tick_upper),
+                new_tick_array_lower: pool.tick_array(tick_lower),
+                new_tick_array_upper: pool.tick_array(tick_upper),
+                swap_tick_array_0: swap_tick_arrays.map(|arrays| arrays[0]),
+                swap_tick_array_1: swap_tick_arrays.map(|arrays| arrays[1]),
+                swap_tick_array_2: swap_tick_arrays.map(|arrays| arrays[2]),
+                token_mint_a: pool.mint_a,
+                token_mint_b: pool.mint_b,
+                token_vault_a: pool.vault_a,
+                token_vault_b: pool.vault_b,
+                user_token_a: owner.token_a,
+                user_token_b: owner.token_b,
+                owner: owner.key,
+                payer: owner.key,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+                allowed_trader: self.allowed_trader(&pool.pool, &owner.key),
+                oracle: self.oracle(&pool.pool),
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::Reposition {
+                tick_lower,
+                tick_upper,
+                params,
+            }
+            .data(),
+        }
+    }
+
+    pub fn reposition(
+        &mut self,
+        pool: &PoolFixture,
+        owner: &User,
+        range: (i32, i32),
+        new_range: (i32, i32),
+        params: RepositionParams,
+    ) -> TxResult {
+        let swap_tick_arrays = (params.swap_amount > 0).then(|| self.swap_tick_arrays(pool, params.swap_a_to_b));
+        let ix = self.ix_reposition(pool, owner, range, new_range, params, swap_tick_arrays);
+        self.bank.process(&ix)
+    }
+
+    pub fn ix_split_position(
+        &self,
+        pool: &PoolFixture,
+        owner: &User,
+        tick_lower: i32,
+        tick_upper: i32,
+        new_owner: &Pubkey,
+        liquidity_delta: u128,
+    ) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::SplitPosition {
+                pool: pool.pool,
+                position: position_pda(&pool.pool, &owner.key, tick_lower, tick_upper),
+                tick_array_lower: pool.tick_array(tick_lower),
+                tick_array_upper: pool.tick_array(tick_upper),
+                new_owner: *new_owner,
+                new_position: position_pda(&pool.pool, new_owner, tick_lower, tick_upper),
+                owner: owner.key,
+                payer: owner.key,
+                system_program: system_program::ID,
+                allowed_trader: self.allowed_trader(&pool.pool, new_owner),
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::SplitPosition { liquidity_delta }.data(),
+        }
+    }
+
+    pub fn split_position(
+        &mut self,
+        pool: &PoolFixture,
+        owner: &User,
+        tick_lower: i32,
+        tick_upper: i32,
+        new_owner: &Pubkey,
+        liquidity_delta: u128,
+    ) -> TxResult {
+        let ix = self.ix_split_position(pool, owner, tick_lower, tick_upper, new_owner, liquidity_delta);
+        self.bank.process(&ix)
+    }
+
+    /// Merges `merged_owner`'s position over `merged_range` into `owner`'s over `range`
+    pub fn ix_merge_positions(
+        &self,
+        pool: &PoolFixture,
+        owner: &User,
+        range: (i32, i32),
+        merged_owner: &User,
+        merged_range: (i32, i32),
+    ) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::MergePositions {
+                pool: pool.pool,
+                position: position_pda(&pool.pool, &owner.key, range.0, range.1),
+                merged_position: position_pda(&pool.pool, &merged_owner.key, merged_range.0, merged_range.1),
+                tick_array_lower: pool.tick_array(range.0),
+                tick_array_upper: pool.tick_array(range.1),
+                owner: owner.key,
+                merged_owner: merged_owner.key,
+                allowed_trader: self.allowed_trader(&pool.pool, &owner.key),
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::MergePositions {}.data(),
+        }
+    }
+
+    pub fn merge_positions(
+        &mut self,
+        pool: &PoolFixture,
+        owner: &User,
+        range: (i32, i32),
+        merged_owner: &User,
+        merged_range: (i32, i32),
+    ) -> TxResult {
+        let ix = self.ix_merge_positions(pool, owner, range, merged_owner, merged_range);
+        self.bank.process(&ix)
+    }
+
+    pub fn ix_set_position_delegate(
+        &self,
+        pool: &PoolFixture,
+        owner: &User,
+        tick_lower: i32,
+        tick_upper: i32,
+        delegate: &Pubkey,
+        permissions: u8,
+    ) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::SetPositionDelegate {
+                position: position_pda(&pool.pool, &owner.key, tick_lower, tick_upper),
+                owner: owner.key,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::SetPositionDelegate {
+                delegate: *delegate,
+                permissions,
+            }
+            .data(),
+        }
+    }
+
+    pub fn set_position_delegate(
+        &mut self,
+        pool: &PoolFixture,
+        owner: &User,
+        tick_lower: i32,
+        tick_upper: i32,
+        delegate: &Pubkey,
+        permissions: u8,
+    ) -> TxResult {
+        let ix = self.ix_set_position_delegate(pool, owner, tick_lower, tick_upper, delegate, permissions);
+        self.bank.process(&ix)
+    }
+
+    pub fn ix_snapshot_cumulatives_inside(
+        &self,
+        pool: &PoolFixture,
+        tick_lower: i32,
+        tick_upper: i32,
+    ) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::SnapshotCumulativesInside {
+                pool: pool.pool,
+                tick_array_lower: pool.tick_array(tick_lower),
+                tick_array_upper: pool.tick_array(tick_upper),
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::SnapshotCumulativesInside { tick_lower, tick_upper }.data(),
+        }
+    }
+
+    pub fn snapshot_cumulatives_inside(
+        &mut self,
+        pool: &PoolFixture,
+        tick_lower: i32,
+        tick_upper: i32,
+    ) -> std::result::Result<CumulativesInside, ProgramError> {
+        let ix = self.ix_snapshot_cumulatives_inside(pool, tick_lower, tick_upper);
+        self.bank.process(&ix)?;
+        Ok(self.bank.return_data())
+    }
+
+    // ─── Swaps ───────────────────────────────────────────────────────────────
+
+    /// The three tick arrays a swap starting at the current tick walks through,
+    /// initializing any that are missing
+    pub fn swap_tick_arrays(&mut self, pool: &PoolFixture, a_to_b: bool) -> [Pubkey; 3] {
+        let tick = self.pool(pool).tick_current;
+        let step = if a_to_b { -pool.ticks_per_array() } else { pool.ticks_per_array() };
+        let start = pool.tick_array_start(tick);
+        [0, 1, 2].map(|i| self.ensure_tick_array(pool, start + step * i))
+    }
+
+    pub fn ix_swap(
+        &self,
+        pool: &PoolFixture,
+        user: &User,
+        params: SwapParams,
+        tick_arrays: [Pubkey; 3],
+    ) -> Instruction {
+        let (user_token_input, user_token_output) = if params.a_to_b {
+            (user.token_a, user.token_b)
+        } else {
+            (user.token_b, user.token_a)
+        };
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::Swap {
+                pool: pool.pool,
+                fee_tier: pool.fee_tier,
+                token_mint_a: pool.mint_a,
+                token_mint_b: pool.mint_b,
+                token_vault_a: pool.vault_a,
+                token_vault_b: pool.vault_b,
+                user_token_input: Some(user_token_input),
+                user_token_output: Some(user_token_output),
+                tick_array_0: tick_arrays[0],
+                tick_array_1: tick_arrays[1],
+                tick_array_2: tick_arrays[2],
+                user: user.key,
+                token_program: spl_token::ID,
+                allowed_trader: self.allowed_trader(&pool.pool, &user.key),
+                oracle: self.oracle(&pool.pool),
+                config: None,
+                referrer_token_account: None,
+                native_sol_account: None,
+                system_program: None,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::Swap { params }.data(),
+        }
+    }
+
+    pub fn swap(&mut self, pool: &PoolFixture, user: &User, params: SwapParams) -> TxResult {
+        let tick_arrays = self.swap_tick_arrays(pool, params.a_to_b);
+        let ix = self.ix_swap(pool, user, params, tick_arrays);
+        self.bank.process(&ix)
+    }
+
+    // ─── Protocol fees ───────────────────────────────────────────────────────
+
+    pub fn ix_collect_protocol_fees(
+        &self,
+        pool: &PoolFixture,
+        recipient: &User,
+        amount_a_requested: u64,
+        amount_b_requested: u64,
+    ) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::CollectProtocolFees {
+                config: config_pda(),
+                pool: pool.pool,
+                fee_tier: pool.fee_tier,
+                token_mint_a: pool.mint_a,
+                token_mint_b: pool.mint_b,
+                token_vault_a: pool.vault_a,
+                token_vault_b: pool.vault_b,
+                recipient_token_a: recipient.token_a,
+                recipient_token_b: recipient.token_b,
+                fee_authority: self.authority,
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::CollectProtocolFees {
+                amount_a_requested,
+                amount_b_requested,
+            }
+            .data(),
+        }
+    }
+
+    pub fn collect_protocol_fees(
+        &mut self,
+        pool: &PoolFixture,
+        recipient: &User,
+        amount_a_requested: u64,
+        amount_b_requested: u64,
+    ) -> TxResult {
+        let ix = self.ix_collect_protocol_fees(pool, recipient, amount_a_requested, amount_b_requested);
+        self.bank.process(&ix)
+    }
+
+    // ─── Creator fees ────────────────────────────────────────────────────────
+
+    pub fn ix_collect_creator_fees(
+        &self,
+        pool: &PoolFixture,
+        creator: &User,
+        amount_a_requested: u64,
+        amount_b_requested: u64,
+    ) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::CollectCreatorFees {
+                pool: pool.pool,
+                fee_tier: pool.fee_tier,
+                token_mint_a: pool.mint_a,
+                token_mint_b: pool.mint_b,
+                token_vault_a: pool.vault_a,
+                token_vault_b: pool.vault_b,
+                recipient_token_a: creator.token_a,
+                recipient_token_b: creator.token_b,
+                creator: creator.key,
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::CollectCreatorFees {
+                amount_a_requested,
+                amount_b_requested,
+            }
+            .data(),
+        }
+    }
+
+    pub fn collect_creator_fees(
+        &mut self,
+        pool: &PoolFixture,
+        creator: &User,
+        amount_a_requested: u64,
+        amount_b_requested: u64,
+    ) -> TxResult {
+        let ix = self.ix_collect_creator_fees(pool, creator, amount_a_requested, amount_b_requested);
+        self.bank.process(&ix)
+    }
+
+    pub fn ix_initialize_protocol_fee_config(&self, recipients: Vec<ProtocolFeeRecipient>) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::InitializeProtocolFeeConfig {
+                config: config_pda(),
+                protocol_fee_config: protocol_fee_config_pda(),
+                fee_authority: self.authority,
+                payer: self.authority,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::InitializeProtocolFeeConfig { recipients }.data(),
+        }
+    }
+
+    pub fn initialize_protocol_fee_config(&mut self, recipients: Vec<ProtocolFeeRecipient>) -> TxResult {
+        let ix = self.ix_initialize_protocol_fee_config(recipients);
+        self.bank.process(&ix)
+    }
+
+    pub fn ix_set_protocol_fee_recipients(&self, recipients: Vec<ProtocolFeeRecipient>) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::SetProtocolFeeRecipients {
+                config: config_pda(),
+                protocol_fee_config: protocol_fee_config_pda(),
+                fee_authority: self.authority,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::SetProtocolFeeRecipients { recipients }.data(),
+        }
+    }
+
+    pub fn set_protocol_fee_recipients(&mut self, recipients: Vec<ProtocolFeeRecipient>) -> TxResult {
+        let ix = self.ix_set_protocol_fee_recipients(recipients);
+        self.bank.process(&ix)
+    }
+
+    /// Distribute to `wallets`' associated token accounts, which must match the recipients
+    pub fn ix_distribute_protocol_fees(&self, pool: &PoolFixture, wallets: &[Pubkey]) -> Instruction {
+        let mut accounts = suniswap::accounts::DistributeProtocolFees {
+            config: config_pda(),
+            protocol_fee_config: protocol_fee_config_pda(),
+            pool: pool.pool,
+            fee_tier: pool.fee_tier,
+            token_mint_a: pool.mint_a,
+            token_mint_b: pool.mint_b,
+            token_vault_a: pool.vault_a,
+            token_vault_b: pool.vault_b,
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None);
+        for wallet in wallets {
+            accounts.push(AccountMeta::new(associated_token_address(wallet, &pool.mint_a), false));
+            accounts.push(AccountMeta::new(associated_token_address(wallet, &pool.mint_b), false));
+        }
+        Instruction {
+            program_id: suniswap::ID,
+            accounts,
+            data: suniswap::instruction::DistributeProtocolFees {}.data(),
+        }
+    }
+
+    pub fn distribute_protocol_fees(&mut self, pool: &PoolFixture, wallets: &[Pubkey]) -> TxResult {
+        let ix = self.ix_distribute_protocol_fees(pool, wallets);
+        self.bank.process(&ix)
+    }
+
+    // ─── Managed vaults ──────────────────────────────────────────────────────
+
+    pub fn ix_initialize_vault(&self, pool: &PoolFixture, id: u16, params: VaultParams) -> Instruction {
+        let vault = managed_vault_pda(&pool.pool, id);
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::InitializeVault {
+                config: config_pda(),
+                pool: pool.pool,
+                vault,
+                share_mint: vault_share_mint_pda(&vault),
+                token_mint_a: pool.mint_a,
+                token_mint_b: pool.mint_b,
+                reserve_a: vault_reserve_pda(&vault, &pool.mint_a),
+                reserve_b: vault_reserve_pda(&vault, &pool.mint_b),
+                authority: self.authority,
+                payer: self.authority,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::InitializeVault { id, params }.data(),
+        }
+    }
+
+    /// Vault 0 on `pool` with a new strategist and treasury, no fees and
+    /// limits at the widest aligned ticks; gives the pool the oracle vault
+    /// deposits need, averaging over 100 seconds, if it has none
+    pub fn create_vault(&mut self, pool: &PoolFixture) -> VaultFixture {
+        if self.oracle(&pool.pool).is_none() {
+            self.initialize_oracle(pool, 100).unwrap();
+        }
+        let strategist = self.create_user(pool, 0);
+        let treasury = self.create_user(pool, 0);
+        let limit = suniswap::constants::MAX_TICK / pool.tick_spacing as i32 * pool.tick_spacing as i32;
+        let params = VaultParams {
+            strategist: strategist.key,
+            treasury: treasury.key,
+            performance_fee_rate: 0,
+            management_fee_rate: 0,
+            tick_lower_limit: -limit,
+            tick_upper_limit: limit,
+        };
+        let ix = self.ix_initialize_vault(pool, 0, params);
+        self.bank.process(&ix).unwrap();
+
+        let vault = managed_vault_pda(&pool.pool, 0);
+        let share_mint = vault_share_mint_pda(&vault);
+        VaultFixture {
+            vault,
+            share_mint,
+            reserve_a: vault_reserve_pda(&vault, &pool.mint_a),
+            reserve_b: vault_reserve_pda(&vault, &pool.mint_b),
+            treasury_shares: self.create_token_account(&share_mint, &treasury.key, 0),
+            strategist,
+            treasury,
+        }
+    }
+
+    pub fn ix_set_vault_params(&self, pool: &PoolFixture, vault: &VaultFixture, params: VaultParams) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::SetVaultParams {
+                config: config_pda(),
+                pool: pool.pool,
+                vault: vault.vault,
+                share_mint: vault.share_mint,
+                authority: self.authority,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::SetVaultParams { params }.data(),
+        }
+    }
+
+    pub fn set_vault_params(&mut self, pool: &PoolFixture, vault: &VaultFixture, params: VaultParams) -> TxResult {
+        let ix = self.ix_set_vault_params(pool, vault, params);
+        self.bank.process(&ix)
+    }
+
+    pub fn ix_open_vault_position(
+        &self,
+        pool: &PoolFixture,
+        vault: &VaultFixture,
+        tick_lower: i32,
+        tick_upper: i32,
+    ) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::OpenVaultPosition {
+                pool: pool.pool,
+                vault: vault.vault,
+                position: position_pda(&pool.pool, &vault.vault, tick_lower, tick_upper),
+                strategist: vault.strategist.key,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::OpenVaultPosition { tick_lower, tick_upper }.data(),
+        }
+    }
+
+    /// Opens the position and creates its tick arrays if needed
+    pub fn open_vault_position(
+        &mut self,
+        pool: &PoolFixture,
+        vault: &VaultFixture,
+        tick_lower: i32,
+        tick_upper: i32,
+    ) -> TxResult {
+        self.ensure_tick_array(pool, tick_lower);
+        self.ensure_tick_array(pool, tick_upper);
+        let ix = self.ix_open_vault_position(pool, vault, tick_lower, tick_upper);
+        self.bank.process(&ix)
+    }
+
+    pub fn ix_rebalance_vault_position(
+        &self,
+        pool: &PoolFixture,
+        vault: &VaultFixture,
+        tick_lower: i32,
+        tick_upper: i32,
+        params: RebalanceVaultParams,
+    ) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::RebalanceVaultPosition {
+                pool: pool.pool,
+                fee_tier: pool.fee_tier,
+                vault: vault.vault,
+                position: position_pda(&pool.pool, &vault.vault, tick_lower, tick_upper),
+                tick_array_lower: pool.tick_array(tick_lower),
+                tick_array_upper: pool.tick_array(tick_upper),
+                token_mint_a: pool.mint_a,
+                token_mint_b: pool.mint_b,
+                token_vault_a: pool.vault_a,
+                token_vault_b: pool.vault_b,
+                reserve_a: vault.reserve_a,
+                reserve_b: vault.reserve_b,
+                strategist: vault.strategist.key,
+                token_program: spl_token::ID,
+                oracle: self.oracle(&pool.pool),
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::RebalanceVaultPosition { params }.data(),
+        }
+    }
+
+    pub fn rebalance_vault_position(
+        &mut self,
+        pool: &PoolFixture,
+        vault: &VaultFixture,
+        tick_lower: i32,
+        tick_upper: i32,
+        params: RebalanceVaultParams,
+    ) -> TxResult {
+        let ix = self.ix_rebalance_vault_position(pool, vault, tick_lower, tick_upper, params);
+        self.bank.process(&ix)
+    }
+
+    pub fn ix_close_vault_position(
+        &self,
+        pool: &PoolFixture,
+        vault: &VaultFixture,
+        tick_lower: i32,
+        tick_upper: i32,
+    ) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::CloseVaultPosition {
+                pool: pool.pool,
+                vault: vault.vault,
+                position: position_pda(&pool.pool, &vault.vault, tick_lower, tick_upper),
+                strategist: vault.strategist.key,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::CloseVaultPosition {}.data(),
+        }
+    }
+
+    pub fn close_vault_position(
+        &mut self,
+        pool: &PoolFixture,
+        vault: &VaultFixture,
+        tick_lower: i32,
+        tick_upper: i32,
+    ) -> TxResult {
+        let ix = self.ix_close_vault_position(pool, vault, tick_lower, tick_upper);
+        self.bank.process(&ix)
+    }
+
+    /// Remaining accounts for the vault's current positions
+    pub fn vault_position_metas(&self, pool: &PoolFixture, vault: &VaultFixture) -> Vec<AccountMeta> {
+        let state = self.vault(vault);
+        state.positions[..state.position_count as usize]
+            .iter()
+            .flat_map(|key| {
+                let position = self.position(key);
+                [
+                    AccountMeta::new(*key, false),
+                    AccountMeta::new(pool.tick_array(position.tick_lower), false),
+                    AccountMeta::new(pool.tick_array(position.tick_upper), false),
+                ]
+            })
+            .collect()
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn ix_deposit_vault(
+        &self,
+        pool: &PoolFixture,
+        vault: &VaultFixture,
+        user: &User,
+        user_shares: &Pubkey,
+        amount_a_max: u64,
+        amount_b_max: u64,
+        shares_min: u64,
+    ) -> Instruction {
+        let mut accounts = suniswap::accounts::DepositVault {
+            pool: pool.pool,
+            fee_tier: pool.fee_tier,
+            vault: vault.vault,
+            share_mint: vault.share_mint,
+            token_mint_a: pool.mint_a,
+            token_mint_b: pool.mint_b,
+            token_vault_a: pool.vault_a,
+            token_vault_b: pool.vault_b,
+            reserve_a: vault.reserve_a,
+            reserve_b: vault.reserve_b,
+            user_token_a: user.token_a,
+            user_token_b: user.token_b,
+            user_shares: *user_shares,
+            user: user.key,
+            token_program: spl_token::ID,
+            allowed_trader: self.allowed_trader(&pool.pool, &user.key),
+            oracle: self.oracle(&pool.pool),
+        }
+        .to_account_metas(None);
+        accounts.extend(self.vault_position_metas(pool, vault));
+        Instruction {
+            program_id: suniswap::ID,
+            accounts,
+            data: suniswap::instruction::DepositVault {
+                amount_a_max,
+                amount_b_max,
+                shares_min,
+            }
+            .data(),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn deposit_vault(
+        &mut self,
+        pool: &PoolFixture,
+        vault: &VaultFixture,
+        user: &User,
+        user_shares: &Pubkey,
+        amount_a_max: u64,
+        amount_b_max: u64,
+        shares_min: u64,
+    ) -> TxResult {
+        let ix = self.ix_deposit_vault(pool, vault, user, user_shares, amount_a_max, amount_b_max, shares_min);
+        self.bank.process(&ix)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn ix_withdraw_vault(
+        &self,
+        pool: &PoolFixture,
+        vault: &VaultFixture,
+        user: &User,
+        user_shares: &Pubkey,
+        shares: u64,
+        amount_a_min: u64,
+        amount_b_min: u64,
+    ) -> Instruction {
+        let mut accounts = suniswap::accounts::WithdrawVault {
+            pool: pool.pool,
+            fee_tier: pool.fee_tier,
+            vault: vault.vault,
+            share_mint: vault.share_mint,
+            token_mint_a: pool.mint_a,
+            token_mint_b: pool.mint_b,
+            token_vault_a: pool.vault_a,
+            token_vault_b: pool.vault_b,
+            reserve_a: vault.reserve_a,
+            reserve_b: vault.reserve_b,
+            user_token_a: user.token_a,
+            user_token_b: user.token_b,
+            user_shares: *user_shares,
+            user: user.key,
+            token_program: spl_token::ID,
+            oracle: self.oracle(&pool.pool),
+        }
+        .to_account_metas(None);
+        accounts.extend(self.vault_position_metas(pool, vault));
+        Instruction {
+            program_id: suniswap::ID,
+            accounts,
+            data: suniswap::instruction::WithdrawVault {
+                shares,
+                amount_a_min,
+                amount_b_min,
+            }
+            .data(),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn withdraw_vault(
+        &mut self,
+        pool: &PoolFixture,
+        vault: &VaultFixture,
+        user: &User,
+        user_shares: &Pubkey,
+        shares: u64,
+        amount_a_min: u64,
+        amount_b_min: u64,
+    ) -> TxResult {
+        let ix = self.ix_withdraw_vault(pool, vault, user, user_shares, shares, amount_a_min, amount_b_min);
+        self.bank.process(&ix)
+    }
+
+    pub fn ix_collect_vault_fees(&self, pool: &PoolFixture, vault: &VaultFixture) -> Instruction {
+        Instruction {
+            program_id: suniswap::ID,
+            accounts: suniswap::accounts::CollectVaultFees {
+                vault: vault.vault,
+                share_mint: vault.share_mint,
+                token_mint_a: pool.mint_a,
+                token_mint_b: pool.mint_b,
+                reserve_a: vault.reserve_a,
+                reserve_b: vault.reserve_b,
+                treasury_token_a: vault.treasury.token_a,
+                treasury_token_b: vault.treasury.token_b,
+                treasury_shares: vault.treasury_shares,
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None),
+            data: suniswap::instruction::CollectVaultFees {}.data(),
+        }
+    }
+
+    pub fn collect_vault_fees(&mut self, pool: &PoolFixture, vault: &VaultFixture) -> TxResult {
+        let ix = self.ix_collect_vault_fees(pool, vault);
+        self.bank.process(&ix)
+    }
+}
+
+pub fn sqrt_price_at(tick: i32) -> u128 {
+    suniswap::math::tick_math::get_sqrt_price_at_tick(tick).unwrap()
+}
+
+/// Exact-input swap with no price limit and no slippage bound
+pub fn exact_in(amount: u64, a_to_b: bool) -> SwapParams {
+    SwapParams {
+        amount: amount as i64,
+        other_amount_threshold: 0,
+        sqrt_price_limit_x64: 0,
+        a_to_b,
+        referral_fee_rate: 0,
+        deadline_unix_ts: None,
+        max_slot: None,
+        max_twap_deviation_ticks: None,
+    }
+}
+
+/// Exact-output swap with no price limit and no slippage bound
+pub fn exact_out(amount: u64, a_to_b: bool) -> SwapParams {
+    SwapParams {
+        amount: -(amount as i64),
+        other_amount_threshold: u64::MAX,
+        sqrt_price_limit_x64: 0,
+        a_to_b,
+        referral_fee_rate: 0,
+        deadline_unix_ts: None,
+        max_slot: None,
+        max_twap_deviation_ticks: None,
+    }
+}
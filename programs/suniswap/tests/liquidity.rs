@@ -0,0 +1,1363 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::InstructionData;
+use common::*;
+use suniswap::errors::SuniswapError;
+use suniswap::events::{
+    FeesCollectedEvent, LiquidityDecreasedEvent, LiquidityIncreasedEvent, PositionClosedEvent,
+    PositionCompoundedEvent, PositionOpenedEvent, PositionRepositionedEvent, PositionSplitEvent,
+    PositionDelegateUpdatedEvent, PositionsMergedEvent, SwapEvent,
+};
+use suniswap::constants::{position_permissions, MAX_SQRT_PRICE_X64, MIN_SQRT_PRICE_X64};
+use suniswap::instructions::{IncreaseLiquidityByAmountsParams, RepositionParams};
+use suniswap::math::liquidity_math::{get_amounts_for_liquidity, get_liquidity_for_amounts};
+use suniswap::state::Pool;
+
+const LIQUIDITY: u128 = 1_000_000_000;
+const BALANCE: u64 = 1_000_000_000_000;
+
+/// Pool at tick 0 (fee 0.3%, spacing 60, 480 ticks per array) with a funded LP
+fn pool_with_lp() -> (TestContext, PoolFixture, User) {
+    let (mut ctx, pool) = TestContext::with_pool(3000, 60, 0);
+    let lp = ctx.create_user(&pool, BALANCE);
+    (ctx, pool, lp)
+}
+
+/// Amounts `increase_liquidity` charges for `liquidity` in [lower, upper) at the current price
+fn deposit_amounts(ctx: &TestContext, pool: &PoolFixture, lower: i32, upper: i32, liquidity: u128) -> (u64, u64) {
+    get_amounts_for_liquidity(
+        ctx.pool(pool).sqrt_price_x64,
+        sqrt_price_at(lower),
+        sqrt_price_at(upper),
+        liquidity,
+        true,
+    )
+    .unwrap()
+}
+
+/// Generate fees for positions around tick 0 by swapping back and forth
+fn churn(ctx: &mut TestContext, pool: &PoolFixture) {
+    let trader = ctx.create_user(pool, BALANCE);
+    ctx.swap(pool, &trader, exact_in(1_000_000, true)).unwrap();
+    ctx.swap(pool, &trader, exact_in(1_000_000, false)).unwrap();
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// OPEN POSITION
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_open_position() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    ctx.open_position(&pool, &lp, -120, 120).unwrap();
+
+    let position = ctx.position(&position_pda(&pool.pool, &lp.key, -120, 120));
+    assert_eq!(position.pool_pubkey(), pool.pool);
+    assert_eq!(position.owner_pubkey(), lp.key);
+    assert_eq!(position.tick_lower, -120);
+    assert_eq!(position.tick_upper, 120);
+    assert_eq!(position.liquidity, 0);
+}
+
+#[test]
+fn test_open_position_validates_ticks() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    assert_error(ctx.open_position(&pool, &lp, 120, 120), SuniswapError::InvalidTickRange);
+    assert_error(ctx.open_position(&pool, &lp, 120, -120), SuniswapError::InvalidTickRange);
+    assert_error(ctx.open_position(&pool, &lp, -100, 120), SuniswapError::InvalidTickLower);
+    assert_error(ctx.open_position(&pool, &lp, -120, 100), SuniswapError::InvalidTickUpper);
+    assert_error(ctx.open_position(&pool, &lp, -443_700, 120), SuniswapError::InvalidTickLower);
+    assert_error(ctx.open_position(&pool, &lp, -120, 443_700), SuniswapError::InvalidTickUpper);
+}
+
+#[test]
+fn test_open_position_blocked_while_pool_paused() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    ctx.modify_zero_copy::<Pool>(&pool.pool, |p| p.is_paused = 1);
+    assert_error(ctx.open_position(&pool, &lp, -120, 120), SuniswapError::PoolPaused);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// INCREASE LIQUIDITY
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_increase_liquidity_in_range() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    let (amount_a, amount_b) = deposit_amounts(&ctx, &pool, -120, 600, LIQUIDITY);
+    let position = ctx.add_liquidity(&pool, &lp, -120, 600, LIQUIDITY);
+
+    assert!(amount_a > 0 && amount_b > 0);
+    assert_eq!(ctx.token_balance(&pool.vault_a), amount_a);
+    assert_eq!(ctx.token_balance(&pool.vault_b), amount_b);
+    assert_eq!(ctx.token_balance(&lp.token_a), BALANCE - amount_a);
+    assert_eq!(ctx.position(&position).liquidity, LIQUIDITY);
+    assert_eq!(ctx.pool(&pool).liquidity, LIQUIDITY);
+
+    // Bounds live in different arrays, each carrying its own tick
+    let lower = ctx.tick_array(&pool.tick_array(-120));
+    let upper = ctx.tick_array(&pool.tick_array(600));
+    assert_eq!(lower.get_tick(-120, 60).unwrap().liquidity_net, LIQUIDITY as i128);
+    assert_eq!(upper.get_tick(600, 60).unwrap().liquidity_net, -(LIQUIDITY as i128));
+    assert!(lower.is_tick_initialized(-120, 60).unwrap());
+    assert!(upper.is_tick_initialized(600, 60).unwrap());
+
+    let event = ctx.bank.events::<LiquidityIncreasedEvent>().pop().unwrap();
+    assert_eq!((event.amount_a, event.amount_b), (amount_a, amount_b));
+    assert_eq!(event.pool_liquidity, LIQUIDITY);
+}
+
+#[test]
+fn test_increase_liquidity_out_of_range() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+
+    // Entirely above the price: token A only, pool liquidity untouched. Both bounds
+    // share a tick array here, so the same account is passed for lower and upper
+    ctx.add_liquidity(&pool, &lp, 120, 240, LIQUIDITY);
+    assert!(ctx.token_balance(&pool.vault_a) > 0);
+    assert_eq!(ctx.token_balance(&pool.vault_b), 0);
+
+    // Entirely below the price: token B only
+    ctx.add_liquidity(&pool, &lp, -240, -120, LIQUIDITY);
+    assert!(ctx.token_balance(&pool.vault_b) > 0);
+    assert_eq!(ctx.pool(&pool).liquidity, 0);
+}
+
+#[test]
+fn test_increase_liquidity_slippage() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    ctx.ensure_tick_array(&pool, -120);
+    ctx.ensure_tick_array(&pool, 120);
+    ctx.open_position(&pool, &lp, -120, 120).unwrap();
+    let (amount_a, amount_b) = deposit_amounts(&ctx, &pool, -120, 120, LIQUIDITY);
+
+    assert_error(
+        ctx.increase_liquidity(&pool, &lp, -120, 120, LIQUIDITY, amount_a - 1, amount_b),
+        SuniswapError::AmountAExceedsMax,
+    );
+    assert_error(
+        ctx.increase_liquidity(&pool, &lp, -120, 120, LIQUIDITY, amount_a, amount_b - 1),
+        SuniswapError::AmountBExceedsMax,
+    );
+    assert!(ctx.increase_liquidity(&pool, &lp, -120, 120, LIQUIDITY, amount_a, amount_b).is_ok());
+}
+
+#[test]
+fn test_increase_liquidity_rejects_zero() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    ctx.add_liquidity(&pool, &lp, -120, 120, LIQUIDITY);
+    assert_error(
+        ctx.increase_liquidity(&pool, &lp, -120, 120, 0, u64::MAX, u64::MAX),
+        SuniswapError::ZeroLiquidity,
+    );
+}
+
+#[test]
+fn test_increase_liquidity_validates_accounts() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    ctx.add_liquidity(&pool, &lp, -120, 120, LIQUIDITY);
+    let ix = ctx.ix_increase_liquidity(&pool, &lp, -120, 120, LIQUIDITY, u64::MAX, u64::MAX);
+
+    // A position and tick array belonging to another pool
+    let other = ctx.create_pool(3000, sqrt_price_at(0)).unwrap();
+    let other_lp = ctx.create_user(&other, BALANCE);
+    let other_position = ctx.add_liquidity(&other, &other_lp, -120, 120, LIQUIDITY);
+
+    let mut bad = ix.clone();
+    replace_account(&mut bad, position_pda(&pool.pool, &lp.key, -120, 120), other_position);
+    assert_error(ctx.bank.process(&bad), SuniswapError::InvalidPosition);
+
+    let mut bad = ix.clone();
+    replace_account(&mut bad, pool.tick_array(-120), other.tick_array(-120));
+    assert_error(ctx.bank.process(&bad), SuniswapError::InvalidTickArray);
+
+    let mut bad = ix.clone();
+    replace_account(&mut bad, pool.mint_a, other.mint_a);
+    assert_error(ctx.bank.process(&bad), SuniswapError::InvalidTokenMint);
+
+    let mut bad = ix.clone();
+    replace_account(&mut bad, pool.vault_b, other.vault_b);
+    assert_error(ctx.bank.process(&bad), SuniswapError::InvalidVault);
+
+    let intruder = ctx.create_user(&pool, BALANCE);
+    let mut bad = ix.clone();
+    replace_account(&mut bad, lp.key, intruder.key);
+    assert_error(ctx.bank.process(&bad), SuniswapError::InvalidPositionOwner);
+
+    assert!(ctx.bank.process(&ix).is_ok());
+}
+
+#[test]
+fn test_increase_liquidity_requires_array_containing_tick() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    ctx.add_liquidity(&pool, &lp, -120, 600, LIQUIDITY);
+
+    // Lower and upper arrays swapped: both belong to the pool but neither holds its tick
+    let mut ix = ctx.ix_increase_liquidity(&pool, &lp, -120, 600, LIQUIDITY, u64::MAX, u64::MAX);
+    let (lower, upper, placeholder) = (pool.tick_array(-120), pool.tick_array(600), Pubkey::new_unique());
+    replace_account(&mut ix, lower, placeholder);
+    replace_account(&mut ix, upper, lower);
+    replace_account(&mut ix, placeholder, upper);
+    assert_error(ctx.bank.process(&ix), SuniswapError::TickArrayNotFound);
+}
+
+#[test]
+fn test_increase_liquidity_blocked_while_pool_paused() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    ctx.add_liquidity(&pool, &lp, -120, 120, LIQUIDITY);
+    ctx.modify_zero_copy::<Pool>(&pool.pool, |p| p.is_paused = 1);
+    assert_error(
+        ctx.increase_liquidity(&pool, &lp, -120, 120, LIQUIDITY, u64::MAX, u64::MAX),
+        SuniswapError::PoolPaused,
+    );
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// INCREASE LIQUIDITY BY AMOUNTS
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Deposit of up to the given amounts, accepting any price
+fn by_amounts(amount_a_max: u64, amount_b_max: u64) -> IncreaseLiquidityByAmountsParams {
+    IncreaseLiquidityByAmountsParams {
+        amount_a_max,
+        amount_b_max,
+        sqrt_price_min_x64: MIN_SQRT_PRICE_X64,
+        sqrt_price_max_x64: MAX_SQRT_PRICE_X64,
+        deadline_unix_ts: None,
+        max_slot: None,
+    }
+}
+
+fn open_empty_position(ctx: &mut TestContext, pool: &PoolFixture, lp: &User, lower: i32, upper: i32) -> Pubkey {
+    ctx.ensure_tick_array(pool, lower);
+    ctx.ensure_tick_array(pool, upper);
+    ctx.open_position(pool, lp, lower, upper).unwrap();
+    position_pda(&pool.pool, &lp.key, lower, upper)
+}
+
+#[test]
+fn test_increase_liquidity_by_amounts_in_range() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    let position = open_empty_position(&mut ctx, &pool, &lp, -120, 600);
+
+    // Token B is the binding side; the deposit takes no more than either maximum
+    let (amount_a, amount_b) = deposit_amounts(&ctx, &pool, -120, 600, LIQUIDITY);
+    ctx.increase_liquidity_by_amounts(&pool, &lp, -120, 600, by_amounts(2 * amount_a, amount_b))
+        .unwrap();
+
+    let liquidity = ctx.position(&position).liquidity;
+    let expected = get_liquidity_for_amounts(
+        ctx.pool(&pool).sqrt_price_x64,
+        sqrt_price_at(-120),
+        sqrt_price_at(600),
+        2 * amount_a,
+        amount_b,
+    )
+    .unwrap();
+    assert_eq!(liquidity, expected);
+    // The amounts were rounded up for LIQUIDITY, so they fund at least that much
+    assert!(liquidity >= LIQUIDITY);
+    assert_eq!(ctx.pool(&pool).liquidity, liquidity);
+
+    let event = ctx.bank.events::<LiquidityIncreasedEvent>().pop().unwrap();
+    assert_eq!(event.liquidity_delta, liquidity);
+    assert!(event.amount_a <= 2 * amount_a && event.amount_b <= amount_b);
+    assert_eq!(ctx.token_balance(&pool.vault_b), event.amount_b);
+}
+
+#[test]
+fn test_increase_liquidity_by_amounts_single_sided() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+
+    // Above the price only token A counts, so no token B needs to be offered
+    let position = open_empty_position(&mut ctx, &pool, &lp, 120, 240);
+    ctx.increase_liquidity_by_amounts(&pool, &lp, 120, 240, by_amounts(1_000_000, 0))
+        .unwrap();
+    assert!(ctx.position(&position).liquidity > 0);
+    assert!(ctx.token_balance(&pool.vault_a) <= 1_000_000);
+    assert_eq!(ctx.token_balance(&pool.vault_b), 0);
+    assert_eq!(ctx.pool(&pool).liquidity, 0);
+}
+
+#[test]
+fn test_increase_liquidity_by_amounts_validation() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    open_empty_position(&mut ctx, &pool, &lp, -120, 120);
+
+    // The price (tick 0) sits outside the band on either side
+    let below = IncreaseLiquidityByAmountsParams {
+        sqrt_price_max_x64: sqrt_price_at(0) - 1,
+        ..by_amounts(1_000_000, 1_000_000)
+    };
+    assert_error(
+        ctx.increase_liquidity_by_amounts(&pool, &lp, -120, 120, below),
+        SuniswapError::PriceOutsideBand,
+    );
+    let above = IncreaseLiquidityByAmountsParams {
+        sqrt_price_min_x64: sqrt_price_at(0) + 1,
+        ..by_amounts(1_000_000, 1_000_000)
+    };
+    assert_error(
+        ctx.increase_liquidity_by_amounts(&pool, &lp, -120, 120, above),
+        SuniswapError::PriceOutsideBand,
+    );
+
+    // In range, both tokens are required
+    assert_error(
+        ctx.increase_liquidity_by_amounts(&pool, &lp, -120, 120, by_amounts(1_000_000, 0)),
+        SuniswapError::ZeroLiquidity,
+    );
+
+    let exact = IncreaseLiquidityByAmountsParams {
+        sqrt_price_min_x64: sqrt_price_at(0),
+        sqrt_price_max_x64: sqrt_price_at(0),
+        ..by_amounts(1_000_000, 1_000_000)
+    };
+    assert!(ctx.increase_liquidity_by_amounts(&pool, &lp, -120, 120, exact).is_ok());
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// OPEN POSITION WITH LIQUIDITY
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_open_position_with_liquidity_creates_tick_arrays() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    let (lower_array, upper_array) = (pool.tick_array(-120), pool.tick_array(600));
+    assert!(!ctx.exists(&lower_array) && !ctx.exists(&upper_array));
+
+    ctx.open_position_with_liquidity(&pool, &lp, -120, 600, by_amounts(1_000_000, 1_000_000))
+        .unwrap();
+
+    let expected = get_liquidity_for_amounts(
+        ctx.pool(&pool).sqrt_price_x64,
+        sqrt_price_at(-120),
+        sqrt_price_at(600),
+        1_000_000,
+        1_000_000,
+    )
+    .unwrap();
+    let position = ctx.position(&position_pda(&pool.pool, &lp.key, -120, 600));
+    assert_eq!((position.tick_lower, position.tick_upper), (-120, 600));
+    assert_eq!(position.owner_pubkey(), lp.key);
+    assert_eq!(position.liquidity, expected);
+    assert_eq!(ctx.pool(&pool).liquidity, expected);
+
+    let lower = ctx.tick_array(&lower_array);
+    let upper = ctx.tick_array(&upper_array);
+    assert_eq!((lower.pool_pubkey(), lower.start_tick_index), (pool.pool, -480));
+    assert_eq!((upper.pool_pubkey(), upper.start_tick_index), (pool.pool, 480));
+    assert_eq!(lower.get_tick(-120, 60).unwrap().liquidity_net, expected as i128);
+    assert_eq!(upper.get_tick(600, 60).unwrap().liquidity_net, -(expected as i128));
+
+    let event = ctx.bank.events::<LiquidityIncreasedEvent>().pop().unwrap();
+    assert_eq!(event.liquidity_delta, expected);
+    assert_eq!(ctx.token_balance(&pool.vault_a), event.amount_a);
+    assert_eq!(ctx.token_balance(&pool.vault_b), event.amount_b);
+    assert_eq!(ctx.bank.events::<PositionOpenedEvent>().len(), 1);
+
+    // The position works with the regular instructions
+    ctx.withdraw(&pool, &lp, -120, 600, expected, 0, 0, true).unwrap();
+}
+
+#[test]
+fn test_open_position_with_liquidity_reuses_tick_arrays() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+
+    // Both bounds in one new array: passed as lower and upper, created once
+    ctx.open_position_with_liquidity(&pool, &lp, 120, 240, by_amounts(1_000_000, 0))
+        .unwrap();
+    let array = ctx.tick_array(&pool.tick_array(120));
+    assert!(array.is_tick_initialized(120, 60).unwrap());
+    assert!(array.is_tick_initialized(240, 60).unwrap());
+
+    // Existing arrays and ticks are left as they are
+    let other = ctx.create_user(&pool, BALANCE);
+    ctx.open_position_with_liquidity(&pool, &other, 120, 240, by_amounts(1_000_000, 0))
+        .unwrap();
+    let liquidity = ctx.position(&position_pda(&pool.pool, &lp.key, 120, 240)).liquidity;
+    let array = ctx.tick_array(&pool.tick_array(120));
+    assert_eq!(array.get_tick(120, 60).unwrap().liquidity_net, 2 * liquidity as i128);
+
+    // An array address that was sent lamports beforehand is still created
+    let funded = pool.tick_array(-120);
+    ctx.bank.airdrop(funded, 1);
+    ctx.open_position_with_liquidity(&pool, &lp, -120, 120, by_amounts(1_000_000, 1_000_000))
+        .unwrap();
+    assert_eq!(ctx.tick_array(&funded).start_tick_index, -480);
+}
+
+#[test]
+fn test_open_position_with_liquidity_validation() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+
+    assert_error(
+        ctx.open_position_with_liquidity(&pool, &lp, -100, 120, by_amounts(1_000_000, 1_000_000)),
+        SuniswapError::InvalidTickLower,
+    );
+    let moved = IncreaseLiquidityByAmountsParams {
+        sqrt_price_min_x64: sqrt_price_at(60),
+        ..by_amounts(1_000_000, 1_000_000)
+    };
+    assert_error(
+        ctx.open_position_with_liquidity(&pool, &lp, -120, 120, moved),
+        SuniswapError::PriceOutsideBand,
+    );
+    assert_error(
+        ctx.open_position_with_liquidity(&pool, &lp, -120, 120, by_amounts(1_000_000, 0)),
+        SuniswapError::ZeroLiquidity,
+    );
+
+    // Each array must be the PDA holding its bound
+    let mut ix = ctx.ix_open_position_with_liquidity(&pool, &lp, -120, 600, by_amounts(1_000_000, 1_000_000));
+    replace_account(&mut ix, pool.tick_array(600), pool.tick_array(1200));
+    assert_error(ctx.bank.process(&ix), SuniswapError::InvalidTickArray);
+
+    // Failed attempts leave nothing behind
+    assert!(!ctx.exists(&position_pda(&pool.pool, &lp.key, -120, 120)));
+    assert!(!ctx.exists(&pool.tick_array(-120)));
+    assert!(!ctx.exists(&pool.tick_array(600)));
+
+    ctx.modify_zero_copy::<Pool>(&pool.pool, |p| p.is_paused = 1);
+    assert_error(
+        ctx.open_position_with_liquidity(&pool, &lp, -120, 120, by_amounts(1_000_000, 1_000_000)),
+        SuniswapError::PoolPaused,
+    );
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// DECREASE LIQUIDITY
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_decrease_liquidity_credits_owed_tokens() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    let position = ctx.add_liquidity(&pool, &lp, -120, 120, LIQUIDITY);
+    let (deposit_a, deposit_b) = (ctx.token_balance(&pool.vault_a), ctx.token_balance(&pool.vault_b));
+
+    ctx.decrease_liquidity(&pool, &lp, -120, 120, LIQUIDITY / 2, 0, 0).unwrap();
+    ctx.decrease_liquidity(&pool, &lp, -120, 120, LIQUIDITY / 2, 0, 0).unwrap();
+
+    let state = ctx.position(&position);
+    assert_eq!(state.liquidity, 0);
+    assert_eq!(ctx.pool(&pool).liquidity, 0);
+    // Withdrawals round down, so the vaults keep at most a unit of dust per side per call
+    assert!(state.tokens_owed_a <= deposit_a && deposit_a - state.tokens_owed_a <= 2);
+    assert!(state.tokens_owed_b <= deposit_b && deposit_b - state.tokens_owed_b <= 2);
+
+    // Ticks are cleared once the last liquidity referencing them is gone
+    let lower = ctx.tick_array(&pool.tick_array(-120));
+    let upper = ctx.tick_array(&pool.tick_array(120));
+    assert!(!lower.is_tick_initialized(-120, 60).unwrap());
+    assert!(!upper.is_tick_initialized(120, 60).unwrap());
+}
+
+#[test]
+fn test_decrease_liquidity_validation() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    ctx.add_liquidity(&pool, &lp, -120, 120, LIQUIDITY);
+    let (amount_a, amount_b) = get_amounts_for_liquidity(
+        ctx.pool(&pool).sqrt_price_x64,
+        sqrt_price_at(-120),
+        sqrt_price_at(120),
+        LIQUIDITY,
+        false,
+    )
+    .unwrap();
+
+    assert_error(
+        ctx.decrease_liquidity(&pool, &lp, -120, 120, 0, 0, 0),
+        SuniswapError::ZeroLiquidity,
+    );
+    assert_error(
+        ctx.decrease_liquidity(&pool, &lp, -120, 120, LIQUIDITY + 1, 0, 0),
+        SuniswapError::InsufficientLiquidity,
+    );
+    assert_error(
+        ctx.decrease_liquidity(&pool, &lp, -120, 120, LIQUIDITY, amount_a + 1, 0),
+        SuniswapError::AmountABelowMin,
+    );
+    assert_error(
+        ctx.decrease_liquidity(&pool, &lp, -120, 120, LIQUIDITY, 0, amount_b + 1),
+        SuniswapError::AmountBBelowMin,
+    );
+    assert!(ctx.decrease_liquidity(&pool, &lp, -120, 120, LIQUIDITY, amount_a, amount_b).is_ok());
+}
+
+#[test]
+fn test_liquidity_deadline_and_max_slot() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    ctx.add_liquidity(&pool, &lp, -120, 120, LIQUIDITY);
+    ctx.bank.warp_to(100, 1_000);
+
+    let expired = IncreaseLiquidityByAmountsParams {
+        deadline_unix_ts: Some(999),
+        ..by_amounts(1_000_000, 1_000_000)
+    };
+    assert_error(
+        ctx.increase_liquidity_by_amounts(&pool, &lp, -120, 120, expired),
+        SuniswapError::DeadlineExceeded,
+    );
+    let late = IncreaseLiquidityByAmountsParams { max_slot: Some(99), ..by_amounts(1_000_000, 1_000_000) };
+    assert_error(
+        ctx.open_position_with_liquidity(&pool, &lp, -240, 240, late),
+        SuniswapError::MaxSlotExceeded,
+    );
+
+    let mut ix = ctx.ix_decrease_liquidity(&pool, &lp, -120, 120, LIQUIDITY, 0, 0);
+    ix.data = suniswap::instruction::DecreaseLiquidity {
+        liquidity_delta: LIQUIDITY,
+        amount_a_min: 0,
+        amount_b_min: 0,
+        deadline_unix_ts: Some(999),
+        max_slot: None,
+    }
+    .data();
+    assert_error(ctx.bank.process(&ix), SuniswapError::DeadlineExceeded);
+
+    let mut ix = ctx.ix_withdraw(&pool, &lp, -120, 120, LIQUIDITY, 0, 0, true);
+    let withdraw = |deadline_unix_ts, max_slot| {
+        suniswap::instruction::Withdraw {
+            liquidity_delta: LIQUIDITY,
+            amount_a_min: 0,
+            amount_b_min: 0,
+            close_position: true,
+            deadline_unix_ts,
+            max_slot,
+        }
+        .data()
+    };
+    ix.data = withdraw(None, Some(99));
+    assert_error(ctx.bank.process(&ix), SuniswapError::MaxSlotExceeded);
+    ix.data = withdraw(Some(1_000), Some(100));
+    assert!(ctx.bank.process(&ix).is_ok());
+}
+
+#[test]
+fn test_decrease_liquidity_validates_accounts() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    ctx.add_liquidity(&pool, &lp, -120, 120, LIQUIDITY);
+    let ix = ctx.ix_decrease_liquidity(&pool, &lp, -120, 120, LIQUIDITY, 0, 0);
+
+    let other = ctx.create_pool(3000, sqrt_price_at(0)).unwrap();
+    let other_lp = ctx.create_user(&other, BALANCE);
+    let other_position = ctx.add_liquidity(&other, &other_lp, -120, 120, LIQUIDITY);
+
+    let mut bad = ix.clone();
+    replace_account(&mut bad, position_pda(&pool.pool, &lp.key, -120, 120), other_position);
+    assert_error(ctx.bank.process(&bad), SuniswapError::InvalidPosition);
+
+    let mut bad = ix.clone();
+    replace_account(&mut bad, pool.tick_array(-120), other.tick_array(-120));
+    assert_error(ctx.bank.process(&bad), SuniswapError::InvalidTickArray);
+
+    let intruder = ctx.create_user(&pool, BALANCE);
+    let mut bad = ix.clone();
+    replace_account(&mut bad, lp.key, intruder.key);
+    assert_error(ctx.bank.process(&bad), SuniswapError::InvalidPositionOwner);
+
+    ctx.modify_zero_copy::<Pool>(&pool.pool, |p| p.is_paused = 1);
+    assert_error(ctx.bank.process(&ix), SuniswapError::PoolPaused);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// COLLECT FEES
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_collect_fees() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    let position = ctx.add_liquidity(&pool, &lp, -120, 120, LIQUIDITY);
+    churn(&mut ctx, &pool);
+
+    // Partial collection first, then everything that is left
+    let before_a = ctx.token_balance(&lp.token_a);
+    ctx.collect_fees(&pool, &lp, -120, 120, 100, 0).unwrap();
+    assert_eq!(ctx.token_balance(&lp.token_a) - before_a, 100);
+    assert_eq!(ctx.bank.events::<FeesCollectedEvent>().pop().unwrap().amount_a, 100);
+
+    ctx.collect_fees(&pool, &lp, -120, 120, u64::MAX, u64::MAX).unwrap();
+    let event = ctx.bank.events::<FeesCollectedEvent>().pop().unwrap();
+    // 0.3% of 1e6 per swap, less the 10% protocol share, and rounded down
+    let total_a = 100 + event.amount_a;
+    assert!((2_690..=2_700).contains(&total_a), "fees a = {total_a}");
+    assert!((2_690..=2_700).contains(&event.amount_b), "fees b = {}", event.amount_b);
+
+    let state = ctx.position(&position);
+    assert_eq!((state.tokens_owed_a, state.tokens_owed_b), (0, 0));
+
+    // Nothing new to collect without further swaps
+    ctx.collect_fees(&pool, &lp, -120, 120, u64::MAX, u64::MAX).unwrap();
+    let event = ctx.bank.events::<FeesCollectedEvent>().pop().unwrap();
+    assert_eq!((event.amount_a, event.amount_b), (0, 0));
+}
+
+#[test]
+fn test_collect_fees_out_of_range_position_earns_nothing() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    ctx.add_liquidity(&pool, &lp, -120, 120, LIQUIDITY);
+    let idle = ctx.create_user(&pool, BALANCE);
+    ctx.add_liquidity(&pool, &idle, 600, 1200, LIQUIDITY);
+    churn(&mut ctx, &pool);
+
+    ctx.collect_fees(&pool, &idle, 600, 1200, u64::MAX, u64::MAX).unwrap();
+    let event = ctx.bank.events::<FeesCollectedEvent>().pop().unwrap();
+    assert_eq!((event.amount_a, event.amount_b), (0, 0));
+}
+
+#[test]
+fn test_collect_fees_late_position_earns_nothing_from_earlier_swaps() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    ctx.add_liquidity(&pool, &lp, -120, 120, LIQUIDITY);
+    churn(&mut ctx, &pool);
+
+    // Shares the already initialized -120 tick and initializes -60 after the fees accrued
+    let late = ctx.create_user(&pool, BALANCE);
+    ctx.add_liquidity(&pool, &late, -120, -60, LIQUIDITY);
+
+    ctx.collect_fees(&pool, &late, -120, -60, u64::MAX, u64::MAX).unwrap();
+    let event = ctx.bank.events::<FeesCollectedEvent>().pop().unwrap();
+    assert_eq!((event.amount_a, event.amount_b), (0, 0));
+}
+
+#[test]
+fn test_collect_fees_validates_accounts() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    ctx.add_liquidity(&pool, &lp, -120, 120, LIQUIDITY);
+    let ix = ctx.ix_collect_fees(&pool, &lp, -120, 120, u64::MAX, u64::MAX);
+
+    ctx.initialize_fee_tier(500, 10).unwrap();
+    let other = ctx.create_pool(500, sqrt_price_at(0)).unwrap();
+    let other_lp = ctx.create_user(&other, BALANCE);
+    let other_position = ctx.add_liquidity(&other, &other_lp, -120, 120, LIQUIDITY);
+
+    let mut bad = ix.clone();
+    replace_account(&mut bad, pool.fee_tier, other.fee_tier);
+    assert_error(ctx.bank.process(&bad), SuniswapError::InvalidFeeTier);
+
+    let mut bad = ix.clone();
+    replace_account(&mut bad, pool.mint_b, other.mint_b);
+    assert_error(ctx.bank.process(&bad), SuniswapError::InvalidTokenMint);
+
+    let mut bad = ix.clone();
+    replace_account(&mut bad, pool.vault_a, other.vault_a);
+    assert_error(ctx.bank.process(&bad), SuniswapError::InvalidVault);
+
+    let mut bad = ix.clone();
+    replace_account(&mut bad, position_pda(&pool.pool, &lp.key, -120, 120), other_position);
+    assert_error(ctx.bank.process(&bad), SuniswapError::InvalidPosition);
+
+    let mut bad = ix.clone();
+    replace_account(&mut bad, pool.tick_array(120), other.tick_array(120));
+    assert_error(ctx.bank.process(&bad), SuniswapError::InvalidTickArray);
+
+    let intruder = ctx.create_user(&pool, BALANCE);
+    let mut bad = ix.clone();
+    replace_account(&mut bad, lp.key, intruder.key);
+    assert_error(ctx.bank.process(&bad), SuniswapError::InvalidPositionOwner);
+
+    ctx.modify_zero_copy::<Pool>(&pool.pool, |p| p.is_paused = 1);
+    assert_error(ctx.bank.process(&ix), SuniswapError::PoolPaused);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// WITHDRAW
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_withdraw_pays_principal_and_fees() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    let position = ctx.add_liquidity(&pool, &lp, -120, 120, LIQUIDITY);
+    churn(&mut ctx, &pool);
+
+    let before = (ctx.token_balance(&lp.token_a), ctx.token_balance(&lp.token_b));
+    ctx.withdraw(&pool, &lp, -120, 120, LIQUIDITY / 2, 0, 0, false).unwrap();
+
+    // One transfer per token covers the principal and every fee earned so far
+    let removed = ctx.bank.events::<LiquidityDecreasedEvent>().pop().unwrap();
+    let paid = ctx.bank.events::<FeesCollectedEvent>().pop().unwrap();
+    assert_eq!(ctx.token_balance(&lp.token_a) - before.0, paid.amount_a);
+    assert_eq!(ctx.token_balance(&lp.token_b) - before.1, paid.amount_b);
+    let (fees_a, fees_b) = (paid.amount_a - removed.amount_a, paid.amount_b - removed.amount_b);
+    assert!((2_690..=2_700).contains(&fees_a), "fees a = {fees_a}");
+    assert!((2_690..=2_700).contains(&fees_b), "fees b = {fees_b}");
+
+    let state = ctx.position(&position);
+    assert_eq!(state.liquidity, LIQUIDITY - LIQUIDITY / 2);
+    assert_eq!((state.tokens_owed_a, state.tokens_owed_b), (0, 0));
+    assert_eq!(ctx.pool(&pool).liquidity, LIQUIDITY - LIQUIDITY / 2);
+
+    // Without removing anything, only newly earned fees are paid
+    ctx.withdraw(&pool, &lp, -120, 120, 0, 0, 0, false).unwrap();
+    let paid = ctx.bank.events::<FeesCollectedEvent>().pop().unwrap();
+    assert_eq!((paid.amount_a, paid.amount_b), (0, 0));
+}
+
+#[test]
+fn test_withdraw_and_close() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    let position = ctx.add_liquidity(&pool, &lp, -120, 120, LIQUIDITY);
+    churn(&mut ctx, &pool);
+
+    // Closing requires removing all of the liquidity
+    assert_error(
+        ctx.withdraw(&pool, &lp, -120, 120, LIQUIDITY / 2, 0, 0, true),
+        SuniswapError::PositionHasLiquidity,
+    );
+
+    let rent = ctx.bank.account(&position).unwrap().lamports;
+    let lamports_before = ctx.bank.account(&lp.key).unwrap().lamports;
+    ctx.withdraw(&pool, &lp, -120, 120, LIQUIDITY, 0, 0, true).unwrap();
+
+    assert!(!ctx.exists(&position));
+    assert_eq!(ctx.bank.account(&lp.key).unwrap().lamports, lamports_before + rent);
+    assert_eq!(ctx.bank.events::<PositionClosedEvent>().pop().unwrap().position, position);
+    assert_eq!(ctx.pool(&pool).liquidity, 0);
+
+    // Only rounding dust stays behind in the vaults
+    assert!(ctx.token_balance(&pool.vault_a) <= 1_000);
+    assert!(ctx.token_balance(&pool.vault_b) <= 1_000);
+}
+
+#[test]
+fn test_withdraw_validation() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    ctx.add_liquidity(&pool, &lp, -120, 120, LIQUIDITY);
+    let (amount_a, amount_b) = get_amounts_for_liquidity(
+        ctx.pool(&pool).sqrt_price_x64,
+        sqrt_price_at(-120),
+        sqrt_price_at(120),
+        LIQUIDITY,
+        false,
+    )
+    .unwrap();
+
+    assert_error(
+        ctx.withdraw(&pool, &lp, -120, 120, LIQUIDITY, amount_a + 1, 0, false),
+        SuniswapError::AmountABelowMin,
+    );
+    assert_error(
+        ctx.withdraw(&pool, &lp, -120, 120, LIQUIDITY, 0, amount_b + 1, false),
+        SuniswapError::AmountBBelowMin,
+    );
+    assert_error(
+        ctx.withdraw(&pool, &lp, -120, 120, LIQUIDITY + 1, 0, 0, false),
+        SuniswapError::InsufficientLiquidity,
+    );
+
+    let ix = ctx.ix_withdraw(&pool, &lp, -120, 120, LIQUIDITY, amount_a, amount_b, false);
+    let intruder = ctx.create_user(&pool, BALANCE);
+    for delta in [LIQUIDITY, 0] {
+        let mut bad = ctx.ix_withdraw(&pool, &lp, -120, 120, delta, 0, 0, false);
+        replace_account(&mut bad, lp.key, intruder.key);
+        assert_error(ctx.bank.process(&bad), SuniswapError::InvalidPositionOwner);
+    }
+
+    let other = ctx.create_pool(3000, sqrt_price_at(0)).unwrap();
+    let mut bad = ix.clone();
+    replace_account(&mut bad, pool.vault_a, other.vault_a);
+    assert_error(ctx.bank.process(&bad), SuniswapError::InvalidVault);
+
+    ctx.modify_zero_copy::<Pool>(&pool.pool, |p| p.is_paused = 1);
+    assert_error(ctx.bank.process(&ix), SuniswapError::PoolPaused);
+    ctx.modify_zero_copy::<Pool>(&pool.pool, |p| p.is_paused = 0);
+    assert!(ctx.bank.process(&ix).is_ok());
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// COMPOUND POSITION
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_compound_position_reinvests_fees() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    let position = ctx.add_liquidity(&pool, &lp, -120, 120, LIQUIDITY);
+    churn(&mut ctx, &pool);
+    let vaults = (
+        ctx.token_balance(&pool.vault_a),
+        ctx.token_balance(&pool.vault_b),
+    );
+
+    ctx.compound_position(&pool, &lp, -120, 120).unwrap();
+    let event = ctx.bank.events::<PositionCompoundedEvent>().pop().unwrap();
+
+    // The liquidity is funded by the settled fees, which cover the deposit
+    let (fees_a, fees_b) = (
+        event.amount_a + event.tokens_owed_a,
+        event.amount_b + event.tokens_owed_b,
+    );
+    assert!((2_690..=2_700).contains(&fees_a), "fees a = {fees_a}");
+    assert!((2_690..=2_700).contains(&fees_b), "fees b = {fees_b}");
+    let expected = get_liquidity_for_amounts(
+        ctx.pool(&pool).sqrt_price_x64,
+        sqrt_price_at(-120),
+        sqrt_price_at(120),
+        fees_a,
+        fees_b,
+    )
+    .unwrap();
+    assert!(expected > 0);
+    assert_eq!(event.liquidity_delta, expected);
+
+    let state = ctx.position(&position);
+    assert_eq!(state.liquidity, LIQUIDITY + expected);
+    assert_eq!(
+        (state.tokens_owed_a, state.tokens_owed_b),
+        (event.tokens_owed_a, event.tokens_owed_b)
+    );
+    assert_eq!(ctx.pool(&pool).liquidity, LIQUIDITY + expected);
+
+    // Nothing left the vaults
+    assert_eq!(
+        (
+            ctx.token_balance(&pool.vault_a),
+            ctx.token_balance(&pool.vault_b)
+        ),
+        vaults
+    );
+
+    // The compounded position still withdraws in full
+    ctx.withdraw(&pool, &lp, -120, 120, LIQUIDITY + expected, 0, 0, true)
+        .unwrap();
+    assert!(ctx.token_balance(&pool.vault_a) <= 1_000);
+    assert!(ctx.token_balance(&pool.vault_b) <= 1_000);
+}
+
+#[test]
+fn test_compound_position_out_of_range_principal() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    let position = ctx.add_liquidity(&pool, &lp, 120, 240, LIQUIDITY);
+
+    // Principal removed above the price is owed in token A only, and all of it
+    // goes back in, short of the rounding lost on the way out
+    ctx.decrease_liquidity(&pool, &lp, 120, 240, LIQUIDITY / 2, 0, 0)
+        .unwrap();
+    ctx.compound_position(&pool, &lp, 120, 240).unwrap();
+
+    let state = ctx.position(&position);
+    assert!(state.liquidity < LIQUIDITY);
+    assert!(
+        state.liquidity >= LIQUIDITY - LIQUIDITY / 10_000,
+        "liquidity = {}",
+        state.liquidity
+    );
+    assert!(state.tokens_owed_a <= 1);
+    assert_eq!(state.tokens_owed_b, 0);
+}
+
+#[test]
+fn test_compound_position_validation() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    ctx.add_liquidity(&pool, &lp, -120, 120, LIQUIDITY);
+
+    // Nothing owed yet
+    assert_error(
+        ctx.compound_position(&pool, &lp, -120, 120),
+        SuniswapError::ZeroLiquidity,
+    );
+
+    churn(&mut ctx, &pool);
+    let ix = ctx.ix_compound_position(&pool, &lp, -120, 120);
+    let intruder = ctx.create_user(&pool, BALANCE);
+    let mut bad = ix.clone();
+    replace_account(&mut bad, lp.key, intruder.key);
+    assert_error(ctx.bank.process(&bad), SuniswapError::InvalidPositionOwner);
+
+    let other = ctx.create_pool(3000, sqrt_price_at(0)).unwrap();
+    let mut bad = ix.clone();
+    replace_account(&mut bad, pool.tick_array(120), other.tick_array(120));
+    ctx.ensure_tick_array(&other, 120);
+    assert_error(ctx.bank.process(&bad), SuniswapError::InvalidTickArray);
+
+    ctx.modify_zero_copy::<Pool>(&pool.pool, |p| p.is_paused = 1);
+    assert_error(ctx.bank.process(&ix), SuniswapError::PoolPaused);
+    ctx.modify_zero_copy::<Pool>(&pool.pool, |p| p.is_paused = 0);
+    assert!(ctx.bank.process(&ix).is_ok());
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// REPOSITION
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn no_swap(liquidity_min: u128) -> RepositionParams {
+    RepositionParams {
+        swap_amount: 0,
+        swap_a_to_b: false,
+        sqrt_price_limit_x64: 0,
+        liquidity_min,
+        deadline_unix_ts: None,
+        max_slot: None,
+    }
+}
+
+#[test]
+fn test_reposition_moves_liquidity_and_fees() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    let old = ctx.add_liquidity(&pool, &lp, -120, 120, LIQUIDITY);
+    churn(&mut ctx, &pool);
+    let balances = (ctx.token_balance(&lp.token_a), ctx.token_balance(&lp.token_b));
+    let vaults = (ctx.token_balance(&pool.vault_a), ctx.token_balance(&pool.vault_b));
+
+    ctx.reposition(&pool, &lp, (-120, 120), (-240, 240), no_swap(0)).unwrap();
+    let event = ctx.bank.events::<PositionRepositionedEvent>().pop().unwrap();
+    let closed = ctx.bank.events::<PositionClosedEvent>().pop().unwrap();
+    assert_eq!(closed.position, old);
+    assert_eq!(event.old_position, old);
+
+    // The old position is gone and the new one holds everything it could fund
+    assert!(!ctx.exists(&old));
+    let new = position_pda(&pool.pool, &lp.key, -240, 240);
+    assert_eq!(event.new_position, new);
+    let state = ctx.position(&new);
+    assert_eq!((state.tick_lower, state.tick_upper), (-240, 240));
+    assert_eq!(state.liquidity, event.liquidity);
+    assert_eq!((state.tokens_owed_a, state.tokens_owed_b), (0, 0));
+    assert_eq!(ctx.pool(&pool).liquidity, event.liquidity);
+
+    // A wider range needs less liquidity for the same tokens plus fees, and
+    // only one side is left over
+    let expected = get_liquidity_for_amounts(
+        ctx.pool(&pool).sqrt_price_x64,
+        sqrt_price_at(-240),
+        sqrt_price_at(240),
+        event.amount_a + event.refund_a,
+        event.amount_b + event.refund_b,
+    )
+    .unwrap();
+    assert_eq!(event.liquidity, expected);
+    assert!(event.liquidity > LIQUIDITY / 2);
+    assert!(event.refund_a == 0 || event.refund_b == 0);
+
+    // Only the refund left the vaults
+    assert_eq!(ctx.token_balance(&lp.token_a), balances.0 + event.refund_a);
+    assert_eq!(ctx.token_balance(&lp.token_b), balances.1 + event.refund_b);
+    assert_eq!(ctx.token_balance(&pool.vault_a), vaults.0 - event.refund_a);
+    assert_eq!(ctx.token_balance(&pool.vault_b), vaults.1 - event.refund_b);
+}
+
+#[test]
+fn test_reposition_swaps_into_one_sided_range() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    let whale = ctx.create_user(&pool, BALANCE);
+    ctx.add_liquidity(&pool, &whale, -1200, 1200, 100 * LIQUIDITY);
+    ctx.add_liquidity(&pool, &lp, -120, 120, LIQUIDITY);
+
+    // Everything goes into a range above the price, which only takes token A,
+    // so all of the withdrawn token B is swapped
+    let (_, amount_b) = get_amounts_for_liquidity(
+        ctx.pool(&pool).sqrt_price_x64,
+        sqrt_price_at(-120),
+        sqrt_price_at(120),
+        LIQUIDITY,
+        false,
+    )
+    .unwrap();
+    let params = RepositionParams {
+        swap_amount: amount_b,
+        ..no_swap(0)
+    };
+    let balance_b = ctx.token_balance(&lp.token_b);
+    ctx.reposition(&pool, &lp, (-120, 120), (120, 240), params).unwrap();
+
+    let swap = ctx.bank.events::<SwapEvent>().pop().unwrap();
+    assert_eq!(swap.amount_in, amount_b);
+    assert!(!swap.a_to_b);
+
+    let event = ctx.bank.events::<PositionRepositionedEvent>().pop().unwrap();
+    assert_eq!((event.amount_b, event.refund_b), (0, 0));
+    assert_eq!(ctx.token_balance(&lp.token_b), balance_b);
+    assert!(ctx.pool(&pool).tick_current < 120);
+
+    let state = ctx.position(&position_pda(&pool.pool, &lp.key, 120, 240));
+    assert_eq!(state.liquidity, event.liquidity);
+    let expected = get_liquidity_for_amounts(
+        ctx.pool(&pool).sqrt_price_x64,
+        sqrt_price_at(120),
+        sqrt_price_at(240),
+        event.amount_a + event.refund_a,
+        0,
+    )
+    .unwrap();
+    assert_eq!(event.liquidity, expected);
+    assert!(event.refund_a <= 1);
+}
+
+#[test]
+fn test_reposition_validation() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    let old = ctx.add_liquidity(&pool, &lp, -120, 120, LIQUIDITY);
+    ctx.ensure_tick_array(&pool, 240);
+
+    // The single slippage bound is on the final liquidity
+    assert_error(
+        ctx.reposition(&pool, &lp, (-120, 120), (-240, 240), no_swap(u128::MAX)),
+        SuniswapError::SlippageExceeded,
+    );
+    assert_eq!(ctx.position(&old).liquidity, LIQUIDITY);
+
+    // The swap can only spend what was withdrawn, and needs its tick arrays
+    let swap = |swap_amount| RepositionParams {
+        swap_amount,
+        swap_a_to_b: true,
+        ..no_swap(0)
+    };
+    assert_error(
+        ctx.reposition(&pool, &lp, (-120, 120), (-240, 240), swap(BALANCE)),
+        SuniswapError::InputExceedsMaximum,
+    );
+    let ix = ctx.ix_reposition(&pool, &lp, (-120, 120), (-240, 240), swap(1_000), None);
+    assert_error(ctx.bank.process(&ix), SuniswapError::TickArrayNotFound);
+
+    assert_error(
+        ctx.reposition(&pool, &lp, (-120, 120), (-240, 250), no_swap(0)),
+        SuniswapError::InvalidTickUpper,
+    );
+
+    // Someone else cannot move the position; the new one is always the owner's
+    let intruder = ctx.create_user(&pool, BALANCE);
+    let mut ix = ctx.ix_reposition(&pool, &intruder, (-120, 120), (-240, 240), no_swap(0), None);
+    replace_account(&mut ix, position_pda(&pool.pool, &intruder.key, -120, 120), old);
+    assert!(ctx.bank.process(&ix).is_err());
+    replace_account(
+        &mut ix,
+        position_pda(&pool.pool, &intruder.key, -240, 240),
+        position_pda(&pool.pool, &lp.key, -240, 240),
+    );
+    assert_error(ctx.bank.process(&ix), SuniswapError::InvalidPositionOwner);
+
+    ctx.modify_zero_copy::<Pool>(&pool.pool, |p| p.is_paused = 1);
+    assert_error(
+        ctx.reposition(&pool, &lp, (-120, 120), (-240, 240), no_swap(0)),
+        SuniswapError::PoolPaused,
+    );
+    ctx.modify_zero_copy::<Pool>(&pool.pool, |p| p.is_paused = 0);
+    ctx.reposition(&pool, &lp, (-120, 120), (-240, 240), no_swap(0)).unwrap();
+    assert!(!ctx.exists(&old));
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// SPLIT AND MERGE
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_split_position() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    let partner = ctx.create_user(&pool, 0);
+    let position = ctx.add_liquidity(&pool, &lp, -120, 120, LIQUIDITY);
+    churn(&mut ctx, &pool);
+    let pool_liquidity = ctx.pool(&pool).liquidity;
+    let lower_tick = *ctx.tick_array(&pool.tick_array(-120)).get_tick(-120, 60).unwrap();
+
+    ctx.split_position(&pool, &lp, -120, 120, &partner.key, LIQUIDITY / 4).unwrap();
+    let event = ctx.bank.events::<PositionSplitEvent>().pop().unwrap();
+    let opened = ctx.bank.events::<PositionOpenedEvent>().pop().unwrap();
+    let split = position_pda(&pool.pool, &partner.key, -120, 120);
+    assert_eq!((event.new_position, opened.position), (split, split));
+    assert_eq!(opened.owner, partner.key);
+
+    // Liquidity and owed tokens divide 3:1, rounding in the original's favour
+    let kept = ctx.position(&position);
+    let moved = ctx.position(&split);
+    assert_eq!(kept.liquidity, LIQUIDITY - LIQUIDITY / 4);
+    assert_eq!(moved.liquidity, LIQUIDITY / 4);
+    assert_eq!(moved.owner_pubkey(), partner.key);
+    assert_eq!((moved.tick_lower, moved.tick_upper), (-120, 120));
+    let total_a = kept.tokens_owed_a + moved.tokens_owed_a;
+    let total_b = kept.tokens_owed_b + moved.tokens_owed_b;
+    assert!(total_a > 0 && total_b > 0);
+    assert_eq!((moved.tokens_owed_a, moved.tokens_owed_b), (total_a / 4, total_b / 4));
+    assert_eq!((event.tokens_owed_a, event.tokens_owed_b), (total_a / 4, total_b / 4));
+    assert_eq!(moved.fee_growth_inside_a_last_x128, kept.fee_growth_inside_a_last_x128);
+    assert_eq!(moved.fee_growth_inside_b_last_x128, kept.fee_growth_inside_b_last_x128);
+
+    // The range itself is untouched
+    assert_eq!(ctx.pool(&pool).liquidity, pool_liquidity);
+    let tick = *ctx.tick_array(&pool.tick_array(-120)).get_tick(-120, 60).unwrap();
+    assert_eq!(tick.liquidity_gross, lower_tick.liquidity_gross);
+    assert_eq!(tick.liquidity_net, lower_tick.liquidity_net);
+
+    // New fees accrue to each side by its liquidity
+    churn(&mut ctx, &pool);
+    ctx.collect_fees(&pool, &lp, -120, 120, u64::MAX, u64::MAX).unwrap();
+    let kept_fees = ctx.bank.events::<FeesCollectedEvent>().pop().unwrap();
+    ctx.collect_fees(&pool, &partner, -120, 120, u64::MAX, u64::MAX).unwrap();
+    let moved_fees = ctx.bank.events::<FeesCollectedEvent>().pop().unwrap();
+    let kept_new = kept_fees.amount_a - kept.tokens_owed_a;
+    let moved_new = moved_fees.amount_a - moved.tokens_owed_a;
+    assert!(moved_new > 0);
+    assert!(kept_new.abs_diff(3 * moved_new) <= 3);
+
+    // The partner can withdraw their part on their own
+    ctx.withdraw(&pool, &partner, -120, 120, LIQUIDITY / 4, 0, 0, true).unwrap();
+    assert!(!ctx.exists(&split));
+    assert!(ctx.token_balance(&partner.token_a) > moved_fees.amount_a);
+}
+
+#[test]
+fn test_merge_positions() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    let partner = ctx.create_user(&pool, BALANCE);
+    let position = ctx.add_liquidity(&pool, &lp, -120, 120, LIQUIDITY);
+    let merged = ctx.add_liquidity(&pool, &partner, -120, 120, LIQUIDITY / 2);
+    churn(&mut ctx, &pool);
+    let pool_liquidity = ctx.pool(&pool).liquidity;
+    let rent = ctx.bank.account(&merged).unwrap().lamports;
+    let partner_lamports = ctx.bank.account(&partner.key).unwrap().lamports;
+
+    ctx.merge_positions(&pool, &lp, (-120, 120), &partner, (-120, 120)).unwrap();
+    let event = ctx.bank.events::<PositionsMergedEvent>().pop().unwrap();
+    assert_eq!((event.position, event.merged_position), (position, merged));
+    assert_eq!(event.liquidity, LIQUIDITY / 2);
+    assert!(event.tokens_owed_a > 0 && event.tokens_owed_b > 0);
+
+    // The merged position is closed with its rent back to its owner
+    assert!(!ctx.exists(&merged));
+    assert_eq!(ctx.bank.account(&partner.key).unwrap().lamports, partner_lamports + rent);
+
+    let state = ctx.position(&position);
+    assert_eq!(state.liquidity, LIQUIDITY + LIQUIDITY / 2);
+    // Owed tokens are combined; the surviving position had twice the liquidity
+    assert!(state.tokens_owed_a.abs_diff(3 * event.tokens_owed_a) <= 3);
+    assert!(state.tokens_owed_b.abs_diff(3 * event.tokens_owed_b) <= 3);
+    assert_eq!(ctx.pool(&pool).liquidity, pool_liquidity);
+
+    // Everything withdraws through the surviving position
+    ctx.withdraw(&pool, &lp, -120, 120, LIQUIDITY + LIQUIDITY / 2, 0, 0, true).unwrap();
+    assert!(ctx.token_balance(&pool.vault_a) <= 1_000);
+    assert!(ctx.token_balance(&pool.vault_b) <= 1_000);
+}
+
+#[test]
+fn test_split_and_merge_validation() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    let partner = ctx.create_user(&pool, BALANCE);
+    ctx.add_liquidity(&pool, &lp, -120, 120, LIQUIDITY);
+    ctx.add_liquidity(&pool, &partner, -60, 120, LIQUIDITY);
+
+    assert_error(
+        ctx.split_position(&pool, &lp, -120, 120, &partner.key, 0),
+        SuniswapError::ZeroLiquidity,
+    );
+    assert_error(
+        ctx.split_position(&pool, &lp, -120, 120, &partner.key, LIQUIDITY + 1),
+        SuniswapError::InsufficientLiquidity,
+    );
+
+    // Only the owner can split off part of a position
+    let intruder = ctx.create_user(&pool, 0);
+    let mut ix = ctx.ix_split_position(&pool, &lp, -120, 120, &intruder.key, 1);
+    replace_account(&mut ix, lp.key, intruder.key);
+    assert_error(ctx.bank.process(&ix), SuniswapError::InvalidPositionOwner);
+
+    // Merging needs two distinct positions over the same range
+    assert_error(
+        ctx.merge_positions(&pool, &lp, (-120, 120), &partner, (-60, 120)),
+        SuniswapError::InvalidTickRange,
+    );
+    assert_error(
+        ctx.merge_positions(&pool, &lp, (-120, 120), &lp, (-120, 120)),
+        SuniswapError::InvalidPosition,
+    );
+    let mut ix = ctx.ix_merge_positions(&pool, &lp, (-120, 120), &partner, (-60, 120));
+    replace_account(&mut ix, partner.key, intruder.key);
+    assert_error(ctx.bank.process(&ix), SuniswapError::InvalidPositionOwner);
+
+    ctx.modify_zero_copy::<Pool>(&pool.pool, |p| p.is_paused = 1);
+    assert_error(
+        ctx.split_position(&pool, &lp, -120, 120, &intruder.key, 1),
+        SuniswapError::PoolPaused,
+    );
+    ctx.modify_zero_copy::<Pool>(&pool.pool, |p| p.is_paused = 0);
+    ctx.split_position(&pool, &lp, -120, 120, &intruder.key, 1).unwrap();
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// DELEGATES
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_delegate_pays_out_to_owner() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    let manager = ctx.create_user(&pool, 0);
+    let position = ctx.add_liquidity(&pool, &lp, -120, 120, LIQUIDITY);
+    churn(&mut ctx, &pool);
+
+    let permissions = position_permissions::COLLECT | position_permissions::DECREASE;
+    ctx.set_position_delegate(&pool, &lp, -120, 120, &manager.key, permissions).unwrap();
+    let event = ctx.bank.events::<PositionDelegateUpdatedEvent>().pop().unwrap();
+    assert_eq!((event.position, event.owner, event.delegate), (position, lp.key, manager.key));
+    assert_eq!(event.permissions, permissions);
+    let state = ctx.position(&position);
+    assert_eq!(state.delegate_pubkey(), manager.key);
+    assert_eq!(state.delegate_permissions, permissions);
+
+    // The delegate can't redirect the fees to itself
+    let mut ix = ctx.ix_collect_fees(&pool, &lp, -120, 120, u64::MAX, u64::MAX);
+    replace_account(&mut ix, lp.key, manager.key);
+    let mut diverted = ix.clone();
+    replace_account(&mut diverted, lp.token_a, manager.token_a);
+    replace_account(&mut diverted, lp.token_b, manager.token_b);
+    assert_error(ctx.bank.process(&diverted), SuniswapError::InvalidTokenAccountOwner);
+
+    let balance_a = ctx.token_balance(&lp.token_a);
+    ctx.bank.process(&ix).unwrap();
+    let collected = ctx.bank.events::<FeesCollectedEvent>().pop().unwrap();
+    assert_eq!(collected.owner, lp.key);
+    assert!(collected.amount_a > 0);
+    assert_eq!(ctx.token_balance(&lp.token_a), balance_a + collected.amount_a);
+    assert_eq!(ctx.token_balance(&manager.token_a), 0);
+
+    // Withdrawals pay the owner, and only the owner can close
+    let mut ix = ctx.ix_withdraw(&pool, &lp, -120, 120, LIQUIDITY, 0, 0, true);
+    replace_account(&mut ix, lp.key, manager.key);
+    assert_error(ctx.bank.process(&ix), SuniswapError::InvalidPositionOwner);
+    let mut ix = ctx.ix_withdraw(&pool, &lp, -120, 120, LIQUIDITY, 0, 0, false);
+    replace_account(&mut ix, lp.key, manager.key);
+    let balance_b = ctx.token_balance(&lp.token_b);
+    ctx.bank.process(&ix).unwrap();
+    let withdrawn = ctx.bank.events::<FeesCollectedEvent>().pop().unwrap();
+    assert!(withdrawn.amount_b > 0);
+    assert_eq!(ctx.token_balance(&lp.token_b), balance_b + withdrawn.amount_b);
+    assert_eq!(ctx.position(&position).liquidity, 0);
+    ctx.withdraw(&pool, &lp, -120, 120, 0, 0, 0, true).unwrap();
+    assert!(!ctx.exists(&position));
+}
+
+#[test]
+fn test_delegate_permissions_enforced() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    let manager = ctx.create_user(&pool, BALANCE);
+    let position = ctx.add_liquidity(&pool, &lp, -120, 120, LIQUIDITY);
+    ctx.set_position_delegate(&pool, &lp, -120, 120, &manager.key, position_permissions::COLLECT).unwrap();
+
+    let as_manager = |mut ix: Instruction| {
+        replace_account(&mut ix, lp.key, manager.key);
+        replace_account(&mut ix, lp.token_a, manager.token_a);
+        replace_account(&mut ix, lp.token_b, manager.token_b);
+        ix
+    };
+    let decrease = as_manager(ctx.ix_decrease_liquidity(&pool, &lp, -120, 120, LIQUIDITY / 2, 0, 0));
+    let increase = as_manager(ctx.ix_increase_liquidity(&pool, &lp, -120, 120, LIQUIDITY, u64::MAX, u64::MAX));
+    let compound = as_manager(ctx.ix_compound_position(&pool, &lp, -120, 120));
+    for ix in [&decrease, &increase, &compound] {
+        assert_error(ctx.bank.process(ix), SuniswapError::InvalidPositionOwner);
+    }
+
+    // Deposits come from whoever signs, so a delegate may fund the position
+    ctx.set_position_delegate(&pool, &lp, -120, 120, &manager.key, position_permissions::INCREASE).unwrap();
+    ctx.bank.process(&increase).unwrap();
+    assert_eq!(ctx.position(&position).liquidity, 2 * LIQUIDITY);
+    assert!(ctx.token_balance(&manager.token_a) < BALANCE);
+    assert_error(ctx.bank.process(&decrease), SuniswapError::InvalidPositionOwner);
+
+    // Owner-only actions stay owner-only whatever the delegate holds
+    ctx.set_position_delegate(&pool, &lp, -120, 120, &manager.key, position_permissions::ALL).unwrap();
+    let split = as_manager(ctx.ix_split_position(&pool, &lp, -120, 120, &manager.key, LIQUIDITY));
+    assert_error(ctx.bank.process(&split), SuniswapError::InvalidPositionOwner);
+    let delegate = as_manager(ctx.ix_set_position_delegate(&pool, &lp, -120, 120, &manager.key, 0));
+    assert_error(ctx.bank.process(&delegate), SuniswapError::InvalidPositionOwner);
+    ctx.bank.process(&decrease).unwrap();
+
+    assert_error(
+        ctx.set_position_delegate(&pool, &lp, -120, 120, &manager.key, 0b1_0000),
+        SuniswapError::InvalidDelegatePermissions,
+    );
+
+    // Clearing the permissions revokes the delegate
+    ctx.set_position_delegate(&pool, &lp, -120, 120, &manager.key, 0).unwrap();
+    assert_error(ctx.bank.process(&decrease), SuniswapError::InvalidPositionOwner);
+    assert!(ctx.decrease_liquidity(&pool, &lp, -120, 120, LIQUIDITY / 2, 0, 0).is_ok());
+}
+
+#[test]
+fn test_delegate_repositions() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    let manager = ctx.create_user(&pool, 0);
+    let old = ctx.add_liquidity(&pool, &lp, -120, 120, LIQUIDITY);
+    churn(&mut ctx, &pool);
+    let permissions = position_permissions::REPOSITION | position_permissions::COLLECT;
+    ctx.set_position_delegate(&pool, &lp, -120, 120, &manager.key, permissions).unwrap();
+
+    let mut ix = ctx.ix_reposition(&pool, &lp, (-120, 120), (-240, 240), no_swap(0), None);
+    replace_account(&mut ix, lp.key, manager.key);
+    let mut diverted = ix.clone();
+    replace_account(&mut diverted, lp.token_a, manager.token_a);
+    assert_error(ctx.bank.process(&diverted), SuniswapError::InvalidTokenAccountOwner);
+
+    let balances = (ctx.token_balance(&lp.token_a), ctx.token_balance(&lp.token_b));
+    let lamports = ctx.bank.account(&manager.key).unwrap().lamports;
+    ctx.bank.process(&ix).unwrap();
+    let event = ctx.bank.events::<PositionRepositionedEvent>().pop().unwrap();
+
+    // The new position stays the owner's and keeps the delegate
+    let new = position_pda(&pool.pool, &lp.key, -240, 240);
+    assert_eq!((event.new_position, event.owner), (new, lp.key));
+    assert!(!ctx.exists(&old));
+    let state = ctx.position(&new);
+    assert_eq!(state.owner_pubkey(), lp.key);
+    assert_eq!(state.delegate_pubkey(), manager.key);
+    assert_eq!(state.delegate_permissions, permissions);
+    assert_eq!(state.liquidity, event.liquidity);
+
+    // Refunds go to the owner; the payer's new rent is covered by the old
+    assert_eq!(ctx.token_balance(&lp.token_a), balances.0 + event.refund_a);
+    assert_eq!(ctx.token_balance(&lp.token_b), balances.1 + event.refund_b);
+    assert_eq!(ctx.bank.account(&manager.key).unwrap().lamports, lamports);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// CLOSE POSITION
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_full_position_lifecycle() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    let position = ctx.add_liquidity(&pool, &lp, -120, 120, LIQUIDITY);
+    churn(&mut ctx, &pool);
+
+    assert_error(ctx.close_position(&pool, &lp, -120, 120), SuniswapError::PositionHasLiquidity);
+
+    ctx.decrease_liquidity(&pool, &lp, -120, 120, LIQUIDITY, 0, 0).unwrap();
+    assert_error(ctx.close_position(&pool, &lp, -120, 120), SuniswapError::PositionHasOwedTokens);
+
+    ctx.collect_fees(&pool, &lp, -120, 120, u64::MAX, 0).unwrap();
+    assert_error(ctx.close_position(&pool, &lp, -120, 120), SuniswapError::PositionHasOwedTokens);
+
+    ctx.collect_fees(&pool, &lp, -120, 120, u64::MAX, u64::MAX).unwrap();
+    let rent = ctx.bank.account(&position).unwrap().lamports;
+    let lamports_before = ctx.bank.account(&lp.key).unwrap().lamports;
+    ctx.close_position(&pool, &lp, -120, 120).unwrap();
+
+    assert!(!ctx.exists(&position));
+    assert_eq!(ctx.bank.account(&lp.key).unwrap().lamports, lamports_before + rent);
+    let event = ctx.bank.events::<PositionClosedEvent>().pop().unwrap();
+    assert_eq!(event.position, position);
+
+    // Only rounding dust stays behind in the vaults
+    assert!(ctx.token_balance(&pool.vault_a) <= 1_000);
+    assert!(ctx.token_balance(&pool.vault_b) <= 1_000);
+
+    // The same range can be reopened after closing
+    ctx.open_position(&pool, &lp, -120, 120).unwrap();
+}
+
+#[test]
+fn test_close_position_validates_accounts() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    ctx.open_position(&pool, &lp, -120, 120).unwrap();
+    let ix = ctx.ix_close_position(&pool, &lp, -120, 120);
+
+    let other = ctx.create_pool(3000, sqrt_price_at(0)).unwrap();
+    let mut bad = ix.clone();
+    replace_account(&mut bad, pool.pool, other.pool);
+    assert_error(ctx.bank.process(&bad), SuniswapError::InvalidPosition);
+
+    let intruder = ctx.create_user(&pool, BALANCE);
+    let mut bad = ix.clone();
+    replace_account(&mut bad, lp.key, intruder.key);
+    assert_error(ctx.bank.process(&bad), SuniswapError::InvalidPositionOwner);
+
+    assert!(ctx.bank.process(&ix).is_ok());
+}
@@ -0,0 +1,190 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::*;
+use suniswap::constants::position_permissions;
+use suniswap::errors::SuniswapError;
+use suniswap::events::{FeesCollectedEvent, LiquidityIncreasedEvent, SwapEvent};
+
+const LIQUIDITY: u128 = 1_000_000_000_000;
+const BALANCE: u64 = 1_000_000_000_000;
+
+/// Pool pairing native SOL with a token at tick 0 (fee 0.3%, spacing 60), with liquidity
+fn native_pool() -> (TestContext, PoolFixture, User) {
+    let mut ctx = TestContext::with_config(10);
+    ctx.initialize_fee_tier(3000, 60).unwrap();
+    let pool = ctx.create_native_pool(3000, sqrt_price_at(0)).unwrap();
+    let lp = ctx.create_user(&pool, BALANCE);
+    ctx.add_liquidity(&pool, &lp, -1200, 1200, LIQUIDITY);
+    (ctx, pool, lp)
+}
+
+/// Whether token A is the native side
+fn native_is_a(pool: &PoolFixture) -> bool {
+    pool.mint_a == spl_token::native_mint::ID
+}
+
+/// The user's token account for the native side and for the other side
+fn accounts(pool: &PoolFixture, user: &User) -> (Pubkey, Pubkey) {
+    if native_is_a(pool) {
+        (user.token_a, user.token_b)
+    } else {
+        (user.token_b, user.token_a)
+    }
+}
+
+fn lamports(ctx: &TestContext, key: &Pubkey) -> u64 {
+    ctx.bank.account(key).unwrap().lamports
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// SWAP
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_swap_native_sol_in_and_out() {
+    let (mut ctx, pool, _) = native_pool();
+    let trader = ctx.create_user(&pool, BALANCE);
+    let (wsol, token) = accounts(&pool, &trader);
+
+    // Pay SOL for the other token
+    let sell_native = exact_in(1_000_000, native_is_a(&pool));
+    let tick_arrays = ctx.swap_tick_arrays(&pool, sell_native.a_to_b);
+    let mut ix = ctx.ix_swap(&pool, &trader, sell_native, tick_arrays);
+    use_native_sol(&mut ix, wsol, &trader.key);
+    let before = lamports(&ctx, &trader.key);
+    ctx.bank.process(&ix).unwrap();
+    let swap = ctx.bank.events::<SwapEvent>().pop().unwrap();
+    assert_eq!(swap.amount_in, 1_000_000);
+    assert_eq!(lamports(&ctx, &trader.key), before - swap.amount_in);
+    assert_eq!(ctx.token_balance(&wsol), BALANCE);
+    assert_eq!(ctx.token_balance(&token), BALANCE + swap.amount_out);
+    assert!(!ctx.exists(&native_sol_pda(&trader.key)));
+
+    // And sell the other token for SOL
+    let buy_native = exact_in(1_000_000, !native_is_a(&pool));
+    let tick_arrays = ctx.swap_tick_arrays(&pool, buy_native.a_to_b);
+    let mut ix = ctx.ix_swap(&pool, &trader, buy_native, tick_arrays);
+    use_native_sol(&mut ix, wsol, &trader.key);
+    let before = lamports(&ctx, &trader.key);
+    ctx.bank.process(&ix).unwrap();
+    let swap = ctx.bank.events::<SwapEvent>().pop().unwrap();
+    assert!(swap.amount_out > 0);
+    assert_eq!(lamports(&ctx, &trader.key), before + swap.amount_out);
+    assert_eq!(ctx.token_balance(&wsol), BALANCE);
+    assert!(!ctx.exists(&native_sol_pda(&trader.key)));
+}
+
+#[test]
+fn test_swap_native_sol_with_prefunded_account() {
+    let (mut ctx, pool, _) = native_pool();
+    let trader = ctx.create_user(&pool, BALANCE);
+    let (wsol, _) = accounts(&pool, &trader);
+    let native_account = native_sol_pda(&trader.key);
+    const GIFT: u64 = 5_000;
+
+    // Lamports sent to the predictable address don't block the swap; they
+    // are wrapped with the input and come back to the trader on close
+    ctx.bank.airdrop(native_account, GIFT);
+    let sell_native = exact_in(1_000_000, native_is_a(&pool));
+    let tick_arrays = ctx.swap_tick_arrays(&pool, sell_native.a_to_b);
+    let mut ix = ctx.ix_swap(&pool, &trader, sell_native, tick_arrays);
+    use_native_sol(&mut ix, wsol, &trader.key);
+    let before = lamports(&ctx, &trader.key);
+    ctx.bank.process(&ix).unwrap();
+    let swap = ctx.bank.events::<SwapEvent>().pop().unwrap();
+    assert_eq!(swap.amount_in, 1_000_000);
+    assert_eq!(lamports(&ctx, &trader.key), before - swap.amount_in + GIFT);
+    assert!(!ctx.exists(&native_account));
+
+    // Likewise when paying out, even if the gift already covers the rent
+    ctx.bank.airdrop(native_account, 10 * GIFT + SOL);
+    let buy_native = exact_in(1_000_000, !native_is_a(&pool));
+    let tick_arrays = ctx.swap_tick_arrays(&pool, buy_native.a_to_b);
+    let mut ix = ctx.ix_swap(&pool, &trader, buy_native, tick_arrays);
+    use_native_sol(&mut ix, wsol, &trader.key);
+    let before = lamports(&ctx, &trader.key);
+    ctx.bank.process(&ix).unwrap();
+    let swap = ctx.bank.events::<SwapEvent>().pop().unwrap();
+    assert_eq!(lamports(&ctx, &trader.key), before + swap.amount_out + 10 * GIFT + SOL);
+    assert!(!ctx.exists(&native_account));
+}
+
+#[test]
+fn test_swap_native_sol_validation() {
+    let (mut ctx, pool, _) = native_pool();
+    let trader = ctx.create_user(&pool, BALANCE);
+    let (wsol, token) = accounts(&pool, &trader);
+    let params = exact_in(1_000_000, native_is_a(&pool));
+    let tick_arrays = ctx.swap_tick_arrays(&pool, params.a_to_b);
+    let ix = ctx.ix_swap(&pool, &trader, params, tick_arrays);
+
+    // Only the native side can be omitted
+    let mut not_native = ix.clone();
+    use_native_sol(&mut not_native, token, &trader.key);
+    assert_error(ctx.bank.process(&not_native), SuniswapError::NotNativeMint);
+
+    // The temporary account must be passed, and be the signer's
+    let mut missing = ix.clone();
+    use_native_sol(&mut missing, wsol, &trader.key);
+    let len = missing.accounts.len();
+    missing.accounts[len - 2].pubkey = suniswap::ID;
+    assert_error(ctx.bank.process(&missing), SuniswapError::InvalidNativeSolAccount);
+
+    let mut wrong = ix.clone();
+    use_native_sol(&mut wrong, wsol, &Pubkey::new_unique());
+    assert_error(ctx.bank.process(&wrong), SuniswapError::InvalidNativeSolAccount);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// LIQUIDITY
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_liquidity_native_sol_in_fees_out() {
+    let (mut ctx, pool, _) = native_pool();
+    let lp = ctx.create_user(&pool, BALANCE);
+    let (wsol, _) = accounts(&pool, &lp);
+    ctx.ensure_tick_array(&pool, -600);
+    ctx.ensure_tick_array(&pool, 600);
+    ctx.open_position(&pool, &lp, -600, 600).unwrap();
+
+    let mut ix = ctx.ix_increase_liquidity(&pool, &lp, -600, 600, LIQUIDITY / 10, u64::MAX, u64::MAX);
+    use_native_sol(&mut ix, wsol, &lp.key);
+    let before = lamports(&ctx, &lp.key);
+    ctx.bank.process(&ix).unwrap();
+    let event = ctx.bank.events::<LiquidityIncreasedEvent>().pop().unwrap();
+    let deposited = if native_is_a(&pool) { event.amount_a } else { event.amount_b };
+    assert!(deposited > 0);
+    assert_eq!(lamports(&ctx, &lp.key), before - deposited);
+    assert_eq!(ctx.token_balance(&wsol), BALANCE);
+
+    // Fees from SOL-in swaps come back out as lamports
+    let trader = ctx.create_user(&pool, BALANCE);
+    ctx.swap(&pool, &trader, exact_in(10_000_000, native_is_a(&pool))).unwrap();
+    let mut ix = ctx.ix_collect_fees(&pool, &lp, -600, 600, u64::MAX, u64::MAX);
+    use_native_sol(&mut ix, wsol, &lp.key);
+    let before = lamports(&ctx, &lp.key);
+    ctx.bank.process(&ix).unwrap();
+    let collected = ctx.bank.events::<FeesCollectedEvent>().pop().unwrap();
+    let fees = if native_is_a(&pool) { collected.amount_a } else { collected.amount_b };
+    assert!(fees > 0);
+    assert_eq!(lamports(&ctx, &lp.key), before + fees);
+    assert!(!ctx.exists(&native_sol_pda(&lp.key)));
+}
+
+#[test]
+fn test_delegate_cannot_collect_native_sol() {
+    let (mut ctx, pool, lp) = native_pool();
+    let manager = ctx.create_user(&pool, 0);
+    let trader = ctx.create_user(&pool, BALANCE);
+    ctx.swap(&pool, &trader, exact_in(10_000_000, native_is_a(&pool))).unwrap();
+    ctx.set_position_delegate(&pool, &lp, -1200, 1200, &manager.key, position_permissions::COLLECT).unwrap();
+
+    // Lamports would go to the signing delegate instead of the owner
+    let (wsol, _) = accounts(&pool, &lp);
+    let mut ix = ctx.ix_collect_fees(&pool, &lp, -1200, 1200, u64::MAX, u64::MAX);
+    use_native_sol(&mut ix, wsol, &manager.key);
+    replace_account(&mut ix, lp.key, manager.key);
+    assert_error(ctx.bank.process(&ix), SuniswapError::InvalidTokenAccountOwner);
+}
@@ -0,0 +1,341 @@
+mod common;
+
+use common::*;
+use suniswap::errors::SuniswapError;
+use suniswap::events::PoolTwapGuardUpdatedEvent;
+use suniswap::instructions::{CumulativesInside, SwapParams};
+use suniswap::state::Oracle;
+
+const LIQUIDITY: u128 = 1_000_000_000;
+const BALANCE: u64 = 1_000_000_000_000;
+
+/// Pool at tick 0 (fee 0.3%, spacing 60, 480 ticks per array) with a funded LP
+fn pool_with_lp() -> (TestContext, PoolFixture, User) {
+    let (mut ctx, pool) = TestContext::with_pool(3000, 60, 0);
+    let lp = ctx.create_user(&pool, BALANCE);
+    (ctx, pool, lp)
+}
+
+/// Advance the clock by `seconds`
+fn wait(ctx: &mut TestContext, seconds: i64) {
+    let clock = ctx.bank.clock();
+    ctx.bank.warp_to(clock.slot + 1, clock.unix_timestamp + seconds);
+}
+
+/// Swap moving the price to `tick`, with amounts far larger than needed
+fn to_tick(ctx: &TestContext, pool: &PoolFixture, tick: i32) -> SwapParams {
+    let a_to_b = tick < ctx.pool(pool).tick_current;
+    SwapParams {
+        sqrt_price_limit_x64: sqrt_price_at(tick),
+        ..exact_in(BALANCE / 10, a_to_b)
+    }
+}
+
+/// Swap until the price reaches `tick`
+fn swap_to_tick(ctx: &mut TestContext, pool: &PoolFixture, trader: &User, tick: i32) {
+    let params = to_tick(ctx, pool, tick);
+    ctx.swap(pool, trader, params).unwrap();
+    assert_eq!(ctx.pool(pool).tick_current, tick);
+}
+
+/// Change in the snapshot between `before` and `after`
+fn delta(before: &CumulativesInside, after: &CumulativesInside) -> CumulativesInside {
+    CumulativesInside {
+        tick_cumulative_inside: after.tick_cumulative_inside.wrapping_sub(before.tick_cumulative_inside),
+        seconds_per_liquidity_inside_x64: after
+            .seconds_per_liquidity_inside_x64
+            .wrapping_sub(before.seconds_per_liquidity_inside_x64),
+        seconds_inside: after.seconds_inside.wrapping_sub(before.seconds_inside),
+    }
+}
+
+fn seconds_per_liquidity(seconds: u128, liquidity: u128) -> u128 {
+    (seconds << 64) / liquidity
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// SNAPSHOT CUMULATIVES INSIDE
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_snapshot_accumulates_while_in_range() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    ctx.add_liquidity(&pool, &lp, -120, 120, LIQUIDITY);
+    ctx.add_liquidity(&pool, &lp, 600, 1200, LIQUIDITY);
+
+    // The lower tick was initialized at the current time, so nothing is inside yet
+    let start = ctx.snapshot_cumulatives_inside(&pool, -120, 120).unwrap();
+    assert_eq!(
+        start,
+        CumulativesInside { tick_cumulative_inside: 0, seconds_per_liquidity_inside_x64: 0, seconds_inside: 0 }
+    );
+    let idle_start = ctx.snapshot_cumulatives_inside(&pool, 600, 1200).unwrap();
+
+    wait(&mut ctx, 100);
+    let end = ctx.snapshot_cumulatives_inside(&pool, -120, 120).unwrap();
+    assert_eq!(
+        delta(&start, &end),
+        CumulativesInside {
+            tick_cumulative_inside: 0,
+            seconds_per_liquidity_inside_x64: seconds_per_liquidity(100, LIQUIDITY),
+            seconds_inside: 100,
+        }
+    );
+
+    // A range above the price accumulates nothing
+    let idle_end = ctx.snapshot_cumulatives_inside(&pool, 600, 1200).unwrap();
+    assert_eq!(delta(&idle_start, &idle_end).seconds_inside, 0);
+    assert_eq!(delta(&idle_start, &idle_end).seconds_per_liquidity_inside_x64, 0);
+}
+
+#[test]
+fn test_snapshot_follows_price_across_ranges() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    let trader = ctx.create_user(&pool, BALANCE);
+    let upper_liquidity = 3 * LIQUIDITY;
+    ctx.add_liquidity(&pool, &lp, -120, 120, LIQUIDITY);
+    ctx.add_liquidity(&pool, &lp, 120, 240, upper_liquidity);
+    ctx.swap_tick_arrays(&pool, true);
+    ctx.swap_tick_arrays(&pool, false);
+
+    let lower_start = ctx.snapshot_cumulatives_inside(&pool, -120, 120).unwrap();
+    let upper_start = ctx.snapshot_cumulatives_inside(&pool, 120, 240).unwrap();
+
+    // 100s at tick 0, then 50s at tick 180 (crossing 120 upwards), then 30s at
+    // tick -60 (crossing it back down)
+    wait(&mut ctx, 100);
+    swap_to_tick(&mut ctx, &pool, &trader, 180);
+    wait(&mut ctx, 50);
+    swap_to_tick(&mut ctx, &pool, &trader, -60);
+    wait(&mut ctx, 30);
+
+    let lower_end = ctx.snapshot_cumulatives_inside(&pool, -120, 120).unwrap();
+    assert_eq!(
+        delta(&lower_start, &lower_end),
+        CumulativesInside {
+            tick_cumulative_inside: -60 * 30,
+            seconds_per_liquidity_inside_x64: seconds_per_liquidity(100, LIQUIDITY)
+                + seconds_per_liquidity(30, LIQUIDITY),
+            seconds_inside: 130,
+        }
+    );
+
+    let upper_end = ctx.snapshot_cumulatives_inside(&pool, 120, 240).unwrap();
+    assert_eq!(
+        delta(&upper_start, &upper_end),
+        CumulativesInside {
+            tick_cumulative_inside: 180 * 50,
+            seconds_per_liquidity_inside_x64: seconds_per_liquidity(50, upper_liquidity),
+            seconds_inside: 50,
+        }
+    );
+
+    // The pool accumulators cover the whole period
+    let state = ctx.pool(&pool);
+    assert_eq!(state.tick_cumulative, 180 * 50);
+    assert_eq!(
+        state.cumulatives_timestamp as i64,
+        ctx.bank.clock().unix_timestamp - 30
+    );
+}
+
+#[test]
+fn test_snapshot_of_position_added_below_price() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    ctx.add_liquidity(&pool, &lp, -120, 120, LIQUIDITY);
+    wait(&mut ctx, 100);
+
+    // Both bounds sit at or below the current tick, so both start with the time so far outside
+    ctx.add_liquidity(&pool, &lp, -240, 0, LIQUIDITY);
+    let start = ctx.snapshot_cumulatives_inside(&pool, -240, 0).unwrap();
+    wait(&mut ctx, 40);
+    let end = ctx.snapshot_cumulatives_inside(&pool, -240, 0).unwrap();
+    assert_eq!(delta(&start, &end).seconds_inside, 0);
+
+    // The range around the price saw both periods, with its liquidity alone in range
+    let around = ctx.snapshot_cumulatives_inside(&pool, -120, 120).unwrap();
+    assert_eq!(around.seconds_inside, 140);
+    assert_eq!(around.seconds_per_liquidity_inside_x64, seconds_per_liquidity(140, LIQUIDITY));
+}
+
+#[test]
+fn test_snapshot_validation() {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    ctx.add_liquidity(&pool, &lp, -120, 120, LIQUIDITY);
+    ctx.ensure_tick_array(&pool, 180);
+
+    assert_error(
+        ctx.snapshot_cumulatives_inside(&pool, 120, -120).map(|_| ()),
+        SuniswapError::InvalidTickRange,
+    );
+    assert_error(
+        ctx.snapshot_cumulatives_inside(&pool, -100, 120).map(|_| ()),
+        SuniswapError::InvalidTickLower,
+    );
+    assert_error(
+        ctx.snapshot_cumulatives_inside(&pool, -120, 100).map(|_| ()),
+        SuniswapError::InvalidTickUpper,
+    );
+    assert_error(
+        ctx.snapshot_cumulatives_inside(&pool, -60, 120).map(|_| ()),
+        SuniswapError::TickNotInitialized,
+    );
+    assert_error(
+        ctx.snapshot_cumulatives_inside(&pool, -120, 180).map(|_| ()),
+        SuniswapError::TickNotInitialized,
+    );
+
+    // Each tick array must contain its bound
+    let mut ix = ctx.ix_snapshot_cumulatives_inside(&pool, -120, 120);
+    replace_account(&mut ix, pool.tick_array(-120), pool.tick_array(120));
+    assert_error(ctx.bank.process(&ix), SuniswapError::TickArrayNotFound);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// TWAP GUARD
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Pool with liquidity over ±1200, an oracle averaging over 100 seconds and a trader
+fn guarded_pool() -> (TestContext, PoolFixture, User) {
+    let (mut ctx, pool, lp) = pool_with_lp();
+    ctx.add_liquidity(&pool, &lp, -1200, 1200, LIQUIDITY);
+    ctx.initialize_oracle(&pool, 100).unwrap();
+    let trader = ctx.create_user(&pool, BALANCE);
+    (ctx, pool, trader)
+}
+
+#[test]
+fn test_initialize_oracle() {
+    let (ctx, pool, _) = guarded_pool();
+    let event = ctx.bank.events::<PoolTwapGuardUpdatedEvent>().pop().unwrap();
+    assert_eq!(
+        (event.pool, event.oracle, event.twap_window, event.max_twap_deviation_ticks),
+        (pool.pool, oracle_pda(&pool.pool), 100, 0)
+    );
+
+    let state = ctx.pool(&pool);
+    assert_eq!(state.oracle_pubkey(), oracle_pda(&pool.pool));
+    assert_eq!((state.twap_window, state.max_twap_deviation_ticks), (100, 0));
+
+    let oracle: Oracle = ctx.account(&oracle_pda(&pool.pool));
+    assert_eq!(oracle.pool, pool.pool);
+    assert_eq!((oracle.observation_cardinality, oracle.observation_cardinality_next), (1, 32));
+    assert_eq!(oracle.observations[0].block_timestamp, ctx.bank.clock().unix_timestamp as u32);
+}
+
+#[test]
+fn test_twap_guard_rejects_swaps_far_from_twap() {
+    let (mut ctx, pool, trader) = guarded_pool();
+    let start = ctx.bank.clock().unix_timestamp as u32;
+    ctx.set_pool_twap_guard(&pool, 100, 100).unwrap();
+
+    // Without history covering the window, the TWAP is taken since the oldest
+    // observation, here the current price
+    let params = to_tick(&ctx, &pool, -180);
+    assert_error(ctx.swap(&pool, &trader, params), SuniswapError::TwapDeviationExceeded);
+
+    // The price sat at 0 for the whole window
+    wait(&mut ctx, 100);
+    swap_to_tick(&mut ctx, &pool, &trader, -60);
+
+    // 50 seconds at -60 average to -30 over the window
+    wait(&mut ctx, 50);
+    let params = to_tick(&ctx, &pool, -180);
+    assert_error(ctx.swap(&pool, &trader, params), SuniswapError::TwapDeviationExceeded);
+    swap_to_tick(&mut ctx, &pool, &trader, -120);
+
+    // The pool's accumulators are recorded at each swap
+    wait(&mut ctx, 50);
+    swap_to_tick(&mut ctx, &pool, &trader, -180);
+    let oracle: Oracle = ctx.account(&oracle_pda(&pool.pool));
+    let observations: Vec<_> = oracle.observations[..4]
+        .iter()
+        .map(|observation| (observation.block_timestamp - start, observation.tick_cumulative))
+        .collect();
+    assert_eq!(observations, [(0, 0), (100, 0), (150, -3000), (200, -9000)]);
+
+    // Windows starting between observations interpolate: half of the 50
+    // seconds at -60, then 50 seconds at -120
+    let state = ctx.pool(&pool);
+    let twap = oracle.twap_tick(75, &state, start + 200).unwrap();
+    assert_eq!(twap, (-1500 - 6000) / 75);
+}
+
+#[test]
+fn test_twap_guard_with_more_swaps_than_observations() {
+    let (mut ctx, pool, trader) = guarded_pool();
+    ctx.set_pool_twap_guard(&pool, 100, 100).unwrap();
+
+    // A swap every second, between -60 and 0, for longer than the window
+    for i in 0..150 {
+        wait(&mut ctx, 1);
+        swap_to_tick(&mut ctx, &pool, &trader, if i % 2 == 0 { -60 } else { 0 });
+    }
+
+    // Observations are at least 4 seconds apart, so the 32 of them span the window
+    let now = ctx.bank.clock().unix_timestamp as u32;
+    let oracle: Oracle = ctx.account(&oracle_pda(&pool.pool));
+    assert_eq!(oracle.observation_cardinality, 32);
+    let oldest = (oracle.observation_index as usize + 1) % 32;
+    let timestamps: Vec<_> = (0..32).map(|i| oracle.observations[(oldest + i) % 32].block_timestamp).collect();
+    assert!(timestamps.windows(2).all(|pair| pair[1] - pair[0] >= 4));
+    assert!(timestamps[0] <= now - 100);
+
+    // Half the window at -60 averages to -30
+    let state = ctx.pool(&pool);
+    assert_eq!(oracle.twap_tick(100, &state, now).unwrap(), -30);
+    let params = to_tick(&ctx, &pool, -180);
+    assert_error(ctx.swap(&pool, &trader, params), SuniswapError::TwapDeviationExceeded);
+
+    // A window longer than the oracle holds averages over what it has
+    ctx.set_pool_twap_guard(&pool, 1000, 100).unwrap();
+    wait(&mut ctx, 1);
+    swap_to_tick(&mut ctx, &pool, &trader, -60);
+}
+
+#[test]
+fn test_swap_requests_twap_guard() {
+    let (mut ctx, pool, trader) = guarded_pool();
+    wait(&mut ctx, 100);
+
+    // The pool checks nothing by itself, but a swap may ask
+    let guarded = SwapParams { max_twap_deviation_ticks: Some(100), ..to_tick(&ctx, &pool, -120) };
+    assert_error(ctx.swap(&pool, &trader, guarded), SuniswapError::TwapDeviationExceeded);
+    swap_to_tick(&mut ctx, &pool, &trader, -120);
+
+    // The tighter of the pool's cap and the swap's applies
+    wait(&mut ctx, 100);
+    ctx.set_pool_twap_guard(&pool, 100, 200).unwrap();
+    let guarded = SwapParams { max_twap_deviation_ticks: Some(50), ..to_tick(&ctx, &pool, -240) };
+    assert_error(ctx.swap(&pool, &trader, guarded), SuniswapError::TwapDeviationExceeded);
+    let guarded = SwapParams { max_twap_deviation_ticks: Some(500), ..to_tick(&ctx, &pool, -360) };
+    assert_error(ctx.swap(&pool, &trader, guarded), SuniswapError::TwapDeviationExceeded);
+    swap_to_tick(&mut ctx, &pool, &trader, -240);
+
+    // Asking without an oracle fails rather than skipping the check
+    let (mut ctx, pool, lp) = pool_with_lp();
+    ctx.add_liquidity(&pool, &lp, -1200, 1200, LIQUIDITY);
+    let guarded = SwapParams { max_twap_deviation_ticks: Some(100), ..to_tick(&ctx, &pool, -60) };
+    assert_error(ctx.swap(&pool, &lp, guarded), SuniswapError::OracleNotInitialized);
+}
+
+#[test]
+fn test_swaps_must_pass_the_pools_oracle() {
+    let (mut ctx, pool, trader) = guarded_pool();
+    wait(&mut ctx, 1);
+    let params = to_tick(&ctx, &pool, -60);
+    let tick_arrays = ctx.swap_tick_arrays(&pool, true);
+    let ix = ctx.ix_swap(&pool, &trader, params, tick_arrays);
+
+    let mut missing = ix.clone();
+    replace_account(&mut missing, oracle_pda(&pool.pool), suniswap::ID);
+    assert_error(ctx.bank.process(&missing), SuniswapError::InvalidOracleAccount);
+
+    let other = ctx.create_pool(3000, sqrt_price_at(0)).unwrap();
+    ctx.initialize_oracle(&other, 100).unwrap();
+    let mut foreign = ix.clone();
+    replace_account(&mut foreign, oracle_pda(&pool.pool), oracle_pda(&other.pool));
+    assert_error(ctx.bank.process(&foreign), SuniswapError::InvalidOracleAccount);
+
+    ctx.bank.process(&ix).unwrap();
+}
@@ -0,0 +1,363 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::*;
+use suniswap::errors::SuniswapError;
+use suniswap::events::{
+    CreatorFeesCollectedEvent, ProtocolFeeRecipientsUpdatedEvent, ProtocolFeesCollectedEvent,
+    ProtocolFeesDistributedEvent, SwapEvent,
+};
+use suniswap::state::{Pool, ProtocolFeeConfig, ProtocolFeeRecipient};
+
+const BALANCE: u64 = 1_000_000_000_000;
+
+/// Pool at tick 0 (fee 0.3%, protocol share 10%) after one swap in each direction
+fn pool_with_protocol_fees() -> (TestContext, PoolFixture, User) {
+    let (mut ctx, pool) = TestContext::with_pool(3000, 60, 0);
+    let lp = ctx.create_user(&pool, BALANCE);
+    ctx.add_liquidity(&pool, &lp, -600, 600, 1_000_000_000_000);
+
+    let trader = ctx.create_user(&pool, BALANCE);
+    ctx.swap(&pool, &trader, exact_in(10_000_000, true)).unwrap();
+    ctx.swap(&pool, &trader, exact_in(10_000_000, false)).unwrap();
+
+    let treasury = ctx.create_user(&pool, 0);
+    (ctx, pool, treasury)
+}
+
+#[test]
+fn test_swaps_accrue_protocol_fees() {
+    let (mut ctx, pool) = TestContext::with_pool(3000, 60, 0);
+    let lp = ctx.create_user(&pool, BALANCE);
+    ctx.add_liquidity(&pool, &lp, -600, 600, 1_000_000_000_000);
+    let trader = ctx.create_user(&pool, BALANCE);
+
+    ctx.swap(&pool, &trader, exact_in(10_000_000, true)).unwrap();
+    let swap = ctx.bank.events::<SwapEvent>().pop().unwrap();
+    assert_eq!(swap.fee_amount, 30_000);
+    assert_eq!(swap.protocol_fee, 3_000);
+    assert_eq!(ctx.pool(&pool).protocol_fees_a, 3_000);
+    assert_eq!(ctx.pool(&pool).protocol_fees_b, 0);
+
+    ctx.swap(&pool, &trader, exact_in(10_000_000, false)).unwrap();
+    let swap = ctx.bank.events::<SwapEvent>().pop().unwrap();
+    assert_eq!(ctx.pool(&pool).protocol_fees_a, 3_000);
+    assert_eq!(ctx.pool(&pool).protocol_fees_b, swap.protocol_fee);
+}
+
+#[test]
+fn test_collect_protocol_fees() {
+    let (mut ctx, pool, treasury) = pool_with_protocol_fees();
+    let accrued = ctx.pool(&pool);
+
+    // A partial request leaves the remainder on the pool
+    ctx.collect_protocol_fees(&pool, &treasury, 1_000, 0).unwrap();
+    assert_eq!(ctx.token_balance(&treasury.token_a), 1_000);
+    assert_eq!(ctx.token_balance(&treasury.token_b), 0);
+    assert_eq!(ctx.pool(&pool).protocol_fees_a, accrued.protocol_fees_a - 1_000);
+
+    // Requests above what has accrued are capped
+    let vault_a = ctx.token_balance(&pool.vault_a);
+    ctx.collect_protocol_fees(&pool, &treasury, u64::MAX, u64::MAX).unwrap();
+    let event = ctx.bank.events::<ProtocolFeesCollectedEvent>().pop().unwrap();
+    assert_eq!(event.amount_a, accrued.protocol_fees_a - 1_000);
+    assert_eq!(event.amount_b, accrued.protocol_fees_b);
+    assert_eq!(ctx.token_balance(&treasury.token_a), accrued.protocol_fees_a);
+    assert_eq!(ctx.token_balance(&treasury.token_b), accrued.protocol_fees_b);
+    assert_eq!(vault_a - ctx.token_balance(&pool.vault_a), event.amount_a);
+
+    let state = ctx.pool(&pool);
+    assert_eq!((state.protocol_fees_a, state.protocol_fees_b), (0, 0));
+}
+
+#[test]
+fn test_collect_protocol_fees_requires_fee_authority() {
+    let (mut ctx, pool, treasury) = pool_with_protocol_fees();
+    let intruder = Pubkey::new_unique();
+    ctx.bank.airdrop(intruder, SOL);
+
+    let mut ix = ctx.ix_collect_protocol_fees(&pool, &treasury, u64::MAX, u64::MAX);
+    replace_account(&mut ix, ctx.authority, intruder);
+    assert_error(ctx.bank.process(&ix), SuniswapError::InvalidFeeAuthority);
+}
+
+#[test]
+fn test_collect_protocol_fees_validates_accounts() {
+    let (mut ctx, pool, treasury) = pool_with_protocol_fees();
+    let ix = ctx.ix_collect_protocol_fees(&pool, &treasury, u64::MAX, u64::MAX);
+
+    ctx.initialize_fee_tier(500, 10).unwrap();
+    let other = ctx.create_pool(500, sqrt_price_at(0)).unwrap();
+
+    let mut bad = ix.clone();
+    replace_account(&mut bad, pool.fee_tier, other.fee_tier);
+    assert_error(ctx.bank.process(&bad), SuniswapError::InvalidFeeTier);
+
+    let mut bad = ix.clone();
+    replace_account(&mut bad, pool.mint_a, other.mint_a);
+    assert_error(ctx.bank.process(&bad), SuniswapError::InvalidTokenMint);
+
+    let mut bad = ix.clone();
+    replace_account(&mut bad, pool.vault_b, other.vault_b);
+    assert_error(ctx.bank.process(&bad), SuniswapError::InvalidVault);
+
+    ctx.modify_zero_copy::<Pool>(&pool.pool, |p| p.config = Pubkey::new_unique().to_bytes());
+    assert_error(ctx.bank.process(&ix), SuniswapError::InvalidConfig);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// DISTRIBUTION
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn recipient(wallet: Pubkey, weight: u16) -> ProtocolFeeRecipient {
+    ProtocolFeeRecipient { wallet, weight }
+}
+
+/// Treasury, insurance fund and creator wallets splitting fees 50/30/20, with
+/// associated token accounts for both of the pool's tokens
+fn fee_split(ctx: &mut TestContext, pool: &PoolFixture) -> Vec<Pubkey> {
+    let wallets = vec![Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+    for wallet in &wallets {
+        ctx.create_associated_token_account(&pool.mint_a, wallet);
+        ctx.create_associated_token_account(&pool.mint_b, wallet);
+    }
+    ctx.initialize_protocol_fee_config(vec![
+        recipient(wallets[0], 5_000),
+        recipient(wallets[1], 3_000),
+        recipient(wallets[2], 2_000),
+    ])
+    .unwrap();
+    wallets
+}
+
+#[test]
+fn test_distribute_protocol_fees_by_weight() {
+    let (mut ctx, pool, _) = pool_with_protocol_fees();
+    let wallets = fee_split(&mut ctx, &pool);
+    let split = ctx.account::<ProtocolFeeConfig>(&protocol_fee_config_pda());
+    assert_eq!(split.config, config_pda());
+    assert_eq!(split.active_recipients()[1], recipient(wallets[1], 3_000));
+
+    let accrued = ctx.pool(&pool);
+    let (vault_a, vault_b) = (ctx.token_balance(&pool.vault_a), ctx.token_balance(&pool.vault_b));
+    ctx.distribute_protocol_fees(&pool, &wallets).unwrap();
+
+    // The first recipients get their weight rounded down, the last the remainder
+    let balances = |ctx: &TestContext, wallet: &Pubkey| {
+        (
+            ctx.token_balance(&associated_token_address(wallet, &pool.mint_a)),
+            ctx.token_balance(&associated_token_address(wallet, &pool.mint_b)),
+        )
+    };
+    let treasury = balances(&ctx, &wallets[0]);
+    let insurance = balances(&ctx, &wallets[1]);
+    let creator = balances(&ctx, &wallets[2]);
+    assert_eq!(treasury, (accrued.protocol_fees_a / 2, accrued.protocol_fees_b / 2));
+    assert_eq!(insurance, (accrued.protocol_fees_a * 3 / 10, accrued.protocol_fees_b * 3 / 10));
+    assert_eq!(creator.0, accrued.protocol_fees_a - treasury.0 - insurance.0);
+    assert_eq!(creator.1, accrued.protocol_fees_b - treasury.1 - insurance.1);
+    assert_eq!(vault_a - ctx.token_balance(&pool.vault_a), accrued.protocol_fees_a);
+    assert_eq!(vault_b - ctx.token_balance(&pool.vault_b), accrued.protocol_fees_b);
+
+    let payouts = ctx.bank.events::<ProtocolFeesDistributedEvent>();
+    assert_eq!(payouts.len(), 3);
+    assert_eq!(payouts[1].recipient, wallets[1]);
+    assert_eq!((payouts[1].amount_a, payouts[1].amount_b), insurance);
+    let swept = ctx.bank.events::<ProtocolFeesCollectedEvent>().pop().unwrap();
+    assert_eq!((swept.amount_a, swept.amount_b), (accrued.protocol_fees_a, accrued.protocol_fees_b));
+    let state = ctx.pool(&pool);
+    assert_eq!((state.protocol_fees_a, state.protocol_fees_b), (0, 0));
+
+    // Nothing left to sweep
+    ctx.distribute_protocol_fees(&pool, &wallets).unwrap();
+    assert_eq!(balances(&ctx, &wallets[0]), treasury);
+}
+
+#[test]
+fn test_protocol_fee_recipients_validation() {
+    let (mut ctx, pool, _) = pool_with_protocol_fees();
+    let wallet = Pubkey::new_unique();
+    for invalid in [
+        vec![],
+        vec![recipient(wallet, 9_999)],
+        vec![recipient(wallet, 10_000), recipient(wallet, 0)],
+        vec![recipient(wallet, 2_000); 5],
+    ] {
+        assert_error(ctx.initialize_protocol_fee_config(invalid), SuniswapError::InvalidProtocolFeeRecipients);
+    }
+
+    let intruder = Pubkey::new_unique();
+    ctx.bank.airdrop(intruder, SOL);
+    let mut ix = ctx.ix_initialize_protocol_fee_config(vec![recipient(wallet, 10_000)]);
+    replace_account(&mut ix, ctx.authority, intruder);
+    assert_error(ctx.bank.process(&ix), SuniswapError::InvalidFeeAuthority);
+
+    // Updates replace the whole list
+    let wallets = fee_split(&mut ctx, &pool);
+    ctx.set_protocol_fee_recipients(vec![recipient(wallets[2], 10_000)]).unwrap();
+    let event = ctx.bank.events::<ProtocolFeeRecipientsUpdatedEvent>().pop().unwrap();
+    assert_eq!(event.recipients, vec![recipient(wallets[2], 10_000)]);
+    let split = ctx.account::<ProtocolFeeConfig>(&protocol_fee_config_pda());
+    assert_eq!(split.active_recipients(), &[recipient(wallets[2], 10_000)]);
+
+    let accrued = ctx.pool(&pool);
+    ctx.distribute_protocol_fees(&pool, &wallets[2..]).unwrap();
+    assert_eq!(ctx.bank.events::<ProtocolFeesDistributedEvent>()[0].amount_a, accrued.protocol_fees_a);
+
+    assert_error(ctx.set_protocol_fee_recipients(vec![]), SuniswapError::InvalidProtocolFeeRecipients);
+    let mut ix = ctx.ix_set_protocol_fee_recipients(vec![recipient(wallet, 10_000)]);
+    replace_account(&mut ix, ctx.authority, intruder);
+    assert_error(ctx.bank.process(&ix), SuniswapError::InvalidFeeAuthority);
+}
+
+#[test]
+fn test_distribute_protocol_fees_requires_recipient_accounts() {
+    let (mut ctx, pool, treasury) = pool_with_protocol_fees();
+    let wallets = fee_split(&mut ctx, &pool);
+
+    // Every recipient, in order
+    assert_error(
+        ctx.distribute_protocol_fees(&pool, &wallets[..2]),
+        SuniswapError::InvalidProtocolFeeRecipientAccount,
+    );
+    assert_error(
+        ctx.distribute_protocol_fees(&pool, &[wallets[1], wallets[0], wallets[2]]),
+        SuniswapError::InvalidProtocolFeeRecipientAccount,
+    );
+
+    // Only their associated token accounts
+    let mut ix = ctx.ix_distribute_protocol_fees(&pool, &wallets);
+    let other_account = ctx.create_token_account(&pool.mint_a, &wallets[0], 0);
+    let ata = associated_token_address(&wallets[0], &pool.mint_a);
+    replace_account(&mut ix, ata, other_account);
+    assert_error(ctx.bank.process(&ix), SuniswapError::InvalidProtocolFeeRecipientAccount);
+
+    // Pool accounts are checked as for collection
+    let mut ix = ctx.ix_distribute_protocol_fees(&pool, &wallets);
+    replace_account(&mut ix, pool.vault_a, treasury.token_a);
+    assert_error(ctx.bank.process(&ix), SuniswapError::InvalidVault);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// CREATOR FEES
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Pool at tick 0 (fee 0.3%, protocol share 10%) paying its creator 20% of swap fees
+fn pool_with_creator() -> (TestContext, PoolFixture, User) {
+    let mut ctx = TestContext::with_config(10);
+    ctx.initialize_fee_tier(3000, 60).unwrap();
+    ctx.set_max_creator_fee_rate(20).unwrap();
+    let key = Pubkey::new_unique();
+    ctx.bank.airdrop(key, SOL);
+    let pool = ctx.create_pool_with_creator(3000, sqrt_price_at(0), key, 20).unwrap();
+    let creator = User {
+        key,
+        token_a: ctx.create_token_account(&pool.mint_a, &key, 0),
+        token_b: ctx.create_token_account(&pool.mint_b, &key, 0),
+    };
+
+    let lp = ctx.create_user(&pool, BALANCE);
+    ctx.add_liquidity(&pool, &lp, -600, 600, 1_000_000_000_000);
+    (ctx, pool, creator)
+}
+
+#[test]
+fn test_swaps_accrue_creator_fees() {
+    let (mut ctx, pool, creator) = pool_with_creator();
+    let state = ctx.pool(&pool);
+    assert_eq!(state.creator_pubkey(), creator.key);
+    assert_eq!(state.creator_fee_rate, 20);
+
+    let trader = ctx.create_user(&pool, BALANCE);
+    ctx.swap(&pool, &trader, exact_in(10_000_000, true)).unwrap();
+    let swap = ctx.bank.events::<SwapEvent>().pop().unwrap();
+    assert_eq!(swap.fee_amount, 30_000);
+    assert_eq!(swap.protocol_fee, 3_000);
+    assert_eq!(swap.creator_fee, 6_000);
+
+    // LPs earn only what is left after both cuts
+    let state = ctx.pool(&pool);
+    assert_eq!((state.creator_fees_a, state.creator_fees_b), (6_000, 0));
+    assert_eq!(state.protocol_fees_a, 3_000);
+    assert_eq!(
+        state.fee_growth_global_a_x128,
+        suniswap::math::swap_math::calculate_fee_growth(21_000, state.liquidity).unwrap(),
+    );
+}
+
+#[test]
+fn test_collect_creator_fees() {
+    let (mut ctx, pool, creator) = pool_with_creator();
+    let trader = ctx.create_user(&pool, BALANCE);
+    ctx.swap(&pool, &trader, exact_in(10_000_000, true)).unwrap();
+    ctx.swap(&pool, &trader, exact_in(10_000_000, false)).unwrap();
+    let accrued = ctx.pool(&pool);
+
+    ctx.collect_creator_fees(&pool, &creator, 1_000, 0).unwrap();
+    assert_eq!(ctx.token_balance(&creator.token_a), 1_000);
+    assert_eq!(ctx.pool(&pool).creator_fees_a, accrued.creator_fees_a - 1_000);
+
+    ctx.collect_creator_fees(&pool, &creator, u64::MAX, u64::MAX).unwrap();
+    let event = ctx.bank.events::<CreatorFeesCollectedEvent>().pop().unwrap();
+    assert_eq!(event.creator, creator.key);
+    assert_eq!(event.amount_a, accrued.creator_fees_a - 1_000);
+    assert_eq!(event.amount_b, accrued.creator_fees_b);
+    assert_eq!(ctx.token_balance(&creator.token_a), accrued.creator_fees_a);
+    assert_eq!(ctx.token_balance(&creator.token_b), accrued.creator_fees_b);
+
+    // Protocol fees are untouched
+    let state = ctx.pool(&pool);
+    assert_eq!((state.creator_fees_a, state.creator_fees_b), (0, 0));
+    assert_eq!(state.protocol_fees_a, accrued.protocol_fees_a);
+}
+
+#[test]
+fn test_collect_creator_fees_requires_creator() {
+    let (mut ctx, pool, creator) = pool_with_creator();
+    let trader = ctx.create_user(&pool, BALANCE);
+    ctx.swap(&pool, &trader, exact_in(10_000_000, true)).unwrap();
+
+    let intruder = ctx.create_user(&pool, 0);
+    assert_error(
+        ctx.collect_creator_fees(&pool, &intruder, u64::MAX, u64::MAX),
+        SuniswapError::InvalidPoolCreator,
+    );
+
+    // Pools without a creator have no one to pay
+    let other = ctx.create_pool(3000, sqrt_price_at(0)).unwrap();
+    let user = ctx.create_user(&other, 0);
+    assert_error(
+        ctx.collect_creator_fees(&other, &user, u64::MAX, u64::MAX),
+        SuniswapError::InvalidPoolCreator,
+    );
+
+    let mut ix = ctx.ix_collect_creator_fees(&pool, &creator, u64::MAX, u64::MAX);
+    replace_account(&mut ix, pool.vault_a, trader.token_a);
+    assert_error(ctx.bank.process(&ix), SuniswapError::InvalidVault);
+}
+
+#[test]
+fn test_creator_fee_rate_validation() {
+    let mut ctx = TestContext::with_config(10);
+    ctx.initialize_fee_tier(3000, 60).unwrap();
+    let creator = Pubkey::new_unique();
+
+    // Disabled until the protocol authority raises the cap
+    assert_error(
+        ctx.create_pool_with_creator(3000, sqrt_price_at(0), creator, 1).map(|_| ()),
+        SuniswapError::CreatorFeeTooHigh,
+    );
+    ctx.set_max_creator_fee_rate(10).unwrap();
+    assert_error(
+        ctx.create_pool_with_creator(3000, sqrt_price_at(0), creator, 11).map(|_| ()),
+        SuniswapError::CreatorFeeTooHigh,
+    );
+
+    // A rate needs someone to pay it to
+    let (mint_a, mint_b) = ctx.create_mint_pair();
+    let ix = ctx.ix_initialize_pool_with_creator(&mint_a, &mint_b, 3000, sqrt_price_at(0), None, 10);
+    assert_error(ctx.bank.process(&ix), SuniswapError::InvalidPoolCreator);
+
+    let pool = ctx.create_pool_with_creator(3000, sqrt_price_at(0), creator, 10).unwrap();
+    assert_eq!(ctx.pool(&pool).creator_fee_rate, 10);
+}
@@ -0,0 +1,683 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::*;
+use suniswap::errors::SuniswapError;
+use suniswap::events::{ReferralFeeEvent, SwapEvent};
+use suniswap::instructions::{AdaptiveFeeParams, SwapParams};
+
+const LIQUIDITY: u128 = 1_000_000_000_000;
+const BALANCE: u64 = 1_000_000_000_000_000;
+
+/// Pool at tick 0 (fee 0.05%, spacing 10) with nested positions at ±20, ±60, ±100 and ±140,
+/// so swaps in either direction cross an initialized tick every 40 ticks
+fn laddered_pool() -> (TestContext, PoolFixture, User) {
+    let (mut ctx, pool) = TestContext::with_pool(500, 10, 0);
+    let lp = ctx.create_user(&pool, BALANCE);
+    for width in [20, 60, 100, 140] {
+        ctx.add_liquidity(&pool, &lp, -width, width, LIQUIDITY);
+    }
+    (ctx, pool, lp)
+}
+
+/// Liquidity of the ladder positions whose range contains `tick`
+fn ladder_liquidity_at(tick: i32) -> u128 {
+    [20, 60, 100, 140]
+        .iter()
+        .filter(|&&width| tick >= -width && tick < width)
+        .count() as u128
+        * LIQUIDITY
+}
+
+struct Balances {
+    user_a: u64,
+    user_b: u64,
+    vault_a: u64,
+    vault_b: u64,
+}
+
+fn balances(ctx: &TestContext, pool: &PoolFixture, user: &User) -> Balances {
+    Balances {
+        user_a: ctx.token_balance(&user.token_a),
+        user_b: ctx.token_balance(&user.token_b),
+        vault_a: ctx.token_balance(&pool.vault_a),
+        vault_b: ctx.token_balance(&pool.vault_b),
+    }
+}
+
+#[test]
+fn test_exact_input_a_to_b_within_range() {
+    let (mut ctx, pool, _lp) = laddered_pool();
+    let trader = ctx.create_user(&pool, BALANCE);
+    let before = balances(&ctx, &pool, &trader);
+
+    ctx.swap(&pool, &trader, exact_in(1_000_000, true)).unwrap();
+
+    let after = balances(&ctx, &pool, &trader);
+    let event = ctx.bank.events::<SwapEvent>().pop().unwrap();
+    assert_eq!(event.amount_in, 1_000_000);
+    assert!(event.amount_out > 0 && event.amount_out < 1_000_000);
+    assert_eq!(event.fee_amount, 500);
+    assert_eq!(before.user_a - after.user_a, event.amount_in);
+    assert_eq!(after.user_b - before.user_b, event.amount_out);
+    assert_eq!(after.vault_a - before.vault_a, event.amount_in);
+    assert_eq!(before.vault_b - after.vault_b, event.amount_out);
+
+    let state = ctx.pool(&pool);
+    assert!(state.sqrt_price_x64 < sqrt_price_at(0));
+    assert_eq!(state.tick_current, -1);
+    assert_eq!(state.liquidity, 4 * LIQUIDITY);
+    assert_eq!(event.sqrt_price_x64, state.sqrt_price_x64);
+}
+
+#[test]
+fn test_exact_input_b_to_a_within_range() {
+    let (mut ctx, pool, _lp) = laddered_pool();
+    let trader = ctx.create_user(&pool, BALANCE);
+    let before = balances(&ctx, &pool, &trader);
+
+    ctx.swap(&pool, &trader, exact_in(1_000_000, false)).unwrap();
+
+    let after = balances(&ctx, &pool, &trader);
+    let event = ctx.bank.events::<SwapEvent>().pop().unwrap();
+    assert!(!event.a_to_b);
+    assert_eq!(before.user_b - after.user_b, 1_000_000);
+    assert_eq!(after.user_a - before.user_a, event.amount_out);
+    assert_eq!(after.vault_b - before.vault_b, 1_000_000);
+
+    let state = ctx.pool(&pool);
+    assert!(state.sqrt_price_x64 > sqrt_price_at(0));
+    assert_eq!(state.tick_current, 0);
+}
+
+#[test]
+fn test_exact_output_a_to_b_crosses_multiple_ticks() {
+    let (mut ctx, pool, _lp) = laddered_pool();
+    let trader = ctx.create_user(&pool, BALANCE);
+    let before = balances(&ctx, &pool, &trader);
+
+    // About 14e9 of token B sits between tick 0 and tick -100, 16e9 down to -140
+    let amount_out = 15_000_000_000;
+    ctx.swap(&pool, &trader, exact_out(amount_out, true)).unwrap();
+
+    let after = balances(&ctx, &pool, &trader);
+    let event = ctx.bank.events::<SwapEvent>().pop().unwrap();
+    assert_eq!(event.amount_out, amount_out);
+    assert_eq!(after.user_b - before.user_b, amount_out);
+    assert_eq!(before.vault_b - after.vault_b, amount_out);
+    assert_eq!(before.user_a - after.user_a, event.amount_in);
+    assert_eq!(after.vault_a - before.vault_a, event.amount_in);
+    // Price fell about 1.1%, so more A is paid than B received
+    assert!(event.amount_in > amount_out);
+
+    // Crossed -20, -60 and -100; only the widest position remains in range
+    let state = ctx.pool(&pool);
+    assert!(state.tick_current < -100 && state.tick_current >= -140);
+    assert_eq!(state.liquidity, ladder_liquidity_at(state.tick_current));
+    assert_eq!(state.liquidity, LIQUIDITY);
+}
+
+#[test]
+fn test_exact_output_b_to_a_crosses_multiple_ticks() {
+    let (mut ctx, pool, _lp) = laddered_pool();
+    let trader = ctx.create_user(&pool, BALANCE);
+    let before = balances(&ctx, &pool, &trader);
+
+    let amount_out = 15_000_000_000;
+    ctx.swap(&pool, &trader, exact_out(amount_out, false)).unwrap();
+
+    let after = balances(&ctx, &pool, &trader);
+    let event = ctx.bank.events::<SwapEvent>().pop().unwrap();
+    assert_eq!(event.amount_out, amount_out);
+    assert_eq!(after.user_a - before.user_a, amount_out);
+    assert_eq!(before.user_b - after.user_b, event.amount_in);
+
+    let state = ctx.pool(&pool);
+    assert!(state.tick_current >= 100 && state.tick_current < 140);
+    assert_eq!(state.liquidity, ladder_liquidity_at(state.tick_current));
+}
+
+#[test]
+fn test_round_trip_restores_liquidity() {
+    let (mut ctx, pool, _lp) = laddered_pool();
+    let trader = ctx.create_user(&pool, BALANCE);
+
+    ctx.swap(&pool, &trader, exact_out(15_000_000_000, true)).unwrap();
+    ctx.swap(&pool, &trader, exact_out(25_000_000_000, false)).unwrap();
+
+    // Back across every tick we crossed on the way down and beyond
+    let state = ctx.pool(&pool);
+    assert!(state.tick_current > 0);
+    assert_eq!(state.liquidity, ladder_liquidity_at(state.tick_current));
+}
+
+#[test]
+fn test_price_limit_stops_swap() {
+    let (mut ctx, pool, _lp) = laddered_pool();
+    let trader = ctx.create_user(&pool, BALANCE);
+    let limit = sqrt_price_at(-30);
+
+    let params = SwapParams {
+        sqrt_price_limit_x64: limit,
+        ..exact_in(1_000_000_000_000, true)
+    };
+    ctx.swap(&pool, &trader, params).unwrap();
+
+    let event = ctx.bank.events::<SwapEvent>().pop().unwrap();
+    assert!(event.amount_in < 1_000_000_000_000);
+    let state = ctx.pool(&pool);
+    assert_eq!(state.sqrt_price_x64, limit);
+    assert_eq!(state.tick_current, -30);
+    assert_eq!(state.liquidity, ladder_liquidity_at(-30));
+}
+
+#[test]
+fn test_swap_stops_at_end_of_tick_arrays() {
+    let (mut ctx, pool, lp) = laddered_pool();
+    let trader = ctx.create_user(&pool, BALANCE);
+    // Below the three arrays (80 ticks each) a swap from tick 0 is given
+    ctx.add_liquidity(&pool, &lp, -300, -250, LIQUIDITY);
+
+    ctx.swap(&pool, &trader, exact_in(1_000_000_000_000, true)).unwrap();
+    let event = ctx.bank.events::<SwapEvent>().pop().unwrap();
+    assert!(event.amount_in < 1_000_000_000_000);
+    let state = ctx.pool(&pool);
+    assert_eq!(state.sqrt_price_x64, sqrt_price_at(-160));
+    assert_eq!(state.tick_current, -161);
+    assert_eq!(state.liquidity, 0);
+
+    // The next swap is handed the arrays below and crosses into the far position
+    let params = SwapParams {
+        sqrt_price_limit_x64: sqrt_price_at(-270),
+        ..exact_in(1_000_000_000_000, true)
+    };
+    ctx.swap(&pool, &trader, params).unwrap();
+    let state = ctx.pool(&pool);
+    assert_eq!(state.tick_current, -270);
+    assert_eq!(state.liquidity, LIQUIDITY);
+}
+
+#[test]
+fn test_swap_keeps_tick_below_crossed_tick_when_price_stalls() {
+    // After crossing -325210 downwards the leftover input is too small to move the
+    // price against this much liquidity, so the price stays on the crossed tick
+    let (mut ctx, pool) = TestContext::with_pool(500, 10, -325_120);
+    let lp = ctx.create_user(&pool, BALANCE);
+    let liquidity = 11_704_035_971_799_092_107;
+    ctx.add_liquidity(&pool, &lp, -325_330, -325_210, liquidity);
+    let trader = ctx.create_user(&pool, BALANCE);
+
+    ctx.swap(&pool, &trader, exact_in(2_873_098_299, true)).unwrap();
+
+    let state = ctx.pool(&pool);
+    assert_eq!(state.sqrt_price_x64, sqrt_price_at(-325_210));
+    assert_eq!(state.tick_current, -325_211);
+    assert_eq!(state.liquidity, liquidity);
+}
+
+#[test]
+fn test_swap_fees_split_between_lps_and_protocol() {
+    let (mut ctx, pool, lp) = laddered_pool();
+    let trader = ctx.create_user(&pool, BALANCE);
+
+    ctx.swap(&pool, &trader, exact_out(15_000_000_000, true)).unwrap();
+    let event = ctx.bank.events::<SwapEvent>().pop().unwrap();
+
+    // Config default protocol share is 10%
+    assert!(event.protocol_fee > 0);
+    assert_eq!(ctx.pool(&pool).protocol_fees_a, event.protocol_fee);
+
+    let before = ctx.token_balance(&lp.token_a);
+    for width in [20, 60, 100, 140] {
+        ctx.collect_fees(&pool, &lp, -width, width, u64::MAX, u64::MAX).unwrap();
+    }
+    let lp_fees = ctx.token_balance(&lp.token_a) - before;
+
+    // LPs share the fee net of the protocol cut, losing at most a unit per position to rounding
+    let lp_share = event.fee_amount - event.protocol_fee;
+    assert!(lp_fees <= lp_share, "{lp_fees} > {lp_share}");
+    assert!(lp_fees + 4 >= lp_share, "{lp_fees} << {lp_share}");
+}
+
+#[test]
+fn test_swap_charges_current_fee_tier_rate() {
+    let (mut ctx, pool, _lp) = laddered_pool();
+    let trader = ctx.create_user(&pool, BALANCE);
+
+    ctx.set_fee_tier_rate(&pool.fee_tier, 3000).unwrap();
+    let before = balances(&ctx, &pool, &trader);
+    ctx.swap(&pool, &trader, exact_in(1_000_000, true)).unwrap();
+
+    // The vault still signs with the seed rate, so the output transfer goes through
+    let event = ctx.bank.events::<SwapEvent>().pop().unwrap();
+    assert_eq!(event.fee_amount, 3000);
+    assert_eq!(balances(&ctx, &pool, &trader).user_b - before.user_b, event.amount_out);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// POOL FEES
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Fee charged on a 1_000_000 exact input swap that stays within a tick
+fn small_swap_fee(ctx: &mut TestContext, pool: &PoolFixture, trader: &User, a_to_b: bool) -> u64 {
+    ctx.swap(pool, trader, exact_in(1_000_000, a_to_b)).unwrap();
+    ctx.bank.events::<SwapEvent>().pop().unwrap().fee_amount
+}
+
+#[test]
+fn test_pool_fee_rate_override() {
+    let (mut ctx, pool, _lp) = laddered_pool();
+    let trader = ctx.create_user(&pool, BALANCE);
+    let state = ctx.pool(&pool);
+    assert_eq!(state.fee_rate, 500);
+    assert_eq!(state.fee_rate_override, 0);
+
+    ctx.set_pool_fee_rate(&pool, Some(1000)).unwrap();
+    assert_eq!(small_swap_fee(&mut ctx, &pool, &trader, true), 1000);
+
+    // The override holds while the tier changes underneath it
+    ctx.set_fee_tier_rate(&pool.fee_tier, 3000).unwrap();
+    assert_eq!(small_swap_fee(&mut ctx, &pool, &trader, true), 1000);
+
+    ctx.set_pool_fee_rate(&pool, None).unwrap();
+    assert_eq!(ctx.pool(&pool).fee_rate_override, 0);
+    assert_eq!(small_swap_fee(&mut ctx, &pool, &trader, true), 3000);
+}
+
+#[test]
+fn test_adaptive_fee_follows_volatility() {
+    let (mut ctx, pool, _lp) = laddered_pool();
+    let trader = ctx.create_user(&pool, BALANCE);
+    ctx.swap_tick_arrays(&pool, true);
+    ctx.swap_tick_arrays(&pool, false);
+    ctx.set_pool_adaptive_fee(
+        &pool,
+        Some(AdaptiveFeeParams {
+            min_fee_rate: 500,
+            max_fee_rate: 4000,
+            volatility_fee_factor: 50,
+            volatility_decay_period: 100,
+        }),
+    )
+    .unwrap();
+
+    // Nothing has moved yet, so the base rate applies
+    assert_eq!(small_swap_fee(&mut ctx, &pool, &trader, true), 500);
+
+    // Move the price ~100 ticks; the accumulator adds the distance to what the
+    // first swap moved
+    let before = ctx.pool(&pool);
+    let params = SwapParams {
+        sqrt_price_limit_x64: sqrt_price_at(-100),
+        ..exact_in(BALANCE / 10, true)
+    };
+    ctx.swap(&pool, &trader, params).unwrap();
+    let state = ctx.pool(&pool);
+    let moved = state.tick_current.abs_diff(before.tick_current);
+    assert!(moved >= 99);
+    assert_eq!(state.volatility_accumulator, before.volatility_accumulator + moved);
+
+    // 500 + 50 per tick exceeds the cap
+    assert_eq!(small_swap_fee(&mut ctx, &pool, &trader, false), 4000);
+
+    // Halfway through the decay period (the capped swap moved the tick as well)
+    let clock = ctx.bank.clock();
+    ctx.bank.warp_to(clock.slot + 1, clock.unix_timestamp + 50);
+    let expected = 500 + (ctx.pool(&pool).volatility_accumulator / 2) as u64 * 50;
+    assert_eq!(small_swap_fee(&mut ctx, &pool, &trader, false), expected);
+
+    // Fully decayed
+    let clock = ctx.bank.clock();
+    ctx.bank.warp_to(clock.slot + 1, clock.unix_timestamp + 100);
+    assert_eq!(small_swap_fee(&mut ctx, &pool, &trader, false), 500);
+
+    // Disabling falls back to the static rate even right after a large move
+    let params = SwapParams {
+        sqrt_price_limit_x64: sqrt_price_at(-100),
+        ..exact_in(BALANCE / 10, true)
+    };
+    ctx.swap(&pool, &trader, params).unwrap();
+    ctx.set_pool_adaptive_fee(&pool, None).unwrap();
+    assert_eq!(ctx.pool(&pool).volatility_accumulator, 0);
+    assert_eq!(small_swap_fee(&mut ctx, &pool, &trader, false), 500);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// CIRCUIT BREAKER
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Exact input swap selling token A until the price reaches `tick`
+fn swap_down_to(tick: i32) -> SwapParams {
+    SwapParams {
+        sqrt_price_limit_x64: sqrt_price_at(tick),
+        ..exact_in(BALANCE / 10, true)
+    }
+}
+
+#[test]
+fn test_circuit_breaker_caps_move_per_swap() {
+    let (mut ctx, pool, _lp) = laddered_pool();
+    let trader = ctx.create_user(&pool, BALANCE);
+    ctx.swap_tick_arrays(&pool, true);
+    ctx.set_pool_circuit_breaker(&pool, 50, 0).unwrap();
+
+    assert_error(ctx.swap(&pool, &trader, swap_down_to(-100)), SuniswapError::PriceMoveLimitExceeded);
+    assert_eq!(ctx.pool(&pool).tick_current, 0);
+
+    // Smaller moves go through, and may add up within the slot
+    ctx.swap(&pool, &trader, swap_down_to(-40)).unwrap();
+    ctx.swap(&pool, &trader, swap_down_to(-80)).unwrap();
+    assert!(ctx.pool(&pool).tick_current <= -80);
+
+    // Zero lifts the cap
+    ctx.set_pool_circuit_breaker(&pool, 0, 0).unwrap();
+    ctx.swap(&pool, &trader, swap_down_to(-140)).unwrap();
+}
+
+#[test]
+fn test_circuit_breaker_caps_move_per_slot() {
+    let (mut ctx, pool, _lp) = laddered_pool();
+    let trader = ctx.create_user(&pool, BALANCE);
+    ctx.swap_tick_arrays(&pool, true);
+    ctx.swap_tick_arrays(&pool, false);
+    ctx.set_pool_circuit_breaker(&pool, 0, 60).unwrap();
+
+    ctx.swap(&pool, &trader, swap_down_to(-40)).unwrap();
+    let state = ctx.pool(&pool);
+    assert_eq!((state.circuit_breaker_slot, state.circuit_breaker_tick), (ctx.bank.clock().slot, 0));
+
+    // The slot's move is measured from where it started, not from the last swap
+    assert_error(ctx.swap(&pool, &trader, swap_down_to(-80)), SuniswapError::PriceMoveLimitExceeded);
+
+    // Moving back towards the starting tick is fine
+    ctx.swap(&pool, &trader, exact_in(1_000_000, false)).unwrap();
+
+    // A new slot starts from the current tick
+    let tick = ctx.pool(&pool).tick_current;
+    let clock = ctx.bank.clock();
+    ctx.bank.warp_to(clock.slot + 1, clock.unix_timestamp + 1);
+    ctx.swap(&pool, &trader, swap_down_to(-80)).unwrap();
+    let state = ctx.pool(&pool);
+    assert_eq!((state.circuit_breaker_slot, state.circuit_breaker_tick), (clock.slot + 1, tick));
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// REFERRAL FEES
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Ladder with referral fees capped at 1%, a trader, and a referrer account for each token
+fn referral_pool() -> (TestContext, PoolFixture, User, Pubkey, Pubkey) {
+    let (mut ctx, pool, _lp) = laddered_pool();
+    ctx.set_max_referral_fee_rate(100).unwrap();
+    let trader = ctx.create_user(&pool, BALANCE);
+    let referrer = Pubkey::new_unique();
+    let referrer_a = ctx.create_token_account(&pool.mint_a, &referrer, 0);
+    let referrer_b = ctx.create_token_account(&pool.mint_b, &referrer, 0);
+    (ctx, pool, trader, referrer_a, referrer_b)
+}
+
+fn referral_swap(ctx: &mut TestContext, pool: &PoolFixture, trader: &User, params: SwapParams, referrer: Pubkey) -> TxResult {
+    let tick_arrays = ctx.swap_tick_arrays(pool, params.a_to_b);
+    let mut ix = ctx.ix_swap(pool, trader, params, tick_arrays);
+    use_referral(&mut ix, referrer);
+    ctx.bank.process(&ix)
+}
+
+#[test]
+fn test_referral_fee_on_input() {
+    let (mut ctx, pool, trader, referrer_a, _) = referral_pool();
+    let before = balances(&ctx, &pool, &trader);
+
+    // 0.5% of the exact input goes to the referrer, the rest into the pool
+    let params = SwapParams { referral_fee_rate: 50, ..exact_in(1_000_000, true) };
+    referral_swap(&mut ctx, &pool, &trader, params, referrer_a).unwrap();
+    let swap = ctx.bank.events::<SwapEvent>().pop().unwrap();
+    assert_eq!(swap.amount_in, 995_000);
+    let after = balances(&ctx, &pool, &trader);
+    assert_eq!(before.user_a - after.user_a, 1_000_000);
+    assert_eq!(after.vault_a - before.vault_a, 995_000);
+    assert_eq!(after.user_b - before.user_b, swap.amount_out);
+    assert_eq!(ctx.token_balance(&referrer_a), 5_000);
+
+    let referral = ctx.bank.events::<ReferralFeeEvent>().pop().unwrap();
+    assert_eq!(referral.pool, pool.pool);
+    assert_eq!(referral.user, trader.key);
+    assert_eq!(referral.referrer_token_account, referrer_a);
+    assert_eq!(referral.mint, pool.mint_a);
+    assert_eq!((referral.amount, referral.fee_rate), (5_000, 50));
+
+    // On an exact output the fee comes on top of the pool's input
+    let before = balances(&ctx, &pool, &trader);
+    let params = SwapParams { referral_fee_rate: 50, ..exact_out(1_000_000, true) };
+    referral_swap(&mut ctx, &pool, &trader, params, referrer_a).unwrap();
+    let swap = ctx.bank.events::<SwapEvent>().pop().unwrap();
+    let fee = ctx.bank.events::<ReferralFeeEvent>().pop().unwrap().amount;
+    assert_eq!(fee, swap.amount_in * 50 / 9_950);
+    let after = balances(&ctx, &pool, &trader);
+    assert_eq!(after.user_b - before.user_b, 1_000_000);
+    assert_eq!(before.user_a - after.user_a, swap.amount_in + fee);
+    assert_eq!(ctx.token_balance(&referrer_a), 5_000 + fee);
+}
+
+#[test]
+fn test_referral_fee_on_output() {
+    let (mut ctx, pool, trader, _, referrer_b) = referral_pool();
+    let before = balances(&ctx, &pool, &trader);
+
+    // The referrer's share of an exact input swap's output
+    let params = SwapParams { referral_fee_rate: 100, ..exact_in(1_000_000, true) };
+    referral_swap(&mut ctx, &pool, &trader, params, referrer_b).unwrap();
+    let swap = ctx.bank.events::<SwapEvent>().pop().unwrap();
+    assert_eq!(swap.amount_in, 1_000_000);
+    let fee = swap.amount_out / 100;
+    let after = balances(&ctx, &pool, &trader);
+    assert_eq!(after.user_b - before.user_b, swap.amount_out - fee);
+    assert_eq!(before.vault_b - after.vault_b, swap.amount_out);
+    assert_eq!(ctx.token_balance(&referrer_b), fee);
+    let referral = ctx.bank.events::<ReferralFeeEvent>().pop().unwrap();
+    assert_eq!((referral.mint, referral.amount), (pool.mint_b, fee));
+
+    // An exact output is what the user receives, the pool paying out the fee on top
+    let before = balances(&ctx, &pool, &trader);
+    let params = SwapParams { referral_fee_rate: 100, ..exact_out(1_000_000, true) };
+    referral_swap(&mut ctx, &pool, &trader, params, referrer_b).unwrap();
+    let swap = ctx.bank.events::<SwapEvent>().pop().unwrap();
+    assert_eq!(swap.amount_out, 1_010_101);
+    let after = balances(&ctx, &pool, &trader);
+    assert_eq!(after.user_b - before.user_b, 1_000_000);
+    assert_eq!(ctx.token_balance(&referrer_b), fee + 10_101);
+}
+
+#[test]
+fn test_referral_slippage_is_net_of_fee() {
+    let (mut ctx, pool, trader, referrer_a, referrer_b) = referral_pool();
+
+    // What the pool pays out for 1_000_000 with no referral
+    let (mut quote_ctx, quote_pool, quote_trader, _, _) = referral_pool();
+    quote_ctx.swap(&quote_pool, &quote_trader, exact_in(1_000_000, true)).unwrap();
+    let quoted_out = quote_ctx.bank.events::<SwapEvent>().pop().unwrap().amount_out;
+    quote_ctx.swap(&quote_pool, &quote_trader, exact_out(1_000_000, true)).unwrap();
+    let quoted_in = quote_ctx.bank.events::<SwapEvent>().pop().unwrap().amount_in;
+
+    let min_out = SwapParams {
+        other_amount_threshold: quoted_out,
+        referral_fee_rate: 100,
+        ..exact_in(1_000_000, true)
+    };
+    assert_error(
+        referral_swap(&mut ctx, &pool, &trader, min_out.clone(), referrer_b),
+        SuniswapError::OutputBelowMinimum,
+    );
+    let net_min_out = SwapParams { other_amount_threshold: quoted_out - quoted_out / 100, ..min_out };
+    referral_swap(&mut ctx, &pool, &trader, net_min_out, referrer_b).unwrap();
+
+    let max_in = SwapParams {
+        other_amount_threshold: quoted_in,
+        referral_fee_rate: 100,
+        ..exact_out(1_000_000, true)
+    };
+    assert_error(
+        referral_swap(&mut ctx, &pool, &trader, max_in, referrer_a),
+        SuniswapError::InputExceedsMaximum,
+    );
+}
+
+#[test]
+fn test_referral_validation() {
+    let (mut ctx, pool, trader, referrer_a, _) = referral_pool();
+    let params = SwapParams { referral_fee_rate: 50, ..exact_in(1_000_000, true) };
+
+    // Above the config cap
+    let too_high = SwapParams { referral_fee_rate: 101, ..params.clone() };
+    assert_error(
+        referral_swap(&mut ctx, &pool, &trader, too_high, referrer_a),
+        SuniswapError::ReferralFeeTooHigh,
+    );
+
+    // A fee needs a referrer account in one of the pool's tokens, and the config
+    assert_error(ctx.swap(&pool, &trader, params.clone()), SuniswapError::InvalidReferralAccount);
+    let other_mint = ctx.create_mint();
+    let stranger = ctx.create_token_account(&other_mint, &Pubkey::new_unique(), 0);
+    assert_error(
+        referral_swap(&mut ctx, &pool, &trader, params.clone(), stranger),
+        SuniswapError::InvalidReferralAccount,
+    );
+    let tick_arrays = ctx.swap_tick_arrays(&pool, true);
+    let mut no_config = ctx.ix_swap(&pool, &trader, params.clone(), tick_arrays);
+    use_referral(&mut no_config, referrer_a);
+    let len = no_config.accounts.len();
+    no_config.accounts[len - 4].pubkey = suniswap::ID;
+    assert_error(ctx.bank.process(&no_config), SuniswapError::InvalidReferralAccount);
+
+    // Referrals are off until the cap is raised
+    ctx.set_max_referral_fee_rate(0).unwrap();
+    assert_error(
+        referral_swap(&mut ctx, &pool, &trader, params, referrer_a),
+        SuniswapError::ReferralFeeTooHigh,
+    );
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// ERRORS
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_swap_rejects_zero_amount() {
+    let (mut ctx, pool, _lp) = laddered_pool();
+    let trader = ctx.create_user(&pool, BALANCE);
+    assert_error(ctx.swap(&pool, &trader, exact_in(0, true)), SuniswapError::ZeroSwapAmount);
+}
+
+#[test]
+fn test_swap_rejects_price_limit_on_wrong_side() {
+    let (mut ctx, pool, _lp) = laddered_pool();
+    let trader = ctx.create_user(&pool, BALANCE);
+
+    let above = SwapParams {
+        sqrt_price_limit_x64: sqrt_price_at(10),
+        ..exact_in(1_000, true)
+    };
+    assert_error(ctx.swap(&pool, &trader, above), SuniswapError::InvalidPriceLimit);
+
+    let below = SwapParams {
+        sqrt_price_limit_x64: sqrt_price_at(-10),
+        ..exact_in(1_000, false)
+    };
+    assert_error(ctx.swap(&pool, &trader, below), SuniswapError::InvalidPriceLimit);
+
+    let under_min = SwapParams {
+        sqrt_price_limit_x64: suniswap::constants::MIN_SQRT_PRICE_X64 - 1,
+        ..exact_in(1_000, true)
+    };
+    assert_error(ctx.swap(&pool, &trader, under_min), SuniswapError::InvalidPriceLimit);
+}
+
+#[test]
+fn test_swap_slippage_bounds() {
+    let (mut ctx, pool, _lp) = laddered_pool();
+    let trader = ctx.create_user(&pool, BALANCE);
+
+    let min_out = SwapParams {
+        other_amount_threshold: 1_000_000,
+        ..exact_in(1_000_000, true)
+    };
+    assert_error(ctx.swap(&pool, &trader, min_out), SuniswapError::OutputBelowMinimum);
+
+    let max_in = SwapParams {
+        other_amount_threshold: 1_000_000,
+        ..exact_out(1_000_000, true)
+    };
+    assert_error(ctx.swap(&pool, &trader, max_in), SuniswapError::InputExceedsMaximum);
+}
+
+#[test]
+fn test_swap_deadline_and_max_slot() {
+    let (mut ctx, pool, _lp) = laddered_pool();
+    let trader = ctx.create_user(&pool, BALANCE);
+    ctx.bank.warp_to(100, 1_000);
+
+    let expired = SwapParams { deadline_unix_ts: Some(999), ..exact_in(1_000, true) };
+    assert_error(ctx.swap(&pool, &trader, expired), SuniswapError::DeadlineExceeded);
+    let late = SwapParams { max_slot: Some(99), ..exact_in(1_000, true) };
+    assert_error(ctx.swap(&pool, &trader, late), SuniswapError::MaxSlotExceeded);
+
+    // Both bounds are inclusive
+    let on_time = SwapParams {
+        deadline_unix_ts: Some(1_000),
+        max_slot: Some(100),
+        ..exact_in(1_000, true)
+    };
+    assert!(ctx.swap(&pool, &trader, on_time).is_ok());
+}
+
+#[test]
+fn test_swap_rejects_paused_pool() {
+    let (mut ctx, pool, _lp) = laddered_pool();
+    let trader = ctx.create_user(&pool, BALANCE);
+    ctx.modify_zero_copy::<suniswap::state::Pool>(&pool.pool, |p| p.is_paused = 1);
+    assert_error(ctx.swap(&pool, &trader, exact_in(1_000, true)), SuniswapError::PoolPaused);
+}
+
+#[test]
+fn test_swap_rejects_mismatched_accounts() {
+    let (mut ctx, pool, _lp) = laddered_pool();
+    let trader = ctx.create_user(&pool, BALANCE);
+    let arrays = ctx.swap_tick_arrays(&pool, true);
+    let ix = ctx.ix_swap(&pool, &trader, exact_in(1_000, true), arrays);
+
+    ctx.initialize_fee_tier(3000, 60).unwrap();
+    let mut wrong_tier = ix.clone();
+    replace_account(&mut wrong_tier, pool.fee_tier, fee_tier_pda(3000));
+    assert_error(ctx.bank.process(&wrong_tier), SuniswapError::InvalidFeeTier);
+
+    let other_mint = ctx.create_mint();
+    let mut wrong_mint = ix.clone();
+    replace_account(&mut wrong_mint, pool.mint_a, other_mint);
+    assert_error(ctx.bank.process(&wrong_mint), SuniswapError::InvalidTokenMint);
+
+    let fake_vault = ctx.create_token_account(&pool.mint_b, &pool.pool, 0);
+    let mut wrong_vault = ix.clone();
+    replace_account(&mut wrong_vault, pool.vault_b, fake_vault);
+    assert_error(ctx.bank.process(&wrong_vault), SuniswapError::InvalidVault);
+}
+
+#[test]
+fn test_swap_rejects_bad_tick_arrays() {
+    let (mut ctx, pool, _lp) = laddered_pool();
+    let trader = ctx.create_user(&pool, BALANCE);
+    let [first, second, third] = ctx.swap_tick_arrays(&pool, true);
+
+    // Arrays from another pool
+    let other = ctx.create_pool(500, sqrt_price_at(0)).unwrap();
+    let foreign = ctx.ensure_tick_array(&other, 0);
+    let ix = ctx.ix_swap(&pool, &trader, exact_in(1_000, true), [foreign, second, third]);
+    assert_error(ctx.bank.process(&ix), SuniswapError::InvalidTickArray);
+
+    // First array not adjacent to the current tick
+    let ix = ctx.ix_swap(&pool, &trader, exact_in(1_000, true), [third, third, third]);
+    assert_error(ctx.bank.process(&ix), SuniswapError::InvalidTickArray);
+
+    // Arrays ordered against the swap direction
+    let ix = ctx.ix_swap(&pool, &trader, exact_in(1_000, true), [first, third, second]);
+    assert_error(ctx.bank.process(&ix), SuniswapError::InvalidTickArray);
+}
@@ -0,0 +1,247 @@
+mod common;
+
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::instruction::Instruction;
+use common::*;
+use suniswap::constants::position_permissions;
+use suniswap::errors::SuniswapError;
+use suniswap::events::{AllowedTraderUpdatedEvent, TraderAllowlistUpdatedEvent};
+use suniswap::instructions::{IncreaseLiquidityByAmountsParams, RepositionParams};
+
+const BALANCE: u64 = 1_000_000_000_000;
+const LIQUIDITY: u128 = 1_000_000_000_000;
+
+/// Pool at tick 0 (fee 0.3%, spacing 60) with an LP, gated by an enabled
+/// trader allowlist that only holds the LP
+fn allowlisted_pool() -> (TestContext, PoolFixture, Pubkey, User) {
+    let (mut ctx, pool) = TestContext::with_pool(3000, 60, 0);
+    let lp = ctx.create_user(&pool, BALANCE);
+    ctx.add_liquidity(&pool, &lp, -1200, 1200, LIQUIDITY);
+
+    let admin = Pubkey::new_unique();
+    ctx.bank.airdrop(admin, 10 * SOL);
+    ctx.initialize_trader_allowlist(&pool, &admin).unwrap();
+    ctx.add_allowed_trader(&pool, &admin, &lp.key).unwrap();
+    ctx.set_trader_allowlist(&pool, &admin, &admin, true).unwrap();
+    (ctx, pool, admin, lp)
+}
+
+fn by_amounts(amount_a_max: u64, amount_b_max: u64) -> IncreaseLiquidityByAmountsParams {
+    IncreaseLiquidityByAmountsParams {
+        amount_a_max,
+        amount_b_max,
+        sqrt_price_min_x64: 0,
+        sqrt_price_max_x64: u128::MAX,
+        deadline_unix_ts: None,
+        max_slot: None,
+    }
+}
+
+/// Pass `entry` as a swap's allowed trader account, which follows the token program
+fn use_allowed_trader(ix: &mut Instruction, entry: Pubkey) {
+    let index = ix.accounts.iter().position(|meta| meta.pubkey == spl_token::ID).unwrap() + 1;
+    ix.accounts[index] = AccountMeta::new_readonly(entry, false);
+}
+
+#[test]
+fn test_allowlist_gates_swaps() {
+    let (mut ctx, pool, admin, _) = allowlisted_pool();
+    assert!(ctx.pool(&pool).is_trader_allowlist_enabled());
+    let trader = ctx.create_user(&pool, BALANCE);
+
+    assert_error(ctx.swap(&pool, &trader, exact_in(1_000_000, true)), SuniswapError::TraderNotAllowed);
+
+    ctx.add_allowed_trader(&pool, &admin, &trader.key).unwrap();
+    let event = ctx.bank.events::<AllowedTraderUpdatedEvent>().pop().unwrap();
+    assert_eq!((event.pool, event.wallet, event.allowed), (pool.pool, trader.key, true));
+    ctx.swap(&pool, &trader, exact_in(1_000_000, true)).unwrap();
+
+    // Switching the allowlist off opens the pool to everyone again
+    let outsider = ctx.create_user(&pool, BALANCE);
+    ctx.set_trader_allowlist(&pool, &admin, &admin, false).unwrap();
+    ctx.swap(&pool, &outsider, exact_in(1_000_000, false)).unwrap();
+}
+
+#[test]
+fn test_allowlist_gates_deposits() {
+    let (mut ctx, pool, admin, lp) = allowlisted_pool();
+    let outsider = ctx.create_user(&pool, BALANCE);
+    ctx.ensure_tick_array(&pool, -600);
+    ctx.ensure_tick_array(&pool, 600);
+
+    assert_error(ctx.open_position(&pool, &outsider, -600, 600), SuniswapError::TraderNotAllowed);
+    assert_error(
+        ctx.open_position_with_liquidity(&pool, &outsider, -600, 600, by_amounts(1_000_000, 1_000_000)),
+        SuniswapError::TraderNotAllowed,
+    );
+
+    // Allowed wallets deposit as usual
+    ctx.increase_liquidity(&pool, &lp, -1200, 1200, LIQUIDITY, u64::MAX, u64::MAX).unwrap();
+    ctx.open_position_with_liquidity(&pool, &lp, -600, 600, by_amounts(1_000_000, 1_000_000)).unwrap();
+
+    // Vault deposits count as deposits by the depositor
+    let vault = ctx.create_vault(&pool);
+    let shares = ctx.create_token_account(&vault.share_mint, &outsider.key, 0);
+    assert_error(
+        ctx.deposit_vault(&pool, &vault, &outsider, &shares, 1_000_000, 1_000_000, 0),
+        SuniswapError::TraderNotAllowed,
+    );
+    ctx.add_allowed_trader(&pool, &admin, &outsider.key).unwrap();
+    ctx.deposit_vault(&pool, &vault, &outsider, &shares, 1_000_000, 1_000_000, 0).unwrap();
+}
+
+#[test]
+fn test_removed_trader_can_still_exit() {
+    let (mut ctx, pool, admin, lp) = allowlisted_pool();
+    let trader = ctx.create_user(&pool, BALANCE);
+    ctx.add_allowed_trader(&pool, &admin, &trader.key).unwrap();
+    ctx.swap(&pool, &trader, exact_in(10_000_000, true)).unwrap();
+
+    ctx.remove_allowed_trader(&pool, &admin, &lp.key).unwrap();
+    assert!(!ctx.exists(&allowed_trader_pda(&pool.pool, &lp.key)));
+    let event = ctx.bank.events::<AllowedTraderUpdatedEvent>().pop().unwrap();
+    assert_eq!((event.wallet, event.allowed), (lp.key, false));
+
+    assert_error(
+        ctx.increase_liquidity(&pool, &lp, -1200, 1200, LIQUIDITY, u64::MAX, u64::MAX),
+        SuniswapError::TraderNotAllowed,
+    );
+    ctx.decrease_liquidity(&pool, &lp, -1200, 1200, LIQUIDITY, 0, 0).unwrap();
+    ctx.collect_fees(&pool, &lp, -1200, 1200, u64::MAX, u64::MAX).unwrap();
+    ctx.close_position(&pool, &lp, -1200, 1200).unwrap();
+}
+
+#[test]
+fn test_allowlist_gates_split_and_merge() {
+    let (mut ctx, pool, admin, lp) = allowlisted_pool();
+    let outsider = ctx.create_user(&pool, BALANCE);
+
+    // Liquidity can't be handed to a wallet off the allowlist
+    assert_error(
+        ctx.split_position(&pool, &lp, -1200, 1200, &outsider.key, LIQUIDITY / 2),
+        SuniswapError::TraderNotAllowed,
+    );
+    ctx.add_allowed_trader(&pool, &admin, &outsider.key).unwrap();
+    ctx.split_position(&pool, &lp, -1200, 1200, &outsider.key, LIQUIDITY / 2).unwrap();
+
+    // Nor merged into a position whose owner has since been removed
+    ctx.remove_allowed_trader(&pool, &admin, &outsider.key).unwrap();
+    assert_error(
+        ctx.merge_positions(&pool, &outsider, (-1200, 1200), &lp, (-1200, 1200)),
+        SuniswapError::TraderNotAllowed,
+    );
+    ctx.merge_positions(&pool, &lp, (-1200, 1200), &outsider, (-1200, 1200)).unwrap();
+    assert_eq!(ctx.position(&position_pda(&pool.pool, &lp.key, -1200, 1200)).liquidity, LIQUIDITY);
+}
+
+#[test]
+fn test_allowlist_checks_position_owner() {
+    let (mut ctx, pool, admin, lp) = allowlisted_pool();
+    let manager = ctx.create_user(&pool, BALANCE);
+    ctx.set_position_delegate(&pool, &lp, -1200, 1200, &manager.key, position_permissions::ALL).unwrap();
+    // Fees for the compound to reinvest
+    ctx.swap(&pool, &lp, exact_in(10_000_000, true)).unwrap();
+    ctx.swap(&pool, &lp, exact_in(10_000_000, false)).unwrap();
+    ctx.ensure_tick_array(&pool, -600);
+    ctx.ensure_tick_array(&pool, 600);
+
+    // Every deposit path, signed by the delegate and passing the owner's entry
+    let as_manager = |mut ix: Instruction| {
+        replace_account(&mut ix, lp.key, manager.key);
+        replace_account(&mut ix, lp.token_a, manager.token_a);
+        replace_account(&mut ix, lp.token_b, manager.token_b);
+        ix
+    };
+    let reposition = RepositionParams {
+        swap_amount: 0,
+        swap_a_to_b: false,
+        sqrt_price_limit_x64: 0,
+        liquidity_min: 0,
+        deadline_unix_ts: None,
+        max_slot: None,
+    };
+    let mut reposition = ctx.ix_reposition(&pool, &lp, (-1200, 1200), (-600, 600), reposition, None);
+    replace_account(&mut reposition, lp.key, manager.key);
+    let deposits = [
+        as_manager(ctx.ix_increase_liquidity(&pool, &lp, -1200, 1200, LIQUIDITY, u64::MAX, u64::MAX)),
+        as_manager(ctx.ix_increase_liquidity_by_amounts(&pool, &lp, -1200, 1200, by_amounts(1_000_000, 1_000_000))),
+        as_manager(ctx.ix_compound_position(&pool, &lp, -1200, 1200)),
+        reposition,
+    ];
+
+    // A delegate can't deposit for an owner who is off the allowlist, even
+    // with its own entry
+    ctx.add_allowed_trader(&pool, &admin, &manager.key).unwrap();
+    ctx.remove_allowed_trader(&pool, &admin, &lp.key).unwrap();
+    for ix in &deposits {
+        let mut ix = ix.clone();
+        replace_account(&mut ix, allowed_trader_pda(&pool.pool, &lp.key), allowed_trader_pda(&pool.pool, &manager.key));
+        assert_error(ctx.bank.process(&ix), SuniswapError::TraderNotAllowed);
+    }
+    assert_error(ctx.compound_position(&pool, &lp, -1200, 1200), SuniswapError::TraderNotAllowed);
+
+    // And can for one who is on it, without being on it itself
+    ctx.add_allowed_trader(&pool, &admin, &lp.key).unwrap();
+    ctx.remove_allowed_trader(&pool, &admin, &manager.key).unwrap();
+    for ix in &deposits {
+        ctx.bank.process(ix).unwrap();
+    }
+}
+
+#[test]
+fn test_allowed_trader_entry_must_match() {
+    let (mut ctx, pool, _, lp) = allowlisted_pool();
+    let trader = ctx.create_user(&pool, BALANCE);
+
+    // Another wallet's entry
+    let tick_arrays = ctx.swap_tick_arrays(&pool, true);
+    let mut ix = ctx.ix_swap(&pool, &trader, exact_in(1_000_000, true), tick_arrays);
+    use_allowed_trader(&mut ix, allowed_trader_pda(&pool.pool, &lp.key));
+    assert_error(ctx.bank.process(&ix), SuniswapError::TraderNotAllowed);
+
+    // The wallet's entry on another pool
+    let other = ctx.create_pool(3000, sqrt_price_at(0)).unwrap();
+    let authority = ctx.authority;
+    ctx.initialize_trader_allowlist(&other, &authority).unwrap();
+    ctx.add_allowed_trader(&other, &authority, &trader.key).unwrap();
+    let mut ix = ctx.ix_swap(&pool, &trader, exact_in(1_000_000, true), tick_arrays);
+    use_allowed_trader(&mut ix, allowed_trader_pda(&other.pool, &trader.key));
+    assert_error(ctx.bank.process(&ix), SuniswapError::TraderNotAllowed);
+}
+
+#[test]
+fn test_trader_allowlist_admin() {
+    let (mut ctx, pool) = TestContext::with_pool(3000, 60, 0);
+    let admin = Pubkey::new_unique();
+    ctx.bank.airdrop(admin, 10 * SOL);
+
+    let mut ix = ctx.ix_initialize_trader_allowlist(&pool, &admin);
+    replace_account(&mut ix, ctx.authority, admin);
+    assert_error(ctx.bank.process(&ix), SuniswapError::NotProtocolAuthority);
+    ctx.initialize_trader_allowlist(&pool, &admin).unwrap();
+    let event = ctx.bank.events::<TraderAllowlistUpdatedEvent>().pop().unwrap();
+    assert_eq!((event.admin, event.enabled), (admin, false));
+    assert!(!ctx.pool(&pool).is_trader_allowlist_enabled());
+
+    // Only the admin manages the list
+    let intruder = ctx.authority;
+    assert_error(
+        ctx.add_allowed_trader(&pool, &intruder, &intruder),
+        SuniswapError::NotTraderAllowlistAdmin,
+    );
+    assert_error(
+        ctx.set_trader_allowlist(&pool, &intruder, &intruder, true),
+        SuniswapError::NotTraderAllowlistAdmin,
+    );
+
+    // Handing the list over leaves the old admin without access
+    let successor = Pubkey::new_unique();
+    ctx.bank.airdrop(successor, 10 * SOL);
+    ctx.set_trader_allowlist(&pool, &admin, &successor, true).unwrap();
+    assert!(ctx.pool(&pool).is_trader_allowlist_enabled());
+    ctx.add_allowed_trader(&pool, &successor, &admin).unwrap();
+    assert_error(
+        ctx.remove_allowed_trader(&pool, &admin, &admin),
+        SuniswapError::NotTraderAllowlistAdmin,
+    );
+}
@@ -0,0 +1,373 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::*;
+use suniswap::constants::managed_vault::MINIMUM_SHARES;
+use suniswap::errors::SuniswapError;
+use suniswap::events::{VaultDepositEvent, VaultFeesCollectedEvent, VaultRebalancedEvent, VaultWithdrawEvent};
+use suniswap::instructions::{RebalanceVaultParams, SwapParams, VaultParams};
+
+const BALANCE: u64 = 1_000_000_000_000;
+const DEPOSIT: u64 = 1_000_000_000;
+
+/// Pool at tick 0 (fee 0.3%, spacing 60) with a vault and a funded depositor
+/// holding a share account
+fn vault_with_user() -> (TestContext, PoolFixture, VaultFixture, User, Pubkey) {
+    let (mut ctx, pool) = TestContext::with_pool(3000, 60, 0);
+    let vault = ctx.create_vault(&pool);
+    let user = ctx.create_user(&pool, BALANCE);
+    let shares = ctx.create_token_account(&vault.share_mint, &user.key, 0);
+    (ctx, pool, vault, user, shares)
+}
+
+fn deploy(amount_a_max: u64, amount_b_max: u64) -> RebalanceVaultParams {
+    RebalanceVaultParams {
+        liquidity_removed: 0,
+        amount_a_max,
+        amount_b_max,
+        liquidity_min: 0,
+    }
+}
+
+fn params(vault: &VaultFixture, performance_fee_rate: u16, management_fee_rate: u16) -> VaultParams {
+    VaultParams {
+        strategist: vault.strategist.key,
+        treasury: vault.treasury.key,
+        performance_fee_rate,
+        management_fee_rate,
+        tick_lower_limit: -6000,
+        tick_upper_limit: 6000,
+    }
+}
+
+/// Generate fees around tick 0 by swapping back and forth
+fn churn(ctx: &mut TestContext, pool: &PoolFixture) {
+    let trader = ctx.create_user(pool, BALANCE);
+    ctx.swap(pool, &trader, exact_in(10_000_000, true)).unwrap();
+    ctx.swap(pool, &trader, exact_in(10_000_000, false)).unwrap();
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// DEPOSIT / WITHDRAW
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_initialize_vault() {
+    let (ctx, pool, vault, _, _) = vault_with_user();
+
+    let state = ctx.vault(&vault);
+    assert_eq!(state.pool, pool.pool);
+    assert_eq!(state.share_mint, vault.share_mint);
+    assert_eq!(state.strategist, vault.strategist.key);
+    assert_eq!(state.treasury, vault.treasury.key);
+    assert_eq!(state.position_count, 0);
+    assert_eq!(ctx.token_balance(&vault.reserve_a), 0);
+    assert_eq!(ctx.token_balance(&vault.reserve_b), 0);
+}
+
+#[test]
+fn test_deposit_and_withdraw_from_reserves() {
+    let (mut ctx, pool, vault, user, shares) = vault_with_user();
+
+    // The first deposit locks MINIMUM_SHARES of what it mints
+    ctx.deposit_vault(&pool, &vault, &user, &shares, DEPOSIT, DEPOSIT, DEPOSIT - MINIMUM_SHARES).unwrap();
+    let event = &ctx.bank.events::<VaultDepositEvent>()[0];
+    assert_eq!(event.shares, DEPOSIT - MINIMUM_SHARES);
+    assert_eq!((event.amount_a, event.amount_b), (DEPOSIT, DEPOSIT));
+    assert_eq!(ctx.token_balance(&shares), DEPOSIT - MINIMUM_SHARES);
+    assert_eq!(ctx.vault(&vault).locked_shares, MINIMUM_SHARES);
+    assert_eq!(ctx.token_balance(&vault.reserve_a), DEPOSIT);
+    assert_eq!(ctx.token_balance(&vault.reserve_b), DEPOSIT);
+
+    // A second depositor gets shares at the same rate, limited by the scarcer token
+    let other = ctx.create_user(&pool, BALANCE);
+    let other_shares = ctx.create_token_account(&vault.share_mint, &other.key, 0);
+    ctx.deposit_vault(&pool, &vault, &other, &other_shares, DEPOSIT / 2, DEPOSIT, 0).unwrap();
+    assert_eq!(ctx.token_balance(&other_shares), DEPOSIT / 2);
+    assert_eq!(ctx.token_balance(&other.token_b), BALANCE - DEPOSIT / 2);
+
+    ctx.withdraw_vault(&pool, &vault, &user, &shares, DEPOSIT / 2, 0, 0).unwrap();
+    let event = &ctx.bank.events::<VaultWithdrawEvent>()[0];
+    assert_eq!((event.amount_a, event.amount_b), (DEPOSIT / 2, DEPOSIT / 2));
+    assert_eq!(ctx.token_balance(&shares), DEPOSIT / 2 - MINIMUM_SHARES);
+    assert_eq!(ctx.token_balance(&user.token_a), BALANCE - DEPOSIT / 2);
+}
+
+#[test]
+fn test_deposit_and_withdraw_slippage() {
+    let (mut ctx, pool, vault, user, shares) = vault_with_user();
+
+    let held = DEPOSIT - MINIMUM_SHARES;
+    assert_error(ctx.deposit_vault(&pool, &vault, &user, &shares, 0, 0, 0), SuniswapError::ZeroShares);
+    assert_error(
+        ctx.deposit_vault(&pool, &vault, &user, &shares, DEPOSIT, DEPOSIT, held + 1),
+        SuniswapError::SlippageExceeded,
+    );
+    ctx.deposit_vault(&pool, &vault, &user, &shares, DEPOSIT, DEPOSIT, 0).unwrap();
+
+    assert_error(ctx.withdraw_vault(&pool, &vault, &user, &shares, 0, 0, 0), SuniswapError::ZeroShares);
+    assert_error(
+        ctx.withdraw_vault(&pool, &vault, &user, &shares, held, held + 1, 0),
+        SuniswapError::AmountABelowMin,
+    );
+    // Can't burn more shares than the user holds
+    assert!(ctx.withdraw_vault(&pool, &vault, &user, &shares, held + 1, 0, 0).is_err());
+}
+
+#[test]
+fn test_first_deposit_locks_minimum_shares() {
+    let (mut ctx, pool, vault, attacker, attacker_shares) = vault_with_user();
+    assert_error(
+        ctx.deposit_vault(&pool, &vault, &attacker, &attacker_shares, MINIMUM_SHARES, MINIMUM_SHARES, 0),
+        SuniswapError::VaultDepositTooSmall,
+    );
+
+    // An attacker takes a single share, then inflates it by sending tokens
+    // straight to the reserves
+    ctx.deposit_vault(&pool, &vault, &attacker, &attacker_shares, MINIMUM_SHARES + 1, MINIMUM_SHARES + 1, 0).unwrap();
+    assert_eq!(ctx.token_balance(&attacker_shares), 1);
+    ctx.credit_tokens(&vault.reserve_a, DEPOSIT);
+    ctx.credit_tokens(&vault.reserve_b, DEPOSIT);
+
+    // The locked shares keep most of the donation, and the next depositor
+    // still gets nearly all of theirs back
+    let victim = ctx.create_user(&pool, BALANCE);
+    let victim_shares = ctx.create_token_account(&vault.share_mint, &victim.key, 0);
+    ctx.deposit_vault(&pool, &vault, &victim, &victim_shares, DEPOSIT, DEPOSIT, 0).unwrap();
+    let shares = ctx.token_balance(&victim_shares);
+    ctx.withdraw_vault(&pool, &vault, &victim, &victim_shares, shares, 0, 0).unwrap();
+    assert!(BALANCE - ctx.token_balance(&victim.token_a) <= DEPOSIT / 1000);
+    assert!(BALANCE - ctx.token_balance(&victim.token_b) <= DEPOSIT / 1000);
+
+    ctx.withdraw_vault(&pool, &vault, &attacker, &attacker_shares, 1, 0, 0).unwrap();
+    let withdrawn = ctx.bank.events::<VaultWithdrawEvent>().pop().unwrap();
+    assert!(withdrawn.amount_a < DEPOSIT / 500);
+}
+
+#[test]
+fn test_vault_moves_only_near_twap() {
+    let (mut ctx, pool, vault, user, shares) = vault_with_user();
+    let lp = ctx.create_user(&pool, BALANCE);
+    ctx.add_liquidity(&pool, &lp, -1200, 1200, DEPOSIT as u128);
+    ctx.deposit_vault(&pool, &vault, &user, &shares, DEPOSIT, DEPOSIT, 0).unwrap();
+
+    // Push the price 600 ticks away from where it has been
+    let clock = ctx.bank.clock();
+    ctx.bank.warp_to(clock.slot + 1, clock.unix_timestamp + 100);
+    let push = SwapParams {
+        sqrt_price_limit_x64: sqrt_price_at(-600),
+        ..exact_in(BALANCE / 10, true)
+    };
+    ctx.swap(&pool, &lp, push).unwrap();
+    assert_error(
+        ctx.deposit_vault(&pool, &vault, &user, &shares, DEPOSIT, DEPOSIT, 0),
+        SuniswapError::TwapDeviationExceeded,
+    );
+    assert_error(
+        ctx.withdraw_vault(&pool, &vault, &user, &shares, DEPOSIT / 2, 0, 0),
+        SuniswapError::TwapDeviationExceeded,
+    );
+    ctx.open_vault_position(&pool, &vault, -1200, 1200).unwrap();
+    assert_error(
+        ctx.rebalance_vault_position(&pool, &vault, -1200, 1200, deploy(DEPOSIT, DEPOSIT)),
+        SuniswapError::TwapDeviationExceeded,
+    );
+
+    // The oracle must be passed
+    let mut ix = ctx.ix_deposit_vault(&pool, &vault, &user, &shares, DEPOSIT, DEPOSIT, 0);
+    replace_account(&mut ix, oracle_pda(&pool.pool), suniswap::ID);
+    assert_error(ctx.bank.process(&ix), SuniswapError::InvalidOracleAccount);
+
+    // Once the TWAP has caught up the vault opens again
+    let clock = ctx.bank.clock();
+    ctx.bank.warp_to(clock.slot + 1, clock.unix_timestamp + 100);
+    ctx.deposit_vault(&pool, &vault, &user, &shares, DEPOSIT, DEPOSIT, 0).unwrap();
+    ctx.rebalance_vault_position(&pool, &vault, -1200, 1200, deploy(DEPOSIT, DEPOSIT)).unwrap();
+    ctx.withdraw_vault(&pool, &vault, &user, &shares, DEPOSIT / 2, 0, 0).unwrap();
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// STRATEGY
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_rebalance_and_withdraw_pro_rata() {
+    let (mut ctx, pool, vault, user, shares) = vault_with_user();
+    ctx.deposit_vault(&pool, &vault, &user, &shares, DEPOSIT, DEPOSIT, 0).unwrap();
+
+    ctx.open_vault_position(&pool, &vault, -600, 600).unwrap();
+    ctx.rebalance_vault_position(&pool, &vault, -600, 600, deploy(DEPOSIT / 2, DEPOSIT / 2)).unwrap();
+    let event = &ctx.bank.events::<VaultRebalancedEvent>()[0];
+    assert!(event.liquidity_added > 0);
+    let position = ctx.position(&position_pda(&pool.pool, &vault.vault, -600, 600));
+    assert_eq!(position.owner_pubkey(), vault.vault);
+    assert_eq!(position.liquidity, event.liquidity_added);
+    assert_eq!(ctx.token_balance(&vault.reserve_a), DEPOSIT - event.amount_a_added);
+
+    // Withdrawing half the shares takes half the position and half the reserves
+    ctx.withdraw_vault(&pool, &vault, &user, &shares, DEPOSIT / 2, 0, 0).unwrap();
+    let remaining = ctx.position(&position_pda(&pool.pool, &vault.vault, -600, 600)).liquidity;
+    assert!(remaining.abs_diff(event.liquidity_added / 2) <= 1);
+    let withdrawn = &ctx.bank.events::<VaultWithdrawEvent>()[0];
+    assert!(withdrawn.amount_a.abs_diff(DEPOSIT / 2) <= 2);
+    assert!(withdrawn.amount_b.abs_diff(DEPOSIT / 2) <= 2);
+
+    // Pull everything back into the reserves and close the position
+    ctx.rebalance_vault_position(&pool, &vault, -600, 600, RebalanceVaultParams {
+        liquidity_removed: remaining,
+        ..deploy(0, 0)
+    })
+    .unwrap();
+    ctx.close_vault_position(&pool, &vault, -600, 600).unwrap();
+    assert_eq!(ctx.vault(&vault).position_count, 0);
+}
+
+#[test]
+fn test_deposit_after_fees_prices_shares_higher() {
+    let (mut ctx, pool, vault, user, shares) = vault_with_user();
+    ctx.deposit_vault(&pool, &vault, &user, &shares, DEPOSIT, DEPOSIT, 0).unwrap();
+    ctx.open_vault_position(&pool, &vault, -600, 600).unwrap();
+    ctx.rebalance_vault_position(&pool, &vault, -600, 600, deploy(DEPOSIT, DEPOSIT)).unwrap();
+    churn(&mut ctx, &pool);
+
+    let other = ctx.create_user(&pool, BALANCE);
+    let other_shares = ctx.create_token_account(&vault.share_mint, &other.key, 0);
+    ctx.deposit_vault(&pool, &vault, &other, &other_shares, DEPOSIT, DEPOSIT, 0).unwrap();
+    assert!(ctx.token_balance(&other_shares) < DEPOSIT);
+}
+
+#[test]
+fn test_strategist_only() {
+    let (mut ctx, pool, vault, _, _) = vault_with_user();
+    let impostor = VaultFixture {
+        strategist: ctx.create_user(&pool, 0),
+        ..vault.clone()
+    };
+
+    assert_error(ctx.open_vault_position(&pool, &impostor, -600, 600), SuniswapError::NotVaultStrategist);
+    ctx.open_vault_position(&pool, &vault, -600, 600).unwrap();
+    assert_error(
+        ctx.rebalance_vault_position(&pool, &impostor, -600, 600, deploy(0, 0)),
+        SuniswapError::NotVaultStrategist,
+    );
+    assert_error(ctx.close_vault_position(&pool, &impostor, -600, 600), SuniswapError::NotVaultStrategist);
+}
+
+#[test]
+fn test_vault_position_limits() {
+    let (mut ctx, pool, vault, user, shares) = vault_with_user();
+    ctx.set_vault_params(&pool, &vault, params(&vault, 0, 0)).unwrap();
+
+    assert_error(ctx.open_vault_position(&pool, &vault, -6060, 600), SuniswapError::VaultRangeOutsideLimits);
+    assert_error(ctx.open_vault_position(&pool, &vault, -600, 6060), SuniswapError::VaultRangeOutsideLimits);
+    ctx.open_vault_position(&pool, &vault, -600, 600).unwrap();
+    ctx.open_vault_position(&pool, &vault, -1200, 1200).unwrap();
+    ctx.open_vault_position(&pool, &vault, -1800, 1800).unwrap();
+    assert_error(ctx.open_vault_position(&pool, &vault, -2400, 2400), SuniswapError::VaultPositionLimit);
+
+    // Deposits must pass every vault position
+    ctx.deposit_vault(&pool, &vault, &user, &shares, DEPOSIT, DEPOSIT, 0).unwrap();
+    ctx.rebalance_vault_position(&pool, &vault, -1200, 1200, deploy(DEPOSIT / 2, DEPOSIT / 2)).unwrap();
+    let mut ix = ctx.ix_deposit_vault(&pool, &vault, &user, &shares, DEPOSIT, DEPOSIT, 0);
+    ix.accounts.truncate(ix.accounts.len() - 3);
+    assert_error(ctx.bank.process(&ix), SuniswapError::InvalidVaultPositions);
+
+    // Positions with liquidity stay open
+    assert_error(ctx.close_vault_position(&pool, &vault, -1200, 1200), SuniswapError::PositionHasLiquidity);
+    ctx.close_vault_position(&pool, &vault, -600, 600).unwrap();
+    assert_eq!(ctx.vault(&vault).position_count, 2);
+    ctx.deposit_vault(&pool, &vault, &user, &shares, DEPOSIT, DEPOSIT, 0).unwrap();
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// FEES
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_set_vault_params_validation() {
+    let (mut ctx, pool, vault, _, _) = vault_with_user();
+
+    let mut bad = params(&vault, 5_001, 0);
+    assert_error(ctx.set_vault_params(&pool, &vault, bad.clone()), SuniswapError::InvalidVaultParams);
+    bad = params(&vault, 0, 501);
+    assert_error(ctx.set_vault_params(&pool, &vault, bad.clone()), SuniswapError::InvalidVaultParams);
+    bad = VaultParams { tick_lower_limit: 6000, ..params(&vault, 0, 0) };
+    assert_error(ctx.set_vault_params(&pool, &vault, bad.clone()), SuniswapError::InvalidVaultParams);
+    bad = VaultParams { tick_lower_limit: -6001, ..params(&vault, 0, 0) };
+    assert_error(ctx.set_vault_params(&pool, &vault, bad), SuniswapError::InvalidVaultParams);
+
+    ctx.set_vault_params(&pool, &vault, params(&vault, 5_000, 500)).unwrap();
+    let state = ctx.vault(&vault);
+    assert_eq!(state.performance_fee_rate, 5_000);
+    assert_eq!(state.management_fee_rate, 500);
+}
+
+#[test]
+fn test_performance_fee_to_treasury() {
+    let (mut ctx, pool, vault, user, shares) = vault_with_user();
+    ctx.set_vault_params(&pool, &vault, params(&vault, 2_000, 0)).unwrap();
+    ctx.deposit_vault(&pool, &vault, &user, &shares, DEPOSIT, DEPOSIT, 0).unwrap();
+    ctx.open_vault_position(&pool, &vault, -600, 600).unwrap();
+    ctx.rebalance_vault_position(&pool, &vault, -600, 600, deploy(DEPOSIT, DEPOSIT)).unwrap();
+    churn(&mut ctx, &pool);
+
+    // Rebalancing harvests the fees, leaving 20% for the treasury
+    ctx.rebalance_vault_position(&pool, &vault, -600, 600, deploy(0, 0)).unwrap();
+    let state = ctx.vault(&vault);
+    assert!(state.treasury_fees_a > 0 && state.treasury_fees_b > 0);
+
+    ctx.collect_vault_fees(&pool, &vault).unwrap();
+    let event = &ctx.bank.events::<VaultFeesCollectedEvent>()[0];
+    assert_eq!((event.amount_a, event.amount_b), (state.treasury_fees_a, state.treasury_fees_b));
+    assert_eq!(event.shares, 0);
+    assert_eq!(ctx.token_balance(&vault.treasury.token_a), state.treasury_fees_a);
+    assert_eq!(ctx.token_balance(&vault.treasury.token_b), state.treasury_fees_b);
+    assert_eq!(ctx.vault(&vault).treasury_fees_a, 0);
+
+    // Nothing owed to the treasury is paid out to shareholders
+    ctx.withdraw_vault(&pool, &vault, &user, &shares, DEPOSIT - MINIMUM_SHARES, 0, 0).unwrap();
+    assert!(ctx.token_balance(&vault.reserve_a) <= 1);
+    assert!(ctx.token_balance(&vault.reserve_b) <= 1);
+}
+
+#[test]
+fn test_withdraw_harvests_into_empty_reserves() {
+    let (mut ctx, pool, vault, user, shares) = vault_with_user();
+    ctx.set_vault_params(&pool, &vault, params(&vault, 2_000, 0)).unwrap();
+    ctx.deposit_vault(&pool, &vault, &user, &shares, DEPOSIT, DEPOSIT, 0).unwrap();
+    ctx.open_vault_position(&pool, &vault, -600, 600).unwrap();
+    ctx.rebalance_vault_position(&pool, &vault, -600, 600, deploy(DEPOSIT, DEPOSIT)).unwrap();
+    assert_eq!(ctx.token_balance(&vault.reserve_a), 0);
+    assert_eq!(ctx.token_balance(&vault.reserve_b), 0);
+    churn(&mut ctx, &pool);
+
+    // The withdrawal harvests fees into the reserves, leaving the treasury's cut there
+    ctx.withdraw_vault(&pool, &vault, &user, &shares, DEPOSIT / 2, 0, 0).unwrap();
+    let state = ctx.vault(&vault);
+    assert!(state.treasury_fees_a > 0 && state.treasury_fees_b > 0);
+    assert!(ctx.token_balance(&vault.reserve_a) >= state.treasury_fees_a);
+    assert!(ctx.token_balance(&vault.reserve_b) >= state.treasury_fees_b);
+    let withdrawn = &ctx.bank.events::<VaultWithdrawEvent>()[0];
+    assert!(withdrawn.amount_a > 0 && withdrawn.amount_b > 0);
+}
+
+#[test]
+fn test_management_fee_mints_treasury_shares() {
+    let (mut ctx, pool, vault, user, shares) = vault_with_user();
+    ctx.set_vault_params(&pool, &vault, params(&vault, 0, 200)).unwrap();
+    ctx.deposit_vault(&pool, &vault, &user, &shares, DEPOSIT, DEPOSIT, 0).unwrap();
+
+    let clock = ctx.bank.clock();
+    ctx.bank.warp_to(clock.slot + 1, clock.unix_timestamp + 31_536_000);
+    ctx.collect_vault_fees(&pool, &vault).unwrap();
+
+    // 2% a year dilutes holders by 2% of the supply
+    let minted = ctx.token_balance(&vault.treasury_shares);
+    assert_eq!(minted, DEPOSIT / 50);
+    assert_eq!(ctx.bank.events::<VaultFeesCollectedEvent>()[0].shares, minted);
+
+    let wrong = ctx.create_token_account(&vault.share_mint, &user.key, 0);
+    let mut ix = ctx.ix_collect_vault_fees(&pool, &vault);
+    replace_account(&mut ix, vault.treasury_shares, wrong);
+    assert_error(ctx.bank.process(&ix), SuniswapError::InvalidVaultTreasury);
+}
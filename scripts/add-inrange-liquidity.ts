@@ -195,7 +195,7 @@ async function main() {
 
   try {
     const tx = await program.methods
-      .increaseLiquidity(liquidityDelta, amountAMax, amountBMax)
+      .increaseLiquidity(liquidityDelta, amountAMax, amountBMax, null, null)
       .accountsStrict({
         pool: poolPda,
         position: positionPda,
@@ -125,7 +125,7 @@ async function main() {
 
   try {
     const tx = await (program.methods as any)
-      .increaseLiquidity(liquidityDelta, amountAMax, amountBMax)
+      .increaseLiquidity(liquidityDelta, amountAMax, amountBMax, null, null)
       .accountsStrict({
         pool: poolPda,
         position: positionPda,
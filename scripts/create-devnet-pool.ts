@@ -231,7 +231,7 @@ async function main() {
 
   try {
     const tx = await program.methods
-      .initializePool(INITIAL_SQRT_PRICE)
+      .initializePool(INITIAL_SQRT_PRICE, null, 0)
       .accountsStrict({
         config: configPda,
         feeTier: feeTierPda,
@@ -353,7 +353,7 @@ async function main() {
     const amountBMax = new BN("1000000000"); // Max 1000 tokens B (6 decimals)
 
     const tx = await program.methods
-      .increaseLiquidity(liquidityDelta, amountAMax, amountBMax)
+      .increaseLiquidity(liquidityDelta, amountAMax, amountBMax, null, null)
       .accountsStrict({
         pool: poolPda,
         position: positionPda,
@@ -0,0 +1,437 @@
+// Generates programs/suniswap/tests/vectors/uniswap_v3_q64.json
+//
+// A line-by-line BigInt port of the Uniswap V3 reference math (TickMath,
+// SqrtPriceMath, SwapMath from v3-core and LiquidityAmounts from v3-periphery)
+// with the Q64.96 resolution replaced by Q64.64. Every uint256 operation in
+// the Solidity code is exact here, so the corpus holds the exact reference
+// results the Rust math is checked against.
+//
+// Run with: npx ts-node scripts/generate-reference-vectors.ts
+// The PRNG is seeded, so re-running reproduces the committed file byte for byte.
+
+import * as fs from "fs";
+import * as path from "path";
+
+const RESOLUTION = BigInt(64);
+const Q64 = BigInt(1) << RESOLUTION;
+const MAX_UINT256 = (BigInt(1) << BigInt(256)) - BigInt(1);
+const MAX_U64 = (BigInt(1) << BigInt(64)) - BigInt(1);
+const MAX_U128 = (BigInt(1) << BigInt(128)) - BigInt(1);
+const FEE_DENOMINATOR = BigInt(1_000_000);
+const ZERO = BigInt(0);
+const ONE = BigInt(1);
+
+// Q64.64 keeps sqrt prices in u128, which halves the V3 tick range
+const MIN_TICK = -443636;
+const MAX_TICK = 443636;
+
+// ─── FullMath ────────────────────────────────────────────────────────────────
+
+function mulDiv(a: bigint, b: bigint, denominator: bigint): bigint {
+  return (a * b) / denominator;
+}
+
+function mulDivRoundingUp(a: bigint, b: bigint, denominator: bigint): bigint {
+  const result = mulDiv(a, b, denominator);
+  return (a * b) % denominator > ZERO ? result + ONE : result;
+}
+
+function divRoundingUp(x: bigint, y: bigint): bigint {
+  return x / y + (x % y > ZERO ? ONE : ZERO);
+}
+
+// ─── TickMath ────────────────────────────────────────────────────────────────
+
+const TICK_FACTORS: [number, string][] = [
+  [0x2, "0xfff97272373d413259a46990580e213a"],
+  [0x4, "0xfff2e50f5f656932ef12357cf3c7fdcc"],
+  [0x8, "0xffe5caca7e10e4e61c3624eaa0941cd0"],
+  [0x10, "0xffcb9843d60f6159c9db58835c926644"],
+  [0x20, "0xff973b41fa98c081472e6896dfb254c0"],
+  [0x40, "0xff2ea16466c96a3843ec78b326b52861"],
+  [0x80, "0xfe5dee046a99a2a811c461f1969c3053"],
+  [0x100, "0xfcbe86c7900a88aedcffc83b479aa3a4"],
+  [0x200, "0xf987a7253ac413176f2b074cf7815e54"],
+  [0x400, "0xf3392b0822b70005940c7a398e4b70f3"],
+  [0x800, "0xe7159475a2c29b7443b29c7fa6e889d9"],
+  [0x1000, "0xd097f3bdfd2022b8845ad8f792aa5825"],
+  [0x2000, "0xa9f746462d870fdf8a65dc1f90e061e5"],
+  [0x4000, "0x70d869a156d2a1b890bb3df62baf32f7"],
+  [0x8000, "0x31be135f97d08fd981231505542fcfa6"],
+  [0x10000, "0x9aa508b5b7a84e1c677de54f3e99bc9"],
+  [0x20000, "0x5d6af8dedb81196699c329225ee604"],
+  [0x40000, "0x2216e584f5fa1ea926041bedfe98"],
+];
+
+function getSqrtRatioAtTick(tick: number): bigint {
+  const absTick = Math.abs(tick);
+  let ratio =
+    absTick & 0x1
+      ? BigInt("0xfffcb933bd6fad37aa2d162d1a594001")
+      : BigInt("0x100000000000000000000000000000000");
+  for (const [bit, factor] of TICK_FACTORS) {
+    if (absTick & bit) ratio = (ratio * BigInt(factor)) >> BigInt(128);
+  }
+  if (tick > 0) ratio = MAX_UINT256 / ratio;
+
+  // Q128.128 -> Q64.64, rounding up like the Q96 version does
+  const shift = BigInt(128) - RESOLUTION;
+  return (ratio >> shift) + (ratio % (ONE << shift) === ZERO ? ZERO : ONE);
+}
+
+/// Greatest tick whose sqrt ratio is at most `sqrtPrice`
+function getTickAtSqrtRatio(sqrtPrice: bigint): number {
+  let low = MIN_TICK;
+  let high = MAX_TICK + 1;
+  while (low < high) {
+    const mid = Math.floor((low + high) / 2);
+    if (getSqrtRatioAtTick(mid) <= sqrtPrice) low = mid + 1;
+    else high = mid;
+  }
+  return low - 1;
+}
+
+// ─── SqrtPriceMath ───────────────────────────────────────────────────────────
+
+function getNextSqrtPriceFromAmount0RoundingUp(
+  sqrtPrice: bigint,
+  liquidity: bigint,
+  amount: bigint,
+  add: boolean
+): bigint {
+  if (amount === ZERO) return sqrtPrice;
+  const numerator1 = liquidity << RESOLUTION;
+  const product = amount * sqrtPrice;
+  if (add) {
+    return mulDivRoundingUp(numerator1, sqrtPrice, numerator1 + product);
+  }
+  if (numerator1 <= product) throw new Error("insufficient liquidity");
+  return mulDivRoundingUp(numerator1, sqrtPrice, numerator1 - product);
+}
+
+function getNextSqrtPriceFromAmount1RoundingDown(
+  sqrtPrice: bigint,
+  liquidity: bigint,
+  amount: bigint,
+  add: boolean
+): bigint {
+  if (add) {
+    return sqrtPrice + (amount << RESOLUTION) / liquidity;
+  }
+  const quotient = divRoundingUp(amount << RESOLUTION, liquidity);
+  if (sqrtPrice <= quotient) throw new Error("insufficient liquidity");
+  return sqrtPrice - quotient;
+}
+
+function getNextSqrtPriceFromInput(
+  sqrtPrice: bigint,
+  liquidity: bigint,
+  amountIn: bigint,
+  zeroForOne: boolean
+): bigint {
+  return zeroForOne
+    ? getNextSqrtPriceFromAmount0RoundingUp(sqrtPrice, liquidity, amountIn, true)
+    : getNextSqrtPriceFromAmount1RoundingDown(sqrtPrice, liquidity, amountIn, true);
+}
+
+function getNextSqrtPriceFromOutput(
+  sqrtPrice: bigint,
+  liquidity: bigint,
+  amountOut: bigint,
+  zeroForOne: boolean
+): bigint {
+  return zeroForOne
+    ? getNextSqrtPriceFromAmount1RoundingDown(sqrtPrice, liquidity, amountOut, false)
+    : getNextSqrtPriceFromAmount0RoundingUp(sqrtPrice, liquidity, amountOut, false);
+}
+
+function getAmount0Delta(a: bigint, b: bigint, liquidity: bigint, roundUp: boolean): bigint {
+  const [lower, upper] = a > b ? [b, a] : [a, b];
+  const numerator1 = liquidity << RESOLUTION;
+  const numerator2 = upper - lower;
+  return roundUp
+    ? divRoundingUp(mulDivRoundingUp(numerator1, numerator2, upper), lower)
+    : mulDiv(numerator1, numerator2, upper) / lower;
+}
+
+function getAmount1Delta(a: bigint, b: bigint, liquidity: bigint, roundUp: boolean): bigint {
+  const [lower, upper] = a > b ? [b, a] : [a, b];
+  return roundUp
+    ? mulDivRoundingUp(liquidity, upper - lower, Q64)
+    : mulDiv(liquidity, upper - lower, Q64);
+}
+
+// ─── SwapMath ────────────────────────────────────────────────────────────────
+
+function computeSwapStep(
+  current: bigint,
+  target: bigint,
+  liquidity: bigint,
+  amountRemaining: bigint,
+  feePips: bigint
+): { sqrtPriceNext: bigint; amountIn: bigint; amountOut: bigint; feeAmount: bigint } {
+  const zeroForOne = current >= target;
+  const exactIn = amountRemaining >= ZERO;
+  let sqrtPriceNext: bigint;
+  let amountIn = ZERO;
+  let amountOut = ZERO;
+
+  if (exactIn) {
+    const amountRemainingLessFee = mulDiv(amountRemaining, FEE_DENOMINATOR - feePips, FEE_DENOMINATOR);
+    amountIn = zeroForOne
+      ? getAmount0Delta(target, current, liquidity, true)
+      : getAmount1Delta(current, target, liquidity, true);
+    sqrtPriceNext =
+      amountRemainingLessFee >= amountIn
+        ? target
+        : getNextSqrtPriceFromInput(current, liquidity, amountRemainingLessFee, zeroForOne);
+  } else {
+    amountOut = zeroForOne
+      ? getAmount1Delta(target, current, liquidity, false)
+      : getAmount0Delta(current, target, liquidity, false);
+    sqrtPriceNext =
+      -amountRemaining >= amountOut
+        ? target
+        : getNextSqrtPriceFromOutput(current, liquidity, -amountRemaining, zeroForOne);
+  }
+
+  const max = target === sqrtPriceNext;
+  if (zeroForOne) {
+    if (!(max && exactIn)) amountIn = getAmount0Delta(sqrtPriceNext, current, liquidity, true);
+    if (!(max && !exactIn)) amountOut = getAmount1Delta(sqrtPriceNext, current, liquidity, false);
+  } else {
+    if (!(max && exactIn)) amountIn = getAmount1Delta(current, sqrtPriceNext, liquidity, true);
+    if (!(max && !exactIn)) amountOut = getAmount0Delta(current, sqrtPriceNext, liquidity, false);
+  }
+
+  if (!exactIn && amountOut > -amountRemaining) amountOut = -amountRemaining;
+
+  const feeAmount =
+    exactIn && sqrtPriceNext !== target
+      ? amountRemaining - amountIn
+      : mulDivRoundingUp(amountIn, feePips, FEE_DENOMINATOR - feePips);
+
+  return { sqrtPriceNext, amountIn, amountOut, feeAmount };
+}
+
+// ─── LiquidityAmounts ────────────────────────────────────────────────────────
+
+function getLiquidityForAmount0(a: bigint, b: bigint, amount0: bigint): bigint {
+  const [lower, upper] = a > b ? [b, a] : [a, b];
+  const intermediate = mulDiv(lower, upper, Q64);
+  return mulDiv(amount0, intermediate, upper - lower);
+}
+
+function getLiquidityForAmount1(a: bigint, b: bigint, amount1: bigint): bigint {
+  const [lower, upper] = a > b ? [b, a] : [a, b];
+  return mulDiv(amount1, Q64, upper - lower);
+}
+
+function getLiquidityForAmounts(
+  current: bigint,
+  lower: bigint,
+  upper: bigint,
+  amount0: bigint,
+  amount1: bigint
+): bigint {
+  if (current <= lower) return getLiquidityForAmount0(lower, upper, amount0);
+  if (current < upper) {
+    const liquidity0 = getLiquidityForAmount0(current, upper, amount0);
+    const liquidity1 = getLiquidityForAmount1(lower, current, amount1);
+    return liquidity0 < liquidity1 ? liquidity0 : liquidity1;
+  }
+  return getLiquidityForAmount1(lower, upper, amount1);
+}
+
+// ─── Sampling ────────────────────────────────────────────────────────────────
+
+// splitmix64
+let state = BigInt("0x5355_4e49_5357_4150".replace(/_/g, ""));
+function nextU64(): bigint {
+  state = (state + BigInt("0x9e3779b97f4a7c15")) & MAX_U64;
+  let z = state;
+  z = ((z ^ (z >> BigInt(30))) * BigInt("0xbf58476d1ce4e5b9")) & MAX_U64;
+  z = ((z ^ (z >> BigInt(27))) * BigInt("0x94d049bb133111eb")) & MAX_U64;
+  return z ^ (z >> BigInt(31));
+}
+
+function randomInt(min: number, max: number): number {
+  return min + Number(nextU64() % BigInt(max - min + 1));
+}
+
+/// Value with a uniformly chosen bit length, so small and large magnitudes are equally covered
+function randomBits(maxBits: number): bigint {
+  const bits = randomInt(1, maxBits);
+  let value = ZERO;
+  for (let filled = 0; filled < bits; filled += 64) value = (value << BigInt(64)) | nextU64();
+  return value & ((ONE << BigInt(bits)) - ONE);
+}
+
+function randomTick(): number {
+  // Half the samples stay near the middle of the range where pools live
+  return randomInt(0, 1) ? randomInt(MIN_TICK, MAX_TICK) : randomInt(-50_000, 50_000);
+}
+
+function attempt<T>(f: () => T): T | undefined {
+  try {
+    return f();
+  } catch {
+    return undefined;
+  }
+}
+
+const MIN_SQRT_PRICE = getSqrtRatioAtTick(MIN_TICK);
+const MAX_SQRT_PRICE = getSqrtRatioAtTick(MAX_TICK);
+const inPriceRange = (price: bigint) => price >= MIN_SQRT_PRICE && price <= MAX_SQRT_PRICE;
+
+// ─── Corpus ──────────────────────────────────────────────────────────────────
+
+const ticks = new Set<number>([0, MIN_TICK, MAX_TICK, MIN_TICK + 1, MAX_TICK - 1]);
+for (let bit = 0; bit < 19; bit++) {
+  for (const tick of [1 << bit, (1 << bit) - 1, (1 << bit) + 1]) {
+    if (tick <= MAX_TICK) {
+      ticks.add(tick);
+      ticks.add(-tick);
+    }
+  }
+}
+while (ticks.size < 300) ticks.add(randomTick());
+const sqrtPriceAtTick = [...ticks]
+  .sort((a, b) => a - b)
+  .map((tick) => ({ tick, sqrtPriceX64: getSqrtRatioAtTick(tick).toString() }));
+
+const tickAtSqrtPrice: { sqrtPriceX64: string; tick: number }[] = [];
+for (const { tick } of sqrtPriceAtTick.slice(0, 100)) {
+  const price = getSqrtRatioAtTick(tick);
+  for (const probe of [price - ONE, price, price + ONE]) {
+    if (inPriceRange(probe)) tickAtSqrtPrice.push({ sqrtPriceX64: probe.toString(), tick: getTickAtSqrtRatio(probe) });
+  }
+}
+while (tickAtSqrtPrice.length < 400) {
+  const probe = MIN_SQRT_PRICE + (randomBits(97) % (MAX_SQRT_PRICE - MIN_SQRT_PRICE + ONE));
+  tickAtSqrtPrice.push({ sqrtPriceX64: probe.toString(), tick: getTickAtSqrtRatio(probe) });
+}
+
+const amountDeltas: Record<string, string>[] = [];
+while (amountDeltas.length < 300) {
+  const sqrtPriceA = getSqrtRatioAtTick(randomTick());
+  const sqrtPriceB = getSqrtRatioAtTick(randomTick());
+  const liquidity = randomBits(64);
+  const amounts = [
+    getAmount0Delta(sqrtPriceA, sqrtPriceB, liquidity, false),
+    getAmount0Delta(sqrtPriceA, sqrtPriceB, liquidity, true),
+    getAmount1Delta(sqrtPriceA, sqrtPriceB, liquidity, false),
+    getAmount1Delta(sqrtPriceA, sqrtPriceB, liquidity, true),
+  ];
+  if (amounts.some((amount) => amount > MAX_U64)) continue;
+  amountDeltas.push({
+    sqrtPriceAX64: sqrtPriceA.toString(),
+    sqrtPriceBX64: sqrtPriceB.toString(),
+    liquidity: liquidity.toString(),
+    amountADown: amounts[0].toString(),
+    amountAUp: amounts[1].toString(),
+    amountBDown: amounts[2].toString(),
+    amountBUp: amounts[3].toString(),
+  });
+}
+
+const nextSqrtPrices: Record<string, string | boolean>[] = [];
+while (nextSqrtPrices.length < 300) {
+  const sqrtPrice = getSqrtRatioAtTick(randomTick());
+  const liquidity = randomBits(64) + ONE;
+  const amount = randomBits(64);
+  const zeroForOne = randomInt(0, 1) === 1;
+  const exactInput = randomInt(0, 1) === 1;
+  const next = attempt(() =>
+    exactInput
+      ? getNextSqrtPriceFromInput(sqrtPrice, liquidity, amount, zeroForOne)
+      : getNextSqrtPriceFromOutput(sqrtPrice, liquidity, amount, zeroForOne)
+  );
+  if (next === undefined || !inPriceRange(next)) continue;
+  nextSqrtPrices.push({
+    sqrtPriceX64: sqrtPrice.toString(),
+    liquidity: liquidity.toString(),
+    amount: amount.toString(),
+    zeroForOne,
+    exactInput,
+    nextSqrtPriceX64: next.toString(),
+  });
+}
+
+const FEE_TIERS = [0, 100, 500, 3000, 10_000, 100_000];
+const swapSteps: Record<string, string | number>[] = [];
+while (swapSteps.length < 500) {
+  const currentTick = randomTick();
+  // Mostly short hops between neighbouring initialized ticks, sometimes the whole range
+  const targetTick = randomInt(0, 3)
+    ? Math.min(MAX_TICK, Math.max(MIN_TICK, currentTick + randomInt(-2_000, 2_000)))
+    : randomTick();
+  const current = getSqrtRatioAtTick(currentTick);
+  const target = getSqrtRatioAtTick(targetTick);
+  const liquidity = randomBits(64) + ONE;
+  const magnitude = randomBits(63) + ONE;
+  const amountRemaining = randomInt(0, 1) ? magnitude : -magnitude;
+  const feePips = BigInt(FEE_TIERS[randomInt(0, FEE_TIERS.length - 1)]);
+  const step = attempt(() => computeSwapStep(current, target, liquidity, amountRemaining, feePips));
+  if (step === undefined) continue;
+  if ([step.amountIn, step.amountOut, step.feeAmount].some((amount) => amount > MAX_U64)) continue;
+  swapSteps.push({
+    sqrtPriceCurrentX64: current.toString(),
+    sqrtPriceTargetX64: target.toString(),
+    liquidity: liquidity.toString(),
+    amountRemaining: amountRemaining.toString(),
+    feeRate: Number(feePips),
+    sqrtPriceNextX64: step.sqrtPriceNext.toString(),
+    amountIn: step.amountIn.toString(),
+    amountOut: step.amountOut.toString(),
+    feeAmount: step.feeAmount.toString(),
+  });
+}
+
+const liquidityForAmounts: Record<string, string>[] = [];
+while (liquidityForAmounts.length < 300) {
+  const [lowerTick, upperTick] = [randomTick(), randomTick()].sort((a, b) => a - b);
+  if (lowerTick === upperTick) continue;
+  const current = getSqrtRatioAtTick(randomInt(0, 1) ? randomTick() : randomInt(lowerTick, upperTick));
+  const lower = getSqrtRatioAtTick(lowerTick);
+  const upper = getSqrtRatioAtTick(upperTick);
+  const amountA = randomBits(64);
+  const amountB = randomBits(64);
+  const liquidity = getLiquidityForAmounts(current, lower, upper, amountA, amountB);
+  if (liquidity > MAX_U128) continue;
+  liquidityForAmounts.push({
+    sqrtPriceCurrentX64: current.toString(),
+    sqrtPriceLowerX64: lower.toString(),
+    sqrtPriceUpperX64: upper.toString(),
+    amountA: amountA.toString(),
+    amountB: amountB.toString(),
+    liquidity: liquidity.toString(),
+  });
+}
+
+const corpus = {
+  description:
+    "Uniswap V3 reference math (v3-core TickMath/SqrtPriceMath/SwapMath, v3-periphery LiquidityAmounts) " +
+    "evaluated exactly at Q64.64 resolution. Generated by scripts/generate-reference-vectors.ts.",
+  minTick: MIN_TICK,
+  maxTick: MAX_TICK,
+  sqrtPriceAtTick,
+  tickAtSqrtPrice,
+  amountDeltas,
+  nextSqrtPrices,
+  swapSteps,
+  liquidityForAmounts,
+};
+
+const output = path.join(__dirname, "..", "programs", "suniswap", "tests", "vectors", "uniswap_v3_q64.json");
+fs.mkdirSync(path.dirname(output), { recursive: true });
+// One vector per line keeps the file reviewable and diffs small
+const body = Object.entries(corpus)
+  .map(([key, value]) =>
+    Array.isArray(value)
+      ? `  ${JSON.stringify(key)}: [\n${value.map((entry) => `    ${JSON.stringify(entry)}`).join(",\n")}\n  ]`
+      : `  ${JSON.stringify(key)}: ${JSON.stringify(value)}`
+  )
+  .join(",\n");
+fs.writeFileSync(output, `{\n${body}\n}\n`);
+console.log(`Wrote ${output}`);
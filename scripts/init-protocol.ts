@@ -104,11 +104,21 @@ async function main() {
       console.log(`  [SKIP] Fee tier ${tier.feeRate / 100}% already exists`);
     } catch {
       try {
+        // New tiers are indexed in creation order
+        const { feeTierCount } = await program.account.suniswapConfig.fetch(configPda);
+        const indexBytes = Buffer.alloc(2);
+        indexBytes.writeUInt16LE(feeTierCount);
+        const [feeTierIndexPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("fee_tier_index"), indexBytes],
+          programId
+        );
+
         const tx = await program.methods
           .initializeFeeTier(tier.feeRate, tier.tickSpacing)
           .accountsStrict({
             config: configPda,
             feeTier: feeTierPda,
+            feeTierIndex: feeTierIndexPda,
             authority: wallet.publicKey,
             payer: wallet.publicKey,
             systemProgram: SystemProgram.programId,
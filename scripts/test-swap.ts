@@ -131,6 +131,10 @@ async function main() {
         otherAmountThreshold: minAmountOut,
         sqrtPriceLimitX64: sqrtPriceLimit,
         aToB: true,
+        referralFeeRate: 0,
+        deadlineUnixTs: null,
+        maxSlot: null,
+        maxTwapDeviationTicks: null,
       })
       .accountsStrict({
         pool: poolPda,
@@ -222,11 +222,21 @@ describe("suniswap", () => {
 
     it("Initializes 0.3% fee tier", async () => {
       try {
+        // The new tier takes the next enumeration index
+        const { feeTierCount } = await program.account.suniswapConfig.fetch(configPda);
+        const indexBytes = Buffer.alloc(2);
+        indexBytes.writeUInt16LE(feeTierCount, 0);
+        const [feeTierIndexPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("fee_tier_index"), indexBytes],
+          program.programId
+        );
+
         await program.methods
           .initializeFeeTier(FEE_RATE, TICK_SPACING)
           .accounts({
             config: configPda,
             feeTier: feeTierPda,
+            feeTierIndex: feeTierIndexPda,
             authority: payer.publicKey,
             payer: payer.publicKey,
             systemProgram: SystemProgram.programId,
@@ -251,7 +261,7 @@ describe("suniswap", () => {
     it("Initializes a pool", async () => {
       try {
         await program.methods
-          .initializePool(INITIAL_SQRT_PRICE)
+          .initializePool(INITIAL_SQRT_PRICE, null, 0)
           .accounts({
             config: configPda,
             feeTier: feeTierPda,
@@ -440,7 +450,7 @@ describe("suniswap", () => {
 
       try {
         const tx = await program.methods
-          .increaseLiquidity(liquidityDelta, amountAMax, amountBMax)
+          .increaseLiquidity(liquidityDelta, amountAMax, amountBMax, null, null)
           .accounts({
             pool: poolPda,
             position: actualPositionPda,
@@ -490,6 +500,10 @@ describe("suniswap", () => {
             otherAmountThreshold: minOutput,
             sqrtPriceLimitX64: sqrtPriceLimit,
             aToB: true,
+            referralFeeRate: 0,
+            deadlineUnixTs: null,
+            maxSlot: null,
+            maxTwapDeviationTicks: null,
           })
           .accounts({
             pool: poolPda,
@@ -661,7 +675,7 @@ describe("suniswap", () => {
       // Try to add zero liquidity
       try {
         await program.methods
-          .increaseLiquidity(new BN(0), new BN("100000000000"), new BN("100000000000"))
+          .increaseLiquidity(new BN(0), new BN("100000000000"), new BN("100000000000"), null, null)
           .accounts({
             pool: poolPda,
             position: testPositionPda,